@@ -55,6 +55,13 @@ pub enum Subcommand {
 		about = "Benchmark runtime pallets."
 	)]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+	/// The custom benchmark-diff subcommmand comparing two sets of benchmark results.
+	#[structopt(
+		name = "benchmark-diff",
+		about = "Report the weights which changed between two sets of benchmark results."
+	)]
+	BenchmarkDiff(frame_benchmarking_cli::BenchmarkDiffCmd),
 }
 
 /// The `factory` command used to generate transactions.
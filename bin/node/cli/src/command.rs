@@ -58,6 +58,7 @@ where
 
 			cmd.run::<node_runtime::Block, node_executor::Executor>(config)
 		},
+		Some(Subcommand::BenchmarkDiff(cmd)) => cmd.run(),
 		Some(Subcommand::Factory(cli_args)) => {
 			cli_args.shared_params.init(&version)?;
 			cli_args.shared_params.update_config(&mut config, load_spec, &version)?;
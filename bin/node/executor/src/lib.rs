@@ -26,5 +26,8 @@ native_executor_instance!(
 	pub Executor,
 	node_runtime::api::dispatch,
 	node_runtime::native_version,
-	frame_benchmarking::benchmarking::HostFunctions,
+	(
+		frame_benchmarking::benchmarking::HostFunctions,
+		frame_benchmarking::benchmarking_allocator::HostFunctions,
+	),
 );
@@ -24,15 +24,16 @@ use sp_std::prelude::*;
 use frame_support::{
 	construct_runtime, parameter_types, debug,
 	weights::Weight,
-	traits::{Currency, Randomness, OnUnbalanced, Imbalance},
+	traits::{Currency, Randomness, OnUnbalanced, Imbalance, InstanceFilter, LockIdentifier},
 };
+use codec::{Encode, Decode};
 use sp_core::u32_trait::{_1, _2, _3, _4};
 pub use node_primitives::{AccountId, Signature};
 use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
 use sp_api::impl_runtime_apis;
 use sp_runtime::{
 	Permill, Perbill, Percent, ApplyExtrinsicResult,
-	impl_opaque_keys, generic, create_runtime_str,
+	impl_opaque_keys, generic, create_runtime_str, RuntimeDebug,
 };
 use sp_runtime::curve::PiecewiseLinear;
 use sp_runtime::transaction_validity::TransactionValidity;
@@ -82,7 +83,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 237,
+	spec_version: 239,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
 };
@@ -303,12 +304,33 @@ impl pallet_staking::Trait for Runtime {
 parameter_types! {
 	pub const LaunchPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
 	pub const VotingPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
-	pub const EmergencyVotingPeriod: BlockNumber = 3 * 24 * 60 * MINUTES;
+	pub const FastTrackVotingPeriod: BlockNumber = 3 * 24 * 60 * MINUTES;
 	pub const MinimumDeposit: Balance = 100 * DOLLARS;
 	pub const EnactmentPeriod: BlockNumber = 30 * 24 * 60 * MINUTES;
 	pub const CooloffPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
 	// One cent: $10,000 / MB
 	pub const PreimageByteDeposit: Balance = 1 * CENTS;
+	pub const PreimageChunkDeposit: Balance = 1 * DOLLARS;
+	pub const MaxPreimageChunks: u32 = 64;
+	pub const VoteDeposit: Balance = 1 * CENTS;
+	pub const MaxProposals: u32 = 100;
+	pub const MaxDeposits: u32 = 100;
+	pub const MaxVetoers: u32 = 100;
+	pub const MaxOptions: u32 = 16;
+	pub const MaxDelegationTargets: u32 = 16;
+	pub const MaxDelegators: u32 = 128;
+	pub const MaxVotes: u32 = 100;
+	pub const MaxQueued: u32 = 100;
+	pub const FinishedRetention: BlockNumber = 28 * 24 * 60 * MINUTES;
+	pub const RemoveVotesOnDelegation: bool = false;
+	pub const HoldDeposits: bool = true;
+	pub const QuadraticVoting: bool = false;
+	pub const SnapshotBalances: bool = true;
+	pub const IndexVotes: bool = false;
+	pub const DepositSurcharge: Balance = 1 * DOLLARS;
+	// Half of the surcharge is gone after about a day.
+	pub const DepositDecay: Perbill = Perbill::from_parts(24_000);
+	pub const DemocracyLockId: LockIdentifier = *b"democrac";
 }
 
 impl pallet_democracy::Trait for Runtime {
@@ -329,7 +351,7 @@ impl pallet_democracy::Trait for Runtime {
 	/// Two thirds of the technical committee can have an ExternalMajority/ExternalDefault vote
 	/// be tabled immediately and with a shorter voting/enactment period.
 	type FastTrackOrigin = pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, TechnicalCollective>;
-	type EmergencyVotingPeriod = EmergencyVotingPeriod;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
 	// To cancel a proposal which has been passed, 2/3 of the council must agree to it.
 	type CancellationOrigin = pallet_collective::EnsureProportionAtLeast<_2, _3, AccountId, CouncilCollective>;
 	// Any single technical committee member may veto a coming council proposal, however they can
@@ -337,7 +359,74 @@ impl pallet_democracy::Trait for Runtime {
 	type VetoOrigin = pallet_collective::EnsureMember<AccountId, TechnicalCollective>;
 	type CooloffPeriod = CooloffPeriod;
 	type PreimageByteDeposit = PreimageByteDeposit;
+	type PreimageChunkDeposit = PreimageChunkDeposit;
+	type MaxPreimageChunks = MaxPreimageChunks;
+	type VoteDeposit = VoteDeposit;
 	type Slash = Treasury;
+	type MaxProposals = MaxProposals;
+	type MaxDeposits = MaxDeposits;
+	type MaxVetoers = MaxVetoers;
+	type ApprovalCriterion = pallet_democracy::AdaptiveQuorumBiasing;
+	type MaxOptions = MaxOptions;
+	type MaxDelegationTargets = MaxDelegationTargets;
+	type MaxDelegators = MaxDelegators;
+	type MaxVotes = MaxVotes;
+	type ConvictionSchedule = pallet_democracy::DefaultConvictionSchedule;
+	type VoteWeightCurve = ();
+	type EnactmentDelayCurve = ();
+	type MaxQueued = MaxQueued;
+	type ReferendumHooks = ();
+	type OnVote = ();
+	type OnDelegation = ();
+	type FinishedRetention = FinishedRetention;
+	type RemoveVotesOnDelegation = RemoveVotesOnDelegation;
+	type HoldDeposits = HoldDeposits;
+	type QuadraticVoting = QuadraticVoting;
+	type SnapshotBalances = SnapshotBalances;
+	type IndexVotes = IndexVotes;
+	type DepositSurcharge = DepositSurcharge;
+	type DepositDecay = DepositDecay;
+	/// A unanimous technical committee can table a referendum with an even shorter voting period.
+	type InstantOrigin = pallet_collective::EnsureProportionAtLeast<_1, _1, AccountId, TechnicalCollective>;
+	/// Half of the technical committee can whitelist a proposal as safe to fast-track...
+	type WhitelistOrigin = pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, TechnicalCollective>;
+	/// ...which half of the council can then table as a majority-carries referendum.
+	type DispatchWhitelistedOrigin = pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
+	type LockId = DemocracyLockId;
+}
+
+/// The kinds of call that a proxy may make on behalf of its stash.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+pub enum ProxyType {
+	/// Any call at all.
+	Any,
+	/// Governance calls only: voting, delegation and council business. This replaces the
+	/// built-in proxy of the democracy module.
+	Governance,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self { ProxyType::Any }
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::Governance => matches!(c,
+				Call::Democracy(..) | Call::Council(..) | Call::TechnicalCommittee(..)
+					| Call::Elections(..) | Call::Treasury(..)
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(ProxyType::Governance, ProxyType::Governance) => true,
+		}
+	}
 }
 
 parameter_types! {
@@ -638,7 +727,7 @@ construct_runtime!(
 		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 		Staking: pallet_staking::{Module, Call, Config<T>, Storage, Event<T>},
 		Session: pallet_session::{Module, Call, Storage, Event, Config<T>},
-		Democracy: pallet_democracy::{Module, Call, Storage, Config, Event<T>},
+		Democracy: pallet_democracy::{Module, Call, Storage, Config<T>, Event<T>},
 		Council: pallet_collective::<Instance1>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
 		TechnicalCommittee: pallet_collective::<Instance2>::{Module, Call, Storage, Origin<T>, Event<T>, Config<T>},
 		Elections: pallet_elections_phragmen::{Module, Call, Storage, Event<T>},
@@ -818,6 +907,42 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_democracy_rpc_runtime_api::DemocracyApi<
+		Block,
+		AccountId,
+		Balance,
+		BlockNumber,
+		Hash,
+	> for Runtime {
+		fn referenda() -> Vec<pallet_democracy::ReferendumStatus<BlockNumber, Hash, Balance>> {
+			Democracy::referendum_statuses()
+		}
+
+		fn public_proposals() -> Vec<pallet_democracy::ProposalStatus<AccountId, Hash, Balance>> {
+			Democracy::proposal_statuses()
+		}
+
+		fn account_governance(
+			who: AccountId,
+		) -> pallet_democracy::AccountGovernance<AccountId, BlockNumber, Balance> {
+			Democracy::account_governance(who)
+		}
+
+		fn account_locks(
+			who: AccountId,
+		) -> Vec<(
+			Option<pallet_democracy::ReferendumIndex>,
+			Balance,
+			pallet_democracy::UnlockAt<BlockNumber>,
+		)> {
+			Democracy::account_locks(who)
+		}
+
+		fn voting_power_of(who: AccountId, index: pallet_democracy::ReferendumIndex) -> Balance {
+			Democracy::voting_power_of(who, index)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -850,60 +975,81 @@ impl_runtime_apis! {
 			steps: Vec<u32>,
 			repeat: u32,
 		) -> Result<Vec<frame_benchmarking::BenchmarkResults>, sp_runtime::RuntimeString> {
-			use frame_benchmarking::Benchmarking;
+			use frame_benchmarking::add_benchmark;
 			// Trying to add benchmarks directly to the Session Pallet caused cyclic dependency issues.
 			// To get around that, we separated the Session benchmarks into its own crate, which is why
 			// we need these two lines below.
 			use pallet_session_benchmarking::Module as SessionBench;
 			impl pallet_session_benchmarking::Trait for Runtime {}
 
-			let result = match module.as_slice() {
-				b"pallet-balances" | b"balances" => Balances::run_benchmark(
-					extrinsic,
-					lowest_range_values,
-					highest_range_values,
-					steps,
-					repeat,
-				),
-				b"pallet-identity" | b"identity" => Identity::run_benchmark(
-					extrinsic,
-					lowest_range_values,
-					highest_range_values,
-					steps,
-					repeat,
-				),
-				b"pallet-session" | b"session" => SessionBench::<Runtime>::run_benchmark(
-					extrinsic,
-					lowest_range_values,
-					highest_range_values,
-					steps,
-					repeat,
-				),
-				b"pallet-staking" | b"staking" => Staking::run_benchmark(
-					extrinsic,
-					lowest_range_values,
-					highest_range_values,
-					steps,
-					repeat,
-				),
-				b"pallet-timestamp" | b"timestamp" => Timestamp::run_benchmark(
-					extrinsic,
-					lowest_range_values,
-					highest_range_values,
-					steps,
-					repeat,
-				),
-				b"pallet-vesting" | b"vesting" => Vesting::run_benchmark(
-					extrinsic,
-					lowest_range_values,
-					highest_range_values,
-					steps,
-					repeat,
-				),
-				_ => Err("Benchmark not found for this pallet."),
-			};
-
-			result.map_err(|e| e.into())
+			// Keys which are accessed in every block anyway, so their accesses are not charged.
+			let whitelist: Vec<Vec<u8>> = vec![
+				// Block number
+				frame_benchmarking::storage_value_key(b"System", b"Number"),
+				// Events
+				frame_benchmarking::storage_value_key(b"System", b"Events"),
+				frame_benchmarking::storage_value_key(b"System", b"EventCount"),
+				// Extrinsic index
+				sp_core::storage::well_known_keys::EXTRINSIC_INDEX.to_vec(),
+			];
+
+			let params = (
+				&module,
+				&extrinsic,
+				&lowest_range_values,
+				&highest_range_values,
+				&steps,
+				repeat,
+				&whitelist,
+			);
+			let mut results = None;
+
+			add_benchmark!(params, results, b"pallet-balances" | b"balances", Balances);
+			add_benchmark!(params, results, b"pallet-collective" | b"council", Council);
+			add_benchmark!(params, results, b"pallet-democracy" | b"democracy", Democracy);
+			add_benchmark!(params, results, b"pallet-identity" | b"identity", Identity);
+			add_benchmark!(params, results, b"pallet-session" | b"session", SessionBench<Runtime>);
+			add_benchmark!(params, results, b"pallet-staking" | b"staking", Staking);
+			add_benchmark!(params, results, b"technical-committee", TechnicalCommittee);
+			add_benchmark!(params, results, b"pallet-timestamp" | b"timestamp", Timestamp);
+			add_benchmark!(params, results, b"pallet-vesting" | b"vesting", Vesting);
+
+			results.unwrap_or(Err("Benchmark not found for this pallet.")).map_err(|e| e.into())
+		}
+
+		fn benchmarks(
+			module: Vec<u8>,
+		) -> Result<Vec<frame_benchmarking::BenchmarkMetadata>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::list_benchmark;
+			use pallet_session_benchmarking::Module as SessionBench;
+
+			let mut list = None;
+
+			list_benchmark!(module, list, b"pallet-balances" | b"balances", Balances);
+			list_benchmark!(module, list, b"pallet-collective" | b"council", Council);
+			list_benchmark!(module, list, b"pallet-democracy" | b"democracy", Democracy);
+			list_benchmark!(module, list, b"pallet-identity" | b"identity", Identity);
+			list_benchmark!(module, list, b"pallet-session" | b"session", SessionBench<Runtime>);
+			list_benchmark!(module, list, b"pallet-staking" | b"staking", Staking);
+			list_benchmark!(module, list, b"technical-committee", TechnicalCommittee);
+			list_benchmark!(module, list, b"pallet-timestamp" | b"timestamp", Timestamp);
+			list_benchmark!(module, list, b"pallet-vesting" | b"vesting", Vesting);
+
+			list.ok_or_else(|| "Benchmark not found for this pallet.".into())
+		}
+
+		fn pallets() -> Vec<Vec<u8>> {
+			// The technical committee shares the weights of the council.
+			vec![
+				b"pallet-balances".to_vec(),
+				b"pallet-collective".to_vec(),
+				b"pallet-democracy".to_vec(),
+				b"pallet-identity".to_vec(),
+				b"pallet-session".to_vec(),
+				b"pallet-staking".to_vec(),
+				b"pallet-timestamp".to_vec(),
+				b"pallet-vesting".to_vec(),
+			]
 		}
 	}
 }
@@ -936,6 +1082,20 @@ mod tests {
 		is_sign_and_submit_transaction::<SubmitTransaction>();
 	}
 
+	#[test]
+	fn governance_proxy_should_only_make_governance_calls() {
+		let vote = Call::Democracy(pallet_democracy::Call::remove_vote(0));
+		let remark = Call::System(frame_system::Call::remark(vec![]));
+
+		assert!(ProxyType::Governance.filter(&vote));
+		assert!(!ProxyType::Governance.filter(&remark));
+		assert!(ProxyType::Any.filter(&remark));
+
+		assert!(ProxyType::Any.is_superset(&ProxyType::Governance));
+		assert!(!ProxyType::Governance.is_superset(&ProxyType::Any));
+		assert!(ProxyType::Governance.is_superset(&ProxyType::Governance));
+	}
+
 	#[test]
 	fn block_hooks_weight_should_not_exceed_limits() {
 		use frame_support::weights::WeighBlock;
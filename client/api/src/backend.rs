@@ -24,7 +24,7 @@ use sp_runtime::{generic::BlockId, Justification, Storage};
 use sp_runtime::traits::{Block as BlockT, NumberFor, HashFor};
 use sp_state_machine::{
 	ChangesTrieState, ChangesTrieStorage as StateChangesTrieStorage, ChangesTrieTransaction,
-	StorageCollection, ChildStorageCollection,
+	StorageCollection, ChildStorageCollection, OffchainStorageCollection,
 };
 use sp_storage::{StorageData, StorageKey, ChildInfo};
 use crate::{
@@ -148,6 +148,12 @@ pub trait BlockImportOperation<Block: BlockT> {
 		child_update: ChildStorageCollection,
 	) -> sp_blockchain::Result<()>;
 
+	/// Set the changes to the offchain database indexed by the runtime while executing the block.
+	fn update_offchain_storage(
+		&mut self,
+		offchain_update: OffchainStorageCollection,
+	) -> sp_blockchain::Result<()>;
+
 	/// Inject changes trie data into the database.
 	fn update_changes_trie(
 		&mut self,
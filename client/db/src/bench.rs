@@ -19,14 +19,19 @@
 use std::sync::Arc;
 use std::path::PathBuf;
 use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use rand::Rng;
 
 use hash_db::{Prefix, Hasher};
+use codec::Encode;
 use sp_trie::{MemoryDB, prefixed_key};
 use sp_core::storage::ChildInfo;
 use sp_runtime::traits::{Block as BlockT, HashFor};
 use sp_runtime::Storage;
-use sp_state_machine::{DBValue, backend::Backend as StateBackend};
+use sp_state_machine::{
+	DBValue, backend::Backend as StateBackend, StorageCollection, ChildStorageCollection,
+	ProofRecorder, ProvingBackend, StorageProof,
+};
 use kvdb::{KeyValueDB, DBTransaction};
 use kvdb_rocksdb::{Database, DatabaseConfig};
 
@@ -47,6 +52,23 @@ impl<Block: BlockT> sp_state_machine::Storage<HashFor<Block>> for StorageDb<Bloc
 	}
 }
 
+/// Whether a key has already been read from or written to the database since the last reset.
+#[derive(Default)]
+struct KeyTracker {
+	has_been_read: bool,
+	has_been_written: bool,
+}
+
+/// The number of database reads and writes since the last reset. A repeated access is one to a
+/// key which has already been read (or written, respectively) since then.
+#[derive(Default)]
+struct ReadWriteTracker {
+	reads: u32,
+	repeat_reads: u32,
+	writes: u32,
+	repeat_writes: u32,
+}
+
 /// State that manages the backend database reference. Allows runtime to control the database.
 pub struct BenchmarkingState<B: BlockT> {
 	path: PathBuf,
@@ -55,6 +77,19 @@ pub struct BenchmarkingState<B: BlockT> {
 	state: RefCell<Option<DbState<B>>>,
 	db: Cell<Option<Arc<dyn KeyValueDB>>>,
 	genesis: <DbState<B> as StateBackend<HashFor<B>>>::Transaction,
+	key_tracker: RefCell<HashMap<Vec<u8>, KeyTracker>>,
+	read_write_tracker: RefCell<ReadWriteTracker>,
+	whitelist: RefCell<Vec<Vec<u8>>>,
+	proof_recorder: ProofRecorder<HashFor<B>>,
+	// The size of the value of each key read since the last reset, as it is in the proof.
+	read_sizes: RefCell<HashMap<Vec<u8>, u32>>,
+	pov_hints: RefCell<Vec<(Vec<u8>, Option<u32>)>>,
+	// The accesses to child tries, which cost differently from those to the main trie, are
+	// tracked apart from them, by the storage key of the child trie and the key within it.
+	child_key_tracker: RefCell<HashMap<(Vec<u8>, Vec<u8>), KeyTracker>>,
+	child_read_write_tracker: RefCell<ReadWriteTracker>,
+	child_whitelist: RefCell<Vec<Vec<u8>>>,
+	child_proof_recorder: ProofRecorder<HashFor<B>>,
 }
 
 impl<B: BlockT> BenchmarkingState<B> {
@@ -76,6 +111,16 @@ impl<B: BlockT> BenchmarkingState<B> {
 			root: Cell::new(root),
 			genesis: Default::default(),
 			genesis_root: Default::default(),
+			key_tracker: Default::default(),
+			read_write_tracker: Default::default(),
+			whitelist: Default::default(),
+			proof_recorder: Default::default(),
+			read_sizes: Default::default(),
+			pov_hints: Default::default(),
+			child_key_tracker: Default::default(),
+			child_read_write_tracker: Default::default(),
+			child_whitelist: Default::default(),
+			child_proof_recorder: Default::default(),
 		};
 
 		state.reopen()?;
@@ -90,7 +135,8 @@ impl<B: BlockT> BenchmarkingState<B> {
 		);
 		state.genesis = transaction.clone();
 		state.genesis_root = root.clone();
-		state.commit(root, transaction)?;
+		state.commit(root, transaction, Default::default(), Default::default())?;
+		state.reset_read_write_count();
 		Ok(state)
 	}
 
@@ -117,6 +163,116 @@ impl<B: BlockT> BenchmarkingState<B> {
 
 		std::fs::remove_dir_all(&self.path).map_err(|_| "Error removing database dir".into())
 	}
+
+	// Whitelisted keys are tracked as already read and written, so that any access to them is a
+	// repeated one.
+	fn track_whitelist(&self) {
+		let mut key_tracker = self.key_tracker.borrow_mut();
+		for key in self.whitelist.borrow().iter() {
+			key_tracker.insert(key.clone(), KeyTracker { has_been_read: true, has_been_written: true });
+		}
+	}
+
+	fn add_read_key(&self, key: &[u8]) {
+		let mut tracker = self.read_write_tracker.borrow_mut();
+		let mut key_tracker = self.key_tracker.borrow_mut();
+		let tracked = key_tracker.entry(key.to_vec()).or_default();
+		if tracked.has_been_read {
+			tracker.repeat_reads += 1;
+		} else {
+			tracked.has_been_read = true;
+			tracker.reads += 1;
+		}
+	}
+
+	// Count a read of `key` through which its value was not returned, such as one made by
+	// iterating or by hashing, noting the size of the value in the proof all the same.
+	fn add_read_key_of_value(&self, key: &[u8]) {
+		self.add_read_key(key);
+		if !self.read_sizes.borrow().contains_key(key) {
+			let size = self.state.borrow().as_ref()
+				.and_then(|state| state.storage(key).ok().and_then(|value| value))
+				.map_or(0, |value| value.len() as u32);
+			self.read_sizes.borrow_mut().insert(key.to_vec(), size);
+		}
+	}
+
+	// Read through a proving backend, so that the trie nodes which the read touches are recorded
+	// in the storage proof.
+	fn prove<R>(
+		&self,
+		read: impl FnOnce(&ProvingBackend<'_, Arc<dyn sp_state_machine::Storage<HashFor<B>>>, HashFor<B>>)
+			-> Result<R, String>,
+	) -> Result<R, String> {
+		let state = self.state.borrow();
+		let state = state.as_ref().ok_or_else(state_err)?;
+		read(&ProvingBackend::new_with_recorder(state, self.proof_recorder.clone()))
+	}
+
+	// Read a child trie through a proving backend of its own, so that the proof of the reads of
+	// child tries is measured apart from that of the main trie.
+	fn prove_child<R>(
+		&self,
+		read: impl FnOnce(&ProvingBackend<'_, Arc<dyn sp_state_machine::Storage<HashFor<B>>>, HashFor<B>>)
+			-> Result<R, String>,
+	) -> Result<R, String> {
+		let state = self.state.borrow();
+		let state = state.as_ref().ok_or_else(state_err)?;
+		read(&ProvingBackend::new_with_recorder(state, self.child_proof_recorder.clone()))
+	}
+
+	// Any access to a whitelisted child trie is a repeated one.
+	fn add_child_read_key(&self, storage_key: &[u8], key: &[u8]) {
+		let mut tracker = self.child_read_write_tracker.borrow_mut();
+		let mut key_tracker = self.child_key_tracker.borrow_mut();
+		let tracked = key_tracker.entry((storage_key.to_vec(), key.to_vec())).or_default();
+		if tracked.has_been_read || self.child_whitelist.borrow().iter().any(|k| k == storage_key) {
+			tracker.repeat_reads += 1;
+		} else {
+			tracked.has_been_read = true;
+			tracker.reads += 1;
+		}
+	}
+
+	fn add_child_write_key(&self, storage_key: &[u8], key: &[u8]) {
+		let mut tracker = self.child_read_write_tracker.borrow_mut();
+		let mut key_tracker = self.child_key_tracker.borrow_mut();
+		let tracked = key_tracker.entry((storage_key.to_vec(), key.to_vec())).or_default();
+		if tracked.has_been_written || self.child_whitelist.borrow().iter().any(|k| k == storage_key) {
+			tracker.repeat_writes += 1;
+		} else {
+			tracked.has_been_written = true;
+			tracker.writes += 1;
+		}
+	}
+
+	// How much bigger the proof would be if the value of every key read under a prefix with a
+	// maximum size hint were of that size, rather than of the size it was read at. The hint with
+	// the longest prefix of a key applies to it.
+	fn pov_hint_adjustment(&self) -> i64 {
+		let hints = self.pov_hints.borrow();
+		self.read_sizes.borrow().iter().map(|(key, &size)| {
+			let hint = hints.iter()
+				.filter(|(prefix, _)| key.starts_with(prefix))
+				.max_by_key(|(prefix, _)| prefix.len());
+			match hint {
+				Some((_, Some(max_size))) => *max_size as i64 - size as i64,
+				_ => 0,
+			}
+		}).sum()
+	}
+
+	fn add_write_key(&self, key: &[u8]) {
+		let mut tracker = self.read_write_tracker.borrow_mut();
+		let mut key_tracker = self.key_tracker.borrow_mut();
+		let tracked = key_tracker.entry(key.to_vec()).or_default();
+		if tracked.has_been_written {
+			tracker.repeat_writes += 1;
+		} else {
+			tracked.has_been_written = true;
+			tracker.writes += 1;
+		}
+	}
 }
 
 impl<B: BlockT> Drop for BenchmarkingState<B> {
@@ -135,11 +291,16 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 	type TrieBackendStorage = <DbState<B> as StateBackend<HashFor<B>>>::TrieBackendStorage;
 
 	fn storage(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-		self.state.borrow().as_ref().ok_or_else(state_err)?.storage(key)
+		self.add_read_key(key);
+		let value = self.prove(|state| state.storage(key))?;
+		let size = value.as_ref().map_or(0, |v| v.len() as u32);
+		self.read_sizes.borrow_mut().entry(key.to_vec()).or_insert(size);
+		Ok(value)
 	}
 
 	fn storage_hash(&self, key: &[u8]) -> Result<Option<B::Hash>, Self::Error> {
-		self.state.borrow().as_ref().ok_or_else(state_err)?.storage_hash(key)
+		self.add_read_key_of_value(key);
+		self.prove(|state| state.storage_hash(key))
 	}
 
 	fn child_storage(
@@ -148,11 +309,13 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 		child_info: ChildInfo,
 		key: &[u8],
 	) -> Result<Option<Vec<u8>>, Self::Error> {
-		self.state.borrow().as_ref().ok_or_else(state_err)?.child_storage(storage_key, child_info, key)
+		self.add_child_read_key(storage_key, key);
+		self.prove_child(|state| state.child_storage(storage_key, child_info, key))
 	}
 
 	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
-		self.state.borrow().as_ref().ok_or_else(state_err)?.exists_storage(key)
+		self.add_read_key_of_value(key);
+		self.prove(|state| state.exists_storage(key))
 	}
 
 	fn exists_child_storage(
@@ -161,11 +324,17 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 		child_info: ChildInfo,
 		key: &[u8],
 	) -> Result<bool, Self::Error> {
-		self.state.borrow().as_ref().ok_or_else(state_err)?.exists_child_storage(storage_key, child_info, key)
+		self.add_child_read_key(storage_key, key);
+		self.prove_child(|state| state.exists_child_storage(storage_key, child_info, key))
 	}
 
+	// The key found by iterating counts as read, as it is in the proof along with its value.
 	fn next_storage_key(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-		self.state.borrow().as_ref().ok_or_else(state_err)?.next_storage_key(key)
+		let next = self.prove(|state| state.next_storage_key(key))?;
+		if let Some(next) = &next {
+			self.add_read_key_of_value(next);
+		}
+		Ok(next)
 	}
 
 	fn next_child_storage_key(
@@ -174,30 +343,48 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 		child_info: ChildInfo,
 		key: &[u8],
 	) -> Result<Option<Vec<u8>>, Self::Error> {
-		self.state.borrow().as_ref().ok_or_else(state_err)?.next_child_storage_key(storage_key, child_info, key)
+		let next = self.prove_child(|state| state.next_child_storage_key(storage_key, child_info, key))?;
+		if let Some(next) = &next {
+			self.add_child_read_key(storage_key, next);
+		}
+		Ok(next)
 	}
 
-	fn for_keys_with_prefix<F: FnMut(&[u8])>(&self, prefix: &[u8], f: F) {
-		if let Some(ref state) = *self.state.borrow() {
-			state.for_keys_with_prefix(prefix, f)
-		}
+	// Every key iterated over counts as read, as for `next_storage_key`.
+	fn for_keys_with_prefix<F: FnMut(&[u8])>(&self, prefix: &[u8], mut f: F) {
+		let _ = self.prove(|state| {
+			state.for_keys_with_prefix(prefix, |key| {
+				self.add_read_key_of_value(key);
+				f(key)
+			});
+			Ok(())
+		});
 	}
 
-	fn for_key_values_with_prefix<F: FnMut(&[u8], &[u8])>(&self, prefix: &[u8], f: F) {
-		if let Some(ref state) = *self.state.borrow() {
-			state.for_key_values_with_prefix(prefix, f)
-		}
+	fn for_key_values_with_prefix<F: FnMut(&[u8], &[u8])>(&self, prefix: &[u8], mut f: F) {
+		let _ = self.prove(|state| {
+			state.for_key_values_with_prefix(prefix, |key, value| {
+				self.add_read_key(key);
+				self.read_sizes.borrow_mut().entry(key.to_vec()).or_insert(value.len() as u32);
+				f(key, value)
+			});
+			Ok(())
+		});
 	}
 
 	fn for_keys_in_child_storage<F: FnMut(&[u8])>(
 		&self,
 		storage_key: &[u8],
 		child_info: ChildInfo,
-		f: F,
+		mut f: F,
 	) {
-		if let Some(ref state) = *self.state.borrow() {
-			state.for_keys_in_child_storage(storage_key, child_info, f)
-		}
+		let _ = self.prove_child(|state| {
+			state.for_keys_in_child_storage(storage_key, child_info, |key| {
+				self.add_child_read_key(storage_key, key);
+				f(key)
+			});
+			Ok(())
+		});
 	}
 
 	fn for_child_keys_with_prefix<F: FnMut(&[u8])>(
@@ -205,11 +392,15 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 		storage_key: &[u8],
 		child_info: ChildInfo,
 		prefix: &[u8],
-		f: F,
+		mut f: F,
 	) {
-		if let Some(ref state) = *self.state.borrow() {
-			state.for_child_keys_with_prefix(storage_key, child_info, prefix, f)
-		}
+		let _ = self.prove_child(|state| {
+			state.for_child_keys_with_prefix(storage_key, child_info, prefix, |key| {
+				self.add_child_read_key(storage_key, key);
+				f(key)
+			});
+			Ok(())
+		});
 	}
 
 	fn storage_root<I>(&self, delta: I) -> (B::Hash, Self::Transaction) where
@@ -252,9 +443,13 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 		None
 	}
 
-	fn commit(&self, storage_root: <HashFor<B> as Hasher>::Out, mut transaction: Self::Transaction)
-		-> Result<(), Self::Error>
-	{
+	fn commit(
+		&self,
+		storage_root: <HashFor<B> as Hasher>::Out,
+		mut transaction: Self::Transaction,
+		storage_changes: StorageCollection,
+		child_storage_changes: ChildStorageCollection,
+	) -> Result<(), Self::Error> {
 		if let Some(db) = self.db.take() {
 			let mut db_transaction = DBTransaction::new();
 
@@ -267,6 +462,10 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 			}
 			db.write(db_transaction).map_err(|_| String::from("Error committing transaction"))?;
 			self.root.set(storage_root);
+			storage_changes.iter().for_each(|(key, _)| self.add_write_key(key));
+			for (storage_key, changes) in &child_storage_changes {
+				changes.iter().for_each(|(key, _)| self.add_child_write_key(storage_key, key));
+			}
 		} else {
 			return Err("Trying to commit to a closed db".into())
 		}
@@ -276,9 +475,73 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 	fn wipe(&self) -> Result<(), Self::Error> {
 		self.kill()?;
 		self.reopen()?;
-		self.commit(self.genesis_root.clone(), self.genesis.clone())?;
+		self.commit(self.genesis_root.clone(), self.genesis.clone(), Default::default(), Default::default())?;
+		self.reset_read_write_count();
 		Ok(())
 	}
+
+	fn read_write_count(&self) -> (u32, u32, u32, u32) {
+		let tracker = self.read_write_tracker.borrow();
+		(tracker.reads, tracker.repeat_reads, tracker.writes, tracker.repeat_writes)
+	}
+
+	fn child_read_write_count(&self) -> (u32, u32, u32, u32) {
+		let tracker = self.child_read_write_tracker.borrow();
+		(tracker.reads, tracker.repeat_reads, tracker.writes, tracker.repeat_writes)
+	}
+
+	fn reset_read_write_count(&self) {
+		*self.read_write_tracker.borrow_mut() = Default::default();
+		self.key_tracker.borrow_mut().clear();
+		self.proof_recorder.write().clear();
+		self.read_sizes.borrow_mut().clear();
+		*self.child_read_write_tracker.borrow_mut() = Default::default();
+		self.child_key_tracker.borrow_mut().clear();
+		self.child_proof_recorder.write().clear();
+		self.track_whitelist();
+	}
+
+	fn proof_size(&self) -> u32 {
+		let trie_nodes = self.proof_recorder.read()
+			.values()
+			.filter_map(|v| v.as_ref().map(|v| v.to_vec()))
+			.collect();
+		let measured = StorageProof::new(trie_nodes).encode().len() as i64;
+		(measured + self.pov_hint_adjustment()).max(0).min(u32::max_value() as i64) as u32
+	}
+
+	fn child_proof_size(&self) -> u32 {
+		let trie_nodes = self.child_proof_recorder.read()
+			.values()
+			.filter_map(|v| v.as_ref().map(|v| v.to_vec()))
+			.collect();
+		StorageProof::new(trie_nodes).encode().len() as u32
+	}
+
+	fn get_whitelist(&self) -> Vec<Vec<u8>> {
+		self.whitelist.borrow().clone()
+	}
+
+	fn set_whitelist(&self, new: Vec<Vec<u8>>) {
+		*self.whitelist.borrow_mut() = new;
+		self.track_whitelist();
+	}
+
+	fn get_child_whitelist(&self) -> Vec<Vec<u8>> {
+		self.child_whitelist.borrow().clone()
+	}
+
+	fn set_child_whitelist(&self, new: Vec<Vec<u8>>) {
+		*self.child_whitelist.borrow_mut() = new;
+	}
+
+	fn get_pov_hints(&self) -> Vec<(Vec<u8>, Option<u32>)> {
+		self.pov_hints.borrow().clone()
+	}
+
+	fn set_pov_hints(&self, new: Vec<(Vec<u8>, Option<u32>)>) {
+		*self.pov_hints.borrow_mut() = new;
+	}
 }
 
 impl<Block: BlockT> std::fmt::Debug for BenchmarkingState<Block> {
@@ -28,6 +28,7 @@ use log::{error, trace, debug};
 use parity_wasm::elements::{deserialize_buffer, DataSegment, Instruction, Module as RawModule};
 use sp_wasm_interface::{
 	FunctionContext, Pointer, WordSize, Sandbox, MemoryId, Result as WResult, Function,
+	AllocationStats,
 };
 use sp_runtime_interface::unpack_ptr_and_len;
 use sc_executor_common::wasm_runtime::{WasmModule, WasmInstance};
@@ -118,6 +119,14 @@ impl<'a> FunctionContext for FunctionExecutor<'a> {
 	fn sandbox(&mut self) -> &mut dyn Sandbox {
 		self
 	}
+
+	fn allocation_stats(&self) -> AllocationStats {
+		self.heap.stats()
+	}
+
+	fn reset_allocation_stats(&mut self) {
+		self.heap.reset_stats()
+	}
 }
 
 impl<'a> Sandbox for FunctionExecutor<'a> {
@@ -154,6 +154,14 @@ impl<'a> sp_wasm_interface::FunctionContext for HostContext<'a> {
 	fn sandbox(&mut self) -> &mut dyn Sandbox {
 		self
 	}
+
+	fn allocation_stats(&self) -> sp_wasm_interface::AllocationStats {
+		self.allocator.borrow().stats()
+	}
+
+	fn reset_allocation_stats(&mut self) {
+		self.allocator.borrow_mut().reset_stats()
+	}
 }
 
 impl<'a> Sandbox for HostContext<'a> {
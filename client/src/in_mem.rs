@@ -28,7 +28,7 @@ use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Zero, NumberFor, Ha
 use sp_runtime::{Justification, Storage};
 use sp_state_machine::{
 	ChangesTrieTransaction, InMemoryBackend, Backend as StateBackend, StorageCollection,
-	ChildStorageCollection,
+	ChildStorageCollection, OffchainStorageCollection,
 };
 use sp_blockchain::{CachedHeaderMetadata, HeaderMetadata};
 
@@ -543,6 +543,13 @@ impl<Block: BlockT> backend::BlockImportOperation<Block> for BlockImportOperatio
 		Ok(())
 	}
 
+	fn update_offchain_storage(
+		&mut self,
+		_offchain_update: OffchainStorageCollection,
+	) -> sp_blockchain::Result<()> {
+		Ok(())
+	}
+
 	fn mark_finalized(
 		&mut self,
 		block: BlockId<Block>,
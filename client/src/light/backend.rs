@@ -28,7 +28,7 @@ use sp_core::storage::{well_known_keys, ChildInfo, OwnedChildInfo};
 use sp_core::offchain::storage::InMemOffchainStorage;
 use sp_state_machine::{
 	Backend as StateBackend, TrieBackend, InMemoryBackend, ChangesTrieTransaction,
-	StorageCollection, ChildStorageCollection,
+	StorageCollection, ChildStorageCollection, OffchainStorageCollection,
 };
 use sp_runtime::{generic::BlockId, Justification, Storage};
 use sp_runtime::traits::{Block as BlockT, NumberFor, Zero, Header, HashFor};
@@ -293,6 +293,14 @@ impl<S, Block> BlockImportOperation<Block> for ImportOperation<Block, S>
 		Ok(())
 	}
 
+	fn update_offchain_storage(
+		&mut self,
+		_offchain_update: OffchainStorageCollection,
+	) -> ClientResult<()> {
+		// blocks are not executed locally => there is nothing indexed
+		Ok(())
+	}
+
 	fn update_changes_trie(
 		&mut self,
 		_update: ChangesTrieTransaction<HashFor<Block>, NumberFor<Block>>,
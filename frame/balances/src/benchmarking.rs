@@ -19,7 +19,7 @@
 use super::*;
 
 use frame_system::RawOrigin;
-use frame_benchmarking::{benchmarks, account};
+use frame_benchmarking::{benchmarks, account, whitelist_account};
 use sp_runtime::traits::Bounded;
 
 use crate::Module as Balances;
@@ -43,6 +43,7 @@ benchmarks! {
 
 		let existential_deposit = T::ExistentialDeposit::get();
 		let caller = account("caller", u, SEED);
+		whitelist_account::<T>(&caller);
 
 		// Give some multiple of the existential deposit + creation fee + transfer fee
 		let balance = existential_deposit.saturating_mul(e.into());
@@ -61,6 +62,7 @@ benchmarks! {
 		let e in ...;
 
 		let caller = account("caller", u, SEED);
+		whitelist_account::<T>(&caller);
 		let recipient: T::AccountId = account("recipient", u, SEED);
 		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
 
@@ -80,6 +82,7 @@ benchmarks! {
 		let e in ...;
 
 		let caller = account("caller", u, SEED);
+		whitelist_account::<T>(&caller);
 		let recipient = account("recipient", u, SEED);
 		let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient);
 
@@ -128,4 +128,14 @@ benchmarks! {
 			let _ = frame_benchmarking::benchmarking::current_time();
 		}
 	}
+
+	// Will check how long it takes to call `current_monotonic_time()`, by which the benchmarks are
+	// timed.
+	current_monotonic_time {
+		let z in 0 .. 1000;
+	}: {
+		for _ in 0 .. z {
+			let _ = frame_benchmarking::benchmarking::current_monotonic_time();
+		}
+	}
 }
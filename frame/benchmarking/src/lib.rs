@@ -24,6 +24,8 @@ pub use utils::*;
 #[doc(hidden)]
 pub use sp_io::storage::root as storage_root;
 pub use sp_runtime::traits::Dispatchable;
+#[doc(hidden)]
+pub use sp_runtime::traits::{OnInitialize, OnFinalize, OnRuntimeUpgrade};
 
 /// Construct pallet benchmarks for weighing dispatchables.
 ///
@@ -53,12 +55,41 @@ pub use sp_runtime::traits::Dispatchable;
 /// Note that due to parsing restrictions, if the `from` expression is not a single token (i.e. a
 /// literal or constant), then it must be parenthesised.
 ///
+/// The range of a parameter may be expressed in terms of the parameters declared before it, in
+/// which case the parameter is kept within it whatever their values, so that no impossible setup
+/// is benchmarked. For example, there cannot be more referenda than proposals:
+///
+/// ```ignore
+/// let p in 1 .. MAX_PROPOSALS;
+/// let r in 1 .. p;
+/// ```
+///
+/// The values a parameter is benchmarked at are spread evenly over its range. They may instead be
+/// spread logarithmically, at every power of two in the range and at both of its ends, or be
+/// listed, in which case the range is from the least to the greatest of them. Both are useful
+/// where the cost of a parameter is not linear, such as the size of a blob which is hashed:
+///
+/// ```ignore
+/// let x in log 1 .. MAX_LENGTH;
+/// let y in [1_024, 32_768, 1_048_576] => setup(y)?;
+/// ```
+///
 /// The macro allows for a number of "arms", each representing an individual benchmark. Using the
 /// simple syntax, the associated dispatchable function maps 1:1 with the benchmark and the name of
 /// the benchmark is the same as that of the associated function. However, extended syntax allows
 /// for arbitrary expresions to be evaluated in a benchmark (including for example,
 /// `on_initialize`).
 ///
+/// The hooks of the pallet are benchmarked with the `on_initialize(n)` and `on_finalize(n)` syntax,
+/// which runs the hook of the pallet's `Module` at block number `n`. The state the hook works on,
+/// such as the number of items it has to process, is set up by the arm as usual, so its
+/// components may control the size of that state.
+///
+/// Storage migrations are benchmarked likewise with the `on_runtime_upgrade()` syntax. The arm
+/// writes the legacy items, with a component for their number, and sets whatever storage version
+/// the migration checks, so the weight returned by the migration can be measured rather than
+/// guessed.
+///
 /// The macro allows for common parameters whose ranges and instancing expressions may be drawn upon
 /// (or not) by each arm. Syntax is available to allow for only the range to be drawn upon if
 /// desired, allowing an alternative instancing expression to be given.
@@ -71,6 +102,37 @@ pub use sp_runtime::traits::Dispatchable;
 /// Any common parameters that are unused in an arm do not have their instancing expressions
 /// evaluated.
 ///
+/// Each arm may be followed by an optional `verify` block, which is run after the benchmarked code
+/// and may use any of the arm's local variables. It is for checking that the benchmarked code
+/// actually did what it should have done, and may use `?` or `ensure!` to fail with a string error.
+/// It is not included in the timings: it is run once for every set of component values after the
+/// timed repetitions, on freshly prepared storage. `assert_last_event` and `assert_event_deposited`
+/// check the events the benchmarked code deposited, and `event_count` counts them.
+///
+/// An arm may be tagged `#[extra]` if it is too slow or too exploratory to be run whenever weights
+/// are regenerated, so that it is only run on request, or `#[skip("reason")]` if it is broken for
+/// now, so that it is not run at all and asking for it fails with the reason. Tagged arms are still
+/// compiled, and are listed with their tags by `Benchmarking::benchmarks`.
+///
+/// The `frame_system` account of the signer of a benchmarked call is read and written by every
+/// signed transaction anyway, so it is added to the whitelist of the run and its accesses are not
+/// counted. An arm which dispatches a call may be tagged `#[no_whitelist]` to count them, for
+/// example if the call touches the account in some way other transactions don't. Tags may be
+/// combined, one after the other.
+///
+/// The values a benchmark reads count towards its proof size at the size they were read at. Where
+/// the setup can't make the values of an item as big as they may get, it may hint that they count
+/// at their maximum size instead, with `storage_pov_hint(b"Module", b"Item", PovMode::MaxSize(n))`
+/// or `add_pov_hint` for any key prefix. An item which is mostly small but may be huge is better
+/// left `PovMode::Measured`, which may also be hinted to override a hint for its whole module.
+///
+/// The reads, writes and proof size of the keys of child tries are counted apart from those of the
+/// main trie, as they cost differently. The setup may whitelist a whole child trie by its storage
+/// key with `add_child_to_whitelist`, such as one which every block touches anyway.
+///
+/// When the runtime is executed as wasm, the peak and total heap allocations of the benchmarked
+/// code are recorded too, to surface calls which use far more memory than their time suggests.
+///
 /// Example:
 /// ```ignore
 /// benchmarks! {
@@ -117,6 +179,31 @@ pub use sp_runtime::traits::Dispatchable;
 ///       m.insert(i);
 ///     }
 ///   }: { m.into_iter().collect::<BTreeSet>() }
+///
+///   // this is benchmarking the `on_initialize` hook of the pallet, with `q` queued items due.
+///   on_initialize_queue {
+///     let q in 0 .. 100;
+///     let now: T::BlockNumber = 10.into();
+///     fill_queue::<T>(q, now);
+///   }: on_initialize(now)
+///
+///   // this is an exhaustive benchmark which is only run on request.
+///   #[extra]
+///   populate_a_huge_set {
+///     let x in 0 .. 10_000_000;
+///     let mut m = Vec::<u32>::new();
+///     for i in 0..x {
+///       m.insert(i);
+///     }
+///   }: { m.into_iter().collect::<BTreeSet>() }
+///
+///   // this is a dispatchable with a check that it left storage the way it should have.
+///   qux {
+///     let caller = account::<T>(b"caller", 0, benchmarks_seed);
+///   }: _(Origin::Signed(caller.clone()), 42)
+///   verify {
+///     ensure!(Something::<T>::get(&caller) == 42, "value was not stored");
+///   }
 /// }
 /// ```
 #[macro_export]
@@ -129,103 +216,471 @@ macro_rules! benchmarks {
 		}
 		$( $rest:tt )*
 	) => {
-		$crate::benchmarks_iter!({
+		$crate::benchmarks_iter!({ } {
 			$( { $common , $common_from , $common_to , $common_instancer } )*
-		} ( ) $( $rest )* );
+		} ( ) ( ) ( ) $( $rest )* );
+	}
+}
+
+/// Construct pallet benchmarks for weighing dispatchables of an instantiable pallet.
+///
+/// The syntax is that of `benchmarks!`, but the benchmarks are generic over the instance `I` of
+/// the pallet as well as over `T: Trait<I>`, so `I` may be used in the benchmark code to name the
+/// storage, origins and calls of the instance, for example `Members::<T, I>::get()` or
+/// `RawOrigin::<T::AccountId, I>::Members(1, 1)`. A named call is dispatched as `Call<T, I>`, and
+/// `run_benchmark` is implemented for `Module<T, I>`, so every instance of the pallet in a runtime
+/// can be benchmarked.
+///
+/// Example:
+/// ```ignore
+/// benchmarks_instance! {
+///   _ { }
+///
+///   set_members {
+///     let m in 1 .. MAX_MEMBERS;
+///     let members = (0 .. m).map(|i| account("member", i, SEED)).collect::<Vec<T::AccountId>>();
+///   }: _(RawOrigin::Root, members, None)
+///   verify {
+///     ensure!(Members::<T, I>::get().len() == m as usize, "members were not set");
+///   }
+/// }
+/// ```
+#[macro_export]
+macro_rules! benchmarks_instance {
+	(
+		_ {
+			$(
+				let $common:ident in $common_from:tt .. $common_to:expr => $common_instancer:expr;
+			)*
+		}
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter!({ I } {
+			$( { $common , $common_from , $common_to , $common_instancer } )*
+		} ( ) ( ) ( ) $( $rest )* );
 	}
 }
 
 #[macro_export]
 #[allow(missing_docs)]
 macro_rules! benchmarks_iter {
+	// detect and extract the `#[extra]` tag:
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		#[extra]
+		$( #[ $( $tag:tt )* ] )*
+		$name:ident
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* $name ) ( $( $names_skip )* )
+			$( #[ $( $tag )* ] )*
+			$name
+			$( $rest )*
+		}
+	};
+	// detect and extract the `#[skip(reason)]` tag:
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		#[skip( $reason:literal )]
+		$( #[ $( $tag:tt )* ] )*
+		$name:ident
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* { $name $reason } )
+			$( #[ $( $tag )* ] )*
+			$name
+			$( $rest )*
+		}
+	};
+	// detect the `#[no_whitelist]` tag, and dispatch a `_` call without whitelisting its signer:
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		#[no_whitelist]
+		$( #[ $( $tag:tt )* ] )*
+		$name:ident { $( $code:tt )* }: _ ( $origin:expr $( , $arg:expr )* )
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			#[no_whitelist]
+			$( #[ $( $tag )* ] )*
+			$name { $( $code )* }: $name ( $origin $( , $arg )* )
+			$( $rest )*
+		}
+	};
+	// detect the `#[no_whitelist]` tag, and dispatch a named call without whitelisting its signer:
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		#[no_whitelist]
+		$( #[ $( $tag:tt )* ] )*
+		$name:ident { $( $code:tt )* }: $dispatch:ident ( $origin:expr $( , $arg:expr )* )
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$( #[ $( $tag )* ] )*
+			$name { $( $code )* }: {
+				<Call<T $(, $instance)?> as $crate::Dispatchable>::dispatch(Call::<T $(, $instance)?>::$dispatch($($arg),*), $origin.into())?;
+			}
+			$( $rest )*
+		}
+	};
+	// mutation arm for the `on_initialize` hook:
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		$name:ident { $( $code:tt )* }: on_initialize ( $n:expr )
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$name { $( $code )* }: {
+				<Module<T $(, $instance)?> as $crate::OnInitialize<T::BlockNumber>>::on_initialize($n);
+			}
+			$( $rest )*
+		}
+	};
+	// mutation arm for the `on_finalize` hook:
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		$name:ident { $( $code:tt )* }: on_finalize ( $n:expr )
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$name { $( $code )* }: {
+				<Module<T $(, $instance)?> as $crate::OnFinalize<T::BlockNumber>>::on_finalize($n);
+			}
+			$( $rest )*
+		}
+	};
+	// mutation arm for the `on_runtime_upgrade` hook:
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		$name:ident { $( $code:tt )* }: on_runtime_upgrade ( )
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$name { $( $code )* }: {
+				<Module<T $(, $instance)?> as $crate::OnRuntimeUpgrade>::on_runtime_upgrade();
+			}
+			$( $rest )*
+		}
+	};
 	// mutation arm:
 	(
+		{ $( $instance:ident )? }
 		{ $( $common:tt )* }
 		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
 		$name:ident { $( $code:tt )* }: _ ( $origin:expr $( , $arg:expr )* )
+		verify $postcode:block
 		$( $rest:tt )*
 	) => {
 		$crate::benchmarks_iter! {
-			{ $( $common )* } ( $( $names )* ) $name { $( $code )* }: $name ( $origin $( , $arg )* ) $( $rest )*
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$name { $( $code )* }: $name ( $origin $( , $arg )* )
+			verify $postcode
+			$( $rest )*
 		}
 	};
 	// mutation arm:
 	(
+		{ $( $instance:ident )? }
 		{ $( $common:tt )* }
 		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
 		$name:ident { $( $code:tt )* }: $dispatch:ident ( $origin:expr $( , $arg:expr )* )
+		verify $postcode:block
 		$( $rest:tt )*
 	) => {
 		$crate::benchmarks_iter! {
-			{ $( $common )* } ( $( $names )* ) $name { $( $code )* }: { 
-				<Call<T> as $crate::Dispatchable>::dispatch(Call::<T>::$dispatch($($arg),*), $origin.into())?;
-			} $( $rest )*
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$name {
+				$( $code )*
+				// The signer is touched by every signed call, so don't count the accesses to it.
+				let origin: <Call<T $(, $instance)?> as $crate::Dispatchable>::Origin = $origin.into();
+				$crate::whitelist_signer::<T::AccountId, _>(origin.clone());
+			}: {
+				<Call<T $(, $instance)?> as $crate::Dispatchable>::dispatch(Call::<T $(, $instance)?>::$dispatch($($arg),*), origin)?;
+			}
+			verify $postcode
+			$( $rest )*
 		}
 	};
 	// iteration arm:
 	(
+		{ $( $instance:ident )? }
 		{ $( $common:tt )* }
 		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
 		$name:ident { $( $code:tt )* }: $eval:block
+		verify $postcode:block
 		$( $rest:tt )*
 	) => {
 		$crate::benchmark_backend! {
-			$name { $( $common )* } { } { $eval } { $( $code )* }
+			{ $( $instance )? } $name { $( $common )* } { } { $eval } { $( $code )* } $postcode
 		}
-		$crate::benchmarks_iter!( { $( $common )* } ( $( $names )* $name ) $( $rest )* );
+		$crate::benchmarks_iter!(
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* $name ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$( $rest )*
+		);
 	};
 	// iteration-exit arm
-	( { $( $common:tt )* } ( $( $names:ident )* ) ) => {
-		$crate::selected_benchmark!( $( $names ),* );
-		$crate::impl_benchmark!( $( $names ),* );
-	}
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+	) => {
+		$crate::selected_benchmark!( { $( $instance )? } $( $names ),* );
+		$crate::impl_benchmark!(
+			{ $( $instance )? } ( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+		);
+	};
+	// add an empty verify block to a `_` call without one
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		$name:ident { $( $code:tt )* }: _ ( $origin:expr $( , $arg:expr )* )
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$name { $( $code )* }: _ ( $origin $( , $arg )* )
+			verify { }
+			$( $rest )*
+		}
+	};
+	// add an empty verify block to a named call without one
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		$name:ident { $( $code:tt )* }: $dispatch:ident ( $origin:expr $( , $arg:expr )* )
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$name { $( $code )* }: $dispatch ( $origin $( , $arg )* )
+			verify { }
+			$( $rest )*
+		}
+	};
+	// add an empty verify block to an evaluated block without one
+	(
+		{ $( $instance:ident )? }
+		{ $( $common:tt )* }
+		( $( $names:ident )* )
+		( $( $names_extra:ident )* )
+		( $( $names_skip:tt )* )
+		$name:ident { $( $code:tt )* }: $eval:block
+		$( $rest:tt )*
+	) => {
+		$crate::benchmarks_iter! {
+			{ $( $instance )? }
+			{ $( $common )* }
+			( $( $names )* ) ( $( $names_extra )* ) ( $( $names_skip )* )
+			$name { $( $code )* }: $eval
+			verify { }
+			$( $rest )*
+		}
+	};
 }
 
 #[macro_export]
 #[allow(missing_docs)]
 macro_rules! benchmark_backend {
 	// parsing arms
-	($name:ident {
+	({ $( $instance:ident )? } $name:ident {
 		$( $common:tt )*
 	} {
 		$( PRE { $( $pre_parsed:tt )* } )*
 	} { $eval:block } {
 			let $pre_id:tt : $pre_ty:ty = $pre_ex:expr;
 			$( $rest:tt )*
-	} ) => {
+	} $postcode:block ) => {
 		$crate::benchmark_backend! {
-			$name { $( $common )* } {
+			{ $( $instance )? } $name { $( $common )* } {
 				$( PRE { $( $pre_parsed )* } )*
 				PRE { $pre_id , $pre_ty , $pre_ex }
-			} { $eval } { $( $rest )* }
+			} { $eval } { $( $rest )* } $postcode
 		}
 	};
-	($name:ident {
+	({ $( $instance:ident )? } $name:ident {
 		$( $common:tt )*
 	} {
 		$( $parsed:tt )*
 	} { $eval:block } {
 		let $param:ident in ( $param_from:expr ) .. $param_to:expr => $param_instancer:expr;
 		$( $rest:tt )*
-	}) => {
+	} $postcode:block ) => {
+		$crate::benchmark_backend! {
+			{ $( $instance )? } $name { $( $common )* } {
+				$( $parsed )*
+				PARAM { $param , $param_from , $param_to , $param_instancer , $crate::ComponentSpacing::Linear }
+			} { $eval } { $( $rest )* } $postcode
+		}
+	};
+	({ $( $instance:ident )? } $name:ident {
+		$( $common:tt )*
+	} {
+		$( $parsed:tt )*
+	} { $eval:block } {
+		let $param:ident in { $param_spacing:expr } ( $param_from:expr ) .. $param_to:expr
+			=> $param_instancer:expr;
+		$( $rest:tt )*
+	} $postcode:block ) => {
 		$crate::benchmark_backend! {
-			$name { $( $common )* } {
+			{ $( $instance )? } $name { $( $common )* } {
 				$( $parsed )*
-				PARAM { $param , $param_from , $param_to , $param_instancer }
-			} { $eval } { $( $rest )* }
+				PARAM { $param , $param_from , $param_to , $param_instancer , $param_spacing }
+			} { $eval } { $( $rest )* } $postcode
+		}
+	};
+	// mutation arm to look after a logarithmic range
+	({ $( $instance:ident )? } $name:ident {
+		$( $common:tt )*
+	} {
+		$( $parsed:tt )*
+	} { $eval:block } {
+		let $param:ident in log $param_from:tt .. $param_to:expr => $param_instancer:expr ;
+		$( $rest:tt )*
+	} $postcode:block ) => {
+		$crate::benchmark_backend! {
+			{ $( $instance )? } $name { $( $common )* } { $( $parsed )* } { $eval } {
+				let $param in { $crate::ComponentSpacing::Logarithmic } ( $param_from ) .. $param_to
+					=> $param_instancer;
+				$( $rest )*
+			} $postcode
+		}
+	};
+	// mutation arm to look after the default tail of `=> ()` for a logarithmic range
+	({ $( $instance:ident )? } $name:ident {
+		$( $common:tt )*
+	} {
+		$( $parsed:tt )*
+	} { $eval:block } {
+		let $param:ident in log $param_from:tt .. $param_to:expr;
+		$( $rest:tt )*
+	} $postcode:block ) => {
+		$crate::benchmark_backend! {
+			{ $( $instance )? } $name { $( $common )* } { $( $parsed )* } { $eval } {
+				let $param in log $param_from .. $param_to => ();
+				$( $rest )*
+			} $postcode
+		}
+	};
+	// mutation arm to look after a list of values, whose range is from the least to the greatest
+	({ $( $instance:ident )? } $name:ident {
+		$( $common:tt )*
+	} {
+		$( $parsed:tt )*
+	} { $eval:block } {
+		let $param:ident in [ $( $param_value:expr ),* $(,)? ] => $param_instancer:expr ;
+		$( $rest:tt )*
+	} $postcode:block ) => {
+		$crate::benchmark_backend! {
+			{ $( $instance )? } $name { $( $common )* } { $( $parsed )* } { $eval } {
+				let $param
+					in { $crate::ComponentSpacing::Values(vec![ $( $param_value ),* ]) }
+					({ [ $( $param_value ),* ].iter().cloned().min().unwrap_or_default() })
+					.. ({ [ $( $param_value ),* ].iter().cloned().max().unwrap_or_default() })
+					=> $param_instancer;
+				$( $rest )*
+			} $postcode
+		}
+	};
+	// mutation arm to look after the default tail of `=> ()` for a list of values
+	({ $( $instance:ident )? } $name:ident {
+		$( $common:tt )*
+	} {
+		$( $parsed:tt )*
+	} { $eval:block } {
+		let $param:ident in [ $( $param_value:expr ),* $(,)? ];
+		$( $rest:tt )*
+	} $postcode:block ) => {
+		$crate::benchmark_backend! {
+			{ $( $instance )? } $name { $( $common )* } { $( $parsed )* } { $eval } {
+				let $param in [ $( $param_value ),* ] => ();
+				$( $rest )*
+			} $postcode
 		}
 	};
 	// mutation arm to look after defaulting to a common param
-	($name:ident {
+	({ $( $instance:ident )? } $name:ident {
 		$( { $common:ident , $common_from:tt , $common_to:expr , $common_instancer:expr } )*
 	} {
 		$( $parsed:tt )*
 	} { $eval:block } {
 		let $param:ident in ...;
 		$( $rest:tt )*
-	}) => {
+	} $postcode:block ) => {
 		$crate::benchmark_backend! {
-			$name {
+			{ $( $instance )? } $name {
 				$( { $common , $common_from , $common_to , $common_instancer } )*
 			} {
 				$( $parsed )*
@@ -235,20 +690,20 @@ macro_rules! benchmark_backend {
 					.. ({ $( let $common = $common_to; )* $param })
 					=> ({ $( let $common = || -> Result<(), &'static str> { $common_instancer ; Ok(()) }; )* $param()? });
 				$( $rest )*
-			}
+			} $postcode
 		}
 	};
 	// mutation arm to look after defaulting only the range to common param
-	($name:ident {
+	({ $( $instance:ident )? } $name:ident {
 		$( { $common:ident , $common_from:tt , $common_to:expr , $common_instancer:expr } )*
 	} {
 		$( $parsed:tt )*
 	} { $eval:block } {
 		let $param:ident in _ .. _ => $param_instancer:expr ;
 		$( $rest:tt )*
-	}) => {
+	} $postcode:block ) => {
 		$crate::benchmark_backend! {
-			$name {
+			{ $( $instance )? } $name {
 				$( { $common , $common_from , $common_to , $common_instancer } )*
 			} {
 				$( $parsed )*
@@ -258,74 +713,95 @@ macro_rules! benchmark_backend {
 					.. ({ $( let $common = $common_to; )* $param })
 					=> $param_instancer ;
 				$( $rest )*
-			}
+			} $postcode
 		}
 	};
 	// mutation arm to look after a single tt for param_from.
-	($name:ident {
+	({ $( $instance:ident )? } $name:ident {
 		$( $common:tt )*
 	} {
 		$( $parsed:tt )*
 	} { $eval:block } {
 		let $param:ident in $param_from:tt .. $param_to:expr => $param_instancer:expr ;
 		$( $rest:tt )*
-	}) => {
+	} $postcode:block ) => {
 		$crate::benchmark_backend! {
-			$name { $( $common )* } { $( $parsed )* } { $eval } {
+			{ $( $instance )? } $name { $( $common )* } { $( $parsed )* } { $eval } {
 				let $param in ( $param_from ) .. $param_to => $param_instancer;
 				$( $rest )*
-			}
+			} $postcode
 		}
 	};
 	// mutation arm to look after the default tail of `=> ()`
-	($name:ident {
+	({ $( $instance:ident )? } $name:ident {
 		$( $common:tt )*
 	} {
 		$( $parsed:tt )*
 	} { $eval:block } {
 		let $param:ident in $param_from:tt .. $param_to:expr;
 		$( $rest:tt )*
-	}) => {
+	} $postcode:block ) => {
 		$crate::benchmark_backend! {
-			$name { $( $common )* } { $( $parsed )* } { $eval } {
+			{ $( $instance )? } $name { $( $common )* } { $( $parsed )* } { $eval } {
 				let $param in $param_from .. $param_to => ();
 				$( $rest )*
-			}
+			} $postcode
 		}
 	};
 	// mutation arm to look after `let _ =`
-	($name:ident {
+	({ $( $instance:ident )? } $name:ident {
 		$( $common:tt )*
 	} {
 		$( $parsed:tt )*
 	} { $eval:block } {
 		let $pre_id:tt = $pre_ex:expr;
 		$( $rest:tt )*
-	}) => {
+	} $postcode:block ) => {
 		$crate::benchmark_backend! {
-			$name { $( $common )* } { $( $parsed )* } { $eval } {
+			{ $( $instance )? } $name { $( $common )* } { $( $parsed )* } { $eval } {
 				let $pre_id : _ = $pre_ex;
 				$( $rest )*
-			}
+			} $postcode
 		}
 	};
 	// actioning arm
-	($name:ident {
+	({ $( $instance:ident )? } $name:ident {
 		$( { $common:ident , $common_from:tt , $common_to:expr , $common_instancer:expr } )*
 	} {
 		$( PRE { $pre_id:tt , $pre_ty:ty , $pre_ex:expr } )*
-		$( PARAM { $param:ident , $param_from:expr , $param_to:expr , $param_instancer:expr } )*
-	} { $eval:block } { $( $post:tt )* } ) => {
+		$( PARAM {
+			$param:ident , $param_from:expr , $param_to:expr , $param_instancer:expr , $param_spacing:expr
+		} )*
+	} { $eval:block } { $( $post:tt )* } $postcode:block ) => {
 		#[allow(non_camel_case_types)]
 		struct $name;
 		#[allow(unused_variables)]
-		impl<T: Trait> $crate::BenchmarkingSetup<T> for $name {
+		impl<T: Trait $( <$instance>, $instance: Instance )?> $crate::BenchmarkingSetup<T $(, $instance)?>
+			for $name
+		{
 			fn components(&self) -> Vec<($crate::BenchmarkParameter, u32, u32)> {
-				vec! [
-					$(
-						($crate::BenchmarkParameter::$param, $param_from, $param_to)
-					),*
-				]
+				self.bounds(&[])
+			}
+
+			fn bounds(&self, components: &[($crate::BenchmarkParameter, u32)])
+				-> Vec<($crate::BenchmarkParameter, u32, u32)>
+			{
+				#[allow(unused_mut)]
+				let mut bounds = Vec::new();
+				$(
+					let from: u32 = $param_from;
+					let to: u32 = $param_to;
+					bounds.push(($crate::BenchmarkParameter::$param, from, to));
+					// Later ranges see the value of this component, within its range.
+					let $param = components.iter()
+						.find(|&c| c.0 == $crate::BenchmarkParameter::$param)
+						.map_or(to, |c| c.1.max(from).min(to));
+				)*
+				bounds
+			}
+
+			fn spacings(&self) -> Vec<$crate::ComponentSpacing> {
+				vec! [ $( $param_spacing ),* ]
 			}
 
 			fn instance(&self, components: &[($crate::BenchmarkParameter, u32)])
@@ -346,6 +822,25 @@ macro_rules! benchmark_backend {
 
 				Ok(Box::new(move || -> Result<(), &'static str> { $eval; Ok(()) }))
 			}
+
+			fn verify(&self, components: &[($crate::BenchmarkParameter, u32)])
+				-> Result<Box<dyn FnOnce() -> Result<(), &'static str>>, &'static str>
+			{
+				$(
+					let $common = $common_from;
+				)*
+				$(
+					// Prepare instance
+					let $param = components.iter().find(|&c| c.0 == $crate::BenchmarkParameter::$param).unwrap().1;
+				)*
+				$(
+					let $pre_id : $pre_ty = $pre_ex;
+				)*
+				$( $param_instancer ; )*
+				$( $post )*
+
+				Ok(Box::new(move || -> Result<(), &'static str> { $eval; $postcode; Ok(()) }))
+			}
 		}
 	}
 }
@@ -362,11 +857,14 @@ macro_rules! benchmark_backend {
 /// struct SetBalance;
 /// impl BenchmarkingSetup for SetBalance { ... }
 ///
-/// selected_benchmark!(Transfer, SetBalance);
+/// selected_benchmark!({ } Transfer, SetBalance);
 /// ```
+///
+/// The leading braces hold the instance type parameter `I` if the pallet is instantiable.
 #[macro_export]
 macro_rules! selected_benchmark {
 	(
+		{ $( $instance:ident )? }
 		$( $bench:ident ),*
 	) => {
 		// The list of available benchmarks for this pallet.
@@ -375,11 +873,36 @@ macro_rules! selected_benchmark {
 			$( $bench, )*
 		}
 
+		// The name of every available benchmark, along with its selector.
+		#[cfg(test)]
+		#[allow(dead_code)]
+		impl SelectedBenchmark {
+			fn all() -> Vec<(&'static str, SelectedBenchmark)> {
+				vec![ $( (stringify!($bench), Self::$bench), )* ]
+			}
+		}
+
 		// Allow us to select a benchmark from the list of available benchmarks.
-		impl<T: Trait> $crate::BenchmarkingSetup<T> for SelectedBenchmark {
+		impl<T: Trait $( <$instance>, $instance: Instance )?> $crate::BenchmarkingSetup<T $(, $instance)?>
+			for SelectedBenchmark
+		{
 			fn components(&self) -> Vec<($crate::BenchmarkParameter, u32, u32)> {
 				match self {
-					$( Self::$bench => <$bench as $crate::BenchmarkingSetup<T>>::components(&$bench), )*
+					$( Self::$bench => <$bench as $crate::BenchmarkingSetup<T $(, $instance)?>>::components(&$bench), )*
+				}
+			}
+
+			fn bounds(&self, components: &[($crate::BenchmarkParameter, u32)])
+				-> Vec<($crate::BenchmarkParameter, u32, u32)>
+			{
+				match self {
+					$( Self::$bench => <$bench as $crate::BenchmarkingSetup<T $(, $instance)?>>::bounds(&$bench, components), )*
+				}
+			}
+
+			fn spacings(&self) -> Vec<$crate::ComponentSpacing> {
+				match self {
+					$( Self::$bench => <$bench as $crate::BenchmarkingSetup<T $(, $instance)?>>::spacings(&$bench), )*
 				}
 			}
 
@@ -387,7 +910,15 @@ macro_rules! selected_benchmark {
 				-> Result<Box<dyn FnOnce() -> Result<(), &'static str>>, &'static str>
 			{
 				match self {
-					$( Self::$bench => <$bench as $crate::BenchmarkingSetup<T>>::instance(&$bench, components), )*
+					$( Self::$bench => <$bench as $crate::BenchmarkingSetup<T $(, $instance)?>>::instance(&$bench, components), )*
+				}
+			}
+
+			fn verify(&self, components: &[($crate::BenchmarkParameter, u32)])
+				-> Result<Box<dyn FnOnce() -> Result<(), &'static str>>, &'static str>
+			{
+				match self {
+					$( Self::$bench => <$bench as $crate::BenchmarkingSetup<T $(, $instance)?>>::verify(&$bench, components), )*
 				}
 			}
 		}
@@ -397,19 +928,50 @@ macro_rules! selected_benchmark {
 #[macro_export]
 macro_rules! impl_benchmark {
 	(
-		$( $name:ident ),*
+		{ $( $instance:ident )? }
+		( $( $name:ident )* )
+		( $( $name_extra:ident )* )
+		( $( { $name_skip:ident $reason:literal } )* )
 	) => {
-		impl<T: Trait> $crate::Benchmarking<$crate::BenchmarkResults> for Module<T> {
+		impl<T: Trait $( <$instance>, $instance: Instance )?> $crate::Benchmarking<$crate::BenchmarkResults>
+			for Module<T $(, $instance)?>
+		{
+			fn benchmarks() -> Vec<$crate::BenchmarkMetadata> {
+				let extra: &[&str] = &[ $( stringify!($name_extra) ),* ];
+				let skip: &[(&str, &str)] = &[ $( (stringify!($name_skip), $reason) ),* ];
+				vec![ $(
+					$crate::BenchmarkMetadata {
+						name: stringify!($name).as_bytes().to_vec(),
+						extra: extra.contains(&stringify!($name)),
+						skip: skip.iter()
+							.find(|(name, _)| *name == stringify!($name))
+							.map(|(_, reason)| reason.as_bytes().to_vec()),
+						components: <SelectedBenchmark as $crate::BenchmarkingSetup<T $(, $instance)?>>::components(
+							&SelectedBenchmark::$name,
+						),
+						spacings: <SelectedBenchmark as $crate::BenchmarkingSetup<T $(, $instance)?>>::spacings(
+							&SelectedBenchmark::$name,
+						),
+					},
+				)* ]
+			}
+
 			fn run_benchmark(
 				extrinsic: Vec<u8>,
 				lowest_range_values: Vec<u32>,
 				highest_range_values: Vec<u32>,
 				steps: Vec<u32>,
 				repeat: u32,
+				whitelist: &[Vec<u8>],
 			) -> Result<Vec<$crate::BenchmarkResults>, &'static str> {
 				// Map the input to the selected benchmark.
 				let extrinsic = sp_std::str::from_utf8(extrinsic.as_slice())
 					.map_err(|_| "`extrinsic` is not a valid utf8 string!")?;
+				$(
+					if extrinsic == stringify!($name_skip) {
+						return Err(concat!("Benchmark is skipped: ", $reason));
+					}
+				)*
 				let selected_benchmark = match extrinsic {
 					$( stringify!($name) => SelectedBenchmark::$name, )*
 					_ => return Err("Could not find extrinsic."),
@@ -419,30 +981,35 @@ macro_rules! impl_benchmark {
 				$crate::benchmarking::commit_db();
 				$crate::benchmarking::wipe_db();
 
-				let components = <SelectedBenchmark as $crate::BenchmarkingSetup<T>>::components(&selected_benchmark);
+				let components = <SelectedBenchmark as $crate::BenchmarkingSetup<T $(, $instance)?>>::components(&selected_benchmark);
+				let spacings = <SelectedBenchmark as $crate::BenchmarkingSetup<T $(, $instance)?>>::spacings(&selected_benchmark);
 				let mut results: Vec<$crate::BenchmarkResults> = Vec::new();
 
 				// Default number of steps for a component.
 				let mut prev_steps = 10;
 
-				// Select the component we will be benchmarking. Each component will be benchmarked.
-				for (idx, (name, low, high)) in components.iter().enumerate() {
-					// Get the number of steps for this component.
-					let steps = steps.get(idx).cloned().unwrap_or(prev_steps);
-					prev_steps = steps;
-
-					let lowest = lowest_range_values.get(idx).cloned().unwrap_or(*low);
-					let highest = highest_range_values.get(idx).cloned().unwrap_or(*high);
+				// The values we will be testing for each component.
+				let sweeps: Vec<Vec<u32>> = components.iter()
+					.enumerate()
+					.map(|(idx, (_, low, high))| {
+						// Get the number of steps for this component.
+						let steps = steps.get(idx).cloned().unwrap_or(prev_steps);
+						prev_steps = steps;
 
-					let diff = highest - lowest;
+						let lowest = lowest_range_values.get(idx).cloned().unwrap_or(*low);
+						let highest = highest_range_values.get(idx).cloned().unwrap_or(*high);
+						spacings[idx].values(lowest, highest, steps)
+					})
+					.collect::<Result<_, _>>()?;
 
-					// Create up to `STEPS` steps for that component between high and low.
-					let step_size = (diff / steps).max(1);
-					let num_of_steps = diff / step_size + 1;
+				// The sets of component values run so far, out of all of them, for reporting progress.
+				let total_steps = sweeps.iter().map(|values| values.len() as u32).sum::<u32>();
+				let mut step = 0;
 
-					for s in 0..num_of_steps {
-						// This is the value we will be testing for component `name`
-						let component_value = lowest + step_size * s;
+				// Select the component we will be benchmarking. Each component will be benchmarked.
+				for (idx, (name, _, _)) in components.iter().enumerate() {
+					// The values we will be testing for component `name`.
+					for &component_value in &sweeps[idx] {
 
 						// Select the max value for all the other components.
 						let c: Vec<($crate::BenchmarkParameter, u32)> = components.iter()
@@ -456,36 +1023,238 @@ macro_rules! impl_benchmark {
 							)
 							.collect();
 
+						// Keep the components whose ranges depend on others within them.
+						let c = <SelectedBenchmark as $crate::BenchmarkingSetup<T $(, $instance)?>>::clamp(&selected_benchmark, &c);
+
 						// Run the benchmark `repeat` times.
-						for _ in 0..repeat {
+						for r in 0..repeat {
+							$crate::benchmarking::report_progress(step, total_steps, r, repeat);
+
+							// Start from the common whitelist, which the setup may add to.
+							$crate::benchmarking::set_whitelist(whitelist.to_vec());
+							// The proof size hints and the whitelisted child tries are given by the
+							// setup of each run.
+							$crate::benchmarking::set_pov_hints(Vec::new());
+							$crate::benchmarking::set_child_whitelist(Vec::new());
+
 							// Set up the externalities environment for the setup we want to benchmark.
-							let closure_to_benchmark = <SelectedBenchmark as $crate::BenchmarkingSetup<T>>::instance(&selected_benchmark, &c)?;
+							let closure_to_benchmark = <SelectedBenchmark as $crate::BenchmarkingSetup<T $(, $instance)?>>::instance(&selected_benchmark, &c)?;
 
 							// Commit the externalities to the database, flushing the DB cache.
 							// This will enable worst case scenario for reading from the database.
 							$crate::benchmarking::commit_db();
 
-							// Time the extrinsic logic.
-							let start_extrinsic = $crate::benchmarking::current_time();
+							// Don't count the database accesses and the allocations of the setup.
+							$crate::benchmarking::reset_read_write_count();
+							$crate::reset_allocation_stats();
+
+							// Time the extrinsic logic, by the monotonic clock and by the CPU time it uses.
+							let start_extrinsic = $crate::benchmarking::current_monotonic_time();
+							let start_extrinsic_cpu = $crate::benchmarking::current_cpu_time();
 							closure_to_benchmark()?;
-							let finish_extrinsic = $crate::benchmarking::current_time();
-							let elapsed_extrinsic = finish_extrinsic - start_extrinsic;
+							let finish_extrinsic_cpu = $crate::benchmarking::current_cpu_time();
+							let finish_extrinsic = $crate::benchmarking::current_monotonic_time();
+							let (peak_memory, allocated_memory, allocations) = $crate::allocation_stats();
+							// The clocks never go backwards, so a negative time is a broken reading.
+							let elapsed_extrinsic = finish_extrinsic.checked_sub(start_extrinsic)
+								.ok_or("The monotonic clock went backwards while timing the extrinsic.")?;
+							let elapsed_extrinsic_cpu = finish_extrinsic_cpu.checked_sub(start_extrinsic_cpu)
+								.ok_or("The CPU clock went backwards while timing the extrinsic.")?;
 
 							// Time the storage root recalculation.
-							let start_storage_root = $crate::benchmarking::current_time();
+							let start_storage_root = $crate::benchmarking::current_monotonic_time();
 							$crate::storage_root();
-							let finish_storage_root = $crate::benchmarking::current_time();
-							let elapsed_storage_root = finish_storage_root - start_storage_root;
+							let finish_storage_root = $crate::benchmarking::current_monotonic_time();
+							let elapsed_storage_root = finish_storage_root.checked_sub(start_storage_root)
+								.ok_or("The monotonic clock went backwards while timing the storage root.")?;
+
+							// Commit the changes, so that the database writes are counted.
+							$crate::benchmarking::commit_db();
+							let (reads, repeat_reads, writes, repeat_writes) =
+								$crate::benchmarking::read_write_count();
+							let proof_size = $crate::benchmarking::proof_size();
+							let (child_reads, child_repeat_reads, child_writes, child_repeat_writes) =
+								$crate::benchmarking::child_read_write_count();
+							let child_proof_size = $crate::benchmarking::child_proof_size();
 
-							results.push((c.clone(), elapsed_extrinsic, elapsed_storage_root));
+							results.push($crate::BenchmarkResults {
+								step,
+								components: c.clone(),
+								extrinsic_time: elapsed_extrinsic,
+								extrinsic_cpu_time: elapsed_extrinsic_cpu,
+								storage_root_time: elapsed_storage_root,
+								reads,
+								repeat_reads,
+								writes,
+								repeat_writes,
+								proof_size,
+								child_reads,
+								child_repeat_reads,
+								child_writes,
+								child_repeat_writes,
+								child_proof_size,
+								peak_memory,
+								allocated_memory,
+								allocations,
+								whitelist: $crate::benchmarking::get_whitelist(),
+							});
 
 							// Wipe the DB back to the genesis state.
 							$crate::benchmarking::wipe_db();
 						}
+
+						// Check the post-conditions of the benchmark once, outside of the timed runs.
+						let closure_to_verify = <SelectedBenchmark as $crate::BenchmarkingSetup<T $(, $instance)?>>::verify(&selected_benchmark, &c)?;
+						closure_to_verify()?;
+						$crate::benchmarking::wipe_db();
+						step += 1;
 					}
 				}
 				return Ok(results);
 			}
 		}
+
+		// The reason a benchmark is skipped, if it is.
+		#[cfg(test)]
+		#[allow(dead_code)]
+		impl SelectedBenchmark {
+			fn skipped(name: &str) -> Option<&'static str> {
+				$( if name == stringify!($name_skip) { return Some($reason) } )*
+				None
+			}
+		}
+	}
+}
+
+/// Generate a test which runs every benchmark of a pallet in a mock runtime, so that broken
+/// benchmark setup is caught by `cargo test` rather than by the benchmarking CLI.
+///
+/// Each benchmark is prepared, run and verified twice in fresh test externalities: once with every
+/// component at the bottom of its range and once with every component at the top. Every failure is
+/// collected before the test fails, so that they are all reported at once. Benchmarks tagged
+/// `#[extra]` are run too, but those tagged `#[skip]` are not.
+///
+/// It must be called in the same module as `benchmarks!`, and is given an expression building the
+/// test externalities and the mock runtime, both named by absolute path:
+///
+/// ```ignore
+/// impl_benchmark_test_suite!(crate::tests::new_test_ext(), crate::tests::Test);
+/// ```
+///
+/// The benchmarks of `benchmarks_instance!` are run for the instance given after the runtime:
+///
+/// ```ignore
+/// impl_benchmark_test_suite!(crate::tests::new_test_ext(), crate::tests::Test, Instance1);
+/// ```
+#[macro_export]
+macro_rules! impl_benchmark_test_suite {
+	( $new_test_ext:expr, $test:ty $(,)? ) => {
+		$crate::impl_benchmark_test_suite!($new_test_ext, $test, ());
+	};
+	( $new_test_ext:expr, $test:ty, $instance:ty $(,)? ) => {
+		#[cfg(test)]
+		mod benchmark_tests {
+			use super::*;
+
+			#[test]
+			fn benchmarks_should_run() {
+				let mut failures = Vec::new();
+				for (name, benchmark) in SelectedBenchmark::all() {
+					if let Some(reason) = SelectedBenchmark::skipped(name) {
+						println!("Skipping {}: {}", name, reason);
+						continue;
+					}
+					let components = <SelectedBenchmark as $crate::BenchmarkingSetup<$test, $instance>>::components(&benchmark);
+					let lowest = components.iter().map(|(n, low, _)| (*n, *low)).collect::<Vec<_>>();
+					let highest = components.iter().map(|(n, _, high)| (*n, *high)).collect::<Vec<_>>();
+					for c in &[lowest, highest] {
+						let c = &<SelectedBenchmark as $crate::BenchmarkingSetup<$test, $instance>>::clamp(&benchmark, c);
+						let result = $new_test_ext.execute_with(|| {
+							<SelectedBenchmark as $crate::BenchmarkingSetup<$test, $instance>>::verify(&benchmark, c)
+								.and_then(|closure| closure())
+						});
+						if let Err(e) = result {
+							failures.push(format!("{} with {:?}: {}", name, c, e));
+						}
+					}
+				}
+				assert!(failures.is_empty(), "benchmarks failed:\n{}", failures.join("\n"));
+			}
+		}
 	}
 }
+
+/// Run the benchmark asked for if it is of the given pallet, in the runtime's implementation of
+/// `dispatch_benchmark` of the `Benchmark` API.
+///
+/// `params` is a tuple of references to the parameters of `dispatch_benchmark`, followed by the
+/// whitelist, and `results` is an `Option` which is set to the results of the first pallet whose
+/// names include the pallet asked for. The pallet is given by its names and by the type of its
+/// module, which implements `Benchmarking`:
+///
+/// ```ignore
+/// let whitelist: Vec<Vec<u8>> = vec![];
+/// let params = (&module, &extrinsic, &lowest_range_values, &highest_range_values, &steps, repeat, &whitelist);
+/// let mut results = None;
+///
+/// add_benchmark!(params, results, b"pallet-balances" | b"balances", Balances);
+/// add_benchmark!(params, results, b"pallet-session" | b"session", SessionBench<Runtime>);
+///
+/// results.unwrap_or(Err("Benchmark not found for this pallet.")).map_err(|e| e.into())
+/// ```
+///
+/// The code is only compiled with the `runtime-benchmarks` feature of the crate calling the macro,
+/// so the setup of the benchmarks is never part of the production runtime.
+#[macro_export]
+macro_rules! add_benchmark {
+	( $params:ident, $results:ident, $( $name:literal )|+ , $location:ty $(,)? ) => {
+		#[cfg(feature = "runtime-benchmarks")]
+		{
+			let (module, extrinsic, lowest_range_values, highest_range_values, steps, repeat, whitelist) =
+				$params;
+			if $results.is_none() && ( $( module.as_slice() == &$name[..] )||+ ) {
+				$results = Some(
+					<$location as $crate::Benchmarking<$crate::BenchmarkResults>>::run_benchmark(
+						extrinsic.clone(),
+						lowest_range_values.clone(),
+						highest_range_values.clone(),
+						steps.clone(),
+						repeat,
+						whitelist,
+					)
+				);
+			}
+		}
+	};
+}
+
+/// List the benchmarks of the pallet asked for if it is the given pallet, in the runtime's
+/// implementation of `benchmarks` of the `Benchmark` API.
+///
+/// `list` is an `Option` which is set to the metadata of the benchmarks of the first pallet whose
+/// names include `module`. The pallet is given as to `add_benchmark!`:
+///
+/// ```ignore
+/// let mut list = None;
+///
+/// list_benchmark!(module, list, b"pallet-balances" | b"balances", Balances);
+/// list_benchmark!(module, list, b"pallet-session" | b"session", SessionBench<Runtime>);
+///
+/// list.ok_or_else(|| "Benchmark not found for this pallet.".into())
+/// ```
+///
+/// Like `add_benchmark!`, the code is only compiled with the `runtime-benchmarks` feature of the
+/// crate calling the macro.
+#[macro_export]
+macro_rules! list_benchmark {
+	( $module:ident, $list:ident, $( $name:literal )|+ , $location:ty $(,)? ) => {
+		#[cfg(feature = "runtime-benchmarks")]
+		{
+			if $list.is_none() && ( $( $module.as_slice() == &$name[..] )||+ ) {
+				$list = Some(
+					<$location as $crate::Benchmarking<$crate::BenchmarkResults>>::benchmarks()
+				);
+			}
+		}
+	};
+}
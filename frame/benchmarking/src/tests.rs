@@ -19,10 +19,11 @@
 #![cfg(test)]
 
 use super::*;
-use codec::Decode;
+use codec::{Encode, Decode};
 use sp_std::prelude::*;
+use std::cell::Cell;
 use sp_runtime::{traits::{BlakeTwo256, IdentityLookup}, testing::{H256, Header}};
-use frame_support::{dispatch::DispatchResult, decl_module, impl_outer_origin};
+use frame_support::{dispatch::DispatchResult, decl_module, impl_outer_origin, ensure, storage::StorageMap};
 use frame_system::{RawOrigin, ensure_signed, ensure_none};
 
 decl_module! {
@@ -36,18 +37,35 @@ decl_module! {
 			let _sender = ensure_none(origin)?;
 			Ok(())
 		}
+
+		fn on_initialize(n: T::BlockNumber) {
+			LAST_INITIALIZED.with(|last| last.set(n.into()));
+		}
+
+		fn on_runtime_upgrade() {
+			MIGRATED_ITEMS.with(|migrated| migrated.set(LEGACY_ITEMS.with(|legacy| legacy.replace(0))));
+		}
 	}
 }
 
+thread_local! {
+	// The block number at which `on_initialize` was last run.
+	static LAST_INITIALIZED: Cell<u32> = Cell::new(0);
+	// The items left for `on_runtime_upgrade` to migrate, and those it last migrated.
+	static LEGACY_ITEMS: Cell<u32> = Cell::new(0);
+	static MIGRATED_ITEMS: Cell<u32> = Cell::new(0);
+}
+
 impl_outer_origin! {
 	pub enum Origin for Test where system = frame_system {}
 }
 
 pub trait Trait {
 	type Event;
-	type BlockNumber;
-	type AccountId: 'static + Default + Decode;
-	type Origin: From<frame_system::RawOrigin<Self::AccountId>> + Into<Result<RawOrigin<Self::AccountId>, Self::Origin>>;
+	type BlockNumber: From<u32> + Into<u32>;
+	type AccountId: 'static + Default + Encode + Decode;
+	type Origin: From<frame_system::RawOrigin<Self::AccountId>> + Into<Result<RawOrigin<Self::AccountId>, Self::Origin>>
+		+ Clone;
 }
 
 #[derive(Clone, Eq, PartialEq)]
@@ -104,15 +122,66 @@ benchmarks!{
 		let caller = account("caller", 0, 0);
 	}: other_dummy (RawOrigin::Signed(caller), b.into())
 
+	#[extra]
+	#[no_whitelist]
+	unwhitelisted_dummy {
+		let b in ...;
+		let caller = account("caller", 0, 0);
+	}: dummy (RawOrigin::Signed(caller), b.into())
+
+	#[extra]
 	sort_vector {
 		let x in 0 .. 10000;
 		let mut m = Vec::<u32>::new();
-		for i in 0..x {
+		for i in (0..x).rev() {
 			m.push(i);
 		}
 	}: {
 		m.sort();
 	}
+	verify {
+		ensure!(m.windows(2).all(|w| w[0] <= w[1]), "You forgot to sort!")
+	}
+
+	#[skip("its verify block fails on purpose")]
+	bad_verify {
+		let x in 1 .. 10000;
+		let mut m = Vec::<u32>::new();
+		for i in (0..x).rev() {
+			m.push(i);
+		}
+	}: { }
+	verify {
+		ensure!(m.windows(2).all(|w| w[0] <= w[1]), "You forgot to sort!")
+	}
+
+	spaced_components {
+		let x in [1_024, 32_768, 1_048_576];
+		let y in log 1 .. 1000;
+	}: { }
+
+	dependent_components {
+		let x in 1 .. 100;
+		let y in 1 .. x;
+	}: { }
+	verify {
+		ensure!(y <= x, "y is out of its range");
+	}
+
+	initialize_block {
+		let b in ...;
+	}: on_initialize(b.into())
+	verify {
+		ensure!(LAST_INITIALIZED.with(|last| last.get()) == b, "on_initialize was not run");
+	}
+
+	migrate_items {
+		let i in 0 .. 100;
+		LEGACY_ITEMS.with(|legacy| legacy.set(i));
+	}: on_runtime_upgrade()
+	verify {
+		ensure!(MIGRATED_ITEMS.with(|migrated| migrated.get()) == i, "on_runtime_upgrade was not run");
+	}
 }
 
 #[test]
@@ -150,6 +219,25 @@ fn benchmarks_macro_rename_works() {
 	});
 }
 
+#[test]
+fn benchmarks_macro_works_without_whitelisting_the_signer() {
+	let selected_benchmark = SelectedBenchmark::unwhitelisted_dummy;
+	let closure = <SelectedBenchmark as BenchmarkingSetup<Test>>::instance(
+		&selected_benchmark,
+		&[(BenchmarkParameter::b, 1)],
+	).expect("failed to create closure");
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(closure(), Ok(()));
+	});
+}
+
+#[test]
+fn system_account_key_matches_frame_system() {
+	let who: u64 = 42;
+	assert_eq!(system_account_key(&who), frame_system::Account::<Test>::hashed_key_for(&who));
+}
+
 #[test]
 fn benchmarks_macro_works_for_non_dispatchable() {
 	let selected_benchmark = SelectedBenchmark::sort_vector;
@@ -164,3 +252,153 @@ fn benchmarks_macro_works_for_non_dispatchable() {
 
 	assert_eq!(closure(), Ok(()));
 }
+
+#[test]
+fn benchmarks_macro_verify_works() {
+	let selected_benchmark = SelectedBenchmark::sort_vector;
+	let closure = <SelectedBenchmark as BenchmarkingSetup<Test>>::verify(
+		&selected_benchmark,
+		&[(BenchmarkParameter::x, 10)],
+	).expect("failed to create closure");
+	assert_eq!(closure(), Ok(()));
+
+	let selected_benchmark = SelectedBenchmark::bad_verify;
+	let closure = <SelectedBenchmark as BenchmarkingSetup<Test>>::instance(
+		&selected_benchmark,
+		&[(BenchmarkParameter::x, 10)],
+	).expect("failed to create closure");
+	assert_eq!(closure(), Ok(()));
+
+	let closure = <SelectedBenchmark as BenchmarkingSetup<Test>>::verify(
+		&selected_benchmark,
+		&[(BenchmarkParameter::x, 10)],
+	).expect("failed to create closure");
+	assert_eq!(closure(), Err("You forgot to sort!"));
+}
+
+#[test]
+fn benchmarks_macro_lists_extra_and_skipped_benchmarks() {
+	let benchmarks = <Module<Test> as Benchmarking<BenchmarkResults>>::benchmarks();
+	let names = benchmarks.iter().map(|b| b.name.clone()).collect::<Vec<_>>();
+	assert_eq!(names, vec![
+		b"dummy".to_vec(),
+		b"other_name".to_vec(),
+		b"unwhitelisted_dummy".to_vec(),
+		b"sort_vector".to_vec(),
+		b"bad_verify".to_vec(),
+		b"spaced_components".to_vec(),
+		b"dependent_components".to_vec(),
+		b"initialize_block".to_vec(),
+		b"migrate_items".to_vec(),
+	]);
+	assert_eq!(
+		benchmarks.iter().map(|b| b.extra).collect::<Vec<_>>(),
+		vec![false, false, true, true, false, false, false, false, false],
+	);
+	assert_eq!(benchmarks[4].skip, Some(b"its verify block fails on purpose".to_vec()));
+	assert_eq!(benchmarks[0].components, vec![(BenchmarkParameter::b, 1, 1000)]);
+	assert_eq!(
+		benchmarks[5].spacings,
+		vec![ComponentSpacing::Values(vec![1_024, 32_768, 1_048_576]), ComponentSpacing::Logarithmic],
+	);
+	assert!(benchmarks.iter().filter(|b| b.name != b"bad_verify").all(|b| b.skip.is_none()));
+
+	assert_eq!(
+		Module::<Test>::run_benchmark(b"bad_verify".to_vec(), vec![], vec![], vec![], 1, &[]),
+		Err("Benchmark is skipped: its verify block fails on purpose"),
+	);
+}
+
+#[test]
+fn benchmarks_macro_spaces_components() {
+	let selected_benchmark = SelectedBenchmark::spaced_components;
+	let components = <SelectedBenchmark as BenchmarkingSetup<Test>>::components(&selected_benchmark);
+	assert_eq!(components, vec![
+		(BenchmarkParameter::x, 1_024, 1_048_576),
+		(BenchmarkParameter::y, 1, 1000),
+	]);
+
+	let spacings = <SelectedBenchmark as BenchmarkingSetup<Test>>::spacings(&selected_benchmark);
+	assert_eq!(spacings[0].values(1_024, 1_048_576, 10), Ok(vec![1_024, 32_768, 1_048_576]));
+	assert_eq!(spacings[0].values(0, 100_000, 10), Ok(vec![1_024, 32_768]));
+	assert_eq!(spacings[1].values(1, 1000, 10), Ok(vec![1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1000]));
+	assert_eq!(spacings[1].values(0, 4, 10), Ok(vec![0, 1, 2, 4]));
+
+	let spacings = <SelectedBenchmark as BenchmarkingSetup<Test>>::spacings(&SelectedBenchmark::dummy);
+	assert_eq!(spacings, vec![ComponentSpacing::Linear]);
+	assert_eq!(spacings[0].values(0, 10, 5), Ok(vec![0, 2, 4, 6, 8, 10]));
+	assert!(spacings[0].values(10, 0, 5).is_err());
+}
+
+#[test]
+fn benchmarks_macro_works_for_hooks() {
+	let selected_benchmark = SelectedBenchmark::initialize_block;
+	let closure = <SelectedBenchmark as BenchmarkingSetup<Test>>::instance(
+		&selected_benchmark,
+		&[(BenchmarkParameter::b, 42)],
+	).expect("failed to create closure");
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(closure(), Ok(()));
+		assert_eq!(LAST_INITIALIZED.with(|last| last.get()), 42);
+	});
+
+	let closure = <SelectedBenchmark as BenchmarkingSetup<Test>>::verify(
+		&selected_benchmark,
+		&[(BenchmarkParameter::b, 7)],
+	).expect("failed to create closure");
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(closure(), Ok(()));
+	});
+
+	let closure = <SelectedBenchmark as BenchmarkingSetup<Test>>::verify(
+		&SelectedBenchmark::migrate_items,
+		&[(BenchmarkParameter::i, 12)],
+	).expect("failed to create closure");
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(closure(), Ok(()));
+		assert_eq!(LEGACY_ITEMS.with(|legacy| legacy.get()), 0);
+	});
+}
+
+#[test]
+fn benchmarks_macro_keeps_dependent_components_in_range() {
+	let selected_benchmark = SelectedBenchmark::dependent_components;
+	let components = <SelectedBenchmark as BenchmarkingSetup<Test>>::components(&selected_benchmark);
+	assert_eq!(components, vec![(BenchmarkParameter::x, 1, 100), (BenchmarkParameter::y, 1, 100)]);
+
+	let bounds = <SelectedBenchmark as BenchmarkingSetup<Test>>::bounds(
+		&selected_benchmark,
+		&[(BenchmarkParameter::x, 10)],
+	);
+	assert_eq!(bounds, vec![(BenchmarkParameter::x, 1, 100), (BenchmarkParameter::y, 1, 10)]);
+
+	let clamp = |x, y| <SelectedBenchmark as BenchmarkingSetup<Test>>::clamp(
+		&selected_benchmark,
+		&[(BenchmarkParameter::x, x), (BenchmarkParameter::y, y)],
+	);
+	assert_eq!(clamp(10, 100), vec![(BenchmarkParameter::x, 10), (BenchmarkParameter::y, 10)]);
+	assert_eq!(clamp(10, 5), vec![(BenchmarkParameter::x, 10), (BenchmarkParameter::y, 5)]);
+	// A component whose range doesn't depend on others may go beyond it on request.
+	assert_eq!(clamp(200, 150), vec![(BenchmarkParameter::x, 200), (BenchmarkParameter::y, 150)]);
+}
+
+#[test]
+fn seeded_data_is_deterministic() {
+	let hash = seeded_hash::<H256>("proposal", 0, 0);
+	assert_eq!(hash, seeded_hash::<H256>("proposal", 0, 0));
+	assert_ne!(hash, seeded_hash::<H256>("proposal", 1, 0));
+	assert_ne!(hash, seeded_hash::<H256>("proposal", 0, 1));
+	assert_ne!(hash, H256::default());
+
+	let blob = seeded_blob("preimage", 100, 0);
+	assert_eq!(blob.len(), 100);
+	assert_eq!(blob, seeded_blob("preimage", 100, 0));
+	assert_eq!(&seeded_blob("preimage", 10, 0)[..], &blob[.. 10]);
+	assert!(seeded_blob("preimage", 0, 0).is_empty());
+
+	let members = accounts::<u64>("member", 3, 0);
+	assert_eq!(members, vec![account("member", 0, 0), account("member", 1, 0), account("member", 2, 0)]);
+}
@@ -18,8 +18,10 @@
 
 use codec::{Encode, Decode};
 use sp_std::{vec::Vec, prelude::Box};
-use sp_io::hashing::blake2_256;
+use sp_io::hashing::{blake2_128, blake2_256, twox_128};
 use sp_runtime::RuntimeString;
+use frame_support::storage::StorageMap;
+use frame_support::traits::Currency;
 
 /// An alphabet of possible parameters to use for benchmarking.
 #[derive(codec::Encode, codec::Decode, Clone, Copy, PartialEq, Debug)]
@@ -29,10 +31,126 @@ pub enum BenchmarkParameter {
 	a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p, q, r, s, t, u, v, w, x, y, z,
 }
 
+/// How the values a component is benchmarked at are spread over its range.
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Debug)]
+pub enum ComponentSpacing {
+	/// Evenly, with the number of steps asked for.
+	Linear,
+	/// At every power of two in the range and at both of its ends, whatever the number of steps,
+	/// so that small values are sampled as finely as large ones.
+	Logarithmic,
+	/// At each value of a list which lies in the range.
+	Values(Vec<u32>),
+}
+
+impl ComponentSpacing {
+	/// The values to benchmark a component at, given its range and the number of steps.
+	///
+	/// Fails if `lowest` is above `highest`.
+	pub fn values(&self, lowest: u32, highest: u32, steps: u32) -> Result<Vec<u32>, &'static str> {
+		if lowest > highest {
+			return Err("The lowest value of a component is above its highest value.")
+		}
+		let values = match self {
+			ComponentSpacing::Linear => {
+				let diff = highest - lowest;
+
+				// Create up to `steps` steps for that component between high and low.
+				let step_size = (diff / steps.max(1)).max(1);
+				let num_of_steps = diff / step_size + 1;
+				(0 .. num_of_steps).map(|s| lowest + step_size * s).collect()
+			}
+			ComponentSpacing::Logarithmic => {
+				let mut values = Vec::new();
+				values.push(lowest);
+				let mut power = 1u32;
+				while power < highest {
+					if power > lowest {
+						values.push(power);
+					}
+					power = power.saturating_mul(2);
+				}
+				if highest > lowest {
+					values.push(highest);
+				}
+				values
+			}
+			ComponentSpacing::Values(list) => {
+				let mut values = list.iter()
+					.cloned()
+					.filter(|v| *v >= lowest && *v <= highest)
+					.collect::<Vec<_>>();
+				values.sort();
+				values.dedup();
+				if values.is_empty() {
+					values.push(highest);
+				}
+				values
+			}
+		};
+		Ok(values)
+	}
+}
+
 /// Results from running benchmarks on a FRAME pallet.
 /// Contains duration of the function call in nanoseconds along with the benchmark parameters
-/// used for that benchmark result.
-pub type BenchmarkResults = (Vec<(BenchmarkParameter, u32)>, u128, u128);
+/// used for that benchmark result, and the database reads and writes it made.
+#[derive(codec::Encode, codec::Decode, Default, Clone, PartialEq, Debug)]
+pub struct BenchmarkResults {
+	/// The index of the set of component values this is a run of, in the order they were run.
+	pub step: u32,
+	/// The value of each component.
+	pub components: Vec<(BenchmarkParameter, u32)>,
+	/// The time taken by the benchmarked code, in nanoseconds.
+	pub extrinsic_time: u128,
+	/// The CPU time used by the benchmarked code, in nanoseconds.
+	pub extrinsic_cpu_time: u128,
+	/// The time taken to recalculate the storage root afterwards, in nanoseconds.
+	pub storage_root_time: u128,
+	/// The number of distinct keys read from the database.
+	pub reads: u32,
+	/// The number of reads of keys which had already been read.
+	pub repeat_reads: u32,
+	/// The number of distinct keys written to the database.
+	pub writes: u32,
+	/// The number of writes to keys which had already been written.
+	pub repeat_writes: u32,
+	/// The encoded size of the storage proof of the reads, in bytes.
+	pub proof_size: u32,
+	/// The number of distinct keys of child tries read.
+	pub child_reads: u32,
+	/// The number of reads of keys of child tries which had already been read.
+	pub child_repeat_reads: u32,
+	/// The number of distinct keys of child tries written.
+	pub child_writes: u32,
+	/// The number of writes to keys of child tries which had already been written.
+	pub child_repeat_writes: u32,
+	/// The encoded size of the storage proof of the reads of child tries, in bytes.
+	pub child_proof_size: u32,
+	/// The greatest number of bytes the benchmarked code held on the heap at once.
+	pub peak_memory: u32,
+	/// The number of bytes the benchmarked code allocated, including those it freed.
+	pub allocated_memory: u64,
+	/// The number of allocations of the benchmarked code.
+	pub allocations: u32,
+	/// The keys whose accesses were counted as repeated ones, whether or not they were accessed.
+	pub whitelist: Vec<Vec<u8>>,
+}
+
+/// A benchmark of a pallet, as listed by `Benchmarking::benchmarks`.
+#[derive(codec::Encode, codec::Decode, Default, Clone, PartialEq, Debug)]
+pub struct BenchmarkMetadata {
+	/// The name of the benchmark.
+	pub name: Vec<u8>,
+	/// Whether the benchmark is tagged `#[extra]`, so that it is only run on request.
+	pub extra: bool,
+	/// Why the benchmark is skipped, if it is tagged `#[skip]`.
+	pub skip: Option<Vec<u8>>,
+	/// The components of the benchmark with their ranges, as `(parameter, lowest, highest)`.
+	pub components: Vec<(BenchmarkParameter, u32, u32)>,
+	/// How the values of each component are spread over its range, in the order of `components`.
+	pub spacings: Vec<ComponentSpacing>,
+}
 
 sp_api::decl_runtime_apis! {
 	/// Runtime api for benchmarking a FRAME runtime.
@@ -46,7 +164,64 @@ sp_api::decl_runtime_apis! {
 			steps: Vec<u32>,
 			repeat: u32,
 		) -> Result<Vec<BenchmarkResults>, RuntimeString>;
+
+		/// List the benchmarks of the given pallet.
+		fn benchmarks(module: Vec<u8>) -> Result<Vec<BenchmarkMetadata>, RuntimeString>;
+
+		/// List the names of the benchmarked pallets, by which they are selected.
+		fn pallets() -> Vec<Vec<u8>>;
+	}
+}
+
+#[cfg(feature = "std")]
+lazy_static::lazy_static! {
+	// The point from which `current_monotonic_time` measures where it falls back to `Instant`.
+	static ref MONOTONIC_BASE: std::time::Instant = std::time::Instant::now();
+	// Whether the raw clock of the hardware can be read. This is decided once, so that the readings
+	// of `current_monotonic_time` in a process are all by the same clock.
+	static ref RAW_CLOCK: bool = read_clock(RAW_CLOCK_ID).is_some();
+	// Whether the CPU time of a thread can be read, likewise for `current_cpu_time`.
+	static ref CPU_CLOCK: bool = read_clock(CPU_CLOCK_ID).is_some();
+}
+
+#[cfg(all(feature = "std", any(target_os = "linux", target_os = "android")))]
+const RAW_CLOCK_ID: libc::clockid_t = libc::CLOCK_MONOTONIC_RAW;
+#[cfg(all(feature = "std", unix, not(any(target_os = "linux", target_os = "android"))))]
+const RAW_CLOCK_ID: libc::clockid_t = libc::CLOCK_MONOTONIC;
+#[cfg(all(feature = "std", unix))]
+const CPU_CLOCK_ID: libc::clockid_t = libc::CLOCK_THREAD_CPUTIME_ID;
+#[cfg(all(feature = "std", not(unix)))]
+const RAW_CLOCK_ID: i32 = 0;
+#[cfg(all(feature = "std", not(unix)))]
+const CPU_CLOCK_ID: i32 = 0;
+
+/// The reading of the given clock in nanoseconds, or `None` if it can't be read.
+#[cfg(all(feature = "std", unix))]
+fn read_clock(clock: libc::clockid_t) -> Option<u128> {
+	let mut time = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+	// Safe, as `time` is a valid `timespec` to write to.
+	if unsafe { libc::clock_gettime(clock, &mut time) } == 0 {
+		Some(time.tv_sec as u128 * 1_000_000_000 + time.tv_nsec as u128)
+	} else {
+		None
+	}
+}
+
+/// The reading of the clock chosen for `current_monotonic_time`.
+#[cfg(feature = "std")]
+fn monotonic_time() -> u128 {
+	// Taken before the raw clock is tried, so that the fallback measures from the first reading.
+	let base = *MONOTONIC_BASE;
+	if *RAW_CLOCK {
+		return read_clock(RAW_CLOCK_ID).expect("The raw clock was read when it was chosen; qed")
 	}
+	std::time::Instant::now().duration_since(base).as_nanos()
+}
+
+/// The clocks of `clock_gettime` can't be read outside of unix.
+#[cfg(all(feature = "std", not(unix)))]
+fn read_clock(_clock: i32) -> Option<u128> {
+	None
 }
 
 /// Interface that provides functions for benchmarking the runtime.
@@ -62,6 +237,32 @@ pub trait Benchmarking {
 			.as_nanos()
 	}
 
+	/// Get the number of nanoseconds passed since some fixed point in the past, by a clock which,
+	/// unlike that of `current_time`, never jumps nor goes backwards and reads at the precision of
+	/// the hardware. Only differences between its readings are meaningful. Where the raw clock of
+	/// the hardware can't be read, the time is measured from the first reading in the process by
+	/// the monotonic clock of `std`. Which of the two is read is decided once for the process.
+	///
+	/// WARNING! This is a non-deterministic call. Do not use this within
+	/// consensus critical logic.
+	fn current_monotonic_time() -> u128 {
+		monotonic_time()
+	}
+
+	/// Get the number of nanoseconds of CPU time used by the current thread, which unlike
+	/// `current_monotonic_time` leaves out the time it spent waiting for I/O or preempted. Where
+	/// this isn't supported, it is the same as `current_monotonic_time`. Which of the two is read
+	/// is decided once for the process.
+	///
+	/// WARNING! This is a non-deterministic call. Do not use this within
+	/// consensus critical logic.
+	fn current_cpu_time() -> u128 {
+		if *CPU_CLOCK {
+			return read_clock(CPU_CLOCK_ID).expect("The CPU clock was read when it was chosen; qed")
+		}
+		monotonic_time()
+	}
+
 	/// Reset the trie database to the genesis state.
 	fn wipe_db(&mut self) {
 		self.wipe()
@@ -71,10 +272,139 @@ pub trait Benchmarking {
 	fn commit_db(&mut self) {
 		self.commit()
 	}
+
+	/// Get the number of database reads and writes since the last reset, as `(reads,
+	/// repeat_reads, writes, repeat_writes)`.
+	fn read_write_count(&self) -> (u32, u32, u32, u32) {
+		self.read_write_count()
+	}
+
+	/// Get the number of reads and writes of the keys of child tries since the last reset, as
+	/// `(reads, repeat_reads, writes, repeat_writes)`.
+	fn child_read_write_count(&self) -> (u32, u32, u32, u32) {
+		self.child_read_write_count()
+	}
+
+	/// Reset the database read and write counts, and the recorded storage proofs, of the main trie
+	/// and of the child tries.
+	fn reset_read_write_count(&mut self) {
+		self.reset_read_write_count()
+	}
+
+	/// Get the encoded size of the storage proof of the reads since the last reset.
+	fn proof_size(&self) -> u32 {
+		self.proof_size()
+	}
+
+	/// Get the encoded size of the storage proof of the reads of child tries since the last reset.
+	fn child_proof_size(&self) -> u32 {
+		self.child_proof_size()
+	}
+
+	/// Get the keys whose accesses are not counted as database reads and writes.
+	fn get_whitelist(&self) -> Vec<Vec<u8>> {
+		self.get_whitelist()
+	}
+
+	/// Set the keys whose accesses are not counted as database reads and writes.
+	fn set_whitelist(&mut self, new: Vec<Vec<u8>>) {
+		self.set_whitelist(new)
+	}
+
+	/// Get the storage keys of the child tries whose accesses are not counted as reads and writes.
+	fn get_child_whitelist(&self) -> Vec<Vec<u8>> {
+		self.get_child_whitelist()
+	}
+
+	/// Set the storage keys of the child tries whose accesses are not counted as reads and writes.
+	fn set_child_whitelist(&mut self, new: Vec<Vec<u8>>) {
+		self.set_child_whitelist(new)
+	}
+
+	/// Get the hints of how the values under each key prefix count towards the proof size, as
+	/// `(prefix, max_size)`, where values are measured if `max_size` is `None`.
+	fn get_pov_hints(&self) -> Vec<(Vec<u8>, Option<u32>)> {
+		self.get_pov_hints()
+	}
+
+	/// Set the hints of how the values under each key prefix count towards the proof size.
+	fn set_pov_hints(&mut self, new: Vec<(Vec<u8>, Option<u32>)>) {
+		self.set_pov_hints(new)
+	}
+
+	/// Report that the `repeat`th of `repeats` runs at the `step`th of the `steps` sets of
+	/// component values of the benchmark is starting, to the `ProgressExt` extension if the client
+	/// registered one.
+	fn report_progress(&mut self, step: u32, steps: u32, repeat: u32, repeats: u32) {
+		use sp_externalities::ExternalitiesExt;
+		if let Some(progress) = self.extension::<ProgressExt>() {
+			(progress.0)(BenchmarkProgress { step, steps, repeat, repeats })
+		}
+	}
+}
+
+/// Interface that provides the statistics of the allocations of the runtime, which only the wasm
+/// executor keeps.
+#[sp_runtime_interface::runtime_interface(wasm_only)]
+pub trait BenchmarkingAllocator {
+	/// Get the statistics of the allocations since the last reset, as `(peak, total, count)`.
+	fn allocations(&mut self) -> (u32, u64, u32) {
+		let stats = self.allocation_stats();
+		(stats.peak, stats.total, stats.count)
+	}
+
+	/// Reset the statistics of the allocations.
+	fn reset_allocations(&mut self) {
+		self.reset_allocation_stats()
+	}
+}
+
+/// Get the statistics of the allocations of the runtime since the last reset, as `(peak, total,
+/// count)`. They are only kept when the runtime is executed as wasm, and are zero natively.
+pub fn allocation_stats() -> (u32, u64, u32) {
+	#[cfg(not(feature = "std"))]
+	{
+		benchmarking_allocator::allocations()
+	}
+	#[cfg(feature = "std")]
+	{
+		(0, 0, 0)
+	}
+}
+
+/// Reset the statistics of the allocations of the runtime, if it is executed as wasm.
+pub fn reset_allocation_stats() {
+	#[cfg(not(feature = "std"))]
+	benchmarking_allocator::reset_allocations()
+}
+
+/// How far a benchmark run has got, as reported by `benchmarking::report_progress`. Both counts
+/// start from zero.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BenchmarkProgress {
+	/// The set of component values being run.
+	pub step: u32,
+	/// The number of sets of component values of the run.
+	pub steps: u32,
+	/// The repetition being run at this set of component values.
+	pub repeat: u32,
+	/// The number of repetitions at each set of component values.
+	pub repeats: u32,
+}
+
+#[cfg(feature = "std")]
+sp_externalities::decl_extension! {
+	/// The extension through which a client follows the progress of benchmark runs, which may take
+	/// hours for a whole runtime.
+	pub struct ProgressExt(Box<dyn FnMut(BenchmarkProgress) + Send>);
 }
 
 /// The pallet benchmarking trait.
 pub trait Benchmarking<T> {
+	/// List every benchmark of this pallet, including the extra and skipped ones.
+	fn benchmarks() -> Vec<BenchmarkMetadata>;
+
 	/// Run the benchmarks for this pallet.
 	///
 	/// Parameters
@@ -83,22 +413,55 @@ pub trait Benchmarking<T> {
 	/// - `lowest_range_values`: The lowest number for each range of parameters.
 	/// - `highest_range_values`: The highest number for each range of parameters.
 	/// - `repeat`: The number of times you want to repeat a benchmark.
+	/// - `whitelist`: The keys whose accesses are not counted as database reads and writes, such
+	///   as the block number. A benchmark may add to them in its setup.
 	fn run_benchmark(
 		extrinsic: Vec<u8>,
 		lowest_range_values: Vec<u32>,
 		highest_range_values: Vec<u32>,
 		steps: Vec<u32>,
 		repeat: u32,
+		whitelist: &[Vec<u8>],
 	) -> Result<Vec<T>, &'static str>;
 }
 
 /// The required setup for creating a benchmark.
-pub trait BenchmarkingSetup<T> {
+///
+/// `I` is the instance of the pallet, which is `()` for pallets which are not instantiable.
+pub trait BenchmarkingSetup<T, I = ()> {
 	/// Return the components and their ranges which should be tested in this benchmark.
 	fn components(&self) -> Vec<(BenchmarkParameter, u32, u32)>;
 
+	/// Return the range of each component given the values of the components declared before it,
+	/// which its range may be expressed in terms of. A component missing from `components` is
+	/// taken to be at the top of its range.
+	fn bounds(&self, components: &[(BenchmarkParameter, u32)]) -> Vec<(BenchmarkParameter, u32, u32)>;
+
+	/// Bring the value of each component whose range depends on other components within its range
+	/// given their values, so that no impossible setup is benchmarked. The other components are
+	/// left as they are, so that they may still be benchmarked beyond their ranges on request.
+	fn clamp(&self, components: &[(BenchmarkParameter, u32)]) -> Vec<(BenchmarkParameter, u32)> {
+		let ranges = self.components();
+		self.bounds(components).into_iter().zip(ranges).map(|((name, from, to), range)| {
+			let value = components.iter().find(|c| c.0 == name).map_or(to, |c| c.1);
+			if (from, to) == (range.1, range.2) {
+				(name, value)
+			} else {
+				(name, value.max(from).min(to))
+			}
+		}).collect()
+	}
+
+	/// Return how the values of each component are spread over its range, in the order of
+	/// `components`.
+	fn spacings(&self) -> Vec<ComponentSpacing>;
+
 	/// Set up the storage, and prepare a closure to test in a single run of the benchmark.
 	fn instance(&self, components: &[(BenchmarkParameter, u32)]) -> Result<Box<dyn FnOnce() -> Result<(), &'static str>>, &'static str>;
+
+	/// Set up the storage, and prepare a closure to run the benchmark once and then check its
+	/// post-conditions.
+	fn verify(&self, components: &[(BenchmarkParameter, u32)]) -> Result<Box<dyn FnOnce() -> Result<(), &'static str>>, &'static str>;
 }
 
 /// Grab an account, seeded by a name and index.
@@ -106,3 +469,167 @@ pub fn account<AccountId: Decode + Default>(name: &'static str, index: u32, seed
 	let entropy = (name, index, seed).using_encoded(blake2_256);
 	AccountId::decode(&mut &entropy[..]).unwrap_or_default()
 }
+
+/// Grab `count` accounts, seeded by a name, which are those of the indices `0 .. count`.
+pub fn accounts<AccountId: Decode + Default>(name: &'static str, count: u32, seed: u32) -> Vec<AccountId> {
+	(0 .. count).map(|index| account(name, index, seed)).collect()
+}
+
+/// A pseudo-random hash, seeded by a name and index, which is the same on every machine and
+/// unlike `Default::default()` doesn't collide with those of other names and indices.
+pub fn seeded_hash<Hash: Decode + Default>(name: &'static str, index: u32, seed: u32) -> Hash {
+	let entropy = ("hash", name, index, seed).using_encoded(blake2_256);
+	Hash::decode(&mut &entropy[..]).unwrap_or_default()
+}
+
+/// `size` pseudo-random bytes, seeded by a name, which are the same on every machine.
+pub fn seeded_blob(name: &'static str, size: u32, seed: u32) -> Vec<u8> {
+	let mut blob = Vec::with_capacity(size as usize);
+	let mut chunk = 0u32;
+	while blob.len() < size as usize {
+		blob.extend_from_slice(&("blob", name, chunk, seed).using_encoded(blake2_256));
+		chunk += 1;
+	}
+	blob.truncate(size as usize);
+	blob
+}
+
+/// Grab an account, seeded by a name and index, and make `balance` its free balance in `C`.
+pub fn funded_account<AccountId, C>(
+	name: &'static str,
+	index: u32,
+	seed: u32,
+	balance: C::Balance,
+) -> AccountId where
+	AccountId: Decode + Default,
+	C: Currency<AccountId>,
+{
+	let who = account(name, index, seed);
+	let _ = C::make_free_balance_be(&who, balance);
+	who
+}
+
+/// Grab an account related to `of`, such as its proxy (with the relation `"proxy"`) or its
+/// delegate (with `"delegate"`). It is always the same for the same account and relation, and
+/// differs between accounts and between relations.
+pub fn related_account<AccountId: Encode + Decode + Default>(
+	of: &AccountId,
+	relation: &'static str,
+) -> AccountId {
+	let entropy = (relation, of).using_encoded(blake2_256);
+	AccountId::decode(&mut &entropy[..]).unwrap_or_default()
+}
+
+/// The storage key of a storage value, given the prefix of its module and its name.
+pub fn storage_value_key(module: &[u8], item: &[u8]) -> Vec<u8> {
+	[twox_128(module), twox_128(item)].concat()
+}
+
+/// Add a key to the whitelist of the current benchmark run, so that accesses to it are not
+/// counted as database reads and writes.
+pub fn add_to_whitelist(key: Vec<u8>) {
+	let mut whitelist = benchmarking::get_whitelist();
+	if !whitelist.contains(&key) {
+		whitelist.push(key);
+		benchmarking::set_whitelist(whitelist);
+	}
+}
+
+/// Add a child trie, by its storage key, to the whitelist of the current benchmark run, so that
+/// accesses to any of its keys are not counted as reads and writes of child tries.
+pub fn add_child_to_whitelist(storage_key: Vec<u8>) {
+	let mut whitelist = benchmarking::get_child_whitelist();
+	if !whitelist.contains(&storage_key) {
+		whitelist.push(storage_key);
+		benchmarking::set_child_whitelist(whitelist);
+	}
+}
+
+/// How the values of a storage item count towards the proof size of a benchmark.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PovMode {
+	/// The values count at the size they were read at, which is the default.
+	Measured,
+	/// Every value read counts as this many bytes, whatever size it was read at. This is for
+	/// items whose values the benchmark can't make as big as they may get.
+	MaxSize(u32),
+}
+
+/// Hint how the values of the storage keys starting with `prefix` count towards the proof size
+/// of the current benchmark run. Where hints overlap, the one with the longest prefix applies, so
+/// a pallet may bound all of its items and have some of them measured.
+pub fn add_pov_hint(prefix: Vec<u8>, mode: PovMode) {
+	let max_size = match mode {
+		PovMode::Measured => None,
+		PovMode::MaxSize(size) => Some(size),
+	};
+	let mut hints = benchmarking::get_pov_hints();
+	hints.retain(|(p, _)| p != &prefix);
+	hints.push((prefix, max_size));
+	benchmarking::set_pov_hints(hints);
+}
+
+/// Hint how the values of a storage item, given the prefix of its module and its name, count
+/// towards the proof size of the current benchmark run, as `add_pov_hint` does.
+pub fn storage_pov_hint(module: &[u8], item: &[u8], mode: PovMode) {
+	add_pov_hint(storage_value_key(module, item), mode)
+}
+
+/// Add the `frame_system` account of `who` to the whitelist of the current benchmark run. The
+/// account of the caller is read and written by every signed transaction anyway, to pay its fees.
+pub fn whitelist_account<T: frame_system::Trait>(who: &T::AccountId) {
+	add_to_whitelist(frame_system::Account::<T>::hashed_key_for(who));
+}
+
+/// The number of events deposited in the current block. In a `verify` block, those of the
+/// benchmarked code alone are the difference from the count taken at the end of the setup.
+pub fn event_count<T: frame_system::Trait>() -> u32 {
+	frame_system::Module::<T>::event_count()
+}
+
+/// Check, for a `verify` block, that `event` is the last event deposited.
+pub fn assert_last_event<T: frame_system::Trait>(
+	event: impl Into<<T as frame_system::Trait>::Event>,
+) -> Result<(), &'static str> {
+	let event = event.into();
+	match frame_system::Module::<T>::events().last() {
+		Some(record) if record.event == event => Ok(()),
+		Some(_) => Err("The last event is not the expected one"),
+		None => Err("No event was deposited"),
+	}
+}
+
+/// Check, for a `verify` block, that `event` is one of the events deposited.
+pub fn assert_event_deposited<T: frame_system::Trait>(
+	event: impl Into<<T as frame_system::Trait>::Event>,
+) -> Result<(), &'static str> {
+	let event = event.into();
+	if frame_system::Module::<T>::events().iter().any(|record| record.event == event) {
+		Ok(())
+	} else {
+		Err("The expected event was not deposited")
+	}
+}
+
+/// Add the `frame_system` account of the signer of `origin`, if it is signed, to the whitelist of
+/// the current benchmark run, as `whitelist_account` does.
+///
+/// This is called for the origin of every call benchmarked by `benchmarks!`, unless it is tagged
+/// with `#[no_whitelist]`.
+pub fn whitelist_signer<AccountId, Origin>(origin: Origin) where
+	AccountId: Encode,
+	Origin: Into<Result<frame_system::RawOrigin<AccountId>, Origin>>,
+{
+	if let Ok(frame_system::RawOrigin::Signed(who)) = origin.into() {
+		add_to_whitelist(system_account_key(&who));
+	}
+}
+
+/// The storage key of the `frame_system` account of `who`, without knowing the runtime.
+pub fn system_account_key<AccountId: Encode>(who: &AccountId) -> Vec<u8> {
+	// `frame_system::Account` is a `blake2_128_concat` map.
+	let mut key = storage_value_key(b"System", b"Account");
+	key.extend_from_slice(&who.using_encoded(blake2_128));
+	who.using_encoded(|encoded| key.extend_from_slice(encoded));
+	key
+}
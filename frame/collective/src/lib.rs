@@ -26,9 +26,10 @@
 //! Voting happens through motions comprising a proposal (i.e. a curried dispatchable) plus a
 //! number of approvals required for it to pass and be called. Motions are open for members to
 //! vote on for a minimum period given by `MotionDuration`. As soon as the needed number of
-//! approvals is given, the motion is closed and executed. If the number of approvals is not reached
-//! during the voting period, then `close` may be called by any account in order to force the end
-//! the motion explicitly. If a prime member is defined then their vote is used in place of any
+//! approvals is given, `close` may be called by any account in order to execute the motion, as it
+//! is charged for the weight of the proposal. If the number of approvals is not reached during the
+//! voting period, then `close` may be called by any account in order to force the end the motion
+//! explicitly. If a prime member is defined then their vote is used in place of any
 //! abstentions and the proposal is executed if there are enough approvals counting the new votes.
 //!
 //! If there are not, or if no prime is set, then the motion is dropped without being executed.
@@ -40,7 +41,7 @@ use sp_std::{prelude::*, result};
 use sp_core::u32_trait::Value as U32;
 use sp_runtime::RuntimeDebug;
 use sp_runtime::traits::{Hash, EnsureOrigin};
-use frame_support::weights::SimpleDispatchInfo;
+use frame_support::weights::{SimpleDispatchInfo, FunctionOf, DispatchClass, GetDispatchInfo, Weight};
 use frame_support::{
 	dispatch::{Dispatchable, Parameter}, codec::{Encode, Decode},
 	traits::{Get, ChangeMembers, InitializeMembers}, decl_module, decl_event,
@@ -48,6 +49,9 @@ use frame_support::{
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 /// Simple index type for proposal counting.
 pub type ProposalIndex = u32;
 
@@ -62,7 +66,10 @@ pub trait Trait<I=DefaultInstance>: frame_system::Trait {
 	type Origin: From<RawOrigin<Self::AccountId, I>>;
 
 	/// The outer call dispatch type.
-	type Proposal: Parameter + Dispatchable<Origin=<Self as Trait<I>>::Origin>;
+	type Proposal: Parameter
+		+ Dispatchable<Origin=<Self as Trait<I>>::Origin>
+		+ From<frame_system::Call<Self>>
+		+ GetDispatchInfo;
 
 	/// The outer event type.
 	type Event: From<Event<Self, I>> + Into<<Self as frame_system::Trait>::Event>;
@@ -165,6 +172,12 @@ decl_error! {
 		AlreadyInitialized,
 		/// The close call is made too early, before the end of the voting.
 		TooEarly,
+		/// The given weight bound for the proposal was too low.
+		WrongProposalWeight,
+		/// The given length bound for the proposal was too low.
+		WrongProposalLength,
+		/// The prime member is not one of the members.
+		PrimeAccountNotMember,
 	}
 }
 
@@ -179,9 +192,29 @@ mod migration {
 	}
 }
 
-// Note: this module is not benchmarked. The weights are obtained based on the similarity of the
-// executed logic with other democracy function. Note that councillor operations are assigned to the
-// operational class.
+mod weight_for {
+	use frame_support::weights::Weight;
+
+	/// Closing a motion whose proposal is `b` bytes long, dispatching it if approved.
+	///
+	/// `proposal` is the weight of the proposal, which is charged for whether it is dispatched or
+	/// not, and given back to the block if it is not.
+	///
+	/// The constants are estimates picked by hand and not yet fitted to measurements. The `close`
+	/// and `close_approved` benchmarks cover the paths without and with a dispatch, for `b` bytes,
+	/// and for numbers of members and of open motions which the arguments do not tell, so that the
+	/// base should be fitted to their worst case. Their results should replace the constants before
+	/// a chain relies on them.
+	pub fn close(proposal: Weight, b: Weight) -> Weight {
+		(200_000 as Weight)
+			.saturating_add(proposal)
+			.saturating_add(b.saturating_mul(1_000))
+	}
+}
+
+// Note: the weights of this module are not yet fitted to its benchmarks. They are obtained based on
+// the similarity of the executed logic with other democracy function. Note that councillor
+// operations are assigned to the operational class.
 decl_module! {
 	pub struct Module<T: Trait<I>, I: Instance=DefaultInstance> for enum Call where origin: <T as frame_system::Trait>::Origin {
 		type Error = Error<T, I>;
@@ -196,12 +229,16 @@ decl_module! {
 		///
 		/// - `new_members`: The new member list. Be nice to the chain and
 		//	provide it sorted.
-		/// - `prime`: The prime member whose vote sets the default.
+		/// - `prime`: The prime member whose vote sets the default for the members who abstain
+		///   when a motion is closed. It must be one of `new_members`.
 		///
 		/// Requires root origin.
 		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
 		fn set_members(origin, new_members: Vec<T::AccountId>, prime: Option<T::AccountId>) {
 			ensure_root(origin)?;
+			if let Some(p) = &prime {
+				ensure!(new_members.contains(p), Error::<T, I>::PrimeAccountNotMember);
+			}
 			let mut new_members = new_members;
 			new_members.sort();
 			let old = Members::<T, I>::get();
@@ -252,9 +289,13 @@ decl_module! {
 			}
 		}
 
+		/// Only records the vote. A motion which can no longer be approved is dropped at once, but
+		/// one which has enough approvals is left to `close`, which is charged for the dispatch of
+		/// its proposal.
+		///
 		/// # <weight>
 		/// - Bounded storage read and writes.
-		/// - Will be slightly heavier if the proposal is approved / disapproved after the vote.
+		/// - Will be slightly heavier if the proposal is disapproved after the vote.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedOperational(200_000)]
 		fn vote(origin, proposal: T::Hash, #[compact] index: ProposalIndex, approve: bool) {
@@ -293,53 +334,100 @@ decl_module! {
 
 			let seats = Self::members().len() as MemberCount;
 
-			let approved = yes_votes >= voting.threshold;
 			let disapproved = seats.saturating_sub(no_votes) < voting.threshold;
-			if approved || disapproved {
-				Self::finalize_proposal(approved, seats, voting, proposal);
+			if disapproved {
+				Self::finalize_proposal(false, seats, voting, proposal);
 			} else {
 				Voting::<T, I>::insert(&proposal, voting);
 			}
 		}
 
-		/// May be called by any signed account after the voting duration has ended in order to
-		/// finish voting and close the proposal.
+		/// May be called by any signed account in order to finish voting and close the proposal,
+		/// once it has enough approvals or else after the voting duration has ended.
+		///
+		/// After the voting duration, abstentions are counted as rejections unless there is a prime
+		/// member set and the prime member cast an approval.
+		///
+		/// - `proposal_weight_bound`: The weight of the proposal, at least. The call is charged for
+		///   it up front, and the part of it unused is given back to the block after, all of it if
+		///   the proposal is not dispatched.
+		/// - `length_bound`: The encoded length of the proposal, at least, which is charged for
+		///   likewise.
 		///
-		/// Abstentions are counted as rejections unless there is a prime member set and the prime
-		/// member cast an approval.
+		/// NOTE: only room in the block is given back. The fee is paid for both bounds in full, as
+		/// transaction payment takes it before dispatch and has no way to return part of it yet,
+		/// so the bounds are best kept tight.
 		///
-		/// - the weight of `proposal` preimage.
+		/// Fails with `WrongProposalLength` if the proposal is longer than `length_bound`, or, if it
+		/// is approved, with `WrongProposalWeight` if its weight is more than
+		/// `proposal_weight_bound`.
+		///
+		/// # <weight>
+		/// - the weight of `proposal` preimage, up to `proposal_weight_bound`.
 		/// - up to three events deposited.
 		/// - one read, two removals, one mutation. (plus three static reads.)
 		/// - computation and i/o `O(P + L + M)` where:
 		///   - `M` is number of members,
 		///   - `P` is number of active proposals,
-		///   - `L` is the encoded length of `proposal` preimage.
-		#[weight = SimpleDispatchInfo::FixedOperational(200_000)]
-		fn close(origin, proposal: T::Hash, #[compact] index: ProposalIndex) {
+		///   - `L` is the encoded length of `proposal` preimage, up to `length_bound`.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|(_, _, w, l): (&T::Hash, &ProposalIndex, &Weight, &u32)| weight_for::close(*w, *l as Weight),
+			DispatchClass::Operational,
+			true,
+		)]
+		fn close(
+			origin,
+			proposal: T::Hash,
+			#[compact] index: ProposalIndex,
+			#[compact] proposal_weight_bound: Weight,
+			#[compact] length_bound: u32,
+		) {
 			let _ = ensure_signed(origin)?;
 
 			let voting = Self::voting(&proposal).ok_or(Error::<T, I>::ProposalMissing)?;
 			ensure!(voting.index == index, Error::<T, I>::WrongIndex);
-			ensure!(system::Module::<T>::block_number() >= voting.end, Error::<T, I>::TooEarly);
-
-			// default to true only if there's a prime and they voted in favour.
-			let default = Self::prime().map_or(
-				false,
-				|who| voting.ayes.iter().any(|a| a == &who),
-			);
 
 			let mut no_votes = voting.nays.len() as MemberCount;
 			let mut yes_votes = voting.ayes.len() as MemberCount;
 			let seats = Self::members().len() as MemberCount;
-			let abstentions = seats - (yes_votes + no_votes);
-			match default {
-				true => yes_votes += abstentions,
-				false => no_votes += abstentions,
+			// A motion with enough approvals may be closed before the end of the voting.
+			if yes_votes < voting.threshold {
+				ensure!(system::Module::<T>::block_number() >= voting.end, Error::<T, I>::TooEarly);
+
+				// default to true only if there's a prime and they voted in favour.
+				let default = Self::prime().map_or(
+					false,
+					|who| voting.ayes.iter().any(|a| a == &who),
+				);
+
+				let abstentions = seats - (yes_votes + no_votes);
+				match default {
+					true => yes_votes += abstentions,
+					false => no_votes += abstentions,
+				}
 			}
 
+			let call = Self::proposal_of(&proposal).ok_or(Error::<T, I>::ProposalMissing)?;
+			let proposal_len = call.using_encoded(|c| c.len()) as u32;
+			ensure!(proposal_len <= length_bound, Error::<T, I>::WrongProposalLength);
+
+			let approved = yes_votes >= voting.threshold;
+			let proposal_weight = if approved {
+				let proposal_weight = call.get_dispatch_info().weight;
+				ensure!(proposal_weight <= proposal_weight_bound, Error::<T, I>::WrongProposalWeight);
+				proposal_weight
+			} else {
+				0
+			};
+
 			Self::deposit_event(RawEvent::Closed(proposal, yes_votes, no_votes));
-			Self::finalize_proposal(yes_votes >= voting.threshold, seats, voting, proposal);
+			Self::finalize_proposal(approved, seats, voting, proposal);
+
+			// Give back the weight charged for beyond what the proposal turned out to need.
+			let charged = weight_for::close(proposal_weight_bound, length_bound as Weight);
+			let used = weight_for::close(proposal_weight, proposal_len as Weight);
+			system::Module::<T>::refund_weight_unchecked(charged.saturating_sub(used));
 		}
 	}
 }
@@ -581,7 +669,7 @@ mod tests {
 		}
 	);
 
-	fn make_ext() -> sp_io::TestExternalities {
+	pub fn make_ext() -> sp_io::TestExternalities {
 		GenesisConfig {
 			collective_Instance1: Some(collective::GenesisConfig {
 				members: vec![1, 2, 3],
@@ -604,24 +692,30 @@ mod tests {
 		Call::System(frame_system::Call::remark(value.encode()))
 	}
 
+	// The weight and the length bounds to close a motion on `proposal` with.
+	fn bounds(proposal: &Call) -> (Weight, u32) {
+		(proposal.get_dispatch_info().weight, proposal.encode().len() as u32)
+	}
+
 	#[test]
 	fn close_works() {
 		make_ext().execute_with(|| {
 			System::set_block_number(1);
 			let proposal = make_proposal(42);
 			let hash = BlakeTwo256::hash_of(&proposal);
+			let (proposal_weight, proposal_len) = bounds(&proposal);
 
 			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
 			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
 
 			System::set_block_number(3);
 			assert_noop!(
-				Collective::close(Origin::signed(4), hash.clone(), 0),
+				Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len),
 				Error::<Test, Instance1>::TooEarly
 			);
 
 			System::set_block_number(4);
-			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0));
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len));
 
 			let record = |event| EventRecord { phase: Phase::Finalization, event, topics: vec![] };
 			assert_eq!(System::events(), vec![
@@ -639,13 +733,14 @@ mod tests {
 			System::set_block_number(1);
 			let proposal = make_proposal(42);
 			let hash = BlakeTwo256::hash_of(&proposal);
+			let (proposal_weight, proposal_len) = bounds(&proposal);
 			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(3)));
 
 			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
 			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
 
 			System::set_block_number(4);
-			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0));
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len));
 
 			let record = |event| EventRecord { phase: Phase::Finalization, event, topics: vec![] };
 			assert_eq!(System::events(), vec![
@@ -663,13 +758,14 @@ mod tests {
 			System::set_block_number(1);
 			let proposal = make_proposal(42);
 			let hash = BlakeTwo256::hash_of(&proposal);
+			let (proposal_weight, proposal_len) = bounds(&proposal);
 			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(1)));
 
 			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
 			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
 
 			System::set_block_number(4);
-			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0));
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len));
 
 			let record = |event| EventRecord { phase: Phase::Finalization, event, topics: vec![] };
 			assert_eq!(System::events(), vec![
@@ -682,6 +778,84 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn prime_must_be_a_member() {
+		make_ext().execute_with(|| {
+			assert_noop!(
+				Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(4)),
+				Error::<Test, Instance1>::PrimeAccountNotMember
+			);
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(3)));
+			assert_eq!(Collective::prime(), Some(3));
+		});
+	}
+
+	#[test]
+	fn close_checks_the_bounds() {
+		make_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal = make_proposal(42);
+			let hash = BlakeTwo256::hash_of(&proposal);
+			let (proposal_weight, proposal_len) = bounds(&proposal);
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(1)));
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
+			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
+
+			System::set_block_number(4);
+			assert_noop!(
+				Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len - 1),
+				Error::<Test, Instance1>::WrongProposalLength
+			);
+			assert_noop!(
+				Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight - 1, proposal_len),
+				Error::<Test, Instance1>::WrongProposalWeight
+			);
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len));
+		});
+	}
+
+	#[test]
+	fn close_of_disapproved_motion_needs_no_weight_bound() {
+		make_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal = make_proposal(42);
+			let hash = BlakeTwo256::hash_of(&proposal);
+			let (_, proposal_len) = bounds(&proposal);
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
+
+			System::set_block_number(4);
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, 0, proposal_len));
+			assert_eq!(Collective::proposals(), Vec::<H256>::new());
+		});
+	}
+
+	#[test]
+	fn close_is_charged_the_bounds_and_refunds_the_rest() {
+		make_ext().execute_with(|| {
+			System::set_block_number(1);
+			let proposal = make_proposal(42);
+			let hash = BlakeTwo256::hash_of(&proposal);
+			let (proposal_weight, proposal_len) = bounds(&proposal);
+			assert_ok!(Collective::set_members(Origin::ROOT, vec![1, 2, 3], Some(1)));
+
+			let call = Call::Collective(crate::Call::close(hash, 0, proposal_weight + 100, proposal_len));
+			let info = call.get_dispatch_info();
+			assert_eq!(info.weight, weight_for::close(proposal_weight + 100, proposal_len as Weight));
+			assert_eq!(info.class, DispatchClass::Operational);
+
+			assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal.clone())));
+			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
+
+			System::set_block_number(4);
+			// Stand in for `CheckWeight`, which registers the weight charged for the call.
+			System::register_extrinsic_weight(info.weight);
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight + 100, proposal_len));
+			assert_eq!(System::all_extrinsics_weight(), info.weight - 100);
+		});
+	}
+
 	#[test]
 	fn removal_of_old_voters_votes_works() {
 		make_ext().execute_with(|| {
@@ -937,8 +1111,12 @@ mod tests {
 			System::set_block_number(1);
 			let proposal = make_proposal(42);
 			let hash: H256 = proposal.blake2_256().into();
+			let (proposal_weight, proposal_len) = bounds(&proposal);
 			assert_ok!(Collective::propose(Origin::signed(1), 2, Box::new(proposal.clone())));
 			assert_ok!(Collective::vote(Origin::signed(2), hash.clone(), 0, true));
+			// The vote only records the approval, the proposal is dispatched by `close`.
+			assert_eq!(Collective::proposals(), vec![hash]);
+			assert_ok!(Collective::close(Origin::signed(4), hash.clone(), 0, proposal_weight, proposal_len));
 
 			assert_eq!(System::events(), vec![
 				EventRecord {
@@ -962,6 +1140,15 @@ mod tests {
 					)),
 					topics: vec![],
 				},
+				EventRecord {
+					phase: Phase::Finalization,
+					event: Event::collective_Instance1(RawEvent::Closed(
+						hex!["68eea8f20b542ec656c6ac2d10435ae3bd1729efc34d1354ab85af840aad2d35"].into(),
+						2,
+						0,
+					)),
+					topics: vec![],
+				},
 				EventRecord {
 					phase: Phase::Finalization,
 					event: Event::collective_Instance1(RawEvent::Approved(
@@ -0,0 +1,54 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for the democracy module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+pub use pallet_democracy::{
+	ReferendumStatus, ProposalStatus, AccountGovernance, ReferendumIndex, UnlockAt,
+};
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the state of governance without reading raw storage.
+	pub trait DemocracyApi<AccountId, Balance, BlockNumber, Hash> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// Get all ongoing referenda, along with their current tallies.
+		fn referenda() -> Vec<ReferendumStatus<BlockNumber, Hash, Balance>>;
+
+		/// Get the public proposal queue, along with the deposits backing each proposal.
+		fn public_proposals() -> Vec<ProposalStatus<AccountId, Hash, Balance>>;
+
+		/// Get the votes, delegation and prior lock of `who`.
+		fn account_governance(who: AccountId) -> AccountGovernance<AccountId, BlockNumber, Balance>;
+
+		/// Get the conviction locks of `who`, each with the referendum behind it if still ongoing
+		/// and until when it holds.
+		fn account_locks(
+			who: AccountId,
+		) -> Vec<(Option<ReferendumIndex>, Balance, UnlockAt<BlockNumber>)>;
+
+		/// Get the votes that the direct vote of `who` in referendum `index` counts for, including
+		/// the votes delegated to them.
+		fn voting_power_of(who: AccountId, index: ReferendumIndex) -> Balance;
+	}
+}
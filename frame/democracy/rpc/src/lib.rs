@@ -0,0 +1,190 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the democracy module.
+
+use std::sync::Arc;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, Header as HeaderT}};
+use sp_api::ProvideRuntimeApi;
+pub use pallet_democracy_rpc_runtime_api::{
+	ReferendumStatus, ProposalStatus, AccountGovernance, ReferendumIndex, UnlockAt,
+	DemocracyApi as DemocracyRuntimeApi,
+};
+pub use self::gen_client::Client as DemocracyClient;
+
+const RUNTIME_ERROR: i64 = 1;
+
+#[rpc]
+pub trait DemocracyApi<BlockHash, BlockNumber, AccountId, Balance> {
+	/// Returns all ongoing referenda, along with their current tallies.
+	#[rpc(name = "democracy_referenda")]
+	fn referenda(
+		&self,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ReferendumStatus<BlockNumber, BlockHash, Balance>>>;
+
+	/// Returns the public proposal queue, along with the deposits backing each proposal.
+	#[rpc(name = "democracy_publicProposals")]
+	fn public_proposals(
+		&self,
+		at: Option<BlockHash>,
+	) -> Result<Vec<ProposalStatus<AccountId, BlockHash, Balance>>>;
+
+	/// Returns the votes, delegation and prior lock of `who`.
+	#[rpc(name = "democracy_accountGovernance")]
+	fn account_governance(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<AccountGovernance<AccountId, BlockNumber, Balance>>;
+
+	/// Returns the conviction locks of `who`, and until when each of them holds.
+	#[rpc(name = "democracy_accountLocks")]
+	fn account_locks(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(Option<ReferendumIndex>, Balance, UnlockAt<BlockNumber>)>>;
+
+	/// Returns the votes that the direct vote of `who` in referendum `index` counts for,
+	/// including the votes delegated to them.
+	#[rpc(name = "democracy_votingPowerOf")]
+	fn voting_power_of(
+		&self,
+		who: AccountId,
+		index: ReferendumIndex,
+		at: Option<BlockHash>,
+	) -> Result<Balance>;
+}
+
+/// A struct that implements the [`DemocracyApi`].
+pub struct Democracy<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Democracy<C, B> {
+	/// Create new `Democracy` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Democracy { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Balance>
+	DemocracyApi<
+		<Block as BlockT>::Hash,
+		<<Block as BlockT>::Header as HeaderT>::Number,
+		AccountId,
+		Balance,
+	> for Democracy<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DemocracyRuntimeApi<
+		Block,
+		AccountId,
+		Balance,
+		<<Block as BlockT>::Header as HeaderT>::Number,
+		<Block as BlockT>::Hash,
+	>,
+	AccountId: Codec,
+	Balance: Codec,
+{
+	fn referenda(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ReferendumStatus<
+		<<Block as BlockT>::Header as HeaderT>::Number,
+		<Block as BlockT>::Hash,
+		Balance,
+	>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.referenda(&at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn public_proposals(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<ProposalStatus<AccountId, <Block as BlockT>::Hash, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.public_proposals(&at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn account_governance(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<AccountGovernance<AccountId, <<Block as BlockT>::Header as HeaderT>::Number, Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.account_governance(&at, who).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn account_locks(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(
+		Option<ReferendumIndex>,
+		Balance,
+		UnlockAt<<<Block as BlockT>::Header as HeaderT>::Number>,
+	)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.account_locks(&at, who).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn voting_power_of(
+		&self,
+		who: AccountId,
+		index: ReferendumIndex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Balance> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.voting_power_of(&at, who, index).map_err(runtime_error_into_rpc_err)
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime trapped".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
@@ -0,0 +1,429 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Democracy pallet benchmarking.
+//!
+//! The default instance is benchmarked, and its weights stand for those of every other instance.
+
+use super::*;
+
+use frame_system::RawOrigin;
+use frame_benchmarking::{benchmarks, account, related_account};
+
+use crate::Module as Democracy;
+
+const SEED: u32 = 0;
+const MAX_REFERENDUMS: u32 = 100;
+const MAX_VOTERS: u32 = 1000;
+const MAX_USERS: u32 = 1000;
+const MAX_BYTES: u32 = 16_384;
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	frame_benchmarking::funded_account::<_, T::Currency>(name, index, SEED, BalanceOf::<T>::max_value())
+}
+
+// Fill the public proposal queue with `p` proposals.
+fn add_proposals<T: Trait>(p: u32) -> Result<(), &'static str> {
+	for i in 0 .. p {
+		let proposer = funded_account::<T>("proposer", i);
+		let proposal_hash = T::Hashing::hash_of(&i);
+		let value = Democracy::<T>::effective_minimum_deposit().max(T::MinimumDeposit::get());
+		Democracy::<T>::propose(RawOrigin::Signed(proposer).into(), proposal_hash, value)?;
+	}
+	Ok(())
+}
+
+// Start `r` referenda, all of which end at `end`.
+fn add_referenda<T: Trait>(r: u32, end: T::BlockNumber) -> Result<(), &'static str> {
+	for i in 0 .. r {
+		let proposal = BoundedCall::Legacy { hash: T::Hashing::hash_of(&i) };
+		Democracy::<T>::inject_referendum(end, proposal, VoteThreshold::SuperMajorityApprove, Zero::zero());
+	}
+	Ok(())
+}
+
+// Cast `v` convicted aye votes in referendum `index`.
+fn add_votes<T: Trait>(v: u32, index: ReferendumIndex) -> Result<(), &'static str> {
+	let vote = Vote { aye: true, conviction: Conviction::Locked1x };
+	for i in 0 .. v {
+		let voter = funded_account::<T>("voter", i);
+		Democracy::<T>::vote(RawOrigin::Signed(voter).into(), index, vote)?;
+	}
+	Ok(())
+}
+
+// Fill the dispatch queue with `q` proposals, all due at `when`. The preimage of the proposal of
+// index `i` is the encoding of `i`, which is not noted.
+fn fill_queue<T: Trait>(q: u32, when: T::BlockNumber) {
+	let queue = (0 .. q)
+		.map(|i| (when, BoundedCall::Legacy { hash: T::Hashing::hash_of(&i) }, i))
+		.collect::<Vec<_>>();
+	DispatchQueue::<T>::put(queue);
+}
+
+// Make the proxy account of `stash` its active proxy.
+fn add_proxy<T: Trait>(stash: T::AccountId) -> T::AccountId {
+	let proxy: T::AccountId = related_account(&stash, "proxy");
+	Democracy::<T>::force_proxy(stash, proxy.clone());
+	proxy
+}
+
+benchmarks! {
+	_ {
+		let p in 1 .. T::MaxProposals::get() => add_proposals::<T>(p)?;
+	}
+
+	// Worst case: the queue holds all but one of `MaxProposals`.
+	propose {
+		let p in 0 .. T::MaxProposals::get() - 1 => add_proposals::<T>(p)?;
+		let caller = funded_account::<T>("caller", 0);
+		let proposal_hash = T::Hashing::hash_of(&p);
+		let value = Democracy::<T>::effective_minimum_deposit().max(T::MinimumDeposit::get());
+	}: _(RawOrigin::Signed(caller), proposal_hash, value)
+
+	// The proposal has `s` seconds besides its proposer's deposit.
+	second {
+		let s in 0 .. T::MaxDeposits::get() - 2;
+		add_proposals::<T>(1)?;
+		for i in 0 .. s {
+			let seconder = funded_account::<T>("seconder", i);
+			Democracy::<T>::second(RawOrigin::Signed(seconder).into(), 0, u32::max_value())?;
+		}
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), 0, s + 1)
+
+	// The caller's second comes first among `s` seconds, so all later ones are scanned.
+	unsecond {
+		let s in 1 .. T::MaxDeposits::get() - 1;
+		add_proposals::<T>(1)?;
+		let caller = funded_account::<T>("caller", 0);
+		Democracy::<T>::second(RawOrigin::Signed(caller.clone()).into(), 0, u32::max_value())?;
+		for i in 1 .. s {
+			let seconder = funded_account::<T>("seconder", i);
+			Democracy::<T>::second(RawOrigin::Signed(seconder).into(), 0, u32::max_value())?;
+		}
+	}: _(RawOrigin::Signed(caller), 0, s + 1)
+
+	clear_public_proposals {
+		let p in ...;
+	}: _(RawOrigin::Root, false)
+
+	// No referendum is launched nor matures, but `r` ongoing referenda are scanned.
+	on_initialize_base {
+		let r in 0 .. MAX_REFERENDUMS;
+		let now = T::LaunchPeriod::get() + 1.into();
+		add_referenda::<T>(r, now + 1.into())?;
+	}: { Democracy::<T>::begin_block(now)?; }
+
+	// The next public referendum is launched out of `p` proposals, refunding the deposits of the
+	// `s` seconders of the winning one.
+	on_initialize_launch {
+		let p in ...;
+		let s in 0 .. T::MaxDeposits::get() - 1;
+		for i in 0 .. s {
+			let seconder = funded_account::<T>("seconder", i);
+			Democracy::<T>::second(RawOrigin::Signed(seconder).into(), 0, u32::max_value())?;
+		}
+		let now = T::LaunchPeriod::get();
+	}: on_initialize(now)
+	verify {
+		ensure!(Democracy::<T>::referendum_count() == 1, "referendum was not launched");
+	}
+
+	// A referendum with `v` voters matures and passes.
+	on_initialize_bake {
+		let v in 0 .. MAX_VOTERS;
+		let now = T::LaunchPeriod::get() + 1.into();
+		add_referenda::<T>(1, now)?;
+		add_votes::<T>(v, 0)?;
+	}: on_initialize(now)
+	verify {
+		ensure!(Democracy::<T>::referendum_info(0).is_none(), "referendum did not mature");
+	}
+
+	// A referendum whose only voter has `d` delegators matures and passes.
+	on_initialize_bake_delegated {
+		let d in 0 .. T::MaxDelegators::get();
+		let now = T::LaunchPeriod::get() + 1.into();
+		add_referenda::<T>(1, now)?;
+		add_votes::<T>(1, 0)?;
+		let voter = funded_account::<T>("voter", 0);
+		for i in 0 .. d {
+			let delegator = funded_account::<T>("delegator", i);
+			let origin = RawOrigin::Signed(delegator).into();
+			Democracy::<T>::delegate(origin, voter.clone(), Conviction::Locked1x)?;
+		}
+	}: on_initialize(now)
+	verify {
+		ensure!(Democracy::<T>::referendum_info(0).is_none(), "referendum did not mature");
+	}
+
+	// A proposal whose preimage is `b` bytes long is decoded and dispatched. The call fails as soon
+	// as it is dispatched, so only the decoding is measured.
+	enact_proposal {
+		let b in 1 .. MAX_BYTES;
+		let call: T::Proposal = Call::<T>::note_preimage(vec![1; b as usize]).into();
+		let encoded = call.encode();
+		let proposal_hash = T::Hashing::hash(&encoded[..]);
+		let caller = funded_account::<T>("caller", 0);
+		Democracy::<T>::note_preimage(RawOrigin::Signed(caller).into(), encoded)?;
+		let proposal = BoundedCall::Legacy { hash: proposal_hash };
+	}: { Democracy::<T>::enact_proposal(proposal, 0)?; }
+
+	// `q` proposals fall due and are dispatched.
+	on_initialize_dispatch {
+		let q in 0 .. T::MaxQueued::get();
+		let now = T::LaunchPeriod::get() + 1.into();
+		fill_queue::<T>(q, now);
+	}: { Democracy::<T>::begin_block(now)?; }
+
+	// The queued proposal rescheduled is the last of `q`, and is moved to the front.
+	set_enactment_delay {
+		let q in 1 .. T::MaxQueued::get();
+		fill_queue::<T>(q, T::EnactmentPeriod::get() + 1.into());
+	}: _(RawOrigin::Root, q - 1, 1.into())
+
+	// The queued proposal cancelled is the last of `q`.
+	cancel_queued {
+		let q in 1 .. T::MaxQueued::get();
+		fill_queue::<T>(q, T::EnactmentPeriod::get());
+	}: _(RawOrigin::Root, q - 1)
+
+	// A preimage `b` bytes long is noted.
+	note_preimage {
+		let b in 0 .. T::MaximumBlockLength::get();
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), vec![1; b as usize])
+
+	// The preimage noted is `b` bytes long, and that of the last of `q` queued proposals.
+	note_imminent_preimage {
+		let q in 1 .. T::MaxQueued::get();
+		let b in 0 .. T::MaximumBlockLength::get();
+		fill_queue::<T>(q, T::EnactmentPeriod::get());
+		let preimage = vec![1; b as usize];
+		let proposal = BoundedCall::Legacy { hash: T::Hashing::hash(&preimage[..]) };
+		DispatchQueue::<T>::mutate(|queue| queue[q as usize - 1].1 = proposal);
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), preimage)
+
+	// A preimage `b` bytes long is reaped by another account than the one which noted it.
+	reap_preimage {
+		let b in 0 .. T::MaximumBlockLength::get();
+		let preimage = vec![1; b as usize];
+		let proposal_hash = T::Hashing::hash(&preimage[..]);
+		let noter = funded_account::<T>("noter", 0);
+		Democracy::<T>::note_preimage(RawOrigin::Signed(noter).into(), preimage)?;
+		let now = T::VotingPeriod::get() + T::EnactmentPeriod::get();
+		frame_system::Module::<T>::set_block_number(now);
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), proposal_hash, b)
+
+	// A chunk `b` bytes long is noted after a first one.
+	note_preimage_chunk {
+		let b in 1 .. T::MaximumBlockLength::get();
+		let proposal_hash = T::Hashing::hash_of(&0);
+		let caller = funded_account::<T>("caller", 0);
+		Democracy::<T>::note_preimage_chunk(
+			RawOrigin::Signed(caller.clone()).into(),
+			proposal_hash,
+			0,
+			vec![1; MAX_BYTES as usize],
+		)?;
+	}: _(RawOrigin::Signed(caller), proposal_hash, 1, vec![1; b as usize])
+
+	// A preimage uploaded in `c` chunks of `b` bytes is assembled.
+	finalize_preimage {
+		let c in 1 .. T::MaxPreimageChunks::get();
+		let b in 1 .. MAX_BYTES;
+		let preimage = vec![1; (b * c) as usize];
+		let proposal_hash = T::Hashing::hash(&preimage[..]);
+		let caller = funded_account::<T>("caller", 0);
+		for (i, chunk) in preimage.chunks(b as usize).enumerate() {
+			Democracy::<T>::note_preimage_chunk(
+				RawOrigin::Signed(caller.clone()).into(),
+				proposal_hash,
+				i as u32,
+				chunk.to_vec(),
+			)?;
+		}
+	}: _(RawOrigin::Signed(caller), proposal_hash, b * c)
+
+	// An upload of `c` chunks is reaped by another account than the one which noted them.
+	reap_preimage_upload {
+		let c in 1 .. T::MaxPreimageChunks::get();
+		let proposal_hash = T::Hashing::hash_of(&0);
+		let uploader = funded_account::<T>("uploader", 0);
+		for i in 0 .. c {
+			Democracy::<T>::note_preimage_chunk(
+				RawOrigin::Signed(uploader.clone()).into(),
+				proposal_hash,
+				i,
+				vec![1; 32],
+			)?;
+		}
+		frame_system::Module::<T>::set_block_number(T::VotingPeriod::get());
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), proposal_hash)
+
+	// The caller vetoes after `v` others, taking the first place in the blacklist entry so that
+	// all of them are shifted.
+	veto_external {
+		let v in 0 .. T::MaxVetoers::get() - 1;
+		let proposal_hash = T::Hashing::hash_of(&0);
+		Democracy::<T>::note_external(proposal_hash, VoteThreshold::SimpleMajority);
+		let mut vetoers = (0 ..= v).map(|i| account("vetoer", i, SEED)).collect::<Vec<T::AccountId>>();
+		vetoers.sort();
+		let caller = vetoers.remove(0);
+		Blacklist::<T>::insert(&proposal_hash, (T::BlockNumber::zero(), vetoers));
+	}: { Democracy::<T>::do_veto_external(caller, proposal_hash)?; }
+
+	// The caller votes for the first time, after `v` others.
+	vote_new {
+		let v in 0 .. MAX_VOTERS;
+		add_referenda::<T>(1, T::VotingPeriod::get())?;
+		add_votes::<T>(v, 0)?;
+		let caller = funded_account::<T>("caller", 0);
+		let vote = Vote { aye: false, conviction: Conviction::Locked6x };
+	}: vote(RawOrigin::Signed(caller), 0, vote)
+
+	// The caller changes the last of `v` votes.
+	vote_existing {
+		let v in 1 .. MAX_VOTERS;
+		add_referenda::<T>(1, T::VotingPeriod::get())?;
+		add_votes::<T>(v, 0)?;
+		let caller: T::AccountId = account("voter", v - 1, SEED);
+		let vote = Vote { aye: false, conviction: Conviction::Locked6x };
+	}: vote(RawOrigin::Signed(caller), 0, vote)
+
+	// The caller votes for the first time in `v` referenda at once.
+	vote_batch {
+		let v in 1 .. T::MaxVotes::get();
+		add_referenda::<T>(v, T::VotingPeriod::get())?;
+		let caller = funded_account::<T>("caller", 0);
+		let vote = Vote { aye: false, conviction: Conviction::Locked6x };
+		let votes = (0 .. v).map(|i| (i, vote)).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(caller), votes)
+
+	// The caller has voted, and abstains after `a` others.
+	abstain {
+		let a in 0 .. MAX_VOTERS;
+		add_referenda::<T>(1, T::VotingPeriod::get())?;
+		for i in 0 .. a {
+			let abstainer = funded_account::<T>("abstainer", i);
+			Democracy::<T>::abstain(RawOrigin::Signed(abstainer).into(), 0)?;
+		}
+		add_votes::<T>(1, 0)?;
+		let caller: T::AccountId = account("voter", 0, SEED);
+	}: _(RawOrigin::Signed(caller), 0)
+
+	// The delegate has voted in `r` ongoing referenda.
+	undelegate {
+		let r in 0 .. T::MaxVotes::get();
+		add_referenda::<T>(r, T::VotingPeriod::get())?;
+		let delegate = funded_account::<T>("delegate", 0);
+		let vote = Vote { aye: true, conviction: Conviction::Locked1x };
+		for i in 0 .. r {
+			Democracy::<T>::vote(RawOrigin::Signed(delegate.clone()).into(), i, vote)?;
+		}
+		let caller = funded_account::<T>("caller", 0);
+		let origin = RawOrigin::Signed(caller.clone()).into();
+		Democracy::<T>::delegate(origin, delegate, Conviction::Locked6x)?;
+	}: _(RawOrigin::Signed(caller))
+
+	// The vote is split across `t` delegates, each of which has `d` delegators already.
+	delegate_split {
+		let t in 1 .. T::MaxDelegationTargets::get();
+		let d in 0 .. T::MaxDelegators::get().saturating_sub(1);
+		let caller = funded_account::<T>("caller", 0);
+		let proportion = Perbill::from_rational_approximation(1, t);
+		let targets = (0 .. t)
+			.map(|i| (account("delegate", i, SEED), proportion))
+			.collect::<Vec<(T::AccountId, Perbill)>>();
+		for (i, (delegate, _)) in targets.iter().enumerate() {
+			for j in 0 .. d {
+				let delegator = funded_account::<T>("delegator", i as u32 * d + j);
+				let origin = RawOrigin::Signed(delegator).into();
+				Democracy::<T>::delegate(origin, delegate.clone(), Conviction::None)?;
+			}
+		}
+	}: _(RawOrigin::Signed(caller), targets, Conviction::Locked6x)
+
+	// The proxy calls fail without the deprecated `legacy-proxy` feature, so they are only run on
+	// request.
+	#[extra]
+	proxy_delegate {
+		let u in 0 .. MAX_USERS;
+		let stash = funded_account::<T>("stash", u);
+		let to: T::AccountId = related_account(&stash, "delegate");
+		let proxy = add_proxy::<T>(stash);
+	}: _(RawOrigin::Signed(proxy), to, Conviction::Locked6x)
+
+	#[extra]
+	proxy_undelegate {
+		let u in 0 .. MAX_USERS;
+		let stash = funded_account::<T>("stash", u);
+		let to: T::AccountId = related_account(&stash, "delegate");
+		Democracy::<T>::delegate(RawOrigin::Signed(stash.clone()).into(), to, Conviction::Locked6x)?;
+		let proxy = add_proxy::<T>(stash);
+	}: _(RawOrigin::Signed(proxy))
+
+	// The vote removed is the last of `v`.
+	#[extra]
+	proxy_remove_vote {
+		let v in 1 .. MAX_VOTERS;
+		add_referenda::<T>(1, T::VotingPeriod::get())?;
+		add_votes::<T>(v, 0)?;
+		let stash: T::AccountId = account("voter", v - 1, SEED);
+		let proxy = add_proxy::<T>(stash);
+	}: _(RawOrigin::Signed(proxy), 0)
+
+	// Someone other than the voter reaps the deposit for the last of `v` votes, once the
+	// referendum is cancelled and `FinishedRetention` has passed since the end of its voting.
+	reap_vote {
+		let v in 1 .. MAX_VOTERS;
+		let end = T::VotingPeriod::get();
+		add_referenda::<T>(1, end)?;
+		add_votes::<T>(v, 0)?;
+		Democracy::<T>::internal_cancel_referendum(0);
+		frame_system::Module::<T>::set_block_number(end + T::FinishedRetention::get());
+		let voter: T::AccountId = account("voter", v - 1, SEED);
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), voter, 0)
+
+	prune_finished {
+		let i in 0 .. MAX_REFERENDUMS;
+		let finished = Finished { approved: true, end: Zero::zero() };
+		for index in 0 .. i {
+			FinishedOf::<T>::insert(index, finished);
+		}
+		let now = T::FinishedRetention::get();
+		frame_system::Module::<T>::set_block_number(now);
+	}: _(RawOrigin::Root, (0 .. i).collect())
+
+	// The `v` votes cast in the first of `r` ongoing referenda are indexed by voter.
+	migrate_to_voting {
+		let r in 1 .. MAX_REFERENDUMS;
+		let v in 0 .. MAX_VOTERS;
+		add_referenda::<T>(r, T::VotingPeriod::get())?;
+		add_votes::<T>(v, 0)?;
+		Voting::<T>::remove_all();
+		StorageVersion::<DefaultInstance>::put(Releases::V3);
+		let votes = migration::pre_migrate_to_voting::<T, DefaultInstance>();
+	}: on_runtime_upgrade()
+	verify {
+		migration::post_migrate_to_voting::<T, DefaultInstance>(votes);
+	}
+}
@@ -0,0 +1,81 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Enactment delay curves, delaying approved proposals according to the turnout they had.
+
+use sp_std::{prelude::*, marker::PhantomData};
+use sp_runtime::Perbill;
+use frame_support::traits::Get;
+
+/// A curve giving the delay after which an approved proposal is enacted, according to the turnout
+/// of its referendum.
+pub trait EnactmentDelayCurve<BlockNumber> {
+	/// The delay for a proposal tabled with `delay` and approved with `turnout`, as a proportion
+	/// of the total issuance.
+	fn delay(turnout: Perbill, delay: BlockNumber) -> BlockNumber;
+}
+
+/// No curve at all: every proposal is enacted with the delay it was tabled with.
+impl<BlockNumber> EnactmentDelayCurve<BlockNumber> for () {
+	fn delay(_: Perbill, delay: BlockNumber) -> BlockNumber {
+		delay
+	}
+}
+
+/// A step curve. `Steps` lists pairs of a turnout and a delay, by increasing turnout: a proposal
+/// approved with less than the turnout of a step is enacted after at least the delay of the first
+/// such step. The delays should hence decrease from one step to the next. A proposal approved
+/// with at least the turnout of the last step is enacted with the delay it was tabled with.
+pub struct TurnoutSteps<Steps>(PhantomData<Steps>);
+
+impl<BlockNumber, Steps> EnactmentDelayCurve<BlockNumber> for TurnoutSteps<Steps> where
+	BlockNumber: Ord,
+	Steps: Get<Vec<(Perbill, BlockNumber)>>,
+{
+	fn delay(turnout: Perbill, delay: BlockNumber) -> BlockNumber {
+		match Steps::get().into_iter().find(|(step, _)| turnout < *step) {
+			Some((_, step_delay)) => step_delay.max(delay),
+			None => delay,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::parameter_types;
+
+	parameter_types! {
+		pub const Steps: Vec<(Perbill, u64)> = vec![
+			(Perbill::from_percent(10), 30),
+			(Perbill::from_percent(50), 10),
+		];
+	}
+
+	#[test]
+	fn turnout_steps_should_work() {
+		let delay = |t, d| <TurnoutSteps<Steps> as EnactmentDelayCurve<u64>>::delay(
+			Perbill::from_percent(t),
+			d,
+		);
+		assert_eq!(delay(5, 2), 30);
+		assert_eq!(delay(10, 2), 10);
+		assert_eq!(delay(49, 20), 20);
+		assert_eq!(delay(50, 2), 2);
+		assert_eq!(delay(100, 0), 0);
+		assert_eq!(<() as EnactmentDelayCurve<u64>>::delay(Perbill::from_percent(0), 2), 2);
+	}
+}
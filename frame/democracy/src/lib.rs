@@ -36,6 +36,10 @@
 //! behind a vote. The conviction will dictate the length of time the tokens
 //! will be locked, as well as the multiplier that scales the vote power.
 //!
+//! The pallet is instantiable: a runtime may include several instances of it, each with its own
+//! queues, referenda, periods and origins, such as one for technical parameters and another for
+//! treasury spending. Instances sharing a currency must be given distinct `LockId`s.
+//!
 //! ### Terminology
 //!
 //! - **Enactment Period:** The minimum period of locking and the period between a proposal being
@@ -51,9 +55,27 @@
 //! account or an external origin) suggests that the system adopt.
 //! - **Referendum:** A proposal that is in the process of being voted on for
 //!   either acceptance or rejection as a change to the system.
+//! - **Multi-option Referendum:** A referendum choosing between several proposals, in which
+//!   voters approve or rank options rather than vote aye or nay. The winning option is enacted.
 //! - **Proxy:** An account that votes on behalf of a separate "Stash" account
 //!   that holds the funds.
 //! - **Delegation:** The act of granting your voting power to the decisions of another account.
+//! - **Vote Deposit:** An amount reserved from a voter for each referendum they vote in, so that
+//!   the records of votes are paid for. It is returned when the vote is removed or, once the
+//!   referendum is over, reaped.
+//! - **Balance Snapshot:** Optionally, the balance of a voter when they first vote in a
+//!   referendum, which caps the weight of their vote there, so that buying in for the vote and
+//!   selling afterwards gains nothing. Enabled through `Trait::SnapshotBalances`.
+//! - **Vote Index:** Optionally, a compact record of each direct vote, written to the off-chain
+//!   index of the node keyed by referendum rather than kept in state. Archive nodes may serve it
+//!   to follow participation without replaying events. Enabled through `Trait::IndexVotes`.
+//! - **Vote Weight Curve:** An optional discount applied to votes according to how close to the
+//!   end of voting they were cast, configured through `Trait::VoteWeightCurve`. Use `()` to give
+//!   every vote its full weight.
+//! - **Enactment Delay Curve:** An optional lengthening of the delay before an approved proposal
+//!   is enacted when its referendum had a low turnout, configured through
+//!   `Trait::EnactmentDelayCurve`. Use `()` to enact every proposal with the delay it was tabled
+//!   with.
 //!
 //! ### Adaptive Quorum Biasing
 //!
@@ -68,6 +90,19 @@
 //! quorum biasing is that _positive bias_ referendums will be rejected by
 //! default and _negative bias_ referendums get passed by default.
 //!
+//! ### Proxies
+//!
+//! The built-in proxy (`open_proxy`, `activate_proxy`, `proxy_vote` and friends) is deprecated.
+//! It is only available while the `legacy-proxy` feature is enabled; without it, the proxy
+//! calls fail with `ProxyDeprecated`, and the first runtime upgrade removes the `Proxy` storage
+//! once, releasing the account references held by open and active proxies.
+//!
+//! A stash should instead appoint a proxy through a general-purpose proxy pallet, using a proxy
+//! type whose `InstanceFilter` lets through governance calls only, such as the
+//! `ProxyType::Governance` of the node runtime. The proxy then dispatches the ordinary `vote`,
+//! `remove_vote`, `delegate` and `undelegate` calls with the stash's origin, so no special
+//! handling is needed here.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -81,25 +116,53 @@
 //!	  Requires a deposit.
 //! - `second` - Signals agreement with a proposal, moves it higher on the
 //!   proposal queue, and requires a matching deposit to the original.
+//! - `unsecond` - Withdraws a second from a proposal that has not yet been tabled, returning its
+//!   deposit.
 //! - `vote` - Votes in a referendum, either the vote is "Aye" to enact the
 //!   proposal or "Nay" to keep the status quo.
-//! - `proxy_vote` - Votes in a referendum on behalf of a stash account.
-//! - `activate_proxy` - Activates a proxy that is already open to the sender.
-//! - `close_proxy` - Clears the proxy status, called by the proxy.
+//! - `vote_batch` - Votes in several referenda at once.
+//! - `proxy_vote` - Votes in a referendum on behalf of a stash account. Deprecated.
+//! - `remove_vote` - Removes the sender's vote, or abstention, from an ongoing referendum.
+//! - `abstain` - Abstains in a referendum, adding to its turnout without supporting either side.
+//! - `proxy_remove_vote` - Removes a vote on behalf of a stash account. Deprecated.
+//! - `reap_vote` - Returns the deposit for a vote in a referendum which is over. Anyone may
+//!   do so for any voter once `FinishedRetention` has passed since the end of voting.
+//! - `vote_multi` - Casts a ballot in a multi-option referendum, approving or ranking
+//!   some of its options.
+//! - `activate_proxy` - Activates a proxy that is already open to the sender. Deprecated.
+//! - `close_proxy` - Clears the proxy status, called by the proxy. Deprecated.
 //! - `deactivate_proxy` - Deactivates a proxy back to the open status, called by
-//!   the stash.
-//! - `open_proxy` - Opens a proxy account on behalf of the sender.
+//!   the stash. Deprecated.
+//! - `open_proxy` - Opens a proxy account on behalf of the sender. Deprecated.
 //! - `delegate` - Delegates the voting power (tokens * conviction) to another
-//!   account.
+//!   account. The sender's direct votes, which override the delegation in their
+//!   referenda, must be removed first unless the runtime removes them itself.
 //! - `undelegate` - Stops the delegation of voting power to another account.
+//! - `delegate_split` - Delegates the voting power to several accounts, each receiving a
+//!   proportion of it.
+//! - `delegate_class` - Delegates the voting power to another account in one class of referenda
+//!   only, overriding any delegation made with `delegate`.
+//! - `undelegate_class` - Stops the delegation of voting power in one class of referenda.
+//! - `proxy_delegate` - Delegates the voting power of a stash account. Deprecated.
+//! - `proxy_undelegate` - Stops the delegation of a stash account. Deprecated.
 //! - `note_preimage` - Registers the preimage for an upcoming proposal, requires
 //!   a deposit that is returned once the proposal is enacted.
 //! - `note_imminent_preimage` - Registers the preimage for an upcoming proposal.
 //!   Does not require a deposit, but the proposal must be in the dispatch queue.
+//! - `note_preimage_chunk` - Registers a chunk of the preimage for an upcoming proposal too
+//!   large to be registered at once, requiring a deposit for each chunk.
+//! - `finalize_preimage` - Assembles the chunks of a preimage, registering it as with
+//!   `note_preimage`.
+//! - `reap_preimage_upload` - Removes an unfinished preimage upload. Anyone other than the
+//!   uploader must wait `VotingPeriod` after its latest chunk, and collects its deposit.
 //! - `reap_preimage` - Removes the preimage for an expired proposal. Will only
 //!   work under the condition that it's the same account that noted it and
 //!   after the voting period, OR it's a different account after the enactment period.
-//! - `unlock` - Unlocks tokens that have an expired lock.
+//! - `unlock` - Redetermines the democracy lock on an account, removing it once nothing
+//!   requires it.
+//! - `set_metadata` - Attaches the hash of an off-chain description to a public proposal, when
+//!   called by its proposer. The external origins may do the same for the external proposal, and
+//!   _Root_ for a referendum.
 //!
 //! #### Cancellation Origin
 //!
@@ -131,10 +194,12 @@
 //!
 //! #### Fast Track Origin
 //!
-//! This call can only be made by the `FastTrackOrigin`.
+//! This call can only be made by the `FastTrackOrigin`, or the `InstantOrigin` to allow a voting
+//! period shorter than `FastTrackVotingPeriod`.
 //!
 //! - `fast_track` - Schedules the current externally proposed proposal that
 //!   is "majority-carries" to become a referendum immediately.
+//! - `set_enactment_delay` - Reschedules a proposal queued for enactment. _Root_ may do so too.
 //!
 //! #### Veto Origin
 //!
@@ -142,38 +207,71 @@
 //!
 //! - `veto_external` - Vetoes and blacklists the external proposal hash.
 //!
+//! #### Whitelist Origin
+//!
+//! These calls can only be made by the `WhitelistOrigin`, such as a body of technical experts.
+//!
+//! - `whitelist_proposal` - Whitelists a proposal hash, allowing it to be tabled by the
+//!   `DispatchWhitelistedOrigin`.
+//! - `remove_whitelisted_proposal` - Removes a proposal hash from the whitelist.
+//!
+//! #### Dispatch Whitelisted Origin
+//!
+//! This call can only be made by the `DispatchWhitelistedOrigin`.
+//!
+//! - `dispatch_whitelisted` - Tables a whitelisted proposal as a majority-carries referendum
+//!   straight away. Together with the `WhitelistOrigin`, this gives a two-key path for proposals
+//!   such as protocol upgrades.
+//!
 //! #### Root
 //!
 //! - `cancel_referendum` - Removes a referendum.
+//! - `start_multi_referendum` - Starts a referendum choosing between several proposals.
 //! - `cancel_queued` - Cancels a proposal that is queued for enactment.
-//! - `clear_public_proposal` - Removes all public proposals.
+//! - `set_enactment_delay` - Reschedules a proposal that is queued for enactment.
+//! - `clear_public_proposal` - Removes all public proposals, either slashing or refunding their
+//!   deposits.
+//! - `prune_finished` - Prunes the outcomes of referenda which finished long enough ago.
+//! - `set_launch_paused` - Suspends, or resumes, the tabling of a referendum every `LaunchPeriod`.
 
-#![recursion_limit="128"]
+#![recursion_limit="256"]
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::prelude::*;
 use sp_std::{result, convert::TryFrom};
 use sp_runtime::{
-	RuntimeDebug, DispatchResult,
-	traits::{Zero, Bounded, CheckedMul, CheckedDiv, EnsureOrigin, Hash, Dispatchable, Saturating},
+	RuntimeDebug, DispatchResult, DispatchError, Perbill, PerThing,
+	traits::{
+		Zero, Bounded, EnsureOrigin, Hash, Dispatchable, Saturating, AtLeast32Bit, BadOrigin,
+		IntegerSquareRoot,
+	},
 };
-use codec::{Ref, Encode, Decode, Input, Output};
+use codec::{Ref, Encode, Decode, HasCompact, Input, Output};
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter, IterableStorageMap,
-	weights::SimpleDispatchInfo,
+	weights::{SimpleDispatchInfo, FunctionOf, DispatchClass, Weight},
 	traits::{
 		Currency, ReservableCurrency, LockableCurrency, WithdrawReason, LockIdentifier, Get,
-		OnUnbalanced, BalanceStatus
+		OnUnbalanced, BalanceStatus, Bounded as BoundedCall, QueryPreimage,
 	}
 };
 use frame_system::{self as system, ensure_signed, ensure_root};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 
 mod vote_threshold;
-pub use vote_threshold::{Approved, VoteThreshold};
+mod vote_weight;
+mod enactment_delay;
+mod preimage;
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+pub use vote_threshold::{
+	Approved, ApprovalCriterion, AdaptiveQuorumBiasing, QuadraticQuorumBiasing, VoteThreshold,
+};
+pub use vote_weight::{VoteWeightCurve, LinearLateDiscount};
+pub use enactment_delay::{EnactmentDelayCurve, TurnoutSteps};
 use frame_support::traits::MigrateAccount;
 
-const DEMOCRACY_ID: LockIdentifier = *b"democrac";
-
 /// A proposal index.
 pub type PropIndex = u32;
 
@@ -181,7 +279,11 @@ pub type PropIndex = u32;
 pub type ReferendumIndex = u32;
 
 /// A value denoting the strength of conviction of a vote.
+///
+/// The votes and lock periods given are those of the `DefaultConvictionSchedule`; a runtime may
+/// configure others through `Trait::ConvictionSchedule`.
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Conviction {
 	/// 0.1x votes, unlocked.
 	None,
@@ -235,35 +337,38 @@ impl TryFrom<u8> for Conviction {
 	}
 }
 
-impl Conviction {
-	/// The amount of time (in number of periods) that our conviction implies a successful voter's
-	/// balance should be locked for.
-	fn lock_periods(self) -> u32 {
-		match self {
-			Conviction::None => 0,
-			Conviction::Locked1x => 1,
-			Conviction::Locked2x => 2,
-			Conviction::Locked3x => 4,
-			Conviction::Locked4x => 8,
-			Conviction::Locked5x => 16,
-			Conviction::Locked6x => 32,
-		}
+/// The votes and the lock that a conviction implies, as one row of a `ConvictionSchedule`.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+pub struct ConvictionParams {
+	/// The votes cast by each unit of balance, in tenths: `10` counts the balance once.
+	pub votes_in_tenths: u32,
+	/// The number of enactment periods for which a successful voter's balance is locked.
+	pub lock_periods: u32,
+}
+
+impl ConvictionParams {
+	/// The votes of a voter of the given `balance` with this conviction, along with the capital
+	/// they contribute to turnout. Conviction scales the votes, but never the capital.
+	fn votes<B: AtLeast32Bit + Copy>(self, balance: B) -> (B, B) {
+		let tenths = B::from(self.votes_in_tenths);
+		let ten = B::from(10u8);
+		let whole = (balance / ten).saturating_mul(tenths);
+		let part = (balance % ten).saturating_mul(tenths) / ten;
+		(whole.saturating_add(part), balance)
 	}
+}
 
-	/// The votes of a voter of the given `balance` with our conviction.
-	fn votes<
-		B: From<u8> + Zero + Copy + CheckedMul + CheckedDiv + Bounded
-	>(self, balance: B) -> (B, B) {
-		match self {
-			Conviction::None => {
-				let r = balance.checked_div(&10u8.into()).unwrap_or_else(Zero::zero);
-				(r, r)
-			}
-			x => (
-				balance.checked_mul(&u8::from(x).into()).unwrap_or_else(B::max_value),
-				balance,
-			)
-		}
+/// The schedule of votes and lock periods for each `Conviction`, indexed by its encoding.
+pub type ConvictionSchedule = [ConvictionParams; 7];
+
+/// The conviction schedule this module has always used: 0.1x votes for no lock, then 1x to 6x
+/// votes for a lock of 1, 2, 4, 8, 16 and 32 enactment periods.
+pub struct DefaultConvictionSchedule;
+
+impl Get<ConvictionSchedule> for DefaultConvictionSchedule {
+	fn get() -> ConvictionSchedule {
+		let row = |votes_in_tenths, lock_periods| ConvictionParams { votes_in_tenths, lock_periods };
+		[row(1, 0), row(10, 1), row(20, 2), row(30, 4), row(40, 8), row(50, 16), row(60, 32)]
 	}
 }
 
@@ -279,8 +384,80 @@ impl Bounded for Conviction {
 
 const MAX_RECURSION_LIMIT: u32 = 16;
 
+/// Weights of the paths through `on_initialize`, and of the calls whose cost grows with their
+/// input.
+///
+/// The constants are estimates picked by hand, as the fixed weights of the calls of this module
+/// are, and not yet fitted to measurements. The paths are covered by the `on_initialize_base`,
+/// `on_initialize_launch`, `on_initialize_bake`, `on_initialize_bake_delegated`,
+/// `on_initialize_dispatch`, `enact_proposal`, `note_preimage`, `reap_preimage`,
+/// `reap_preimage_upload` and `veto_external` benchmarks, whose results should replace the
+/// constants before a chain relies on them.
+mod weight_for {
+	use frame_support::weights::Weight;
+
+	/// Scanning `r` ongoing referenda for those that mature in this block.
+	pub fn base(r: Weight) -> Weight {
+		(5_000_000 as Weight).saturating_add(r.saturating_mul(400_000))
+	}
+
+	/// Tabling the next referendum out of `p` public proposals, refunding the deposits of its `s`
+	/// backers.
+	pub fn launch(p: Weight, s: Weight) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(p.saturating_mul(500_000))
+			.saturating_add(s.saturating_mul(300_000))
+	}
+
+	/// Tallying and concluding a maturing referendum with `v` voters, counting `d` delegations
+	/// toward their votes.
+	pub fn bake(v: Weight, d: Weight) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(v.saturating_mul(2_000_000))
+			.saturating_add(d.saturating_mul(1_500_000))
+	}
+
+	/// Scanning a dispatch queue of `q` proposals for those due in this block.
+	pub fn dispatch(q: Weight) -> Weight {
+		q.saturating_mul(100_000)
+	}
+
+	/// Enacting a proposal whose preimage is `b` bytes long.
+	///
+	/// The call dispatched is accounted for by its own weight, which this does not include.
+	pub fn enact(b: Weight) -> Weight {
+		(10_000_000 as Weight).saturating_add(b.saturating_mul(1_000))
+	}
+
+	/// Hashing and storing a preimage `b` bytes long.
+	pub fn note(b: Weight) -> Weight {
+		(5_000_000 as Weight).saturating_add(b.saturating_mul(500))
+	}
+
+	/// Reading and clearing a preimage `b` bytes long.
+	pub fn reap(b: Weight) -> Weight {
+		(1_000_000 as Weight).saturating_add(b.saturating_mul(100))
+	}
+
+	/// Reading and clearing the `c` chunks of a preimage upload.
+	pub fn chunks(c: Weight) -> Weight {
+		c.saturating_mul(200_000)
+	}
+
+	/// Undelegating from `t` accounts, each with `d` delegators.
+	pub fn undelegate(t: Weight, d: Weight) -> Weight {
+		(500_000 as Weight).saturating_add(t.saturating_mul(d).saturating_mul(1_000))
+	}
+
+	/// Vetoing an external proposal which `v` accounts have already vetoed.
+	pub fn veto(v: Weight) -> Weight {
+		(200_000 as Weight).saturating_add(v.saturating_mul(20_000))
+	}
+}
+
 /// A number of lock periods, plus a vote, one way or the other.
 #[derive(Copy, Clone, Eq, PartialEq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct Vote {
 	pub aye: bool,
 	pub conviction: Conviction,
@@ -305,13 +482,146 @@ impl Decode for Vote {
 	}
 }
 
-type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
-type NegativeImbalanceOf<T> =
-<<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+/// The tally of votes in a referendum.
+#[derive(Encode, Decode, Default, Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Tally<Balance> {
+	/// The number of aye votes, expressed in terms of post-conviction lock-vote.
+	pub ayes: Balance,
+	/// The number of nay votes, expressed in terms of post-conviction lock-vote.
+	pub nays: Balance,
+	/// The amount of funds currently expressing their opinion. Pre-conviction.
+	pub turnout: Balance,
+}
+
+impl<Balance: Saturating + Copy> Tally<Balance> {
+	/// Add `votes` backed by `capital` to the side of `aye`.
+	fn add(mut self, aye: bool, votes: Balance, capital: Balance) -> Self {
+		if aye {
+			self.ayes = self.ayes.saturating_add(votes);
+		} else {
+			self.nays = self.nays.saturating_add(votes);
+		}
+		self.turnout = self.turnout.saturating_add(capital);
+		self
+	}
+}
+
+/// The status of an ongoing referendum, as reported to governance frontends.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ReferendumStatus<BlockNumber, Hash, Balance> {
+	/// The index of the referendum.
+	pub index: ReferendumIndex,
+	/// When voting on this referendum will end.
+	pub end: BlockNumber,
+	/// The hash of the proposal being voted on.
+	pub proposal_hash: Hash,
+	/// The thresholding mechanism to determine whether it passed.
+	pub threshold: VoteThreshold,
+	/// The delay (in blocks) to wait after a successful referendum before deploying.
+	pub delay: BlockNumber,
+	/// The current tally of the votes, direct and delegated.
+	pub tally: Tally<Balance>,
+}
+
+/// The status of a public proposal waiting in the queue, as reported to governance frontends.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalStatus<AccountId, Hash, Balance> {
+	/// The index of the proposal.
+	pub index: PropIndex,
+	/// The hash of the proposal.
+	pub proposal_hash: Hash,
+	/// The account that made the proposal.
+	pub proposer: AccountId,
+	/// The deposit made by the proposer and by each seconder.
+	pub deposit: Balance,
+	/// The accounts backing the proposal, including the proposer.
+	pub backers: Vec<AccountId>,
+}
+
+/// The governance position of an account, as reported to governance frontends.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccountGovernance<AccountId, BlockNumber, Balance> {
+	/// The votes cast by the account in ongoing referenda.
+	pub votes: Vec<(ReferendumIndex, Vote)>,
+	/// The account to which votes are delegated, if any, and with what conviction.
+	pub delegation: Option<(AccountId, Conviction)>,
+	/// The amount locked by past votes and delegations.
+	pub prior_locked: Balance,
+	/// The block number from which the prior lock may be removed.
+	pub prior_lock_expiry: BlockNumber,
+}
+
+/// Until when a conviction lock holds, as reported to governance frontends.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum UnlockAt<BlockNumber> {
+	/// The balance is locked until the given block number.
+	Block(BlockNumber),
+	/// The vote is in an ongoing referendum and locks nothing yet; should it end up on the
+	/// winning side, the balance will be locked until the given block number.
+	IfWon(BlockNumber),
+	/// The balance is locked for as long as it is delegated.
+	WhileDelegating,
+}
+
+/// Handler for the lifecycle of referenda, binary or multi-option, allowing other pallets to
+/// react to their outcome.
+pub trait ReferendumHooks {
+	/// Referendum `index` has begun.
+	fn on_started(_index: ReferendumIndex) {}
+
+	/// Referendum `index` has ended in favour of its proposal, or of one of its options.
+	fn on_passed(_index: ReferendumIndex) {}
+
+	/// Referendum `index` has ended without passing.
+	fn on_failed(_index: ReferendumIndex) {}
+
+	/// Referendum `index` has been cancelled before it ended.
+	fn on_cancelled(_index: ReferendumIndex) {}
+}
+
+impl ReferendumHooks for () {}
+
+/// Handler for accounts voting and removing their votes, allowing other pallets to track
+/// participation in governance.
+pub trait OnVote<AccountId> {
+	/// `who` has voted, cast a ballot or abstained in referendum `index`.
+	fn on_vote(_who: &AccountId, _index: ReferendumIndex) {}
+
+	/// `who` has removed their vote or abstention in referendum `index`.
+	fn on_remove_vote(_who: &AccountId, _index: ReferendumIndex) {}
+}
+
+impl<AccountId> OnVote<AccountId> for () {}
 
-pub trait Trait: frame_system::Trait + Sized {
-	type Proposal: Parameter + Dispatchable<Origin=Self::Origin>;
-	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+/// Handler for accounts delegating and undelegating their votes, allowing other pallets to track
+/// participation in governance.
+pub trait OnDelegation<AccountId> {
+	/// `who` has delegated their vote to `to`, in the referenda of `class` or in all of them if
+	/// `None`.
+	fn on_delegate(_who: &AccountId, _to: &AccountId, _class: Option<VoteThreshold>) {}
+
+	/// `who` has stopped delegating their vote, in the referenda of `class` or in all of them if
+	/// `None`.
+	fn on_undelegate(_who: &AccountId, _class: Option<VoteThreshold>) {}
+}
+
+impl<AccountId> OnDelegation<AccountId> for () {}
+
+type BalanceOf<T, I=DefaultInstance> =
+	<<T as Trait<I>>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T, I=DefaultInstance> =
+	<<T as Trait<I>>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+/// A proposal, either held inline or referenced by the hash of its preimage.
+pub type BoundedCallOf<T> = BoundedCall<<T as frame_system::Trait>::Hash>;
+
+pub trait Trait<I=DefaultInstance>: frame_system::Trait + Sized {
+	type Proposal: Parameter + Dispatchable<Origin=Self::Origin> + From<Call<Self, I>>;
+	type Event: From<Event<Self, I>> + Into<<Self as frame_system::Trait>::Event>;
 
 	/// Currency type for this module.
 	type Currency: ReservableCurrency<Self::AccountId>
@@ -331,10 +641,14 @@ pub trait Trait: frame_system::Trait + Sized {
 	type VotingPeriod: Get<Self::BlockNumber>;
 
 	/// The minimum amount to be used as a deposit for a public referendum proposal.
-	type MinimumDeposit: Get<BalanceOf<Self>>;
+	type MinimumDeposit: Get<BalanceOf<Self, I>>;
 
 	/// Origin from which the next tabled referendum may be forced. This is a normal
 	/// "super-majority-required" referendum.
+	///
+	/// Each of the three external origins is checked on its own, so none implies another: a
+	/// runtime may, say, let a simple majority of its council schedule a majority-carries
+	/// referendum but require unanimity for a default-carries one.
 	type ExternalOrigin: EnsureOrigin<Self::Origin>;
 
 	/// Origin from which the next tabled referendum may be forced; this allows for the tabling of
@@ -350,8 +664,9 @@ pub trait Trait: frame_system::Trait + Sized {
 	/// majority-carries vote.
 	type FastTrackOrigin: EnsureOrigin<Self::Origin>;
 
-	/// Minimum voting period allowed for an fast-track/emergency referendum.
-	type EmergencyVotingPeriod: Get<Self::BlockNumber>;
+	/// Minimum voting period allowed for a fast-tracked referendum, unless tabled by the
+	/// `InstantOrigin`.
+	type FastTrackVotingPeriod: Get<Self::BlockNumber>;
 
 	/// Origin from which any referendum may be cancelled in an emergency.
 	type CancellationOrigin: EnsureOrigin<Self::Origin>;
@@ -363,10 +678,125 @@ pub trait Trait: frame_system::Trait + Sized {
 	type CooloffPeriod: Get<Self::BlockNumber>;
 
 	/// The amount of balance that must be deposited per byte of preimage stored.
-	type PreimageByteDeposit: Get<BalanceOf<Self>>;
+	type PreimageByteDeposit: Get<BalanceOf<Self, I>>;
+
+	/// The amount of balance that must be deposited for each chunk of a preimage uploaded in
+	/// chunks, on top of that for its bytes.
+	type PreimageChunkDeposit: Get<BalanceOf<Self, I>>;
+
+	/// The maximum number of chunks a preimage may be uploaded in.
+	type MaxPreimageChunks: Get<u32>;
+
+	/// The amount of balance reserved from a voter for their direct vote in each referendum. It
+	/// is returned when the vote is removed or, once the referendum is over, reaped.
+	type VoteDeposit: Get<BalanceOf<Self, I>>;
+
+	/// Handler for the unbalanced reduction when slashing a deposit: that of an invalid preimage,
+	/// or those backing the public proposals wiped by `clear_public_proposals` without refund.
+	type Slash: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
+
+	/// The maximum number of public proposals that can exist at any time.
+	type MaxProposals: Get<u32>;
+
+	/// The maximum number of deposits a public proposal may have: that of its proposer and one
+	/// for each second.
+	type MaxDeposits: Get<u32>;
+
+	/// The maximum number of accounts that may have vetoed the same external proposal hash
+	/// while it is blacklisted.
+	type MaxVetoers: Get<u32>;
+
+	/// The criterion by which finished referenda are judged, given the `VoteThreshold` of the
+	/// origin that tabled them.
+	type ApprovalCriterion: ApprovalCriterion<BalanceOf<Self, I>>;
+
+	/// The maximum number of options in a multi-option referendum.
+	type MaxOptions: Get<u32>;
+
+	/// The maximum number of accounts a split delegation may be spread across.
+	type MaxDelegationTargets: Get<u32>;
 
-	/// Handler for the unbalanced reduction when slashing a preimage deposit.
-	type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+	/// The maximum number of accounts which may delegate to a single account. It bounds the
+	/// delegations a tally reads for each vote.
+	type MaxDelegators: Get<u32>;
+
+	/// The maximum number of ongoing referenda in which an account may have direct votes, and
+	/// hence of votes in a batch.
+	type MaxVotes: Get<u32>;
+
+	/// The votes and lock periods implied by each conviction. The votes and lock periods should
+	/// both increase with the conviction.
+	type ConvictionSchedule: Get<ConvictionSchedule>;
+
+	/// The curve by which votes are discounted according to how long before the end of voting
+	/// they were cast. Use `()` to give every vote its full weight.
+	type VoteWeightCurve: VoteWeightCurve<Self::BlockNumber>;
+
+	/// The curve by which the enactment of approved proposals is delayed according to the turnout
+	/// of their referenda. Use `()` to enact every proposal with the delay it was tabled with.
+	type EnactmentDelayCurve: EnactmentDelayCurve<Self::BlockNumber>;
+
+	/// The maximum number of passed referenda that can be waiting for enactment at any time.
+	type MaxQueued: Get<u32>;
+
+	/// Handler for the start and end of each referendum. Use `()` if nothing needs to know.
+	type ReferendumHooks: ReferendumHooks;
+
+	/// Handler for votes being cast and removed. Use `()` if nothing needs to know.
+	type OnVote: OnVote<Self::AccountId>;
+
+	/// Handler for votes being delegated and undelegated. Use `()` if nothing needs to know.
+	type OnDelegation: OnDelegation<Self::AccountId>;
+
+	/// The number of blocks for which the outcome of a finished referendum is kept before it may
+	/// be pruned.
+	type FinishedRetention: Get<Self::BlockNumber>;
+
+	/// Whether delegating removes the direct votes of the delegator in the referenda the
+	/// delegation covers. If not, delegating fails as long as there are any.
+	type RemoveVotesOnDelegation: Get<bool>;
+
+	/// Whether the deposits backing a public proposal are held until its referendum is over,
+	/// rather than returned as soon as it is tabled. Held deposits are returned once the
+	/// referendum ends or is cancelled by _Root_, and slashed if it is cancelled by the
+	/// `CancellationOrigin`.
+	type HoldDeposits: Get<bool>;
+
+	/// Whether votes are quadratic: a voter's votes are those of the square root of their
+	/// balance, with conviction applied afterwards, while their whole balance still counts
+	/// towards turnout. Pair it with `QuadraticQuorumBiasing` as the `ApprovalCriterion`.
+	type QuadraticVoting: Get<bool>;
+
+	/// Whether the direct vote of an account in a referendum is weighted by a snapshot of its
+	/// balance, taken when it first votes there, rather than by its balance when the votes are
+	/// tallied. Should its balance have fallen below the snapshot since, the lower one counts.
+	type SnapshotBalances: Get<bool>;
+
+	/// Whether a record of each direct vote is written to the off-chain index, under the key
+	/// given by `vote_index_key`, for archive nodes to serve. Nothing is kept in state.
+	type IndexVotes: Get<bool>;
+
+	/// The amount by which each proposal in the public queue raises the effective minimum
+	/// deposit above `MinimumDeposit`. Zero leaves the minimum deposit fixed.
+	type DepositSurcharge: Get<BalanceOf<Self, I>>;
+
+	/// The proportion of its excess over `MinimumDeposit` that the effective minimum deposit
+	/// sheds every block, rounding up to at least one unit.
+	type DepositDecay: Get<Perbill>;
+
+	/// Origin from which the next majority-carries (or more permissive) referendum may be tabled
+	/// to vote immediately, with a voting period shorter than `FastTrackVotingPeriod`.
+	type InstantOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin from which a proposal hash may be whitelisted, or removed from the whitelist.
+	type WhitelistOrigin: EnsureOrigin<Self::Origin>;
+
+	/// Origin from which a whitelisted proposal may be tabled as a majority-carries referendum.
+	type DispatchWhitelistedOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The identifier of the lock placed on the balance of voters and delegators. Each instance
+	/// of the pallet sharing a currency needs its own, lest they release each other's locks.
+	type LockId: Get<LockIdentifier>;
 }
 
 /// Info regarding an ongoing referendum.
@@ -374,8 +804,8 @@ pub trait Trait: frame_system::Trait + Sized {
 pub struct ReferendumInfo<BlockNumber: Parameter, Hash: Parameter> {
 	/// When voting on this referendum will end.
 	end: BlockNumber,
-	/// The hash of the proposal being voted on.
-	proposal_hash: Hash,
+	/// The proposal being voted on.
+	proposal: BoundedCall<Hash>,
 	/// The thresholding mechanism to determine whether it passed.
 	threshold: VoteThreshold,
 	/// The delay (in blocks) to wait after a successful referendum before deploying.
@@ -386,11 +816,127 @@ impl<BlockNumber: Parameter, Hash: Parameter> ReferendumInfo<BlockNumber, Hash>
 	/// Create a new instance.
 	pub fn new(
 		end: BlockNumber,
-		proposal_hash: Hash,
+		proposal: BoundedCall<Hash>,
 		threshold: VoteThreshold,
 		delay: BlockNumber
 	) -> Self {
-		ReferendumInfo { end, proposal_hash, threshold, delay }
+		ReferendumInfo { end, proposal, threshold, delay }
+	}
+}
+
+/// The outcome of a finished referendum.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Finished<BlockNumber> {
+	/// Whether the referendum was approved. A multi-option referendum is approved if any of its
+	/// options won.
+	pub approved: bool,
+	/// When voting on the referendum ended.
+	pub end: BlockNumber,
+}
+
+/// The record of a direct vote written to the off-chain index when `IndexVotes` is set.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct VoteRecord<Balance: HasCompact, BlockNumber: HasCompact> {
+	/// The vote, as last cast.
+	pub vote: Vote,
+	/// The total balance of the voter when they last cast it.
+	#[codec(compact)]
+	pub balance: Balance,
+	/// When they last cast it.
+	#[codec(compact)]
+	pub at: BlockNumber,
+}
+
+/// A preimage being uploaded in chunks, each of which is kept in `PreimageChunks` until the
+/// upload is finalized.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PreimageUpload<AccountId, Balance, BlockNumber> {
+	/// The account uploading the preimage.
+	pub who: AccountId,
+	/// The number of chunks noted so far.
+	pub chunks: u32,
+	/// The total length of the chunks noted so far.
+	pub len: u32,
+	/// The deposit reserved for the chunks noted so far.
+	pub deposit: Balance,
+	/// The block at which the latest chunk was noted.
+	pub since: BlockNumber,
+}
+
+/// How the ballots of a multi-option referendum are counted.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TallyRule {
+	/// Each ballot approves a subset of the options, each of which scores the voter's balance.
+	Approval,
+	/// Each ballot ranks some of the options in order of preference. Of `n` options, the first
+	/// preference scores `n` times the voter's balance, the second `n - 1` times, and so on.
+	Ranked,
+}
+
+/// Something to which the hash of an off-chain description may be attached.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum MetadataOwner {
+	/// The external proposal waiting to be tabled.
+	External,
+	/// A public proposal.
+	Proposal(PropIndex),
+	/// An ongoing referendum, binary or multi-option.
+	Referendum(ReferendumIndex),
+}
+
+/// Info regarding an ongoing multi-option referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MultiReferendumInfo<BlockNumber: Parameter, Hash: Parameter> {
+	/// When voting on this referendum will end.
+	end: BlockNumber,
+	/// The proposals being chosen between.
+	options: Vec<BoundedCall<Hash>>,
+	/// The rule by which the ballots are counted.
+	rule: TallyRule,
+	/// The delay (in blocks) to wait after the referendum before deploying the winning option.
+	delay: BlockNumber,
+}
+
+// A value placed in storage that represents the current version of the Democracy storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+enum Releases {
+	/// Proposals are referenced by the hash of their preimage only.
+	V1,
+	/// Proposals are referenced by `BoundedCall`.
+	V2,
+	/// `Locks` holds a `PriorLock` rather than a bare expiry block.
+	V3,
+	/// `Voting` indexes the direct votes of each account.
+	V4,
+	/// A `PriorLock` keeps each lock not outlasted by a greater one, rather than their aggregate.
+	V5,
+	/// Each public proposal has at most `MaxDeposits` deposits.
+	V6,
+	/// The state of the deprecated built-in proxy has been removed.
+	V7,
+	/// `Delegators` indexes delegations by delegate, while the state of the built-in proxy is
+	/// kept as it is enabled.
+	V8,
+	/// `Delegators` indexes delegations by delegate, and the state of the built-in proxy has
+	/// been removed.
+	V9,
+}
+
+impl Releases {
+	/// The latest version, given whether the built-in proxy is still enabled.
+	fn latest() -> Self {
+		if cfg!(feature = "legacy-proxy") { Releases::V8 } else { Releases::V9 }
+	}
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
 	}
 }
 
@@ -412,20 +958,92 @@ impl<AccountId> ProxyState<AccountId> {
 	}
 }
 
+/// The locks left behind by past votes and delegations, i.e. ones whose original reason has
+/// been forgotten. Each holds the block number until which it must stay in place and the amount
+/// locked.
+///
+/// The amount locked at any time is the greatest of the locks still in place, so a lock which
+/// another outlasts while locking at least as much is dropped. The locks kept are ordered by
+/// expiry, and hence by decreasing amount.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct PriorLock<BlockNumber, Balance>(Vec<(BlockNumber, Balance)>);
+
+impl<BlockNumber, Balance> Default for PriorLock<BlockNumber, Balance> {
+	fn default() -> Self {
+		PriorLock(Vec::new())
+	}
+}
+
+impl<BlockNumber: Ord + Copy + Zero, Balance: Ord + Copy + Zero> PriorLock<BlockNumber, Balance> {
+	/// A single lock of `amount` until `until`.
+	pub fn new(until: BlockNumber, amount: Balance) -> Self {
+		let mut prior = Self::default();
+		prior.accumulate(until, amount);
+		prior
+	}
+
+	/// Accumulates an additional lock of `amount` until `until`.
+	pub fn accumulate(&mut self, until: BlockNumber, amount: Balance) {
+		if amount.is_zero() || self.0.iter().any(|&(u, a)| u >= until && a >= amount) {
+			return;
+		}
+		self.0.retain(|&(u, a)| u > until || a > amount);
+		let index = self.0.iter().position(|&(u, _)| u > until).unwrap_or(self.0.len());
+		self.0.insert(index, (until, amount));
+	}
+
+	/// The amount that is locked: the greatest of the locks.
+	pub fn locked(&self) -> Balance {
+		self.0.first().map_or_else(Zero::zero, |&(_, amount)| amount)
+	}
+
+	/// The block number from which the whole lock may be removed.
+	pub fn expiry(&self) -> BlockNumber {
+		self.0.last().map_or_else(Zero::zero, |&(until, _)| until)
+	}
+
+	/// Clear the locks which have expired by `now`.
+	pub fn rejig(&mut self, now: BlockNumber) {
+		self.0.retain(|&(until, _)| now < until);
+	}
+
+	/// Whether anything remains locked.
+	pub fn is_unlocked(&self) -> bool {
+		self.0.is_empty()
+	}
+}
+
 decl_storage! {
-	trait Store for Module<T: Trait> as Democracy {
+	trait Store for Module<T: Trait<I>, I: Instance=DefaultInstance> as Democracy {
 		/// The number of (public) proposals that have been made so far.
 		pub PublicPropCount get(fn public_prop_count) build(|_| 0 as PropIndex) : PropIndex;
-		/// The public proposals. Unsorted. The second item is the proposal's hash.
-		pub PublicProps get(fn public_props): Vec<(PropIndex, T::Hash, T::AccountId)>;
+		/// The public proposals. Unsorted. The second item is the proposal.
+		pub PublicProps get(fn public_props): Vec<(PropIndex, BoundedCallOf<T>, T::AccountId)>;
+		/// The minimum deposit currently required for a public proposal. Raised by
+		/// `DepositSurcharge` for each proposal in the queue whenever one is made, it decays back
+		/// towards `MinimumDeposit` over time.
+		pub EffectiveMinimumDeposit get(fn effective_minimum_deposit)
+			build(|_| T::MinimumDeposit::get()): BalanceOf<T, I>;
 		/// Map of hashes to the proposal preimage, along with who registered it and their deposit.
 		/// The block number is the block at which it was deposited.
 		pub Preimages:
 			map hasher(identity) T::Hash
-			=> Option<(Vec<u8>, T::AccountId, BalanceOf<T>, T::BlockNumber)>;
-		/// Those who have locked a deposit.
+			=> Option<(Vec<u8>, T::AccountId, BalanceOf<T, I>, T::BlockNumber)>;
+		/// Preimages being uploaded in chunks, by the hash of the whole preimage.
+		pub PreimageUploads get(fn preimage_upload):
+			map hasher(identity) T::Hash
+			=> Option<PreimageUpload<T::AccountId, BalanceOf<T, I>, T::BlockNumber>>;
+		/// The chunks noted so far of each preimage being uploaded, by index.
+		pub PreimageChunks: map hasher(twox_64_concat) (T::Hash, u32) => Option<Vec<u8>>;
+		/// Those who have locked a deposit, at most `MaxDeposits` of them.
 		pub DepositOf get(fn deposit_of):
-			map hasher(twox_64_concat) PropIndex => Option<(BalanceOf<T>, Vec<T::AccountId>)>;
+			map hasher(twox_64_concat) PropIndex => Option<(BalanceOf<T, I>, Vec<T::AccountId>)>;
+
+		/// The deposits backing an ongoing referendum tabled from a public proposal, held until
+		/// it is over if `HoldDeposits` is set.
+		pub ReferendumDeposits get(fn referendum_deposits):
+			map hasher(twox_64_concat) ReferendumIndex
+			=> Option<(BalanceOf<T, I>, Vec<T::AccountId>)>;
 
 		/// The next free referendum index, aka the number of referenda started so far.
 		pub ReferendumCount get(fn referendum_count) build(|_| 0 as ReferendumIndex): ReferendumIndex;
@@ -436,8 +1054,10 @@ decl_storage! {
 		pub ReferendumInfoOf get(fn referendum_info):
 			map hasher(twox_64_concat) ReferendumIndex
 			=> Option<ReferendumInfo<T::BlockNumber, T::Hash>>;
-		/// Queue of successful referenda to be dispatched. Stored ordered by block number.
-		pub DispatchQueue get(fn dispatch_queue): Vec<(T::BlockNumber, T::Hash, ReferendumIndex)>;
+		/// Queue of successful referenda to be dispatched. Stored ordered by block number. Holds
+		/// at most `MaxQueued` items.
+		pub DispatchQueue get(fn dispatch_queue):
+			Vec<(T::BlockNumber, BoundedCallOf<T>, ReferendumIndex)>;
 
 		/// Get the voters for the current proposal.
 		pub VotersFor get(fn voters_for):
@@ -449,6 +1069,53 @@ decl_storage! {
 		/// also check for simple existence with `VoteOf::contains_key` first.
 		pub VoteOf get(fn vote_of): map hasher(twox_64_concat) (ReferendumIndex, T::AccountId) => Vote;
 
+		/// The ongoing referenda in which an account has a direct vote, a ballot or an abstention,
+		/// in ascending order. The account-wise counterpart of `VotersFor` and `Abstainers`.
+		pub Voting get(fn voting): map hasher(twox_64_concat) T::AccountId => Vec<ReferendumIndex>;
+
+		/// The accounts abstaining in a given referendum. Their balance counts toward its turnout,
+		/// but toward neither side. An account is never both a voter and an abstainer. Each
+		/// abstention counts towards `MaxVotes` and holds `VoteDeposit`, which bounds its length
+		/// as those of `VotersFor`.
+		pub Abstainers get(fn abstainers):
+			map hasher(twox_64_concat) ReferendumIndex => Vec<T::AccountId>;
+
+		/// The block in which a particular voter last cast their vote in a given referendum. Votes
+		/// cast before this was recorded have no entry, and keep their full weight.
+		pub VotedAt get(fn voted_at):
+			map hasher(twox_64_concat) (ReferendumIndex, T::AccountId) => Option<T::BlockNumber>;
+
+		/// The balance of a particular voter when they first voted in a given referendum, which
+		/// caps the weight of their vote there. Only taken if `SnapshotBalances` is set.
+		pub BalanceSnapshots get(fn balance_snapshot):
+			map hasher(twox_64_concat) (ReferendumIndex, T::AccountId) => Option<BalanceOf<T, I>>;
+
+		/// The deposit reserved for the direct vote of a particular voter in a given referendum,
+		/// along with the end of its voting period. It outlives the vote when the referendum is
+		/// over, until reaped with `reap_vote`.
+		pub VoteDeposits get(fn vote_deposit):
+			map hasher(twox_64_concat) (ReferendumIndex, T::AccountId)
+			=> Option<(BalanceOf<T, I>, T::BlockNumber)>;
+
+		/// Information concerning an ongoing multi-option referendum. Multi-option referenda share
+		/// their index space, and `VotersFor`, with the binary referenda.
+		pub MultiReferendumInfoOf get(fn multi_referendum_info):
+			map hasher(twox_64_concat) ReferendumIndex
+			=> Option<MultiReferendumInfo<T::BlockNumber, T::Hash>>;
+
+		/// The indices of the ongoing multi-option referenda.
+		pub MultiReferenda get(fn multi_referenda): Vec<ReferendumIndex>;
+
+		/// Get the ballot in a given multi-option referendum of a particular voter: the indices of
+		/// the options approved or, for a ranked referendum, of the options in order of preference.
+		pub BallotOf get(fn ballot_of):
+			map hasher(twox_64_concat) (ReferendumIndex, T::AccountId) => Vec<u32>;
+
+		/// The hash of the off-chain description of a proposal or referendum. The description of a
+		/// proposal is carried over to its referendum when tabled, and removed once it is over.
+		pub MetadataOf get(fn metadata_of):
+			map hasher(twox_64_concat) MetadataOwner => Option<T::Hash>;
+
 		/// Who is able to vote for whom. Value is the fund-holding account, key is the
 		/// vote-transaction-sending account.
 		pub Proxy get(fn proxy): map hasher(twox_64_concat) T::AccountId => Option<ProxyState<T::AccountId>>;
@@ -457,33 +1124,107 @@ decl_storage! {
 		pub Delegations get(fn delegations):
 			map hasher(twox_64_concat) T::AccountId => (T::AccountId, Conviction);
 
+		/// Get the accounts to which another account is delegating vote, each along with the
+		/// proportion of its balance delegated to them, and the lock periods. An account delegates
+		/// either here or in `Delegations`, never both.
+		pub SplitDelegations get(fn split_delegations):
+			map hasher(twox_64_concat) T::AccountId
+			=> Option<(Vec<(T::AccountId, Perbill)>, Conviction)>;
+
+		/// Get the account (and lock periods) to which another account is delegating vote in one
+		/// class of referenda, identified by the threshold they are tabled with. Overrides
+		/// `Delegations` for referenda of that class.
+		pub ClassDelegations get(fn class_delegations):
+			map hasher(twox_64_concat) (T::AccountId, VoteThreshold)
+			=> Option<(T::AccountId, Conviction)>;
+
+		/// The accounts delegating to an account in `Delegations`, `SplitDelegations` or
+		/// `ClassDelegations`, each listed once however many of their delegations go to it. At
+		/// most `MaxDelegators` of them, save for those indexed by the migration to `Delegators`.
+		pub Delegators get(fn delegators):
+			map hasher(twox_64_concat) T::AccountId => Vec<T::AccountId>;
+
 		/// Accounts for which there are locks in action which may be removed at some point in the
-		/// future. The value is the block number at which the lock expires and may be removed.
-		pub Locks get(locks): map hasher(twox_64_concat) T::AccountId => Option<T::BlockNumber>;
+		/// future. The value holds the amounts locked by past votes and delegations, each along
+		/// with the block number at which it expires; the greatest of them is locked.
+		pub Locks get(locks):
+			map hasher(twox_64_concat) T::AccountId
+			=> Option<PriorLock<T::BlockNumber, BalanceOf<T, I>>>;
 
 		/// True if the last referendum tabled was submitted externally. False if it was a public
 		/// proposal.
 		pub LastTabledWasExternal: bool;
 
+		/// True if no referendum is to be tabled at the end of each `LaunchPeriod`, until it is
+		/// set back to false.
+		pub LaunchPaused get(fn launch_paused): bool;
+
 		/// The referendum to be tabled whenever it would be valid to table an external proposal.
 		/// This happens when a referendum needs to be tabled and one of two conditions are met:
 		/// - `LastTabledWasExternal` is `false`; or
 		/// - `PublicProps` is empty.
-		pub NextExternal: Option<(T::Hash, VoteThreshold)>;
+		pub NextExternal: Option<(BoundedCallOf<T>, VoteThreshold)>;
 
 		/// A record of who vetoed what. Maps proposal hash to a possible existent block number
 		/// (until when it may not be resubmitted) and who vetoed it.
 		pub Blacklist get(fn blacklist):
 			map hasher(identity) T::Hash => Option<(T::BlockNumber, Vec<T::AccountId>)>;
 
-		/// Record of all proposals that have been subject to emergency cancellation.
+		/// The outcome of each referendum that was voted to its end, kept until pruned. Cancelled
+		/// referenda have none.
+		pub FinishedOf get(fn finished_of):
+			map hasher(twox_64_concat) ReferendumIndex => Option<Finished<T::BlockNumber>>;
+
+		/// Record of all proposals that have been subject to emergency cancellation. A proposal
+		/// recorded here may not be emergency-cancelled again.
 		pub Cancellations: map hasher(identity) T::Hash => bool;
+
+		/// The proposal hashes whitelisted by the `WhitelistOrigin`, each of which may be tabled
+		/// once by the `DispatchWhitelistedOrigin`.
+		pub Whitelist get(fn is_whitelisted): map hasher(identity) T::Hash => bool;
+
+		/// Storage version of the pallet.
+		///
+		/// New networks start with the latest version, as determined by the genesis build.
+		StorageVersion build(|_| Releases::latest()): Releases;
+	}
+	add_extra_genesis {
+		/// Public proposals to start the queue with: the proposer, the hash of the proposal and
+		/// the deposit, which is reserved from the proposer.
+		config(proposals): Vec<(T::AccountId, T::Hash, BalanceOf<T, I>)>;
+		/// Referenda to start with: the hash of the proposal, the threshold and the delay before
+		/// enactment. Voting on them ends after `VotingPeriod`.
+		config(referenda): Vec<(T::Hash, VoteThreshold, T::BlockNumber)>;
+		build(|config: &GenesisConfig<T, I>| {
+			assert!(
+				config.proposals.len() as u32 <= T::MaxProposals::get(),
+				"There may be no more than `MaxProposals` genesis proposals",
+			);
+			for (index, (who, hash, deposit)) in config.proposals.iter().enumerate() {
+				let index = index as PropIndex;
+				T::Currency::reserve(who, *deposit)
+					.expect("Genesis proposers must be able to afford their deposits");
+				<DepositOf<T, I>>::insert(index, (*deposit, vec![who.clone()]));
+				<PublicProps<T, I>>::mutate(|props|
+					props.push((index, <Module<T, I>>::bound_hash(*hash), who.clone()))
+				);
+			}
+			PublicPropCount::<I>::put(config.proposals.len() as PropIndex);
+
+			let end = T::VotingPeriod::get();
+			for (index, (hash, threshold, delay)) in config.referenda.iter().enumerate() {
+				let proposal = <Module<T, I>>::bound_hash(*hash);
+				let info = ReferendumInfo { end, proposal, threshold: *threshold, delay: *delay };
+				<ReferendumInfoOf<T, I>>::insert(index as ReferendumIndex, info);
+			}
+			ReferendumCount::<I>::put(config.referenda.len() as ReferendumIndex);
+		});
 	}
 }
 
 decl_event! {
-	pub enum Event<T> where
-		Balance = BalanceOf<T>,
+	pub enum Event<T, I=DefaultInstance> where
+		Balance = BalanceOf<T, I>,
 		<T as frame_system::Trait>::AccountId,
 		<T as frame_system::Trait>::Hash,
 		<T as frame_system::Trait>::BlockNumber,
@@ -502,6 +1243,10 @@ decl_event! {
 		NotPassed(ReferendumIndex),
 		/// A referendum has been cancelled.
 		Cancelled(ReferendumIndex),
+		/// The deposits held for a referendum have been returned.
+		DepositsRefunded(ReferendumIndex),
+		/// The deposits held for a referendum have been slashed.
+		DepositsSlashed(ReferendumIndex),
 		/// A proposal has been enacted.
 		Executed(ReferendumIndex, bool),
 		/// An account has delegated their vote to another account.
@@ -522,11 +1267,73 @@ decl_event! {
 		PreimageReaped(Hash, AccountId, Balance, AccountId),
 		/// An account has been unlocked successfully.
 		Unlocked(AccountId),
+		/// An account has seconded a proposal.
+		Seconded(AccountId, PropIndex),
+		/// An account has withdrawn a second from a proposal.
+		Unseconded(AccountId, PropIndex),
+		/// An account has voted in a referendum.
+		Voted(AccountId, ReferendumIndex, Vote),
+		/// An external proposal has been scheduled to be tabled.
+		ExternalProposed(Hash, VoteThreshold),
+		/// A proposal queued for enactment has been cancelled.
+		QueuedCancelled(ReferendumIndex),
+		/// A proposal queued for enactment has been rescheduled to be enacted at a block.
+		EnactmentRescheduled(ReferendumIndex, BlockNumber),
+		/// All public proposals have been cleared.
+		PublicProposalsCleared,
+		/// The tabling of referenda every `LaunchPeriod` has been paused (true) or resumed
+		/// (false).
+		LaunchPausedSet(bool),
+		/// An account (first item) has opened itself to become the proxy of a stash.
+		ProxyOpened(AccountId, AccountId),
+		/// A stash (first item) has activated its proxy.
+		ProxyActivated(AccountId, AccountId),
+		/// A stash (first item) has deactivated its proxy.
+		ProxyDeactivated(AccountId, AccountId),
+		/// A proxy has been closed.
+		ProxyClosed(AccountId),
+		/// An account has removed its vote from an ongoing referendum.
+		VoteRemoved(AccountId, ReferendumIndex),
+		/// A multi-option referendum has begun.
+		MultiStarted(ReferendumIndex, TallyRule),
+		/// An account has cast a ballot in a multi-option referendum.
+		BallotCast(AccountId, ReferendumIndex),
+		/// A multi-option referendum has been decided in favour of an option (second item).
+		MultiPassed(ReferendumIndex, u32),
+		/// The hash of an off-chain description has been attached to a proposal or referendum.
+		MetadataSet(MetadataOwner, Hash),
+		/// The off-chain description of a proposal or referendum has been cleared.
+		MetadataCleared(MetadataOwner),
+		/// A referendum has passed but could not be queued for enactment, as the dispatch queue
+		/// was full.
+		QueueFull(ReferendumIndex),
+		/// An account has abstained in a referendum.
+		Abstained(AccountId, ReferendumIndex),
+		/// An account (first item) has delegated their vote in a class of referenda to another.
+		ClassDelegated(AccountId, VoteThreshold, AccountId),
+		/// An account has cancelled their delegation in a class of referenda.
+		ClassUndelegated(AccountId, VoteThreshold),
+		/// The outcome of a finished referendum has been pruned.
+		FinishedPruned(ReferendumIndex),
+		/// A chunk (last item) of a preimage being uploaded was noted, and its deposit taken.
+		PreimageChunkNoted(Hash, AccountId, u32),
+		/// An unfinished preimage upload was removed and its deposit collected by the reaper
+		/// (last item).
+		PreimageUploadReaped(Hash, AccountId, Balance, AccountId),
+		/// The deposit (last item) for the vote of an account in a referendum which is over has
+		/// been returned.
+		VoteReaped(AccountId, ReferendumIndex, Balance),
+		/// A proposal hash has been whitelisted.
+		Whitelisted(Hash),
+		/// A proposal hash has been removed from the whitelist without being tabled.
+		WhitelistRemoved(Hash),
+		/// A whitelisted proposal has been tabled as the given referendum.
+		WhitelistedTabled(Hash, ReferendumIndex),
 	}
 }
 
 decl_error! {
-	pub enum Error for Module<T: Trait> {
+	pub enum Error for Module<T: Trait<I>, I: Instance> {
 		/// Value too low
 		ValueLow,
 		/// Proposal does not exist
@@ -580,43 +1387,259 @@ decl_error! {
 		/// A proxy-pairing was attempted to an account that was open to another account.
 		WrongOpen,
 		/// A proxy-de-pairing was attempted to an account that was not active.
-		NotActive
+		NotActive,
+		/// The maximum number of public proposals has been reached.
+		TooManyProposals,
+		/// Invalid upper bound.
+		WrongUpperBound,
+		/// The built-in proxy is deprecated and has been disabled.
+		ProxyDeprecated,
+		/// The account has not voted in the referendum.
+		NotVoter,
+		/// A multi-option referendum needs between two and `MaxOptions` options.
+		WrongOptionCount,
+		/// The ballot is empty, names an unknown option, or names an option twice.
+		InvalidBallot,
+		/// Only the proposer may describe a public proposal.
+		NotProposer,
+		/// The account is already abstaining in the referendum.
+		AlreadyAbstaining,
+		/// The outcome of the referendum is unknown, or too recent to be pruned.
+		NotPrunable,
+		/// The account has direct votes in referenda the delegation would cover.
+		VotesExist,
+		/// A different external proposal is already scheduled and may not be replaced.
+		ProposalAlreadyQueued,
+		/// The voting period is too short for the origin fast-tracking the referendum.
+		VotingPeriodLow,
+		/// A split delegation needs between one and `MaxDelegationTargets` distinct accounts,
+		/// whose proportions add up to no more than the whole.
+		InvalidSplit,
+		/// The account has not seconded the proposal.
+		NotSeconder,
+		/// The proposal already has the maximum number of deposits.
+		TooManyDeposits,
+		/// The proposal has already been vetoed by the maximum number of accounts.
+		TooManyVetoers,
+		/// No preimage is being uploaded with this hash.
+		UploadMissing,
+		/// The preimage is being uploaded by another account.
+		NotUploader,
+		/// The chunk is not the next one of the upload.
+		WrongChunkIndex,
+		/// The chunks uploaded do not hash to the preimage hash.
+		PreimageMismatch,
+		/// The chunk is empty.
+		EmptyChunk,
+		/// The upload already has the maximum number of chunks.
+		TooManyChunks,
+		/// The account would have direct votes in more than `MaxVotes` referenda.
+		MaxVotesReached,
+		/// The account delegated to already has `MaxDelegators` delegators.
+		TooManyDelegators,
+		/// The account cannot afford the deposits for its new votes.
+		InsufficientFunds,
+		/// The proposal hash is already whitelisted.
+		AlreadyWhitelisted,
+		/// The proposal hash is not whitelisted.
+		NotWhitelisted,
 	}
 }
 
-impl<T: Trait> MigrateAccount<T::AccountId> for Module<T> {
+impl<T: Trait<I>, I: Instance> MigrateAccount<T::AccountId> for Module<T, I> {
 	fn migrate_account(a: &T::AccountId) {
-		Proxy::<T>::migrate_key_from_blake(a);
-		Locks::<T>::migrate_key_from_blake(a);
-		Delegations::<T>::migrate_key_from_blake(a);
-		for i in LowestUnbaked::get()..ReferendumCount::get() {
-			VoteOf::<T>::migrate_key_from_blake((i, a));
+		Proxy::<T, I>::migrate_key_from_blake(a);
+		Locks::<T, I>::migrate_key_from_blake(a);
+		Delegations::<T, I>::migrate_key_from_blake(a);
+		for i in LowestUnbaked::<I>::get()..ReferendumCount::<I>::get() {
+			VoteOf::<T, I>::migrate_key_from_blake((i, a));
 		}
 	}
 }
 
 mod migration {
 	use super::*;
-	pub fn migrate<T: Trait>() {
-		Blacklist::<T>::remove_all();
-		Cancellations::<T>::remove_all();
-		for i in LowestUnbaked::get()..ReferendumCount::get() {
-			VotersFor::<T>::migrate_key_from_blake(i);
-			ReferendumInfoOf::<T>::migrate_key_from_blake(i);
+	pub fn migrate<T: Trait<I>, I: Instance>() {
+		if StorageVersion::<I>::get() == Releases::V1 {
+			migrate_to_v2::<T, I>();
+		}
+		if StorageVersion::<I>::get() == Releases::V2 {
+			// Bare expiries are translated straight into the latest layout of `PriorLock`.
+			migrate_to_prior_locks::<T, I>();
+			migrate_to_voting::<T, I>();
+			StorageVersion::<I>::put(Releases::V5);
+		}
+		if StorageVersion::<I>::get() == Releases::V3 {
+			migrate_to_voting::<T, I>();
+			StorageVersion::<I>::put(Releases::V4);
+		}
+		if StorageVersion::<I>::get() == Releases::V4 {
+			migrate_to_separate_locks::<T, I>();
+			StorageVersion::<I>::put(Releases::V5);
+		}
+		if StorageVersion::<I>::get() == Releases::V5 {
+			migrate_to_bounded_deposits::<T, I>();
+			StorageVersion::<I>::put(Releases::V6);
+		}
+		if StorageVersion::<I>::get() == Releases::V6 && !cfg!(feature = "legacy-proxy") {
+			remove_proxies::<T, I>();
+			StorageVersion::<I>::put(Releases::V7);
+		}
+		if StorageVersion::<I>::get() == Releases::V6 {
+			migrate_to_delegators::<T, I>();
+			StorageVersion::<I>::put(Releases::V8);
+		}
+		if StorageVersion::<I>::get() == Releases::V7 {
+			migrate_to_delegators::<T, I>();
+			StorageVersion::<I>::put(Releases::V9);
 		}
-		for (p, h, _) in PublicProps::<T>::get().into_iter() {
-			DepositOf::<T>::migrate_key_from_blake(p);
-			Preimages::<T>::migrate_key_from_blake(h);
+		if StorageVersion::<I>::get() == Releases::V8 && !cfg!(feature = "legacy-proxy") {
+			remove_proxies::<T, I>();
+			StorageVersion::<I>::put(Releases::V9);
+		}
+	}
+
+	/// Check the state ahead of the migration to `Voting`, returning the number of direct votes
+	/// which it is expected to index.
+	#[cfg(any(test, feature = "runtime-benchmarks"))]
+	pub fn pre_migrate_to_voting<T: Trait<I>, I: Instance>() -> u32 {
+		assert_eq!(StorageVersion::<I>::get(), Releases::V3, "Migration to `Voting` already done");
+		(LowestUnbaked::<I>::get()..ReferendumCount::<I>::get())
+			.map(|i| VotersFor::<T, I>::decode_len(i).unwrap_or(0) as u32)
+			.sum()
+	}
+
+	/// Check the state after the migration to `Voting`: the `votes` expected are indexed, each
+	/// of them by the account which cast it.
+	#[cfg(any(test, feature = "runtime-benchmarks"))]
+	pub fn post_migrate_to_voting<T: Trait<I>, I: Instance>(votes: u32) {
+		assert!(StorageVersion::<I>::get() >= Releases::V4, "Migration to `Voting` not done");
+		let mut indexed = 0;
+		for (who, indices) in Voting::<T, I>::iter() {
+			assert!(indices.windows(2).all(|w| w[0] < w[1]), "`Voting` entry not ordered");
+			for i in indices {
+				assert!(VoteOf::<T, I>::contains_key((i, &who)), "`Voting` entry without a vote");
+				indexed += 1;
+			}
+		}
+		assert_eq!(indexed, votes, "Votes missing from `Voting`");
+	}
+
+	/// Remove the state of the deprecated built-in proxy, along with the reference each open or
+	/// active proxy holds on its account. Proxies hold no deposit besides that reference.
+	pub fn remove_proxies<T: Trait<I>, I: Instance>() {
+		for (who, _) in Proxy::<T, I>::drain() {
+			system::Module::<T>::dec_ref(&who);
+		}
+	}
+
+	fn migrate_to_v2<T: Trait<I>, I: Instance>() {
+		Blacklist::<T, I>::remove_all();
+		Cancellations::<T, I>::remove_all();
+		for i in LowestUnbaked::<I>::get()..ReferendumCount::<I>::get() {
+			VotersFor::<T, I>::migrate_key_from_blake(i);
+			ReferendumInfoOf::<T, I>::migrate_key_from_blake(i);
+		}
+		migrate_to_bounded_calls::<T, I>();
+		for (p, proposal, _) in PublicProps::<T, I>::get().into_iter() {
+			DepositOf::<T, I>::migrate_key_from_blake(p);
+			if let Some(h) = proposal.lookup_hash() {
+				Preimages::<T, I>::migrate_key_from_blake(h);
+			}
+		}
+
+		StorageVersion::<I>::put(Releases::V2);
+	}
+
+	/// Turn every hash-only proposal reference into a `BoundedCall::Legacy`.
+	///
+	/// The preimages themselves are left where they are: a legacy reference is resolved lazily
+	/// against `Preimages` whenever its call is needed.
+	fn migrate_to_bounded_calls<T: Trait<I>, I: Instance>() {
+		let legacy = |hash| BoundedCall::Legacy { hash };
+		let _ = PublicProps::<T, I>::translate::<Vec<(PropIndex, T::Hash, T::AccountId)>, _>(
+			|props| props.map(|p| p.into_iter().map(|(i, h, who)| (i, legacy(h), who)).collect())
+		);
+		let _ = NextExternal::<T, I>::translate::<(T::Hash, VoteThreshold), _>(
+			|next| next.map(|(h, threshold)| (legacy(h), threshold))
+		);
+		let _ = DispatchQueue::<T, I>::translate::<Vec<(T::BlockNumber, T::Hash, ReferendumIndex)>, _>(
+			|queue| queue.map(|q| q.into_iter().map(|(n, h, i)| (n, legacy(h), i)).collect())
+		);
+		ReferendumInfoOf::<T, I>::translate::<(T::BlockNumber, T::Hash, VoteThreshold, T::BlockNumber), _>(
+			|_, (end, h, threshold, delay)| Some(ReferendumInfo::new(end, legacy(h), threshold, delay))
+		);
+	}
+
+	/// Turn every bare lock expiry into a `PriorLock`.
+	///
+	/// Locks used to cover the whole balance, so the amount carried over is the maximum.
+	fn migrate_to_prior_locks<T: Trait<I>, I: Instance>() {
+		Locks::<T, I>::translate::<T::BlockNumber, _>(
+			|_, until| Some(PriorLock::new(until, BalanceOf::<T, I>::max_value()))
+		);
+	}
+
+	/// Turn every aggregate lock into a `PriorLock` holding it alone.
+	///
+	/// The locks it aggregated are not known any more, so the aggregate stays in place whole.
+	fn migrate_to_separate_locks<T: Trait<I>, I: Instance>() {
+		Locks::<T, I>::translate::<(T::BlockNumber, BalanceOf<T, I>), _>(
+			|_, (until, amount)| Some(PriorLock::new(until, amount)).filter(|p| !p.is_unlocked())
+		);
+	}
+
+	/// Index every delegation in `Delegations`, `SplitDelegations` and `ClassDelegations` by its
+	/// delegate. A delegate may be left with more than `MaxDelegators` delegators, who delegated
+	/// before the bound.
+	fn migrate_to_delegators<T: Trait<I>, I: Instance>() {
+		for (who, (to, _)) in Delegations::<T, I>::iter() {
+			Module::<T, I>::index_delegator(&who, &to);
+		}
+		for (who, (targets, _)) in SplitDelegations::<T, I>::iter() {
+			for (to, _) in targets {
+				Module::<T, I>::index_delegator(&who, &to);
+			}
+		}
+		for ((who, _), (to, _)) in ClassDelegations::<T, I>::iter() {
+			Module::<T, I>::index_delegator(&who, &to);
+		}
+	}
+
+	/// Drop the latest seconds of every public proposal with more than `MaxDeposits` deposits,
+	/// returning the deposits of the seconds dropped.
+	fn migrate_to_bounded_deposits<T: Trait<I>, I: Instance>() {
+		let max = T::MaxDeposits::get() as usize;
+		for (index, _, _) in PublicProps::<T, I>::get() {
+			if let Some((deposit, mut depositors)) = DepositOf::<T, I>::get(index) {
+				if depositors.len() > max {
+					for who in depositors.split_off(max) {
+						T::Currency::unreserve(&who, deposit);
+					}
+					DepositOf::<T, I>::insert(index, (deposit, depositors));
+				}
+			}
+		}
+	}
+
+	/// Index the direct votes in ongoing referenda by voter, out of `VotersFor`.
+	///
+	/// Referenda are visited in ascending order, so each account's indices come out sorted.
+	pub fn migrate_to_voting<T: Trait<I>, I: Instance>() {
+		for i in LowestUnbaked::<I>::get()..ReferendumCount::<I>::get() {
+			for who in VotersFor::<T, I>::get(i) {
+				Voting::<T, I>::append_or_insert(&who, &[i][..]);
+			}
 		}
 	}
 }
 
 decl_module! {
-	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
-		type Error = Error<T>;
+	pub struct Module<T: Trait<I>, I: Instance=DefaultInstance> for enum Call where origin: T::Origin {
+		type Error = Error<T, I>;
 
 		fn on_runtime_upgrade() {
-			migration::migrate::<T>();
+			migration::migrate::<T, I>();
 		}
 
 		/// The minimum period of locking and the period between a proposal being approved and enacted.
@@ -633,16 +1656,77 @@ decl_module! {
 		const VotingPeriod: T::BlockNumber = T::VotingPeriod::get();
 
 		/// The minimum amount to be used as a deposit for a public referendum proposal.
-		const MinimumDeposit: BalanceOf<T> = T::MinimumDeposit::get();
+		const MinimumDeposit: BalanceOf<T, I> = T::MinimumDeposit::get();
+
+		/// The amount by which each proposal in the public queue raises the effective minimum
+		/// deposit.
+		const DepositSurcharge: BalanceOf<T, I> = T::DepositSurcharge::get();
 
-		/// Minimum voting period allowed for an emergency referendum.
-		const EmergencyVotingPeriod: T::BlockNumber = T::EmergencyVotingPeriod::get();
+		/// The proportion of its excess over `MinimumDeposit` that the effective minimum deposit
+		/// sheds every block.
+		const DepositDecay: Perbill = T::DepositDecay::get();
+
+		/// Minimum voting period allowed for a fast-tracked referendum, unless tabled by the
+		/// `InstantOrigin`.
+		const FastTrackVotingPeriod: T::BlockNumber = T::FastTrackVotingPeriod::get();
 
 		/// Period in blocks where an external proposal may not be re-submitted after being vetoed.
 		const CooloffPeriod: T::BlockNumber = T::CooloffPeriod::get();
 
 		/// The amount of balance that must be deposited per byte of preimage stored.
-		const PreimageByteDeposit: BalanceOf<T> = T::PreimageByteDeposit::get();
+		const PreimageByteDeposit: BalanceOf<T, I> = T::PreimageByteDeposit::get();
+
+		/// The amount of balance that must be deposited for each chunk of a preimage uploaded in
+		/// chunks.
+		const PreimageChunkDeposit: BalanceOf<T, I> = T::PreimageChunkDeposit::get();
+
+		/// The maximum number of chunks a preimage may be uploaded in.
+		const MaxPreimageChunks: u32 = T::MaxPreimageChunks::get();
+
+		/// The amount of balance reserved from a voter for their direct vote in each referendum.
+		const VoteDeposit: BalanceOf<T, I> = T::VoteDeposit::get();
+
+		/// The maximum number of public proposals that can exist at any time.
+		const MaxProposals: u32 = T::MaxProposals::get();
+
+		/// The maximum number of deposits a public proposal may have.
+		const MaxDeposits: u32 = T::MaxDeposits::get();
+
+		/// The maximum number of accounts that may have vetoed the same external proposal hash
+		/// while it is blacklisted.
+		const MaxVetoers: u32 = T::MaxVetoers::get();
+
+		/// The maximum number of options in a multi-option referendum.
+		const MaxOptions: u32 = T::MaxOptions::get();
+
+		/// The maximum number of accounts a split delegation may be spread across.
+		const MaxDelegationTargets: u32 = T::MaxDelegationTargets::get();
+
+		/// The maximum number of accounts which may delegate to a single account.
+		const MaxDelegators: u32 = T::MaxDelegators::get();
+
+		/// The maximum number of ongoing referenda in which an account may have direct votes.
+		const MaxVotes: u32 = T::MaxVotes::get();
+
+		/// The maximum number of passed referenda that can be waiting for enactment at any time.
+		const MaxQueued: u32 = T::MaxQueued::get();
+
+		/// The number of blocks for which the outcome of a finished referendum is kept before it
+		/// may be pruned.
+		const FinishedRetention: T::BlockNumber = T::FinishedRetention::get();
+
+		/// Whether delegating removes the direct votes of the delegator in the referenda the
+		/// delegation covers, rather than failing.
+		const RemoveVotesOnDelegation: bool = T::RemoveVotesOnDelegation::get();
+
+		/// Whether the deposits backing a public proposal are held until its referendum is over.
+		const HoldDeposits: bool = T::HoldDeposits::get();
+
+		/// Whether votes are those of the square root of the balance behind them.
+		const QuadraticVoting: bool = T::QuadraticVoting::get();
+
+		/// Whether direct votes are weighted by the balance of the voter when they first voted.
+		const SnapshotBalances: bool = T::SnapshotBalances::get();
 
 		fn deposit_event() = default;
 
@@ -652,29 +1736,41 @@ decl_module! {
 		/// have funds to cover the deposit.
 		///
 		/// - `proposal_hash`: The hash of the proposal preimage.
-		/// - `value`: The amount of deposit (must be at least `MinimumDeposit`).
+		/// - `value`: The amount of deposit (must be at least `EffectiveMinimumDeposit`).
+		///
+		/// Fails with `TooManyProposals` if there are already `MaxProposals` public proposals.
+		/// Raises `EffectiveMinimumDeposit` by `DepositSurcharge` for each public proposal.
 		///
 		/// Emits `Proposed`.
 		///
 		/// # <weight>
-		/// - `O(1)`.
-		/// - Two DB changes, one DB entry.
+		/// - `O(P)` where `P` is the number of public proposals, bounded by `MaxProposals`.
+		/// - Two DB changes, one DB entry, one length read of `PublicProps`.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
 		fn propose(origin,
 			proposal_hash: T::Hash,
-			#[compact] value: BalanceOf<T>
+			#[compact] value: BalanceOf<T, I>
 		) {
 			let who = ensure_signed(origin)?;
-			ensure!(value >= T::MinimumDeposit::get(), Error::<T>::ValueLow);
+			let minimum = Self::effective_minimum_deposit().max(T::MinimumDeposit::get());
+			ensure!(value >= minimum, Error::<T, I>::ValueLow);
+			let queued = <PublicProps<T, I>>::decode_len().unwrap_or(0) as u32;
+			ensure!(queued < T::MaxProposals::get(), Error::<T, I>::TooManyProposals);
 			T::Currency::reserve(&who, value)?;
 
 			let index = Self::public_prop_count();
-			PublicPropCount::put(index + 1);
-			<DepositOf<T>>::insert(index, (value, &[&who][..]));
+			PublicPropCount::<I>::put(index + 1);
+			<DepositOf<T, I>>::insert(index, (value, &[&who][..]));
+
+			let new_prop = (index, Self::bound_hash(proposal_hash), who);
+			<PublicProps<T, I>>::append_or_put(&[Ref::from(&new_prop)][..]);
 
-			let new_prop = (index, proposal_hash, who);
-			<PublicProps<T>>::append_or_put(&[Ref::from(&new_prop)][..]);
+			let surcharge = T::DepositSurcharge::get().saturating_mul((queued + 1).into());
+			let congested = T::MinimumDeposit::get().saturating_add(surcharge);
+			if congested > minimum {
+				<EffectiveMinimumDeposit<T, I>>::put(congested);
+			}
 
 			Self::deposit_event(RawEvent::Proposed(index, value));
 		}
@@ -685,19 +1781,74 @@ decl_module! {
 		/// must have funds to cover the deposit, equal to the original deposit.
 		///
 		/// - `proposal`: The index of the proposal to second.
+		/// - `seconds_upper_bound`: an upper bound on the current number of seconds on this
+		///   proposal. Extrinsic is weighted according to this value with no refund.
 		///
-		/// # <weight>
-		/// - `O(1)`.
+		/// Fails with `TooManyDeposits` if the proposal already has `MaxDeposits` deposits.
+		///
+		/// Emits `Seconded`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of seconds a proposal already has, bounded by
+		///   `MaxDeposits`.
 		/// - One DB entry.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
-		fn second(origin, #[compact] proposal: PropIndex) {
+		#[weight = FunctionOf(
+			|(_, s): (&PropIndex, &u32)| 5_000_000u32.saturating_add(s.saturating_mul(10_000)),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn second(origin, #[compact] proposal: PropIndex, #[compact] seconds_upper_bound: u32) {
 			let who = ensure_signed(origin)?;
 			let mut deposit = Self::deposit_of(proposal)
-				.ok_or(Error::<T>::ProposalMissing)?;
+				.ok_or(Error::<T, I>::ProposalMissing)?;
+			ensure!(deposit.1.len() as u32 <= seconds_upper_bound, Error::<T, I>::WrongUpperBound);
+			ensure!(
+				(deposit.1.len() as u32) < T::MaxDeposits::get(),
+				Error::<T, I>::TooManyDeposits,
+			);
 			T::Currency::reserve(&who, deposit.0)?;
-			deposit.1.push(who);
-			<DepositOf<T>>::insert(proposal, deposit);
+			deposit.1.push(who.clone());
+			<DepositOf<T, I>>::insert(proposal, deposit);
+			Self::deposit_event(RawEvent::Seconded(who, proposal));
+		}
+
+		/// Withdraw a second from a proposal which has not yet been tabled.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the sender must have seconded
+		/// the proposal. One of the sender's seconds is removed and its deposit returned. The
+		/// deposit the proposer placed with the proposal is never returned by this call, even
+		/// if the proposer has also seconded it.
+		///
+		/// - `proposal`: The index of the proposal to withdraw the second from.
+		/// - `seconds_upper_bound`: an upper bound on the current number of seconds on this
+		///   proposal. Extrinsic is weighted according to this value with no refund.
+		///
+		/// Emits `Unseconded`.
+		///
+		/// # <weight>
+		/// - `O(S)` where `S` is the number of seconds a proposal already has, bounded by
+		///   `MaxDeposits`.
+		/// - One DB entry.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|(_, s): (&PropIndex, &u32)| 5_000_000u32.saturating_add(s.saturating_mul(10_000)),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn unsecond(origin, #[compact] proposal: PropIndex, #[compact] seconds_upper_bound: u32) {
+			let who = ensure_signed(origin)?;
+			let mut deposit = Self::deposit_of(proposal)
+				.ok_or(Error::<T, I>::ProposalMissing)?;
+			ensure!(deposit.1.len() as u32 <= seconds_upper_bound, Error::<T, I>::WrongUpperBound);
+			// The first depositor is the proposer, whose deposit stays until the proposal is
+			// tabled or cleared.
+			let position = deposit.1.iter().skip(1).rposition(|a| a == &who)
+				.ok_or(Error::<T, I>::NotSeconder)?;
+			deposit.1.remove(position + 1);
+			T::Currency::unreserve(&who, deposit.0);
+			<DepositOf<T, I>>::insert(proposal, deposit);
+			Self::deposit_event(RawEvent::Unseconded(who, proposal));
 		}
 
 		/// Vote in a referendum. If `vote.is_aye()`, the vote is to enact the proposal;
@@ -708,9 +1859,13 @@ decl_module! {
 		/// - `ref_index`: The index of the referendum to vote for.
 		/// - `vote`: The vote configuration.
 		///
+		/// Emits `Voted`.
+		///
 		/// # <weight>
 		/// - `O(1)`.
 		/// - One DB change, one DB entry.
+		/// - The larger of the costs of a first vote and of a change of vote, as measured by the
+		///   `vote_new` and `vote_existing` benchmarks.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
 		fn vote(origin,
@@ -721,6 +1876,47 @@ decl_module! {
 			Self::do_vote(who, ref_index, vote)
 		}
 
+		/// Vote in several referenda at once, as with `vote` for each of them in turn.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `votes`: The index of each referendum to vote for, along with the vote configuration.
+		///
+		/// Nothing is voted unless every referendum is ongoing, the sender would have direct votes
+		/// in at most `MaxVotes` referenda afterwards, and can afford the deposits of the new ones.
+		///
+		/// Emits `Voted` for each vote.
+		///
+		/// # <weight>
+		/// - `O(V)` where `V` is the number of votes, bounded by `MaxVotes`.
+		/// - One DB change and one DB entry per vote.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|(votes,): (&Vec<(ReferendumIndex, Vote)>,)|
+				200_000u32.saturating_mul((votes.len() as u32).max(1)),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn vote_batch(origin, votes: Vec<(ReferendumIndex, Vote)>) {
+			let who = ensure_signed(origin)?;
+			ensure!(votes.len() as u32 <= T::MaxVotes::get(), Error::<T, I>::MaxVotesReached);
+			let mut indices = Self::voting(&who);
+			for (ref_index, _) in votes.iter() {
+				ensure!(Self::is_active_referendum(*ref_index), Error::<T, I>::ReferendumInvalid);
+				if let Err(i) = indices.binary_search(ref_index) {
+					indices.insert(i, *ref_index);
+				}
+			}
+			ensure!(indices.len() as u32 <= T::MaxVotes::get(), Error::<T, I>::MaxVotesReached);
+			let new_votes = indices.len().saturating_sub(Self::voting(&who).len()) as u32;
+			let deposits = T::VoteDeposit::get().saturating_mul(new_votes.into());
+			ensure!(T::Currency::can_reserve(&who, deposits), Error::<T, I>::InsufficientFunds);
+
+			for (ref_index, vote) in votes {
+				Self::do_vote(who.clone(), ref_index, vote)?;
+			}
+		}
+
 		/// Vote in a referendum on behalf of a stash. If `vote.is_aye()`, the vote is to enact
 		/// the proposal; otherwise it is a vote to keep the status quo.
 		///
@@ -729,6 +1925,8 @@ decl_module! {
 		/// - `ref_index`: The index of the referendum to proxy vote for.
 		/// - `vote`: The vote configuration.
 		///
+		/// Emits `Voted`.
+		///
 		/// # <weight>
 		/// - `O(1)`.
 		/// - One DB change, one DB entry.
@@ -739,17 +1937,138 @@ decl_module! {
 			vote: Vote
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let voter = Self::proxy(who).and_then(|a| a.as_active()).ok_or(Error::<T>::NotProxy)?;
+			let voter = Self::proxied_stash(who)?;
 			Self::do_vote(voter, ref_index, vote)
 		}
 
-		/// Schedule an emergency cancellation of a referendum. Cannot happen twice to the same
+		/// Remove a vote, a ballot in a multi-option referendum, or an abstention, from an ongoing
 		/// referendum.
 		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `ref_index`: The index of the referendum to remove the vote from.
+		///
+		/// Emits `VoteRemoved`.
+		///
+		/// # <weight>
+		/// - `O(V)` where `V` is the number of voters, or of abstainers, in the referendum.
+		/// - One DB change, one DB clear.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
+		fn remove_vote(origin, #[compact] ref_index: ReferendumIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_remove_vote(who, ref_index)
+		}
+
+		/// Remove a vote from an ongoing referendum on behalf of a stash.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the active proxy of the stash.
+		///
+		/// - `ref_index`: The index of the referendum to remove the vote from.
+		///
+		/// Emits `VoteRemoved`.
+		///
+		/// # <weight>
+		/// - `O(V)` where `V` is the number of voters in the referendum.
+		/// - One DB change, one DB clear.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
+		fn proxy_remove_vote(origin, #[compact] ref_index: ReferendumIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let voter = Self::proxied_stash(who)?;
+			Self::do_remove_vote(voter, ref_index)
+		}
+
+		/// Return the deposit for the vote of `target` in a referendum which is over, removing
+		/// its record.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `target`: The account which voted.
+		/// - `ref_index`: The index of the referendum voted in.
+		///
+		/// The voter may do so as soon as the referendum is over. Anyone else may only once
+		/// `FinishedRetention` blocks have passed since the end of its voting period.
+		///
+		/// Emits `VoteReaped`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB clear.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
+		fn reap_vote(origin, target: T::AccountId, #[compact] ref_index: ReferendumIndex) {
+			let who = ensure_signed(origin)?;
+			let (deposit, end) = Self::vote_deposit((ref_index, &target))
+				.ok_or(Error::<T, I>::NotVoter)?;
+			ensure!(!Self::is_ongoing_referendum(ref_index), Error::<T, I>::Early);
+			if who != target {
+				let now = <frame_system::Module<T>>::block_number();
+				ensure!(now >= end + T::FinishedRetention::get(), Error::<T, I>::Early);
+			}
+
+			<VoteDeposits<T, I>>::remove((ref_index, &target));
+			T::Currency::unreserve(&target, deposit);
+			Self::deposit_event(RawEvent::VoteReaped(target, ref_index, deposit));
+		}
+
+		/// Cast a ballot in a multi-option referendum, replacing any earlier ballot.
+		///
+		/// The whole balance of the voter is locked until the referendum ends. A first ballot
+		/// counts towards `MaxVotes` and reserves `VoteDeposit`, as a vote does; it may be
+		/// withdrawn with `remove_vote`.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `ref_index`: The index of the multi-option referendum.
+		/// - `ballot`: The indices of the options approved or, for a ranked referendum, of the
+		///   options in order of preference. No option may appear twice.
+		///
+		/// Emits `BallotCast`.
+		///
+		/// # <weight>
+		/// - `O(B^2 + R)` where `B` is the length of the ballot, bounded by `MaxOptions`, and `R`
+		///   the number of referenda the voter votes in, bounded by `MaxVotes`.
+		/// - Up to four DB changes, one DB entry, one balance reserve.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
+		fn vote_multi(origin, #[compact] ref_index: ReferendumIndex, ballot: Vec<u32>) {
+			let who = ensure_signed(origin)?;
+			let info = Self::multi_referendum_info(ref_index)
+				.ok_or(Error::<T, I>::ReferendumInvalid)?;
+			let options = info.options.len() as u32;
+			ensure!(
+				!ballot.is_empty() && ballot.iter().enumerate().all(|(i, o)|
+					*o < options && !ballot[..i].contains(o)
+				),
+				Error::<T, I>::InvalidBallot
+			);
+
+			if !<BallotOf<T, I>>::contains_key((ref_index, &who)) {
+				Self::reserve_vote_deposit(&who, ref_index, info.end)?;
+				<VotersFor<T, I>>::append_or_insert(ref_index, &[&who][..]);
+				Self::index_vote(&who, ref_index);
+			}
+			<BallotOf<T, I>>::insert((ref_index, &who), ballot);
+			let locked = Self::accumulate_prior_lock(&who, info.end, T::Currency::total_balance(&who));
+			let reasons = WithdrawReason::Transfer.into();
+			T::Currency::extend_lock(T::LockId::get(), &who, locked, reasons);
+			T::OnVote::on_vote(&who, ref_index);
+			Self::deposit_event(RawEvent::BallotCast(who, ref_index));
+		}
+
+		/// Schedule an emergency cancellation of a referendum. Cannot happen twice to the same
+		/// proposal hash: once a proposal has been emergency-cancelled, any later referendum on it
+		/// must be stopped through the slower paths of a veto or a root cancellation.
+		///
+		/// Any deposits held for the referendum are slashed.
+		///
 		/// The dispatch origin of this call must be `CancellationOrigin`.
 		///
 		/// -`ref_index`: The index of the referendum to cancel.
 		///
+		/// Emits `Cancelled`, and `DepositsSlashed` if there were deposits held.
+		///
 		/// # <weight>
 		/// - Depends on size of storage vec `VotersFor` for this referendum.
 		/// # </weight>
@@ -757,12 +2076,13 @@ decl_module! {
 		fn emergency_cancel(origin, ref_index: ReferendumIndex) {
 			T::CancellationOrigin::ensure_origin(origin)?;
 
-			let info = Self::referendum_info(ref_index).ok_or(Error::<T>::BadIndex)?;
-			let h = info.proposal_hash;
-			ensure!(!<Cancellations<T>>::contains_key(h), Error::<T>::AlreadyCanceled);
+			let info = Self::referendum_info(ref_index).ok_or(Error::<T, I>::BadIndex)?;
+			let h = Self::proposal_hash(&info.proposal);
+			ensure!(!<Cancellations<T, I>>::contains_key(h), Error::<T, I>::AlreadyCanceled);
 
-			<Cancellations<T>>::insert(h, true);
-			Self::clear_referendum(ref_index);
+			<Cancellations<T, I>>::insert(h, true);
+			Self::slash_referendum_deposits(ref_index);
+			Self::internal_cancel_referendum(ref_index);
 		}
 
 		/// Schedule a referendum to be tabled once it is legal to schedule an external
@@ -772,6 +2092,11 @@ decl_module! {
 		///
 		/// - `proposal_hash`: The preimage hash of the proposal.
 		///
+		/// Fails with `DuplicateProposal` if this proposal is already scheduled, or with
+		/// `ProposalAlreadyQueued` if another one is.
+		///
+		/// Emits `ExternalProposed`.
+		///
 		/// # <weight>
 		/// - `O(1)`.
 		/// - One DB change.
@@ -779,14 +2104,14 @@ decl_module! {
 		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
 		fn external_propose(origin, proposal_hash: T::Hash) {
 			T::ExternalOrigin::ensure_origin(origin)?;
-			ensure!(!<NextExternal<T>>::exists(), Error::<T>::DuplicateProposal);
-			if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+			Self::ensure_external_replaceable(&proposal_hash, false)?;
+			if let Some((until, _)) = <Blacklist<T, I>>::get(proposal_hash) {
 				ensure!(
 					<frame_system::Module<T>>::block_number() >= until,
-					Error::<T>::ProposalBlacklisted,
+					Error::<T, I>::ProposalBlacklisted,
 				);
 			}
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityApprove));
+			Self::note_external(proposal_hash, VoteThreshold::SuperMajorityApprove);
 		}
 
 		/// Schedule a majority-carries referendum to be tabled next once it is legal to schedule
@@ -797,7 +2122,11 @@ decl_module! {
 		/// - `proposal_hash`: The preimage hash of the proposal.
 		///
 		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
-		/// pre-scheduled `external_propose` call.
+		/// pre-scheduled `external_propose` call. Any other scheduled proposal is not replaced:
+		/// this fails with `DuplicateProposal` if it is the same one, or with
+		/// `ProposalAlreadyQueued` otherwise.
+		///
+		/// Emits `ExternalProposed`.
 		///
 		/// # <weight>
 		/// - `O(1)`.
@@ -806,7 +2135,8 @@ decl_module! {
 		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
 		fn external_propose_majority(origin, proposal_hash: T::Hash) {
 			T::ExternalMajorityOrigin::ensure_origin(origin)?;
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SimpleMajority));
+			Self::ensure_external_replaceable(&proposal_hash, true)?;
+			Self::note_external(proposal_hash, VoteThreshold::SimpleMajority);
 		}
 
 		/// Schedule a negative-turnout-bias referendum to be tabled next once it is legal to
@@ -817,7 +2147,11 @@ decl_module! {
 		/// - `proposal_hash`: The preimage hash of the proposal.
 		///
 		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
-		/// pre-scheduled `external_propose` call.
+		/// pre-scheduled `external_propose` call. Any other scheduled proposal is not replaced:
+		/// this fails with `DuplicateProposal` if it is the same one, or with
+		/// `ProposalAlreadyQueued` otherwise.
+		///
+		/// Emits `ExternalProposed`.
 		///
 		/// # <weight>
 		/// - `O(1)`.
@@ -826,18 +2160,95 @@ decl_module! {
 		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
 		fn external_propose_default(origin, proposal_hash: T::Hash) {
 			T::ExternalDefaultOrigin::ensure_origin(origin)?;
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityAgainst));
+			Self::ensure_external_replaceable(&proposal_hash, true)?;
+			Self::note_external(proposal_hash, VoteThreshold::SuperMajorityAgainst);
+		}
+
+		/// Whitelist a proposal hash, so that the `DispatchWhitelistedOrigin` may table it.
+		///
+		/// The dispatch origin of this call must be `WhitelistOrigin`.
+		///
+		/// - `proposal_hash`: The preimage hash of the proposal.
+		///
+		/// Emits `Whitelisted`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
+		fn whitelist_proposal(origin, proposal_hash: T::Hash) {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_whitelisted(&proposal_hash), Error::<T, I>::AlreadyWhitelisted);
+			<Whitelist<T, I>>::insert(&proposal_hash, true);
+			Self::deposit_event(RawEvent::Whitelisted(proposal_hash));
+		}
+
+		/// Remove a proposal hash from the whitelist before it is tabled.
+		///
+		/// The dispatch origin of this call must be `WhitelistOrigin`.
+		///
+		/// - `proposal_hash`: The preimage hash of the proposal.
+		///
+		/// Emits `WhitelistRemoved`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB clear.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
+		fn remove_whitelisted_proposal(origin, proposal_hash: T::Hash) {
+			T::WhitelistOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_whitelisted(&proposal_hash), Error::<T, I>::NotWhitelisted);
+			<Whitelist<T, I>>::remove(&proposal_hash);
+			Self::deposit_event(RawEvent::WhitelistRemoved(proposal_hash));
+		}
+
+		/// Table a whitelisted proposal immediately as a majority-carries referendum, removing it
+		/// from the whitelist.
+		///
+		/// The dispatch origin of this call must be `DispatchWhitelistedOrigin`.
+		///
+		/// - `proposal_hash`: The preimage hash of the whitelisted proposal.
+		///
+		/// Voting lasts `VotingPeriod`, and the proposal is enacted `EnactmentPeriod` after it is
+		/// approved. A blacklisted proposal may not be tabled until its blacklisting expires.
+		///
+		/// Emits `WhitelistedTabled` and `Started`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB clear.
+		/// - One extra DB entry.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(5_000_000)]
+		fn dispatch_whitelisted(origin, proposal_hash: T::Hash) {
+			T::DispatchWhitelistedOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_whitelisted(&proposal_hash), Error::<T, I>::NotWhitelisted);
+			let now = <frame_system::Module<T>>::block_number();
+			if let Some((until, _)) = <Blacklist<T, I>>::get(proposal_hash) {
+				ensure!(now >= until, Error::<T, I>::ProposalBlacklisted);
+			}
+
+			<Whitelist<T, I>>::remove(&proposal_hash);
+			let ref_index = Self::internal_start_referendum(
+				Self::bound_hash(proposal_hash),
+				VoteThreshold::SimpleMajority,
+				T::EnactmentPeriod::get(),
+			);
+			Self::deposit_event(RawEvent::WhitelistedTabled(proposal_hash, ref_index));
 		}
 
 		/// Schedule the currently externally-proposed majority-carries referendum to be tabled
 		/// immediately. If there is no externally-proposed referendum currently, or if there is one
 		/// but it is not a majority-carries referendum then it fails.
 		///
-		/// The dispatch of this call must be `FastTrackOrigin`.
+		/// The dispatch of this call must be `FastTrackOrigin` or `InstantOrigin`.
 		///
 		/// - `proposal_hash`: The hash of the current external proposal.
-		/// - `voting_period`: The period that is allowed for voting on this proposal. Increased to
-		///   `EmergencyVotingPeriod` if too low.
+		/// - `voting_period`: The period that is allowed for voting on this proposal. Must be at
+		///   least `FastTrackVotingPeriod`, unless the origin is `InstantOrigin`, in which case it
+		///   must only be non-zero.
 		/// - `delay`: The number of block after voting has ended in approval and this should be
 		///   enacted. This doesn't have a minimum amount.
 		///
@@ -848,25 +2259,33 @@ decl_module! {
 		/// - One DB change.
 		/// - One extra DB entry.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
+		#[weight = SimpleDispatchInfo::FixedOperational(200_000)]
 		fn fast_track(origin,
 			proposal_hash: T::Hash,
 			voting_period: T::BlockNumber,
 			delay: T::BlockNumber
 		) {
-			T::FastTrackOrigin::ensure_origin(origin)?;
-			let (e_proposal_hash, threshold) = <NextExternal<T>>::get().ok_or(Error::<T>::ProposalMissing)?;
+			if T::InstantOrigin::try_origin(origin.clone()).is_err() {
+				T::FastTrackOrigin::ensure_origin(origin)?;
+				ensure!(
+					voting_period >= T::FastTrackVotingPeriod::get(),
+					Error::<T, I>::VotingPeriodLow,
+				);
+			}
+			ensure!(!voting_period.is_zero(), Error::<T, I>::VotingPeriodLow);
+			let (proposal, threshold) = <NextExternal<T, I>>::get()
+				.ok_or(Error::<T, I>::ProposalMissing)?;
 			ensure!(
 				threshold != VoteThreshold::SuperMajorityApprove,
-				Error::<T>::NotSimpleMajority,
+				Error::<T, I>::NotSimpleMajority,
 			);
-			ensure!(proposal_hash == e_proposal_hash, Error::<T>::InvalidHash);
+			ensure!(proposal_hash == Self::proposal_hash(&proposal), Error::<T, I>::InvalidHash);
 
-			<NextExternal<T>>::kill();
+			<NextExternal<T, I>>::kill();
 			let now = <frame_system::Module<T>>::block_number();
-			// We don't consider it an error if `vote_period` is too low, like `emergency_propose`.
-			let period = voting_period.max(T::EmergencyVotingPeriod::get());
-			Self::inject_referendum(now + period, proposal_hash, threshold, delay);
+			let end = now + voting_period;
+			let ref_index = Self::inject_referendum(end, proposal, threshold, delay);
+			Self::transfer_metadata(MetadataOwner::External, ref_index);
 		}
 
 		/// Veto and blacklist the external proposal hash.
@@ -875,51 +2294,88 @@ decl_module! {
 		///
 		/// - `proposal_hash`: The preimage hash of the proposal to veto and blacklist.
 		///
+		/// The blacklist entry of `proposal_hash` records every account that has vetoed it, and
+		/// lasts until `CooloffPeriod` after the latest veto. An account may veto a given proposal
+		/// hash only once, so it cannot veto it again within that period either.
+		///
+		/// Fails with `TooManyVetoers` if `MaxVetoers` accounts have already vetoed it.
+		///
 		/// Emits `Vetoed`.
 		///
 		/// # <weight>
+		/// - `O(V)` where `V` is the number of accounts that have already vetoed the proposal,
+		///   bounded by `MaxVetoers`. Weighted for `MaxVetoers`, giving back to the block the
+		///   weight of the accounts that have not vetoed it.
 		/// - Two DB entries.
 		/// - One DB clear.
-		/// - Performs a binary search on `existing_vetoers` which should not
-		///   be very large.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
+		#[weight = FunctionOf(
+			|_: (&T::Hash,)| weight_for::veto(T::MaxVetoers::get() as Weight),
+			DispatchClass::Operational,
+			true,
+		)]
 		fn veto_external(origin, proposal_hash: T::Hash) {
 			let who = T::VetoOrigin::ensure_origin(origin)?;
-
-			if let Some((e_proposal_hash, _)) = <NextExternal<T>>::get() {
-				ensure!(proposal_hash == e_proposal_hash, Error::<T>::ProposalMissing);
-			} else {
-				Err(Error::<T>::NoProposal)?;
-			}
-
-			let mut existing_vetoers = <Blacklist<T>>::get(&proposal_hash)
-				.map(|pair| pair.1)
-				.unwrap_or_else(Vec::new);
-			let insert_position = existing_vetoers.binary_search(&who)
-				.err().ok_or(Error::<T>::AlreadyVetoed)?;
-
-			existing_vetoers.insert(insert_position, who.clone());
-			let until = <frame_system::Module<T>>::block_number() + T::CooloffPeriod::get();
-			<Blacklist<T>>::insert(&proposal_hash, (until, existing_vetoers));
-
-			Self::deposit_event(RawEvent::Vetoed(who, proposal_hash, until));
-			<NextExternal<T>>::kill();
+			let vetoers = Self::do_veto_external(who, proposal_hash)?;
+			Self::refund_weight(
+				weight_for::veto(T::MaxVetoers::get() as Weight),
+				weight_for::veto(vetoers as Weight),
+			);
 		}
 
 		/// Remove a referendum.
 		///
+		/// Any deposits held for the referendum are returned.
+		///
 		/// The dispatch origin of this call must be _Root_.
 		///
 		/// - `ref_index`: The index of the referendum to cancel.
 		///
+		/// Emits `Cancelled`, and `DepositsRefunded` if there were deposits held.
+		///
 		/// # <weight>
-		/// - `O(1)`.
+		/// - `O(D)` where `D` is the number of deposits held, bounded by `MaxDeposits`.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedOperational(10_000)]
 		fn cancel_referendum(origin, #[compact] ref_index: ReferendumIndex) {
 			ensure_root(origin)?;
-			Self::clear_referendum(ref_index);
+			Self::internal_cancel_referendum(ref_index);
+		}
+
+		/// Start a referendum choosing between several proposals.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		///
+		/// - `options`: The hashes of the proposals to choose between; at least two and at most
+		///   `MaxOptions`.
+		/// - `rule`: The rule by which the ballots are counted.
+		/// - `delay`: The number of blocks after the referendum ends before the winning option is
+		///   enacted.
+		///
+		/// Emits `MultiStarted`.
+		///
+		/// # <weight>
+		/// - `O(O)` where `O` is the number of options, bounded by `MaxOptions`.
+		/// - Three DB changes.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn start_multi_referendum(origin,
+			options: Vec<T::Hash>,
+			rule: TallyRule,
+			delay: T::BlockNumber,
+		) {
+			ensure_root(origin)?;
+			ensure!(
+				options.len() >= 2 && options.len() as u32 <= T::MaxOptions::get(),
+				Error::<T, I>::WrongOptionCount
+			);
+			let options = options.into_iter().map(Self::bound_hash).collect();
+			Self::inject_multi_referendum(
+				<frame_system::Module<T>>::block_number() + T::VotingPeriod::get(),
+				options,
+				rule,
+				delay,
+			);
 		}
 
 		/// Cancel a proposal queued for enactment.
@@ -928,22 +2384,64 @@ decl_module! {
 		///
 		/// - `which`: The index of the referendum to cancel.
 		///
+		/// Emits `QueuedCancelled`.
+		///
 		/// # <weight>
+		/// - `O(Q)` where `Q` is the length of the dispatch queue, bounded by `MaxQueued`.
 		/// - One DB change.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedOperational(10_000)]
 		fn cancel_queued(origin, which: ReferendumIndex) {
 			ensure_root(origin)?;
-			let mut items = <DispatchQueue<T>>::get();
+			let mut items = <DispatchQueue<T, I>>::get();
 			let original_len = items.len();
 			items.retain(|i| i.2 != which);
-			ensure!(items.len() < original_len, Error::<T>::ProposalMissing);
-			<DispatchQueue<T>>::put(items);
+			ensure!(items.len() < original_len, Error::<T, I>::ProposalMissing);
+			<DispatchQueue<T, I>>::put(items);
+			Self::deposit_event(RawEvent::QueuedCancelled(which));
+		}
+
+		/// Reschedule a proposal queued for enactment, to be enacted `delay` blocks from now, or
+		/// in the next block if `delay` is zero. The delay may be shorter or longer than the one
+		/// the referendum was tabled with.
+		///
+		/// The dispatch origin of this call must be _Root_ or `FastTrackOrigin`.
+		///
+		/// - `which`: The index of the referendum whose proposal to reschedule.
+		/// - `delay`: The number of blocks to wait before enacting the proposal.
+		///
+		/// Emits `EnactmentRescheduled`.
+		///
+		/// # <weight>
+		/// - `O(Q)` where `Q` is the length of the dispatch queue, bounded by `MaxQueued`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(10_000)]
+		fn set_enactment_delay(origin, which: ReferendumIndex, delay: T::BlockNumber) {
+			T::FastTrackOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+			let mut queue = <DispatchQueue<T, I>>::get();
+			let position = queue.iter().position(|i| i.2 == which)
+				.ok_or(Error::<T, I>::ProposalMissing)?;
+			let (_, proposal, _) = queue.remove(position);
+
+			// The queue has already been dispatched from in this block.
+			let now = <frame_system::Module<T>>::block_number();
+			let when = now + delay.max(1u32.into());
+			let pos = queue.binary_search_by_key(&when, |x| x.0).unwrap_or_else(|e| e);
+			queue.insert(pos, (when, proposal, which));
+			<DispatchQueue<T, I>>::put(queue);
+			Self::deposit_event(RawEvent::EnactmentRescheduled(which, when));
+		}
+
+		fn on_finalize() {
+			#[cfg(feature = "try-state")]
+			Self::do_try_state().expect("Democracy state must stay consistent");
 		}
 
 		fn on_initialize(n: T::BlockNumber) {
-			if let Err(e) = Self::begin_block(n) {
-				sp_runtime::print(e);
+			match Self::begin_block(n) {
+				Ok(weight) => <frame_system::Module<T>>::register_extra_weight_unchecked(weight),
+				Err(e) => sp_runtime::print(e),
 			}
 		}
 
@@ -955,21 +2453,25 @@ decl_module! {
 		///
 		/// - `proxy`: The account that will be activated as proxy.
 		///
+		/// Emits `ProxyActivated`.
+		///
 		/// # <weight>
 		/// - One extra DB entry.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
 		fn activate_proxy(origin, proxy: T::AccountId) {
 			let who = ensure_signed(origin)?;
-			Proxy::<T>::try_mutate(&proxy, |a| match a.take() {
-				None => Err(Error::<T>::NotOpen),
-				Some(ProxyState::Active(_)) => Err(Error::<T>::AlreadyProxy),
+			Self::ensure_legacy_proxy()?;
+			Proxy::<T, I>::try_mutate(&proxy, |a| match a.take() {
+				None => Err(Error::<T, I>::NotOpen),
+				Some(ProxyState::Active(_)) => Err(Error::<T, I>::AlreadyProxy),
 				Some(ProxyState::Open(x)) if &x == &who => {
-					*a = Some(ProxyState::Active(who));
+					*a = Some(ProxyState::Active(who.clone()));
 					Ok(())
 				}
-				Some(ProxyState::Open(_)) => Err(Error::<T>::WrongOpen),
+				Some(ProxyState::Open(_)) => Err(Error::<T, I>::WrongOpen),
 			})?;
+			Self::deposit_event(RawEvent::ProxyActivated(who, proxy));
 		}
 
 		/// Clear the proxy. Called by the proxy.
@@ -978,18 +2480,22 @@ decl_module! {
 		///
 		/// The dispatch origin of this call must be _Signed_.
 		///
+		/// Emits `ProxyClosed`.
+		///
 		/// # <weight>
 		/// - One DB clear.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
 		fn close_proxy(origin) {
 			let who = ensure_signed(origin)?;
-			Proxy::<T>::mutate(&who, |a| {
+			Self::ensure_legacy_proxy()?;
+			Proxy::<T, I>::mutate(&who, |a| {
 				if a.is_some() {
 					system::Module::<T>::dec_ref(&who);
 				}
 				*a = None;
 			});
+			Self::deposit_event(RawEvent::ProxyClosed(who));
 		}
 
 		/// Deactivate the proxy, but leave open to this account. Called by the stash.
@@ -1002,25 +2508,35 @@ decl_module! {
 		///
 		/// - `proxy`: The account that will be deactivated as proxy.
 		///
+		/// Emits `ProxyDeactivated`.
+		///
 		/// # <weight>
 		/// - One DB clear.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
 		fn deactivate_proxy(origin, proxy: T::AccountId) {
 			let who = ensure_signed(origin)?;
-			Proxy::<T>::try_mutate(&proxy, |a| match a.take() {
-				None | Some(ProxyState::Open(_)) => Err(Error::<T>::NotActive),
+			Self::ensure_legacy_proxy()?;
+			Proxy::<T, I>::try_mutate(&proxy, |a| match a.take() {
+				None | Some(ProxyState::Open(_)) => Err(Error::<T, I>::NotActive),
 				Some(ProxyState::Active(x)) if &x == &who => {
-					*a = Some(ProxyState::Open(who));
+					*a = Some(ProxyState::Open(who.clone()));
 					Ok(())
 				}
-				Some(ProxyState::Active(_)) => Err(Error::<T>::WrongProxy),
+				Some(ProxyState::Active(_)) => Err(Error::<T, I>::WrongProxy),
 			})?;
+			Self::deposit_event(RawEvent::ProxyDeactivated(who, proxy));
 		}
 
 		/// Delegate vote.
 		///
-		/// Currency is locked indefinitely for as long as it's delegated.
+		/// Currency is locked indefinitely for as long as it's delegated. Replaces any split
+		/// delegation made with `delegate_split`. `to` may have at most `MaxDelegators`
+		/// delegators.
+		///
+		/// The sender may not have direct votes in ongoing referenda, unless
+		/// `RemoveVotesOnDelegation` is set, in which case they are removed. Votes cast while
+		/// delegating override the delegation in their referendum, and are kept on undelegating.
 		///
 		/// The dispatch origin of this call must be _Signed_.
 		///
@@ -1028,30 +2544,23 @@ decl_module! {
 		/// - `conviction`: The conviction that will be attached to the delegated
 		///   votes.
 		///
-		/// Emits `Delegated`.
+		/// Emits `Delegated`, and `VoteRemoved` for each vote removed.
 		///
 		/// # <weight>
-		/// - One extra DB entry.
+		/// - `O(R + D)` where `R` is the number of referenda the sender has direct votes in and
+		///   `D` the number of delegators of `to`, bounded by `MaxDelegators`.
+		/// - Two extra DB entries.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(500_000)]
 		pub fn delegate(origin, to: T::AccountId, conviction: Conviction) {
 			let who = ensure_signed(origin)?;
-			<Delegations<T>>::insert(&who, (&to, conviction));
-			// Currency is locked indefinitely as long as it's delegated.
-			T::Currency::extend_lock(
-				DEMOCRACY_ID,
-				&who,
-				Bounded::max_value(),
-				WithdrawReason::Transfer.into()
-			);
-			Locks::<T>::remove(&who);
-			Self::deposit_event(RawEvent::Delegated(who, to));
+			Self::do_delegate(who, to, conviction)?;
 		}
 
 		/// Undelegate vote.
 		///
-		/// Must be sent from an account that has called delegate previously.
-		/// The tokens will be reduced from an indefinite lock to the maximum
+		/// Must be sent from an account that has called `delegate` or `delegate_split`
+		/// previously. The tokens will be reduced from an indefinite lock to the maximum
 		/// possible according to the conviction of the prior delegation.
 		///
 		/// The dispatch origin of this call must be _Signed_.
@@ -1059,102 +2568,252 @@ decl_module! {
 		/// Emits `Undelegated`.
 		///
 		/// # <weight>
-		/// - O(1).
+		/// - `O(T * D)` where `T` is the number of accounts delegated to, bounded by
+		///   `MaxDelegationTargets`, and `D` the number of delegators of each, bounded by
+		///   `MaxDelegators`. Delegated votes are only counted when a referendum is tallied, so
+		///   nothing needs removing from the referenda the delegates have voted in; the
+		///   `undelegate` benchmark checks that their number does not affect the cost.
+		/// - Weighted for the bounds, giving back to the block the weight of the delegators not
+		///   scanned.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(500_000)]
+		#[weight = FunctionOf(
+			|_: ()| weight_for::undelegate(
+				T::MaxDelegationTargets::get() as Weight,
+				T::MaxDelegators::get() as Weight,
+			),
+			DispatchClass::Normal,
+			true,
+		)]
 		fn undelegate(origin) {
 			let who = ensure_signed(origin)?;
-			ensure!(<Delegations<T>>::contains_key(&who), Error::<T>::NotDelegated);
-			let (_, conviction) = <Delegations<T>>::take(&who);
-			// Indefinite lock is reduced to the maximum voting lock that could be possible.
-			let now = <frame_system::Module<T>>::block_number();
-			let locked_until = now + T::EnactmentPeriod::get() * conviction.lock_periods().into();
-			Locks::<T>::insert(&who, locked_until);
-			T::Currency::set_lock(
-				DEMOCRACY_ID,
-				&who,
-				Bounded::max_value(),
-				WithdrawReason::Transfer.into(),
-			);
-			Self::deposit_event(RawEvent::Undelegated(who));
+			let delegators = Self::do_undelegate(who)?;
+			Self::refund_undelegate_weight(delegators);
 		}
 
-		/// Clears all public proposals.
+		/// Delegate vote to several accounts, each receiving a proportion of the sender's voting
+		/// power. Proportions adding up to less than the whole leave the rest undelegated.
 		///
-		/// The dispatch origin of this call must be _Root_.
+		/// The part of the balance delegated is locked indefinitely for as long as it's delegated.
+		/// Replaces any delegation made with `delegate`, and is undone by `undelegate`; otherwise
+		/// the same rules apply as for `delegate`.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `targets`: The accounts to make delegates of the sender, along with the proportion
+		///   of its balance delegated to each. There may be at most `MaxDelegationTargets` of
+		///   them, each with at most `MaxDelegators` delegators.
+		/// - `conviction`: The conviction that will be attached to the delegated votes.
+		///
+		/// Emits `Delegated` for each of the `targets`, and `VoteRemoved` for each vote removed.
 		///
 		/// # <weight>
-		/// - `O(1)`.
-		/// - One DB clear.
+		/// - `O(R + T * D)` where `R` is the number of referenda the sender has direct votes in,
+		///   `T` the number of targets and `D` the number of delegators of each, bounded by
+		///   `MaxDelegators`.
+		/// - One extra DB entry, and one per target.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(10_000)]
-		fn clear_public_proposals(origin) {
-			ensure_root(origin)?;
-
-			<PublicProps<T>>::kill();
+		#[weight = FunctionOf(
+			|(targets, _): (&Vec<(T::AccountId, Perbill)>, &Conviction)|
+				500_000u32.saturating_add((targets.len() as u32).saturating_mul(
+					10_000u32.saturating_add(T::MaxDelegators::get().saturating_mul(1_000))
+				)),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn delegate_split(origin, targets: Vec<(T::AccountId, Perbill)>, conviction: Conviction) {
+			let who = ensure_signed(origin)?;
+			Self::do_delegate_split(who, targets, conviction)?;
 		}
 
-		/// Register the preimage for an upcoming proposal. This doesn't require the proposal to be
-		/// in the dispatch queue but does require a deposit, returned once enacted.
+		/// Delegate the vote of a stash.
 		///
-		/// The dispatch origin of this call must be _Signed_.
+		/// Currency is locked indefinitely for as long as it's delegated.
 		///
-		/// - `encoded_proposal`: The preimage of a proposal.
+		/// The dispatch origin of this call must be _Signed_ by the active proxy of the stash.
 		///
-		/// Emits `PreimageNoted`.
+		/// - `to`: The account to make a delegate of the stash.
+		/// - `conviction`: The conviction that will be attached to the delegated
+		///   votes.
+		///
+		/// Emits `Delegated`.
 		///
 		/// # <weight>
-		/// - Dependent on the size of `encoded_proposal` but protected by a
-		///   required deposit.
+		/// - One extra DB entry.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
-		fn note_preimage(origin, encoded_proposal: Vec<u8>) {
+		#[weight = SimpleDispatchInfo::FixedNormal(500_000)]
+		fn proxy_delegate(origin, to: T::AccountId, conviction: Conviction) {
 			let who = ensure_signed(origin)?;
-			let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-			ensure!(!<Preimages<T>>::contains_key(&proposal_hash), Error::<T>::DuplicatePreimage);
-
-			let deposit = <BalanceOf<T>>::from(encoded_proposal.len() as u32)
-				.saturating_mul(T::PreimageByteDeposit::get());
-			T::Currency::reserve(&who, deposit)?;
-
-			let now = <frame_system::Module<T>>::block_number();
-			<Preimages<T>>::insert(proposal_hash, (encoded_proposal, who.clone(), deposit, now));
-
-			Self::deposit_event(RawEvent::PreimageNoted(proposal_hash, who, deposit));
+			let stash = Self::proxied_stash(who)?;
+			Self::do_delegate(stash, to, conviction)?;
 		}
 
-		/// Register the preimage for an upcoming proposal. This requires the proposal to be
-		/// in the dispatch queue. No deposit is needed.
+		/// Undelegate the vote of a stash.
 		///
-		/// The dispatch origin of this call must be _Signed_.
+		/// The stash must have delegated previously. The tokens will be reduced from an
+		/// indefinite lock to the maximum possible according to the conviction of the prior
+		/// delegation.
 		///
-		/// - `encoded_proposal`: The preimage of a proposal.
+		/// The dispatch origin of this call must be _Signed_ by the active proxy of the stash.
 		///
-		/// Emits `PreimageNoted`.
+		/// Emits `Undelegated`.
 		///
 		/// # <weight>
-		/// - Dependent on the size of `encoded_proposal`.
+		/// - `O(T * D)`, as for `undelegate`.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
-		fn note_imminent_preimage(origin, encoded_proposal: Vec<u8>) {
+		#[weight = FunctionOf(
+			|_: ()| weight_for::undelegate(
+				T::MaxDelegationTargets::get() as Weight,
+				T::MaxDelegators::get() as Weight,
+			),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn proxy_undelegate(origin) {
 			let who = ensure_signed(origin)?;
-			let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
-			ensure!(!<Preimages<T>>::contains_key(&proposal_hash), Error::<T>::DuplicatePreimage);
-			let queue = <DispatchQueue<T>>::get();
-			ensure!(queue.iter().any(|item| &item.1 == &proposal_hash), Error::<T>::NotImminent);
-
-			let now = <frame_system::Module<T>>::block_number();
-			let free = <BalanceOf<T>>::zero();
-			<Preimages<T>>::insert(proposal_hash, (encoded_proposal, who.clone(), free, now));
-
-			Self::deposit_event(RawEvent::PreimageNoted(proposal_hash, who, free));
+			let stash = Self::proxied_stash(who)?;
+			let delegators = Self::do_undelegate(stash)?;
+			Self::refund_undelegate_weight(delegators);
 		}
 
-		/// Remove an expired proposal preimage and collect the deposit.
+		/// Clears all public proposals.
 		///
-		/// The dispatch origin of this call must be _Signed_.
+		/// The dispatch origin of this call must be _Root_.
+		///
+		/// - `refund`: Whether to refund the deposits of the proposers and seconders, rather than
+		///   slash them.
+		///
+		/// Emits `PublicProposalsCleared`.
+		///
+		/// # <weight>
+		/// - `O(P)` where `P` is the number of public proposals, bounded by `MaxProposals`.
+		/// - `O(S)` where `S` is the total number of seconds those proposals have.
+		/// - One DB clear, and one DB change per proposal.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(100_000)]
+		fn clear_public_proposals(origin, refund: bool) {
+			ensure_root(origin)?;
+
+			for (index, _, _) in <PublicProps<T, I>>::take() {
+				<MetadataOf<T, I>>::remove(MetadataOwner::Proposal(index));
+				if let Some((deposit, depositors)) = <DepositOf<T, I>>::take(index) {
+					for d in &depositors {
+						if refund {
+							T::Currency::unreserve(d, deposit);
+						} else {
+							T::Slash::on_unbalanced(T::Currency::slash_reserved(d, deposit).0);
+						}
+					}
+				}
+			}
+			Self::deposit_event(RawEvent::PublicProposalsCleared);
+		}
+
+		/// Pause, or resume, the tabling of a referendum at the end of each `LaunchPeriod`.
+		///
+		/// While paused, the public proposals and the external proposal stay queued, and ongoing
+		/// referenda carry on. Referenda may still be started by other means, such as `fast_track`.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		///
+		/// - `paused`: Whether tabling is to be paused.
+		///
+		/// Emits `LaunchPausedSet`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// - One DB change.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedOperational(10_000)]
+		fn set_launch_paused(origin, paused: bool) {
+			ensure_root(origin)?;
+			LaunchPaused::<I>::put(paused);
+			Self::deposit_event(RawEvent::LaunchPausedSet(paused));
+		}
+
+		/// Register the preimage for an upcoming proposal. This doesn't require the proposal to be
+		/// in the dispatch queue but does require a deposit, returned once enacted.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `encoded_proposal`: The preimage of a proposal.
+		///
+		/// Emits `PreimageNoted`.
+		///
+		/// # <weight>
+		/// - `O(B)` where `B` is the length of `encoded_proposal`, which is also protected by a
+		///   required deposit.
+		/// - One DB entry.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|(encoded_proposal,): (&Vec<u8>,)| weight_for::note(encoded_proposal.len() as Weight),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn note_preimage(origin, encoded_proposal: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+			let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
+			ensure!(
+				!<Preimages<T, I>>::contains_key(&proposal_hash),
+				Error::<T, I>::DuplicatePreimage,
+			);
+
+			let deposit = <BalanceOf<T, I>>::from(encoded_proposal.len() as u32)
+				.saturating_mul(T::PreimageByteDeposit::get());
+			T::Currency::reserve(&who, deposit)?;
+
+			let now = <frame_system::Module<T>>::block_number();
+			<Preimages<T, I>>::insert(proposal_hash, (encoded_proposal, who.clone(), deposit, now));
+
+			Self::deposit_event(RawEvent::PreimageNoted(proposal_hash, who, deposit));
+		}
+
+		/// Register the preimage for an upcoming proposal. This requires the proposal to be
+		/// in the dispatch queue. No deposit is needed.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `encoded_proposal`: The preimage of a proposal.
+		///
+		/// Emits `PreimageNoted`.
+		///
+		/// # <weight>
+		/// - `O(B + Q)` where `B` is the length of `encoded_proposal` and `Q` is the length of
+		///   the dispatch queue, bounded by `MaxQueued`.
+		/// - One DB entry.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|(encoded_proposal,): (&Vec<u8>,)| weight_for::note(encoded_proposal.len() as Weight)
+				.saturating_add(weight_for::dispatch(T::MaxQueued::get() as Weight)),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn note_imminent_preimage(origin, encoded_proposal: Vec<u8>) {
+			let who = ensure_signed(origin)?;
+			let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
+			ensure!(
+				!<Preimages<T, I>>::contains_key(&proposal_hash),
+				Error::<T, I>::DuplicatePreimage,
+			);
+			let queue = <DispatchQueue<T, I>>::get();
+			ensure!(
+				queue.iter().any(|item| item.1.lookup_hash().as_ref() == Some(&proposal_hash)),
+				Error::<T, I>::NotImminent,
+			);
+
+			let now = <frame_system::Module<T>>::block_number();
+			let free = <BalanceOf<T, I>>::zero();
+			<Preimages<T, I>>::insert(proposal_hash, (encoded_proposal, who.clone(), free, now));
+
+			Self::deposit_event(RawEvent::PreimageNoted(proposal_hash, who, free));
+		}
+
+		/// Remove an expired proposal preimage and collect the deposit.
+		///
+		/// The dispatch origin of this call must be _Signed_.
 		///
 		/// - `proposal_hash`: The preimage hash of a proposal.
+		/// - `proposal_len_upper_bound`: an upper bound on the length of the preimage. Extrinsic
+		///   is weighted according to this value with no refund.
 		///
 		/// This will only work after `VotingPeriod` blocks from the time that the preimage was
 		/// noted, if it's the same account doing it. If it's a different account, then it'll only
@@ -1163,48 +2822,249 @@ decl_module! {
 		/// Emits `PreimageReaped`.
 		///
 		/// # <weight>
-		/// - One DB clear.
+		/// - `O(B)` where `B` is the length of the preimage, which is read before it is cleared.
+		/// - `O(Q)` where `Q` is the length of the dispatch queue, bounded by `MaxQueued`.
 		/// # </weight>
-		#[weight = SimpleDispatchInfo::FixedNormal(10_000)]
-		fn reap_preimage(origin, proposal_hash: T::Hash) {
+		#[weight = FunctionOf(
+			|(_, b): (&T::Hash, &u32)| weight_for::reap(*b as Weight)
+				.saturating_add(weight_for::dispatch(T::MaxQueued::get() as Weight)),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn reap_preimage(origin, proposal_hash: T::Hash, #[compact] proposal_len_upper_bound: u32) {
 			let who = ensure_signed(origin)?;
 
-			let (_, old, deposit, then) = <Preimages<T>>::get(&proposal_hash)
-				.ok_or(Error::<T>::PreimageMissing)?;
+			let len = Self::len(&proposal_hash).ok_or(Error::<T, I>::PreimageMissing)?;
+			ensure!(len <= proposal_len_upper_bound, Error::<T, I>::WrongUpperBound);
+			let (_, old, deposit, then) = <Preimages<T, I>>::get(&proposal_hash)
+				.ok_or(Error::<T, I>::PreimageMissing)?;
 			let now = <frame_system::Module<T>>::block_number();
 			let (voting, enactment) = (T::VotingPeriod::get(), T::EnactmentPeriod::get());
 			let additional = if who == old { Zero::zero() } else { enactment };
-			ensure!(now >= then + voting + additional, Error::<T>::Early);
+			ensure!(now >= then + voting + additional, Error::<T, I>::Early);
 
-			let queue = <DispatchQueue<T>>::get();
-			ensure!(!queue.iter().any(|item| &item.1 == &proposal_hash), Error::<T>::Imminent);
+			let queue = <DispatchQueue<T, I>>::get();
+			ensure!(
+				!queue.iter().any(|item| item.1.lookup_hash().as_ref() == Some(&proposal_hash)),
+				Error::<T, I>::Imminent,
+			);
 
 			let _ = T::Currency::repatriate_reserved(&old, &who, deposit, BalanceStatus::Free);
-			<Preimages<T>>::remove(&proposal_hash);
+			<Preimages<T, I>>::remove(&proposal_hash);
 			Self::deposit_event(RawEvent::PreimageReaped(proposal_hash, old, deposit, who));
 		}
 
-		/// Unlock tokens that have an expired lock.
+		/// Note a chunk of the preimage of an upcoming proposal too large to be noted at once.
+		/// The chunks are noted in order, then assembled by `finalize_preimage`. Each requires a
+		/// deposit of `PreimageChunkDeposit` plus `PreimageByteDeposit` for each of its bytes,
+		/// which becomes that of the preimage once it is finalized.
+		///
+		/// The dispatch origin of this call must be _Signed_. Only the account which noted the
+		/// first chunk may note the others.
+		///
+		/// - `proposal_hash`: The hash of the whole preimage.
+		/// - `index`: The index of the chunk, which must be the number of chunks noted so far.
+		/// - `chunk`: The chunk of the preimage, which must not be empty.
+		///
+		/// Fails with `TooManyChunks` if the upload already has `MaxPreimageChunks` chunks, so
+		/// that an upload left unfinished may always be reaped in time.
+		///
+		/// Emits `PreimageChunkNoted`.
+		///
+		/// # <weight>
+		/// - `O(C)` where `C` is the length of `chunk`, which is also protected by a required
+		///   deposit.
+		/// - Two DB entries.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|(_, _, chunk): (&T::Hash, &u32, &Vec<u8>)| weight_for::note(chunk.len() as Weight),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn note_preimage_chunk(
+			origin,
+			proposal_hash: T::Hash,
+			#[compact] index: u32,
+			chunk: Vec<u8>,
+		) {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!<Preimages<T, I>>::contains_key(&proposal_hash),
+				Error::<T, I>::DuplicatePreimage,
+			);
+			let now = <frame_system::Module<T>>::block_number();
+			let mut upload = match <PreimageUploads<T, I>>::get(&proposal_hash) {
+				Some(upload) => {
+					ensure!(upload.who == who, Error::<T, I>::NotUploader);
+					upload
+				}
+				None => PreimageUpload {
+					who: who.clone(),
+					chunks: 0,
+					len: 0,
+					deposit: Zero::zero(),
+					since: now,
+				},
+			};
+			ensure!(index == upload.chunks, Error::<T, I>::WrongChunkIndex);
+			ensure!(upload.chunks < T::MaxPreimageChunks::get(), Error::<T, I>::TooManyChunks);
+			ensure!(!chunk.is_empty(), Error::<T, I>::EmptyChunk);
+
+			let deposit = <BalanceOf<T, I>>::from(chunk.len() as u32)
+				.saturating_mul(T::PreimageByteDeposit::get())
+				.saturating_add(T::PreimageChunkDeposit::get());
+			T::Currency::reserve(&who, deposit)?;
+
+			upload.chunks += 1;
+			upload.len = upload.len.saturating_add(chunk.len() as u32);
+			upload.deposit = upload.deposit.saturating_add(deposit);
+			upload.since = now;
+			<PreimageChunks<T, I>>::insert((proposal_hash, index), chunk);
+			<PreimageUploads<T, I>>::insert(proposal_hash, upload);
+
+			Self::deposit_event(RawEvent::PreimageChunkNoted(proposal_hash, who, index));
+		}
+
+		/// Assemble the chunks of a preimage uploaded with `note_preimage_chunk`, noting it as if
+		/// with `note_preimage`. The deposits of the chunks become that of the preimage.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the uploader.
+		///
+		/// - `proposal_hash`: The hash of the whole preimage.
+		/// - `proposal_len_upper_bound`: an upper bound on the length of the preimage. Extrinsic
+		///   is weighted according to this value and `MaxPreimageChunks`; the weight beyond that
+		///   of the actual preimage is given back to the block, but its fee is not refunded.
+		///
+		/// Fails with `PreimageMismatch`, leaving the upload in place, if the chunks do not hash to
+		/// `proposal_hash`.
+		///
+		/// Emits `PreimageNoted`.
+		///
+		/// # <weight>
+		/// - `O(B)` where `B` is the length of the preimage, which is hashed.
+		/// - `O(C)` where `C` is the number of chunks, which are read and cleared, bounded by
+		///   `MaxPreimageChunks`.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|(_, b): (&T::Hash, &u32)| weight_for::note(*b as Weight)
+				.saturating_add(weight_for::chunks(T::MaxPreimageChunks::get() as Weight)),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn finalize_preimage(
+			origin,
+			proposal_hash: T::Hash,
+			#[compact] proposal_len_upper_bound: u32,
+		) {
+			let who = ensure_signed(origin)?;
+			let upload = <PreimageUploads<T, I>>::get(&proposal_hash)
+				.ok_or(Error::<T, I>::UploadMissing)?;
+			ensure!(upload.who == who, Error::<T, I>::NotUploader);
+			ensure!(upload.len <= proposal_len_upper_bound, Error::<T, I>::WrongUpperBound);
+			ensure!(
+				!<Preimages<T, I>>::contains_key(&proposal_hash),
+				Error::<T, I>::DuplicatePreimage,
+			);
+
+			let mut encoded_proposal = Vec::with_capacity(upload.len as usize);
+			for index in 0..upload.chunks {
+				let chunk = <PreimageChunks<T, I>>::get((proposal_hash, index)).unwrap_or_default();
+				encoded_proposal.extend(chunk);
+			}
+			ensure!(
+				T::Hashing::hash(&encoded_proposal[..]) == proposal_hash,
+				Error::<T, I>::PreimageMismatch,
+			);
+
+			Self::clear_preimage_upload(&proposal_hash, upload.chunks);
+			let now = <frame_system::Module<T>>::block_number();
+			let preimage = (encoded_proposal, who.clone(), upload.deposit, now);
+			<Preimages<T, I>>::insert(proposal_hash, preimage);
+
+			Self::refund_weight(
+				weight_for::note(proposal_len_upper_bound as Weight)
+					.saturating_add(weight_for::chunks(T::MaxPreimageChunks::get() as Weight)),
+				weight_for::note(upload.len as Weight)
+					.saturating_add(weight_for::chunks(upload.chunks as Weight)),
+			);
+			Self::deposit_event(RawEvent::PreimageNoted(proposal_hash, who, upload.deposit));
+		}
+
+		/// Remove an unfinished preimage upload and collect its deposit.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `proposal_hash`: The hash of the whole preimage.
+		///
+		/// The uploader may do so at any time. Anyone else may only once `VotingPeriod` blocks
+		/// have passed since the latest chunk was noted.
+		///
+		/// Emits `PreimageUploadReaped`.
+		///
+		/// # <weight>
+		/// - `O(C)` where `C` is the number of chunks, which are cleared, bounded by
+		///   `MaxPreimageChunks`. Weighted for `MaxPreimageChunks`, giving back to the block the
+		///   weight of the chunks not uploaded.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|_: (&T::Hash,)| (1_000_000 as Weight)
+				.saturating_add(weight_for::chunks(T::MaxPreimageChunks::get() as Weight)),
+			DispatchClass::Normal,
+			true,
+		)]
+		fn reap_preimage_upload(origin, proposal_hash: T::Hash) {
+			let who = ensure_signed(origin)?;
+			let upload = <PreimageUploads<T, I>>::get(&proposal_hash)
+				.ok_or(Error::<T, I>::UploadMissing)?;
+			if who != upload.who {
+				let now = <frame_system::Module<T>>::block_number();
+				ensure!(now >= upload.since + T::VotingPeriod::get(), Error::<T, I>::Early);
+			}
+
+			let _ = T::Currency::repatriate_reserved(
+				&upload.who,
+				&who,
+				upload.deposit,
+				BalanceStatus::Free,
+			);
+			Self::clear_preimage_upload(&proposal_hash, upload.chunks);
+			Self::refund_weight(
+				weight_for::chunks(T::MaxPreimageChunks::get() as Weight),
+				weight_for::chunks(upload.chunks as Weight),
+			);
+			Self::deposit_event(
+				RawEvent::PreimageUploadReaped(proposal_hash, upload.who, upload.deposit, who)
+			);
+		}
+
+		/// Unlock tokens that are no longer required to be locked.
+		///
+		/// The lock on `target` is recomputed from what still requires it: an ongoing delegation
+		/// keeps the whole balance locked, while prior locks from past votes and undone delegations
+		/// are dropped once expired. The lock is removed entirely when nothing remains.
 		///
 		/// The dispatch origin of this call must be _Signed_.
 		///
 		/// - `target`: The account to remove the lock on.
 		///
-		/// Emits `Unlocked`.
+		/// Emits `Unlocked` if the lock was removed entirely.
 		///
 		/// # <weight>
 		/// - `O(1)`.
+		/// - When there is no lock to update, only the weight of the DB reads that find so is
+		///   kept, and the rest is given back to the block.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(10_000)]
 		fn unlock(origin, target: T::AccountId) {
 			ensure_signed(origin)?;
+			if !Locks::<T, I>::contains_key(&target) && !Self::is_delegating(&target) {
+				Self::refund_weight(10_000, 5_000);
+				Err(Error::<T, I>::NotLocked)?;
+			}
 
-			let expiry = Locks::<T>::get(&target).ok_or(Error::<T>::NotLocked)?;
-			ensure!(expiry <= system::Module::<T>::block_number(), Error::<T>::NotExpired);
-
-			T::Currency::remove_lock(DEMOCRACY_ID, &target);
-			Locks::<T>::remove(&target);
-			Self::deposit_event(RawEvent::Unlocked(target));
+			if Self::update_lock(&target).is_zero() {
+				Self::deposit_event(RawEvent::Unlocked(target));
+			}
 		}
 
 		/// Become a proxy.
@@ -1217,34 +3077,220 @@ decl_module! {
 		///
 		/// `close_proxy` must be called before the account can be destroyed.
 		///
+		/// Emits `ProxyOpened`.
+		///
 		/// # <weight>
 		/// - One extra DB entry.
 		/// # </weight>
 		#[weight = SimpleDispatchInfo::FixedNormal(100_000)]
 		fn open_proxy(origin, target: T::AccountId) {
 			let who = ensure_signed(origin)?;
-			Proxy::<T>::mutate(&who, |a| {
+			Self::ensure_legacy_proxy()?;
+			Proxy::<T, I>::mutate(&who, |a| {
 				if a.is_none() {
 					system::Module::<T>::inc_ref(&who);
 				}
-				*a = Some(ProxyState::Open(target));
+				*a = Some(ProxyState::Open(target.clone()));
 			});
+			Self::deposit_event(RawEvent::ProxyOpened(who, target));
+		}
+
+		/// Attach the hash of an off-chain description to a proposal or referendum, or clear it.
+		///
+		/// The dispatch origin of this call depends on `owner`:
+		/// - for a public proposal, it must be _Signed_ by the proposer;
+		/// - for the external proposal, it must be `ExternalOrigin`, `ExternalMajorityOrigin` or
+		///   `ExternalDefaultOrigin`;
+		/// - for a referendum, it must be _Root_.
+		///
+		/// - `owner`: The proposal or referendum to describe.
+		/// - `maybe_hash`: The hash of the description, or `None` to clear it.
+		///
+		/// Emits `MetadataSet` or `MetadataCleared`.
+		///
+		/// # <weight>
+		/// - `O(P)` where `P` is the number of public proposals, bounded by `MaxProposals`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(50_000)]
+		fn set_metadata(origin, owner: MetadataOwner, maybe_hash: Option<T::Hash>) {
+			Self::ensure_metadata_origin(origin, owner)?;
+			if let Some(hash) = maybe_hash {
+				<MetadataOf<T, I>>::insert(owner, hash);
+				Self::deposit_event(RawEvent::MetadataSet(owner, hash));
+			} else if <MetadataOf<T, I>>::take(owner).is_some() {
+				Self::deposit_event(RawEvent::MetadataCleared(owner));
+			}
+		}
+
+		/// Abstain in a referendum: the balance of the sender, along with that of the accounts
+		/// delegating to them, counts toward its turnout but toward neither side. Any vote the
+		/// sender has cast in the referendum is removed.
+		///
+		/// An abstention is never on the winning side, so leaves no lock behind. It counts towards
+		/// `MaxVotes` and reserves `VoteDeposit`, as a vote does.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `ref_index`: The index of the referendum to abstain in.
+		///
+		/// Emits `Abstained`.
+		///
+		/// # <weight>
+		/// - `O(V + A + R)` where `V` is the number of voters, and `A` that of abstainers, in the
+		///   referendum, and `R` the number of referenda the sender votes in, bounded by
+		///   `MaxVotes`.
+		/// - Two DB changes, up to four DB clears, one balance reserve.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(200_000)]
+		fn abstain(origin, #[compact] ref_index: ReferendumIndex) {
+			let who = ensure_signed(origin)?;
+			let info = Self::referendum_info(ref_index).ok_or(Error::<T, I>::ReferendumInvalid)?;
+			if <VoteOf<T, I>>::contains_key((ref_index, &who)) {
+				Self::do_remove_vote(who.clone(), ref_index)?;
+			}
+			<Abstainers<T, I>>::try_mutate(ref_index, |abstainers| -> DispatchResult {
+				ensure!(!abstainers.contains(&who), Error::<T, I>::AlreadyAbstaining);
+				Self::reserve_vote_deposit(&who, ref_index, info.end)?;
+				abstainers.push(who.clone());
+				Ok(())
+			})?;
+			Self::index_vote(&who, ref_index);
+			T::OnVote::on_vote(&who, ref_index);
+			Self::deposit_event(RawEvent::Abstained(who, ref_index));
+		}
+
+		/// Delegate vote in one class of referenda, overriding any delegation made with
+		/// `delegate` for referenda of that class.
+		///
+		/// Currency is locked indefinitely for as long as it's delegated.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `class`: The class of referenda, identified by the threshold they are tabled with:
+		///   `SuperMajorityApprove` for public proposals and `external_propose`,
+		///   `SimpleMajority` for `external_propose_majority` (fast-tracked or not) and
+		///   `SuperMajorityAgainst` for `external_propose_default`.
+		/// - `to`: The account to make a delegate of the sender.
+		/// - `conviction`: The conviction that will be attached to the delegated votes.
+		///
+		/// The same rules as for `delegate` apply to direct votes of the sender, in referenda of
+		/// `class` only.
+		///
+		/// Emits `ClassDelegated`, and `VoteRemoved` for each vote removed.
+		///
+		/// # <weight>
+		/// - `O(R + D)` where `R` is the number of referenda the sender has direct votes in and
+		///   `D` the number of delegators of `to`, bounded by `MaxDelegators`.
+		/// - Two extra DB entries.
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(500_000)]
+		fn delegate_class(origin, class: VoteThreshold, to: T::AccountId, conviction: Conviction) {
+			let who = ensure_signed(origin)?;
+			Self::ensure_delegator_room(&who, &to)?;
+			Self::clear_direct_votes(&who, Some(class))?;
+			let previous = <ClassDelegations<T, I>>::get((&who, class));
+			<ClassDelegations<T, I>>::insert((&who, class), (&to, conviction));
+			Self::index_delegator(&who, &to);
+			if let Some((previous, _)) = previous {
+				Self::unindex_delegator(&who, &previous);
+			}
+			// Currency is locked indefinitely as long as it's delegated.
+			T::Currency::extend_lock(
+				T::LockId::get(),
+				&who,
+				Bounded::max_value(),
+				WithdrawReason::Transfer.into()
+			);
+			T::OnDelegation::on_delegate(&who, &to, Some(class));
+			Self::deposit_event(RawEvent::ClassDelegated(who, class, to));
+		}
+
+		/// Undelegate vote in one class of referenda.
+		///
+		/// Unless the sender still delegates in another class, or with `delegate`, the tokens
+		/// will be reduced from an indefinite lock to the maximum possible according to the
+		/// conviction of the prior delegation.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `class`: The class of referenda to stop delegating in.
+		///
+		/// Emits `ClassUndelegated`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// # </weight>
+		#[weight = SimpleDispatchInfo::FixedNormal(500_000)]
+		fn undelegate_class(origin, class: VoteThreshold) {
+			let who = ensure_signed(origin)?;
+			let (to, conviction) = <ClassDelegations<T, I>>::take((&who, class))
+				.ok_or(Error::<T, I>::NotDelegated)?;
+			Self::unindex_delegator(&who, &to);
+			Self::release_delegation_lock(&who, conviction, Perbill::one());
+			T::OnDelegation::on_undelegate(&who, Some(class));
+			Self::deposit_event(RawEvent::ClassUndelegated(who, class));
+		}
+
+		/// Prune the outcomes of finished referenda. Each must have ended at least
+		/// `FinishedRetention` blocks ago.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		///
+		/// - `indices`: The indices of the referenda to prune the outcome of.
+		///
+		/// Emits `FinishedPruned` for each of them.
+		///
+		/// # <weight>
+		/// - `O(I)` where `I` is the number of indices.
+		/// - `I` DB clears.
+		/// # </weight>
+		#[weight = FunctionOf(
+			|(indices,): (&Vec<ReferendumIndex>,)|
+				10_000u32.saturating_add((indices.len() as u32).saturating_mul(10_000)),
+			DispatchClass::Operational,
+			true,
+		)]
+		fn prune_finished(origin, indices: Vec<ReferendumIndex>) {
+			ensure_root(origin)?;
+			let now = <frame_system::Module<T>>::block_number();
+			for index in indices.iter() {
+				let finished = Self::finished_of(index).ok_or(Error::<T, I>::NotPrunable)?;
+				let prunable = finished.end.saturating_add(T::FinishedRetention::get()) <= now;
+				ensure!(prunable, Error::<T, I>::NotPrunable);
+			}
+			for index in indices {
+				<FinishedOf<T, I>>::remove(index);
+				Self::deposit_event(RawEvent::FinishedPruned(index));
+			}
 		}
 	}
 }
 
-impl<T: Trait> Module<T> {
+impl<T: Trait<I>, I: Instance> Module<T, I> {
 	// exposed immutables.
 
 	/// Get the amount locked in support of `proposal`; `None` if proposal isn't a valid proposal
 	/// index.
-	pub fn locked_for(proposal: PropIndex) -> Option<BalanceOf<T>> {
+	pub fn locked_for(proposal: PropIndex) -> Option<BalanceOf<T, I>> {
 		Self::deposit_of(proposal).map(|(d, l)| d * (l.len() as u32).into())
 	}
 
 	/// Return true if `ref_index` is an on-going referendum.
 	pub fn is_active_referendum(ref_index: ReferendumIndex) -> bool {
-		<ReferendumInfoOf<T>>::contains_key(ref_index)
+		<ReferendumInfoOf<T, I>>::contains_key(ref_index)
+	}
+
+	/// Return true if `ref_index` is an on-going referendum, either a binary or a multi-option one.
+	pub fn is_ongoing_referendum(ref_index: ReferendumIndex) -> bool {
+		Self::is_active_referendum(ref_index) || <MultiReferendumInfoOf<T, I>>::contains_key(ref_index)
+	}
+
+	/// The key in the off-chain index under which the `VoteRecord` of the direct vote of `who` in
+	/// `ref_index` is written when `IndexVotes` is set. The keys of the votes in a referendum
+	/// share the prefix made of everything before the account.
+	pub fn vote_index_key(ref_index: ReferendumIndex, who: &T::AccountId) -> Vec<u8> {
+		(b"democracy/votes", ref_index, who).encode()
 	}
 
 	/// Get all referenda currently active.
@@ -1270,84 +3316,449 @@ impl<T: Trait> Module<T> {
 			.collect()
 	}
 
-	/// Get the voters for the current proposal.
-	pub fn tally(ref_index: ReferendumIndex) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
-		let (approve, against, capital):
-			(BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) = Self::voters_for(ref_index)
-				.iter()
-				.map(|voter| (
-					T::Currency::total_balance(voter), Self::vote_of((ref_index, voter.clone()))
-				))
-				.map(|(balance, Vote { aye, conviction })| {
-					let (votes, turnout) = conviction.votes(balance);
-					if aye {
-						(votes, Zero::zero(), turnout)
-					} else {
-						(Zero::zero(), votes, turnout)
-					}
-				}).fold(
-					(Zero::zero(), Zero::zero(), Zero::zero()),
-					|(a, b, c), (d, e, f)| (a + d, b + e, c + f)
-				);
-		let (del_approve, del_against, del_capital) = Self::tally_delegation(ref_index);
-		(approve + del_approve, against + del_against, capital + del_capital)
-	}
-
-	/// Get the delegated voters for the current proposal.
-	/// I think this goes into a worker once https://github.com/paritytech/substrate/issues/1458 is
-	/// done.
-	fn tally_delegation(ref_index: ReferendumIndex) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
-		Self::voters_for(ref_index).iter().fold(
-			(Zero::zero(), Zero::zero(), Zero::zero()),
-			|(approve_acc, against_acc, turnout_acc), voter| {
-				let Vote { aye, conviction } = Self::vote_of((ref_index, voter.clone()));
-				let (votes, turnout) = Self::delegated_votes(
-					ref_index,
-					voter.clone(),
-					conviction,
-					MAX_RECURSION_LIMIT
-				);
-				if aye {
-					(approve_acc + votes, against_acc, turnout_acc + turnout)
-				} else {
-					(approve_acc, against_acc + votes, turnout_acc + turnout)
-				}
-			}
-		)
+	/// Get the tally of the votes, direct and delegated, for the current proposal.
+	///
+	/// Votes, along with the votes delegated to them, are discounted by `T::VoteWeightCurve`
+	/// according to how long before the end of voting they were cast. Abstentions only add to the
+	/// turnout.
+	pub fn tally(ref_index: ReferendumIndex) -> Tally<BalanceOf<T, I>> {
+		Self::count_tally(ref_index).0
 	}
 
-	fn delegated_votes(
-		ref_index: ReferendumIndex,
-		to: T::AccountId,
-		parent_conviction: Conviction,
-		recursion_limit: u32,
-	) -> (BalanceOf<T>, BalanceOf<T>) {
-		if recursion_limit == 0 { return (Zero::zero(), Zero::zero()); }
-		<Delegations<T>>::iter()
-			.filter(|(delegator, (delegate, _))|
-				*delegate == to && !<VoteOf<T>>::contains_key(&(ref_index, delegator.clone()))
-			).fold(
-				(Zero::zero(), Zero::zero()),
-				|(votes_acc, turnout_acc), (delegator, (_delegate, max_conviction))| {
-					let conviction = Conviction::min(parent_conviction, max_conviction);
-					let balance = T::Currency::total_balance(&delegator);
-					let (votes, turnout) = conviction.votes(balance);
-					let (del_votes, del_turnout) = Self::delegated_votes(
-						ref_index,
-						delegator,
-						conviction,
-						recursion_limit - 1
-					);
-					(votes_acc + votes + del_votes, turnout_acc + turnout + del_turnout)
-				}
-			)
+	/// The tally of referendum `ref_index`, along with the number of delegations counted in it.
+	fn count_tally(ref_index: ReferendumIndex) -> (Tally<BalanceOf<T, I>>, u32) {
+		let end = Self::referendum_info(ref_index).map(|info| info.end);
+		let mut counted = 0;
+		let tally = Self::voters_for(ref_index).iter().fold(Tally::default(), |tally, voter| {
+			let aye = Self::vote_of((ref_index, voter.clone())).aye;
+			let (votes, capital) = Self::voter_power(ref_index, end, voter, &mut counted);
+			tally.add(aye, votes, capital)
+		});
+		let tally = Self::abstainers(ref_index).into_iter().fold(tally, |mut tally, abstainer| {
+			let capital = T::Currency::total_balance(&abstainer);
+			let (_, del_capital) = Self::delegated_votes(
+				ref_index,
+				abstainer,
+				Conviction::None,
+				MAX_RECURSION_LIMIT,
+				&mut counted,
+			);
+			tally.turnout = tally.turnout.saturating_add(capital).saturating_add(del_capital);
+			tally
+		});
+		(tally, counted)
 	}
 
-	// Exposed mutables.
+	/// Get the votes that the direct vote of `who` in referendum `ref_index` counts for, including
+	/// the votes delegated to them, possibly through several levels of delegation, and any late
+	/// discount. Zero if `who` has no direct vote there.
+	pub fn voting_power_of(who: T::AccountId, ref_index: ReferendumIndex) -> BalanceOf<T, I> {
+		if !<VoteOf<T, I>>::contains_key((ref_index, &who)) {
+			return Zero::zero();
+		}
+		let end = Self::referendum_info(ref_index).map(|info| info.end);
+		Self::voter_power(ref_index, end, &who, &mut 0).0
+	}
+
+	/// The votes and capital behind the direct vote of `voter` in referendum `ref_index`, ending
+	/// at `end`, including what is delegated to them, the delegations of which are added to
+	/// `counted`.
+	fn voter_power(
+		ref_index: ReferendumIndex,
+		end: Option<T::BlockNumber>,
+		voter: &T::AccountId,
+		counted: &mut u32,
+	) -> (BalanceOf<T, I>, BalanceOf<T, I>) {
+		let conviction = Self::vote_of((ref_index, voter.clone())).conviction;
+		let weight = match (end, Self::voted_at((ref_index, voter.clone()))) {
+			(Some(end), Some(at)) => T::VoteWeightCurve::weight(end.saturating_sub(at)),
+			_ => Perbill::one(),
+		};
+		let balance = Self::vote_balance(ref_index, voter);
+		let (votes, capital) = Self::conviction_votes(conviction, balance);
+		let (del_votes, del_capital) = Self::delegated_votes(
+			ref_index,
+			voter.clone(),
+			conviction,
+			MAX_RECURSION_LIMIT,
+			counted,
+		);
+		// I think the delegation part goes into a worker once
+		// https://github.com/paritytech/substrate/issues/1458 is done.
+		(weight * votes.saturating_add(del_votes), capital.saturating_add(del_capital))
+	}
+
+	/// The balance behind the direct vote of `voter` in `ref_index`: their current balance, capped
+	/// by the snapshot taken when they first voted there, if any.
+	fn vote_balance(ref_index: ReferendumIndex, voter: &T::AccountId) -> BalanceOf<T, I> {
+		let balance = T::Currency::total_balance(voter);
+		match Self::balance_snapshot((ref_index, voter.clone())) {
+			Some(snapshot) => snapshot.min(balance),
+			None => balance,
+		}
+	}
+
+	/// The votes of `balance` voted with `conviction`, along with the capital they contribute to
+	/// turnout: with `QuadraticVoting`, the votes are those of the square root of the balance.
+	fn conviction_votes(
+		conviction: Conviction,
+		balance: BalanceOf<T, I>,
+	) -> (BalanceOf<T, I>, BalanceOf<T, I>) {
+		let params = Self::conviction_params(conviction);
+		if T::QuadraticVoting::get() {
+			(params.votes(balance.integer_sqrt()).0, balance)
+		} else {
+			params.votes(balance)
+		}
+	}
+
+	/// The votes and lock periods implied by `conviction`.
+	fn conviction_params(conviction: Conviction) -> ConvictionParams {
+		T::ConvictionSchedule::get()[u8::from(conviction) as usize]
+	}
+
+	/// Ensure that the deprecated built-in proxy is still enabled.
+	fn ensure_legacy_proxy() -> DispatchResult {
+		ensure!(cfg!(feature = "legacy-proxy"), Error::<T, I>::ProxyDeprecated);
+		Ok(())
+	}
+
+	/// The stash for which `who` is the active proxy.
+	/// Ensure that `origin` may describe `owner`, which must exist.
+	fn ensure_metadata_origin(origin: T::Origin, owner: MetadataOwner) -> DispatchResult {
+		match owner {
+			MetadataOwner::External => {
+				T::ExternalOrigin::try_origin(origin).map(|_| ())
+					.or_else(|o| T::ExternalMajorityOrigin::try_origin(o).map(|_| ()))
+					.or_else(|o| T::ExternalDefaultOrigin::try_origin(o).map(|_| ()))
+					.map_err(|_| BadOrigin)?;
+				ensure!(<NextExternal<T, I>>::exists(), Error::<T, I>::NoProposal);
+			}
+			MetadataOwner::Proposal(index) => {
+				let who = ensure_signed(origin)?;
+				let (_, _, proposer) = Self::public_props().into_iter()
+					.find(|p| p.0 == index)
+					.ok_or(Error::<T, I>::ProposalMissing)?;
+				ensure!(proposer == who, Error::<T, I>::NotProposer);
+			}
+			MetadataOwner::Referendum(index) => {
+				ensure_root(origin)?;
+				ensure!(Self::is_ongoing_referendum(index), Error::<T, I>::ReferendumInvalid);
+			}
+		}
+		Ok(())
+	}
+
+	/// Carry the description of a proposal over to the referendum it has been tabled as.
+	fn transfer_metadata(from: MetadataOwner, to: ReferendumIndex) {
+		if let Some(hash) = <MetadataOf<T, I>>::take(from) {
+			<MetadataOf<T, I>>::insert(MetadataOwner::Referendum(to), hash);
+		}
+	}
+
+	fn proxied_stash(who: T::AccountId) -> result::Result<T::AccountId, DispatchError> {
+		Self::ensure_legacy_proxy()?;
+		Self::proxy(who).and_then(|a| a.as_active()).ok_or_else(|| Error::<T, I>::NotProxy.into())
+	}
+
+	/// Get the scores of the options of a multi-option referendum, in order.
+	pub fn multi_tally(ref_index: ReferendumIndex) -> Vec<BalanceOf<T, I>> {
+		let info = match Self::multi_referendum_info(ref_index) {
+			Some(info) => info,
+			None => return Vec::new(),
+		};
+		let options = info.options.len() as u32;
+		let mut scores = vec![BalanceOf::<T, I>::zero(); options as usize];
+		for voter in Self::voters_for(ref_index) {
+			let balance = T::Currency::total_balance(&voter);
+			for (rank, option) in Self::ballot_of((ref_index, voter)).into_iter().enumerate() {
+				let points = match info.rule {
+					TallyRule::Approval => 1,
+					TallyRule::Ranked => options.saturating_sub(rank as u32),
+				};
+				if let Some(score) = scores.get_mut(option as usize) {
+					*score = score.saturating_add(balance.saturating_mul(points.into()));
+				}
+			}
+		}
+		scores
+	}
+
+	/// The votes and capital delegated to `to` in referendum `ref_index`, through at most
+	/// `recursion_limit` levels of delegation, adding the number of delegations counted to
+	/// `counted`.
+	fn delegated_votes(
+		ref_index: ReferendumIndex,
+		to: T::AccountId,
+		parent_conviction: Conviction,
+		recursion_limit: u32,
+		counted: &mut u32,
+	) -> (BalanceOf<T, I>, BalanceOf<T, I>) {
+		if recursion_limit == 0 { return (Zero::zero(), Zero::zero()); }
+		// Delegations in the class of the referendum override the general ones.
+		let class = Self::referendum_info(ref_index).map(|info| info.threshold);
+		// Only the delegation that applies to the referendum counts, whichever it goes to.
+		Self::delegators(&to).into_iter()
+			.filter_map(|delegator| {
+				let specific = class.and_then(|c| Self::class_delegations((&delegator, c)));
+				let (delegate, conviction, proportion) = match specific {
+					Some((delegate, conviction)) => (delegate, conviction, Perbill::one()),
+					None if <Delegations<T, I>>::contains_key(&delegator) => {
+						let (delegate, conviction) = Self::delegations(&delegator);
+						(delegate, conviction, Perbill::one())
+					}
+					None => {
+						let (targets, conviction) = Self::split_delegations(&delegator)?;
+						let (delegate, proportion) = targets.into_iter().find(|(d, _)| *d == to)?;
+						(delegate, conviction, proportion)
+					}
+				};
+				Some((delegator, delegate, conviction, proportion))
+			})
+			.filter(|(delegator, delegate, _, _)|
+				*delegate == to && !<VoteOf<T, I>>::contains_key(&(ref_index, delegator.clone())) &&
+					!Self::abstainers(ref_index).contains(delegator)
+			).fold(
+				(Zero::zero(), Zero::zero()),
+				|(votes_acc, turnout_acc), (delegator, _delegate, max_conviction, proportion)| {
+					*counted = counted.saturating_add(1);
+					let conviction = Conviction::min(parent_conviction, max_conviction);
+					let balance = proportion * T::Currency::total_balance(&delegator);
+					let (votes, turnout) = Self::conviction_votes(conviction, balance);
+					// Whatever is delegated to the delegator is passed on in the same proportion.
+					let (del_votes, del_turnout) = Self::delegated_votes(
+						ref_index,
+						delegator,
+						conviction,
+						recursion_limit - 1,
+						&mut *counted,
+					);
+					let (del_votes, del_turnout) = (proportion * del_votes, proportion * del_turnout);
+					(votes_acc + votes + del_votes, turnout_acc + turnout + del_turnout)
+				}
+			)
+	}
+
+	/// Get the status of all ongoing referenda, along with their current tallies.
+	pub fn referendum_statuses()
+		-> Vec<ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T, I>>>
+	{
+		Self::active_referenda().into_iter()
+			.map(|(index, info)| ReferendumStatus {
+				index,
+				end: info.end,
+				proposal_hash: Self::proposal_hash(&info.proposal),
+				threshold: info.threshold,
+				delay: info.delay,
+				tally: Self::tally(index),
+			})
+			.collect()
+	}
+
+	/// Get the status of all public proposals in the queue, along with their deposits.
+	pub fn proposal_statuses() -> Vec<ProposalStatus<T::AccountId, T::Hash, BalanceOf<T, I>>> {
+		Self::public_props().into_iter()
+			.filter_map(|(index, proposal, proposer)| {
+				let (deposit, backers) = Self::deposit_of(index)?;
+				Some(ProposalStatus {
+					index,
+					proposal_hash: Self::proposal_hash(&proposal),
+					proposer,
+					deposit,
+					backers,
+				})
+			})
+			.collect()
+	}
+
+	/// Get the votes, delegation and prior lock of `who`.
+	pub fn account_governance(
+		who: T::AccountId,
+	) -> AccountGovernance<T::AccountId, T::BlockNumber, BalanceOf<T, I>> {
+		let votes = Self::voting(&who).into_iter()
+			.map(|index| (index, Self::vote_of((index, who.clone()))))
+			.collect();
+		let delegation = if <Delegations<T, I>>::contains_key(&who) {
+			Some(Self::delegations(&who))
+		} else {
+			None
+		};
+		let prior = Self::locks(&who).unwrap_or_default();
+		AccountGovernance {
+			votes,
+			delegation,
+			prior_locked: prior.locked(),
+			prior_lock_expiry: prior.expiry(),
+		}
+	}
+
+	/// Get the conviction locks of `who`: the lock each of their votes in ongoing referenda will
+	/// leave should it win, the locks left by past votes and delegations, and the lock of their
+	/// current delegation, if any.
+	///
+	/// The locks left by referenda already tallied are merged into the prior lock, so they come
+	/// without a referendum index.
+	pub fn account_locks(
+		who: T::AccountId,
+	) -> Vec<(Option<ReferendumIndex>, BalanceOf<T, I>, UnlockAt<T::BlockNumber>)> {
+		let balance = T::Currency::total_balance(&who);
+		let enactment_period = T::EnactmentPeriod::get();
+		let mut locks = Self::voting(&who).into_iter()
+			.filter_map(|index| {
+				let end = Self::referendum_info(index)?.end;
+				let vote = Self::vote_of((index, who.clone()));
+				let lock_periods = Self::conviction_params(vote.conviction).lock_periods;
+				if lock_periods.is_zero() {
+					return None;
+				}
+				let until = end + enactment_period * lock_periods.into();
+				Some((Some(index), balance, UnlockAt::IfWon(until)))
+			})
+			.collect::<Vec<_>>();
+		let now = <frame_system::Module<T>>::block_number();
+		let prior = Self::locks(&who).unwrap_or_default();
+		locks.extend(prior.0.into_iter()
+			.filter(|&(until, _)| now < until)
+			.map(|(until, amount)| (None, amount, UnlockAt::Block(until)))
+		);
+		if Self::is_delegating(&who) {
+			locks.push((None, Self::delegation_lock(&who).min(balance), UnlockAt::WhileDelegating));
+		}
+		locks
+	}
+
+	/// Check the invariants of the democracy state, returning a description of the first one
+	/// found broken:
+	///
+	/// - Every account has at least the proposal, preimage and preimage upload deposits it made
+	///   reserved, and no public proposal has more than `MaxDeposits` deposits.
+	/// - Every chunk of a preimage upload is kept.
+	/// - Proposal and referendum indices are below `PublicPropCount` and `ReferendumCount`, and
+	///   every ongoing referendum is at or above `LowestUnbaked`.
+	/// - Every direct vote is in an ongoing referendum, and is indexed in both `VotersFor` and
+	///   `Voting`.
+	/// - Every delegation in `Delegations`, `SplitDelegations` and `ClassDelegations` is indexed
+	///   in `Delegators`, and every account indexed there delegates to the account it is
+	///   indexed under.
+	/// - Every entry of `Locks` still locks something.
+	/// - The dispatch queue holds at most `MaxQueued` items, sorted by block number.
+	#[cfg(any(test, feature = "try-state"))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		use sp_std::collections::btree_map::BTreeMap;
+
+		let mut deposits = BTreeMap::<T::AccountId, BalanceOf<T, I>>::new();
+		let props = Self::public_props();
+		ensure!(props.len() as u32 <= T::MaxProposals::get(), "Too many public proposals");
+		for (index, _, _) in props.iter() {
+			ensure!(*index < Self::public_prop_count(), "Public proposal index out of range");
+			let (deposit, backers) = Self::deposit_of(index)
+				.ok_or("Public proposal without deposit")?;
+			ensure!(backers.len() as u32 <= T::MaxDeposits::get(), "Too many proposal deposits");
+			for backer in backers {
+				let total = deposits.entry(backer).or_insert_with(Zero::zero);
+				*total = total.saturating_add(deposit);
+			}
+		}
+		for (index, (deposit, backers)) in <ReferendumDeposits<T, I>>::iter() {
+			ensure!(Self::is_active_referendum(index), "Deposits held for a referendum not ongoing");
+			for backer in backers {
+				let total = deposits.entry(backer).or_insert_with(Zero::zero);
+				*total = total.saturating_add(deposit);
+			}
+		}
+		for (_, (_, who, deposit, _)) in <Preimages<T, I>>::iter() {
+			let total = deposits.entry(who).or_insert_with(Zero::zero);
+			*total = total.saturating_add(deposit);
+		}
+		for (hash, upload) in <PreimageUploads<T, I>>::iter() {
+			for index in 0..upload.chunks {
+				ensure!(
+					<PreimageChunks<T, I>>::contains_key((hash, index)),
+					"Preimage chunk missing",
+				);
+			}
+			let total = deposits.entry(upload.who).or_insert_with(Zero::zero);
+			*total = total.saturating_add(upload.deposit);
+		}
+		for ((index, who), (deposit, _)) in <VoteDeposits<T, I>>::iter() {
+			ensure!(
+				!Self::is_ongoing_referendum(index) ||
+					<VoteOf<T, I>>::contains_key((index, &who)) ||
+					<BallotOf<T, I>>::contains_key((index, &who)) ||
+					Self::abstainers(index).contains(&who),
+				"Vote deposit without a vote",
+			);
+			let total = deposits.entry(who).or_insert_with(Zero::zero);
+			*total = total.saturating_add(deposit);
+		}
+		for (who, deposit) in deposits {
+			ensure!(T::Currency::reserved_balance(&who) >= deposit, "Deposit not reserved");
+		}
+
+		let count = Self::referendum_count();
+		ensure!(Self::lowest_unbaked() <= count, "`LowestUnbaked` beyond `ReferendumCount`");
+		for (index, _) in <ReferendumInfoOf<T, I>>::iter() {
+			ensure!(index < count, "Referendum index out of range");
+			ensure!(index >= Self::lowest_unbaked(), "Ongoing referendum below `LowestUnbaked`");
+		}
+		for (index, _) in <MultiReferendumInfoOf<T, I>>::iter() {
+			ensure!(index < count, "Referendum index out of range");
+			ensure!(index >= Self::lowest_unbaked(), "Ongoing referendum below `LowestUnbaked`");
+		}
+
+		for ((index, who), _) in <VoteOf<T, I>>::iter() {
+			ensure!(Self::is_active_referendum(index), "Vote in a referendum not ongoing");
+			ensure!(Self::voters_for(index).contains(&who), "Vote missing from `VotersFor`");
+			ensure!(Self::voting(&who).binary_search(&index).is_ok(), "Vote missing from `Voting`");
+		}
+		for ((index, who), _) in <BalanceSnapshots<T, I>>::iter() {
+			ensure!(<VoteOf<T, I>>::contains_key((index, &who)), "Balance snapshot without a vote");
+		}
+		for (who, indices) in <Voting<T, I>>::iter() {
+			ensure!(indices.windows(2).all(|w| w[0] < w[1]), "`Voting` entry not ordered");
+			for index in indices {
+				ensure!(
+					<VoteOf<T, I>>::contains_key((index, &who)) ||
+						<BallotOf<T, I>>::contains_key((index, &who)) ||
+						Self::abstainers(index).contains(&who),
+					"`Voting` entry without a vote",
+				);
+			}
+		}
+		for (who, (to, _)) in <Delegations<T, I>>::iter() {
+			ensure!(Self::delegators(&to).contains(&who), "Delegation missing from `Delegators`");
+		}
+		for (who, (targets, _)) in <SplitDelegations<T, I>>::iter() {
+			for (to, _) in targets {
+				ensure!(
+					Self::delegators(&to).contains(&who),
+					"Delegation missing from `Delegators`",
+				);
+			}
+		}
+		for ((who, _), (to, _)) in <ClassDelegations<T, I>>::iter() {
+			ensure!(Self::delegators(&to).contains(&who), "Delegation missing from `Delegators`");
+		}
+		for (to, delegators) in <Delegators<T, I>>::iter() {
+			for who in delegators {
+				ensure!(Self::delegates_to(&who, &to), "`Delegators` entry without a delegation");
+			}
+		}
+		for (_, prior) in <Locks<T, I>>::iter() {
+			ensure!(!prior.is_unlocked(), "`Locks` entry locking nothing");
+		}
+
+		let queue = Self::dispatch_queue();
+		ensure!(queue.len() as u32 <= T::MaxQueued::get(), "Dispatch queue too long");
+		ensure!(queue.windows(2).all(|w| w[0].0 <= w[1].0), "Dispatch queue not sorted");
+		Ok(())
+	}
+
+	// Exposed mutables.
 
 	#[cfg(feature = "std")]
 	pub fn force_proxy(stash: T::AccountId, proxy: T::AccountId) {
-		Proxy::<T>::mutate(&proxy, |o| {
+		Proxy::<T, I>::mutate(&proxy, |o| {
 			if o.is_none() {
 				system::Module::<T>::inc_ref(&proxy);
 			}
@@ -1357,13 +3768,13 @@ impl<T: Trait> Module<T> {
 
 	/// Start a referendum.
 	pub fn internal_start_referendum(
-		proposal_hash: T::Hash,
+		proposal: BoundedCallOf<T>,
 		threshold: VoteThreshold,
 		delay: T::BlockNumber
 	) -> ReferendumIndex {
-		<Module<T>>::inject_referendum(
+		<Module<T, I>>::inject_referendum(
 			<frame_system::Module<T>>::block_number() + T::VotingPeriod::get(),
-			proposal_hash,
+			proposal,
 			threshold,
 			delay
 		)
@@ -1372,211 +3783,827 @@ impl<T: Trait> Module<T> {
 	/// Remove a referendum.
 	pub fn internal_cancel_referendum(ref_index: ReferendumIndex) {
 		Self::deposit_event(RawEvent::Cancelled(ref_index));
-		<Module<T>>::clear_referendum(ref_index);
+		<Module<T, I>>::clear_referendum(ref_index);
+		T::ReferendumHooks::on_cancelled(ref_index);
 	}
 
 	// private.
 
 	/// Actually enact a vote, if legit.
 	fn do_vote(who: T::AccountId, ref_index: ReferendumIndex, vote: Vote) -> DispatchResult {
-		ensure!(Self::is_active_referendum(ref_index), Error::<T>::ReferendumInvalid);
-		if !<VoteOf<T>>::contains_key((ref_index, &who)) {
-			<VotersFor<T>>::append_or_insert(ref_index, &[&who][..]);
+		let info = Self::referendum_info(ref_index).ok_or(Error::<T, I>::ReferendumInvalid)?;
+		if !<VoteOf<T, I>>::contains_key((ref_index, &who)) {
+			// An abstention turns into the vote, keeping its deposit and its place in `Voting`.
+			if Self::abstainers(ref_index).contains(&who) {
+				<Abstainers<T, I>>::mutate(ref_index, |abstainers| abstainers.retain(|a| a != &who));
+			} else {
+				Self::reserve_vote_deposit(&who, ref_index, info.end)?;
+			}
+			if T::SnapshotBalances::get() {
+				let balance = T::Currency::total_balance(&who);
+				<BalanceSnapshots<T, I>>::insert((ref_index, &who), balance);
+			}
+			<VotersFor<T, I>>::append_or_insert(ref_index, &[&who][..]);
+			Self::index_vote(&who, ref_index);
 		}
-		<VoteOf<T>>::insert((ref_index, &who), vote);
+		let now = <frame_system::Module<T>>::block_number();
+		<VoteOf<T, I>>::insert((ref_index, &who), vote);
+		<VotedAt<T, I>>::insert((ref_index, &who), now);
+		if T::IndexVotes::get() {
+			let balance = T::Currency::total_balance(&who);
+			let record = VoteRecord { vote, balance, at: now };
+			sp_io::offchain_index::set(&Self::vote_index_key(ref_index, &who), &record.encode());
+		}
+		T::OnVote::on_vote(&who, ref_index);
+		Self::deposit_event(RawEvent::Voted(who, ref_index, vote));
 		Ok(())
 	}
 
-	/// Start a referendum
-	fn inject_referendum(
-		end: T::BlockNumber,
-		proposal_hash: T::Hash,
-		threshold: VoteThreshold,
-		delay: T::BlockNumber,
-	) -> ReferendumIndex {
-		let ref_index = Self::referendum_count();
-		ReferendumCount::put(ref_index + 1);
-		let item = ReferendumInfo { end, proposal_hash, threshold, delay };
-		<ReferendumInfoOf<T>>::insert(ref_index, item);
-		Self::deposit_event(RawEvent::Started(ref_index, threshold));
-		ref_index
+	/// Actually remove a vote, or the ballot in a multi-option referendum, if there is one.
+	fn do_remove_vote(who: T::AccountId, ref_index: ReferendumIndex) -> DispatchResult {
+		ensure!(Self::is_ongoing_referendum(ref_index), Error::<T, I>::ReferendumInvalid);
+		if <MultiReferendumInfoOf<T, I>>::contains_key(ref_index) {
+			ensure!(<BallotOf<T, I>>::contains_key((ref_index, &who)), Error::<T, I>::NotVoter);
+			<BallotOf<T, I>>::remove((ref_index, &who));
+		} else if !<VoteOf<T, I>>::contains_key((ref_index, &who)) {
+			let mut abstainers = Self::abstainers(ref_index);
+			let position = abstainers.iter().position(|a| a == &who)
+				.ok_or(Error::<T, I>::NotVoter)?;
+			abstainers.swap_remove(position);
+			<Abstainers<T, I>>::insert(ref_index, abstainers);
+			if let Some((deposit, _)) = <VoteDeposits<T, I>>::take((ref_index, &who)) {
+				T::Currency::unreserve(&who, deposit);
+			}
+			Self::unindex_vote(&who, ref_index);
+			T::OnVote::on_remove_vote(&who, ref_index);
+			Self::deposit_event(RawEvent::VoteRemoved(who, ref_index));
+			return Ok(());
+		}
+		<VoteOf<T, I>>::remove((ref_index, &who));
+		<VotedAt<T, I>>::remove((ref_index, &who));
+		<BalanceSnapshots<T, I>>::remove((ref_index, &who));
+		if T::IndexVotes::get() {
+			sp_io::offchain_index::clear(&Self::vote_index_key(ref_index, &who));
+		}
+		if let Some((deposit, _)) = <VoteDeposits<T, I>>::take((ref_index, &who)) {
+			T::Currency::unreserve(&who, deposit);
+		}
+		<VotersFor<T, I>>::mutate(ref_index, |voters| voters.retain(|v| v != &who));
+		Self::unindex_vote(&who, ref_index);
+		T::OnVote::on_remove_vote(&who, ref_index);
+		Self::deposit_event(RawEvent::VoteRemoved(who, ref_index));
+		Ok(())
 	}
 
-	/// Remove all info on a referendum.
-	fn clear_referendum(ref_index: ReferendumIndex) {
-		<ReferendumInfoOf<T>>::remove(ref_index);
-
-		LowestUnbaked::mutate(|i| if *i == ref_index {
-			*i += 1;
-			let end = ReferendumCount::get();
-			while !Self::is_active_referendum(*i) && *i < end {
-				*i += 1;
-			}
-		});
-		<VotersFor<T>>::remove(ref_index);
-		for v in Self::voters_for(ref_index) {
-			<VoteOf<T>>::remove((ref_index, v));
+	/// Delegate the vote of `who` to `to`.
+	fn do_delegate(who: T::AccountId, to: T::AccountId, conviction: Conviction) -> DispatchResult {
+		Self::ensure_delegator_room(&who, &to)?;
+		Self::clear_direct_votes(&who, None)?;
+		let previous = <SplitDelegations<T, I>>::take(&who)
+			.map_or_else(Vec::new, |(targets, _)| targets.into_iter().map(|(d, _)| d).collect())
+			.into_iter()
+			.chain(Self::general_delegate(&who))
+			.collect::<Vec<_>>();
+		<Delegations<T, I>>::insert(&who, (&to, conviction));
+		Self::index_delegator(&who, &to);
+		for previous in previous {
+			Self::unindex_delegator(&who, &previous);
 		}
+		// Currency is locked indefinitely as long as it's delegated.
+		T::Currency::extend_lock(
+			T::LockId::get(),
+			&who,
+			Bounded::max_value(),
+			WithdrawReason::Transfer.into()
+		);
+		T::OnDelegation::on_delegate(&who, &to, None);
+		Self::deposit_event(RawEvent::Delegated(who, to));
+		Ok(())
 	}
 
-	/// Enact a proposal from a referendum.
-	fn enact_proposal(proposal_hash: T::Hash, index: ReferendumIndex) -> DispatchResult {
-		if let Some((encoded_proposal, who, amount, _)) = <Preimages<T>>::take(&proposal_hash) {
-			if let Ok(proposal) = T::Proposal::decode(&mut &encoded_proposal[..]) {
-				let _ = T::Currency::unreserve(&who, amount);
-				Self::deposit_event(RawEvent::PreimageUsed(proposal_hash, who, amount));
+	/// Delegate the vote of `who` to each of `targets`, in proportion.
+	fn do_delegate_split(
+		who: T::AccountId,
+		targets: Vec<(T::AccountId, Perbill)>,
+		conviction: Conviction,
+	) -> DispatchResult {
+		ensure!(
+			!targets.is_empty() && targets.len() as u32 <= T::MaxDelegationTargets::get(),
+			Error::<T, I>::InvalidSplit,
+		);
+		let mut accounts = targets.iter().map(|(to, _)| to).collect::<Vec<_>>();
+		accounts.sort();
+		accounts.dedup();
+		ensure!(accounts.len() == targets.len(), Error::<T, I>::InvalidSplit);
+		let total = targets.iter().map(|(_, p)| p.deconstruct() as u64).sum::<u64>();
+		ensure!(total <= Perbill::one().deconstruct() as u64, Error::<T, I>::InvalidSplit);
+		for (to, _) in targets.iter() {
+			Self::ensure_delegator_room(&who, to)?;
+		}
 
-				let ok = proposal.dispatch(frame_system::RawOrigin::Root.into()).is_ok();
-				Self::deposit_event(RawEvent::Executed(index, ok));
+		Self::clear_direct_votes(&who, None)?;
+		let previous = <SplitDelegations<T, I>>::get(&who)
+			.map_or_else(Vec::new, |(targets, _)| targets.into_iter().map(|(d, _)| d).collect())
+			.into_iter()
+			.chain(Self::general_delegate(&who))
+			.collect::<Vec<_>>();
+		<Delegations<T, I>>::remove(&who);
+		<SplitDelegations<T, I>>::insert(&who, (&targets, conviction));
+		for (to, _) in targets.iter() {
+			Self::index_delegator(&who, to);
+		}
+		for previous in previous {
+			Self::unindex_delegator(&who, &previous);
+		}
+		T::Currency::extend_lock(
+			T::LockId::get(),
+			&who,
+			Self::delegation_lock(&who),
+			WithdrawReason::Transfer.into()
+		);
+		for (to, _) in targets {
+			T::OnDelegation::on_delegate(&who, &to, None);
+			Self::deposit_event(RawEvent::Delegated(who.clone(), to));
+		}
+		Ok(())
+	}
 
-				Ok(())
-			} else {
-				T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
-				Self::deposit_event(RawEvent::PreimageInvalid(proposal_hash, index));
-				Err(Error::<T>::PreimageInvalid.into())
-			}
-		} else {
-			Self::deposit_event(RawEvent::PreimageMissing(proposal_hash, index));
-			Err(Error::<T>::PreimageMissing.into())
+	/// Make way for `who` to delegate in the referenda of `class`, or in all of them if `None`:
+	/// remove their direct votes there if `RemoveVotesOnDelegation` is set, or else fail if there
+	/// are any.
+	fn clear_direct_votes(who: &T::AccountId, class: Option<VoteThreshold>) -> DispatchResult {
+		let covered = Self::voting(who).into_iter()
+			.filter(|index| class.map_or(true, |class|
+				Self::referendum_info(index).map_or(false, |info| info.threshold == class)
+			))
+			.collect::<Vec<_>>();
+		if covered.is_empty() {
+			return Ok(());
+		}
+		ensure!(T::RemoveVotesOnDelegation::get(), Error::<T, I>::VotesExist);
+		for index in covered {
+			Self::do_remove_vote(who.clone(), index)?;
 		}
+		Ok(())
 	}
 
-	/// Table the next waiting proposal for a vote.
-	fn launch_next(now: T::BlockNumber) -> DispatchResult {
-		if LastTabledWasExternal::take() {
-			Self::launch_public(now).or_else(|_| Self::launch_external(now))
+	/// Undelegate the vote of `who`, leaving behind the lock its conviction implies.
+	///
+	/// Returns the total number of delegators of the accounts `who` delegated to.
+	fn do_undelegate(who: T::AccountId) -> Result<u32, DispatchError> {
+		let (targets, conviction) = if <Delegations<T, I>>::contains_key(&who) {
+			let (to, conviction) = <Delegations<T, I>>::take(&who);
+			(vec![(to, Perbill::one())], conviction)
 		} else {
-			Self::launch_external(now).or_else(|_| Self::launch_public(now))
-		}.map_err(|_| Error::<T>::NoneWaiting.into())
+			<SplitDelegations<T, I>>::take(&who).ok_or(Error::<T, I>::NotDelegated)?
+		};
+		let mut delegators = 0u32;
+		for (to, _) in targets.iter() {
+			delegators = delegators.saturating_add(Self::delegators(to).len() as u32);
+			Self::unindex_delegator(&who, to);
+		}
+		let proportion = Self::split_proportion(&targets);
+		Self::release_delegation_lock(&who, conviction, proportion);
+		T::OnDelegation::on_undelegate(&who, None);
+		Self::deposit_event(RawEvent::Undelegated(who));
+		Ok(delegators)
 	}
 
-	/// Table the waiting external proposal for a vote, if there is one.
-	fn launch_external(now: T::BlockNumber) -> DispatchResult {
-		if let Some((proposal, threshold)) = <NextExternal<T>>::take() {
-			LastTabledWasExternal::put(true);
-			Self::deposit_event(RawEvent::ExternalTabled);
-			Self::inject_referendum(
-				now + T::VotingPeriod::get(),
-				proposal,
-				threshold,
-				T::EnactmentPeriod::get(),
+	/// Give back to the block the weight `undelegate` was charged for `MaxDelegationTargets`
+	/// accounts with `MaxDelegators` delegators each, beyond that of the `delegators` which were
+	/// actually scanned.
+	fn refund_undelegate_weight(delegators: u32) {
+		Self::refund_weight(
+			weight_for::undelegate(
+				T::MaxDelegationTargets::get() as Weight,
+				T::MaxDelegators::get() as Weight,
+			),
+			weight_for::undelegate(1, delegators as Weight),
+		);
+	}
+
+	/// Give back to the block the part of the weight `charged` for a call which the path it took
+	/// did not use. The fee paid for `charged` is not refunded.
+	fn refund_weight(charged: Weight, used: Weight) {
+		<frame_system::Module<T>>::refund_weight_unchecked(charged.saturating_sub(used));
+	}
+
+	/// The proportion of their balance delegated by an account splitting it across `targets`.
+	fn split_proportion(targets: &[(T::AccountId, Perbill)]) -> Perbill {
+		targets.iter().fold(Perbill::zero(), |total, (_, p)| total.saturating_add(*p))
+	}
+
+	/// Reduce the indefinite lock of `who`, who has just stopped delegating `proportion` of their
+	/// balance with `conviction`, to the maximum voting lock that could be possible; unless they
+	/// still delegate elsewhere.
+	fn release_delegation_lock(who: &T::AccountId, conviction: Conviction, proportion: Perbill) {
+		let now = <frame_system::Module<T>>::block_number();
+		let lock_periods = Self::conviction_params(conviction).lock_periods;
+		let locked_until = now + T::EnactmentPeriod::get() * lock_periods.into();
+		let balance = proportion * T::Currency::total_balance(who);
+		let locked = Self::accumulate_prior_lock(who, locked_until, balance);
+		if !Self::is_delegating(who) {
+			T::Currency::set_lock(
+				T::LockId::get(),
+				who,
+				locked,
+				WithdrawReason::Transfer.into(),
 			);
-			Ok(())
+		}
+	}
+
+	/// The account to which `who` delegates with `delegate`, if any.
+	fn general_delegate(who: &T::AccountId) -> Option<T::AccountId> {
+		if <Delegations<T, I>>::contains_key(who) {
+			Some(Self::delegations(who).0)
 		} else {
-			Err(Error::<T>::NoneWaiting)?
+			None
 		}
 	}
 
-	/// Table the waiting public proposal with the highest backing for a vote.
-	fn launch_public(now: T::BlockNumber) -> DispatchResult {
-		let mut public_props = Self::public_props();
-		if let Some((winner_index, _)) = public_props.iter()
-			.enumerate()
-			.max_by_key(|x| Self::locked_for((x.1).0).unwrap_or_else(Zero::zero)
-				/* ^^ defensive only: All current public proposals have an amount locked*/)
-		{
-			let (prop_index, proposal, _) = public_props.swap_remove(winner_index);
-			<PublicProps<T>>::put(public_props);
+	/// Whether any delegation of `who` goes to `to`.
+	fn delegates_to(who: &T::AccountId, to: &T::AccountId) -> bool {
+		Self::general_delegate(who).as_ref() == Some(to) ||
+			Self::split_delegations(who).map_or(false, |(targets, _)|
+				targets.iter().any(|(delegate, _)| delegate == to)
+			) || [
+			VoteThreshold::SuperMajorityApprove,
+			VoteThreshold::SuperMajorityAgainst,
+			VoteThreshold::SimpleMajority,
+		].iter().any(|class|
+			Self::class_delegations((who, class)).map_or(false, |(delegate, _)| &delegate == to)
+		)
+	}
 
-			if let Some((deposit, depositors)) = <DepositOf<T>>::take(prop_index) {
-				// refund depositors
-				for d in &depositors {
-					T::Currency::unreserve(d, deposit);
+	/// Ensure that `who` may delegate to `to` without `to` having more than `MaxDelegators`
+	/// delegators.
+	fn ensure_delegator_room(who: &T::AccountId, to: &T::AccountId) -> DispatchResult {
+		let delegators = Self::delegators(to);
+		ensure!(
+			delegators.contains(who) || (delegators.len() as u32) < T::MaxDelegators::get(),
+			Error::<T, I>::TooManyDelegators,
+		);
+		Ok(())
+	}
+
+	/// Record in `Delegators` that `who` delegates to `to`.
+	fn index_delegator(who: &T::AccountId, to: &T::AccountId) {
+		<Delegators<T, I>>::mutate(to, |delegators| if !delegators.contains(who) {
+			delegators.push(who.clone());
+		});
+	}
+
+	/// Remove `who` from the delegators of `to` in `Delegators`, unless one of its delegations
+	/// still goes to them.
+	fn unindex_delegator(who: &T::AccountId, to: &T::AccountId) {
+		if Self::delegates_to(who, to) {
+			return;
+		}
+		<Delegators<T, I>>::mutate_exists(to, |maybe_delegators| {
+			if let Some(delegators) = maybe_delegators {
+				delegators.retain(|d| d != who);
+				if delegators.is_empty() {
+					*maybe_delegators = None;
 				}
-				Self::deposit_event(RawEvent::Tabled(prop_index, deposit, depositors));
-				Self::inject_referendum(
-					now + T::VotingPeriod::get(),
-					proposal,
-					VoteThreshold::SuperMajorityApprove,
-					T::EnactmentPeriod::get(),
-				);
 			}
-			Ok(())
-		} else {
-			Err(Error::<T>::NoneWaiting)?
-		}
+		});
+	}
 
+	/// Whether `who` delegates their vote, in any class of referenda.
+	fn is_delegating(who: &T::AccountId) -> bool {
+		Self::is_delegating_whole(who) || <SplitDelegations<T, I>>::contains_key(who)
 	}
 
-	fn bake_referendum(
-		now: T::BlockNumber,
-		index: ReferendumIndex,
-		info: ReferendumInfo<T::BlockNumber, T::Hash>
-	) -> DispatchResult {
-		let (approve, against, capital) = Self::tally(index);
-		let total_issuance = T::Currency::total_issuance();
-		let approved = info.threshold.approved(approve, against, capital, total_issuance);
-		let enactment_period = T::EnactmentPeriod::get();
+	/// Whether `who` delegates the whole of their vote, in any class of referenda.
+	fn is_delegating_whole(who: &T::AccountId) -> bool {
+		<Delegations<T, I>>::contains_key(who) || [
+			VoteThreshold::SuperMajorityApprove,
+			VoteThreshold::SuperMajorityAgainst,
+			VoteThreshold::SimpleMajority,
+		].iter().any(|class| <ClassDelegations<T, I>>::contains_key((who, class)))
+	}
 
-		// Logic defined in https://www.slideshare.net/gavofyork/governance-in-polkadot-poc3
-		// Essentially, we extend the lock-period of the coins behind the winning votes to be the
-		// vote strength times the public delay period from now.
-		for (a, lock_periods) in Self::voters_for(index).into_iter()
-			.map(|a| (a.clone(), Self::vote_of((index, a))))
-			// ^^^ defensive only: all items come from `voters`; for an item to be in `voters`
-			// there must be a vote registered; qed
-			.filter(|&(_, vote)| vote.aye == approved)  // Just the winning coins
-			.map(|(a, vote)| (a, vote.conviction.lock_periods()))
-			.filter(|&(_, lock_periods)| !lock_periods.is_zero()) // Just the lock votes
-		{
-			// now plus: the base lock period multiplied by the number of periods this voter
-			// offered to lock should they win...
-			let locked_until = now + enactment_period * lock_periods.into();
-			Locks::<T>::insert(&a, locked_until);
-			// ...extend their bondage until at least then.
-			T::Currency::extend_lock(
-				DEMOCRACY_ID,
-				&a,
-				Bounded::max_value(),
-				WithdrawReason::Transfer.into()
-			);
+	/// The amount of the balance of `who` locked by their delegations: all of it, unless they
+	/// only delegate part of it with `delegate_split`.
+	fn delegation_lock(who: &T::AccountId) -> BalanceOf<T, I> {
+		match <SplitDelegations<T, I>>::get(who) {
+			Some((targets, _)) if !Self::is_delegating_whole(who) => {
+				let proportion = Self::split_proportion(&targets);
+				if proportion == Perbill::one() {
+					Bounded::max_value()
+				} else {
+					proportion * T::Currency::total_balance(who)
+				}
+			}
+			_ => Bounded::max_value(),
 		}
+	}
 
-		Self::clear_referendum(index);
+	/// Fold a lock of `amount` until `until` into the prior lock of `who`, returning the amount
+	/// that must now be locked.
+	fn accumulate_prior_lock(
+		who: &T::AccountId,
+		until: T::BlockNumber,
+		amount: BalanceOf<T, I>,
+	) -> BalanceOf<T, I> {
+		Locks::<T, I>::mutate(who, |prior| {
+			let prior = prior.get_or_insert_with(Default::default);
+			prior.accumulate(until, amount);
+			prior.locked()
+		})
+	}
 
-		if approved {
-			Self::deposit_event(RawEvent::Passed(index));
-			if info.delay.is_zero() {
-				let _ = Self::enact_proposal(info.proposal_hash, index);
-			} else {
-				let item = (now + info.delay,info.proposal_hash, index);
-				<DispatchQueue<T>>::mutate(|queue| {
-					let pos = queue.binary_search_by_key(&item.0, |x| x.0).unwrap_or_else(|e| e);
-					queue.insert(pos, item);
-				});
+	/// Rejig the balance lock of `who` according to what still requires it, returning the amount
+	/// now locked.
+	///
+	/// Votes in ongoing referenda lock nothing until they are tallied, at which point the winning
+	/// side's locks are folded into the prior lock.
+	fn update_lock(who: &T::AccountId) -> BalanceOf<T, I> {
+		let now = <frame_system::Module<T>>::block_number();
+		let prior = Locks::<T, I>::mutate_exists(who, |maybe_prior| {
+			let mut prior = maybe_prior.take().unwrap_or_default();
+			prior.rejig(now);
+			let locked = prior.locked();
+			if !prior.is_unlocked() {
+				*maybe_prior = Some(prior);
 			}
+			locked
+		});
+
+		let locked = if Self::is_delegating(who) {
+			// Currency is locked indefinitely as long as it's delegated.
+			Self::delegation_lock(who).max(prior)
 		} else {
-			Self::deposit_event(RawEvent::NotPassed(index));
+			prior
+		};
+		if locked.is_zero() {
+			T::Currency::remove_lock(T::LockId::get(), who);
+		} else {
+			T::Currency::set_lock(T::LockId::get(), who, locked, WithdrawReason::Transfer.into());
 		}
+		locked
+	}
 
-		Ok(())
+	/// Remove the upload of the preimage `proposal_hash`, along with its `chunks` chunks.
+	fn clear_preimage_upload(proposal_hash: &T::Hash, chunks: u32) {
+		for index in 0..chunks {
+			<PreimageChunks<T, I>>::remove((*proposal_hash, index));
+		}
+		<PreimageUploads<T, I>>::remove(proposal_hash);
 	}
 
-	/// Current era is ending; we should finish up any proposals.
-	fn begin_block(now: T::BlockNumber) -> DispatchResult {
-		// pick out another public referendum if it's time.
-		if (now % T::LaunchPeriod::get()).is_zero() {
+	/// Actually veto and blacklist the external proposal `proposal_hash` on behalf of `who`.
+	///
+	/// Returns the number of accounts that had already vetoed it.
+	fn do_veto_external(
+		who: T::AccountId,
+		proposal_hash: T::Hash,
+	) -> Result<u32, DispatchError> {
+		if let Some((e_proposal, _)) = <NextExternal<T, I>>::get() {
+			ensure!(
+				proposal_hash == Self::proposal_hash(&e_proposal),
+				Error::<T, I>::ProposalMissing,
+			);
+		} else {
+			Err(Error::<T, I>::NoProposal)?;
+		}
+
+		let mut existing_vetoers = <Blacklist<T, I>>::get(&proposal_hash)
+			.map(|pair| pair.1)
+			.unwrap_or_else(Vec::new);
+		let insert_position = existing_vetoers.binary_search(&who)
+			.err().ok_or(Error::<T, I>::AlreadyVetoed)?;
+		ensure!(
+			(existing_vetoers.len() as u32) < T::MaxVetoers::get(),
+			Error::<T, I>::TooManyVetoers,
+		);
+
+		let vetoers = existing_vetoers.len() as u32;
+		existing_vetoers.insert(insert_position, who.clone());
+		let until = <frame_system::Module<T>>::block_number() + T::CooloffPeriod::get();
+		<Blacklist<T, I>>::insert(&proposal_hash, (until, existing_vetoers));
+
+		Self::deposit_event(RawEvent::Vetoed(who, proposal_hash, until));
+		<NextExternal<T, I>>::kill();
+		<MetadataOf<T, I>>::remove(MetadataOwner::External);
+		Ok(vetoers)
+	}
+
+	/// Ensure that `proposal_hash` may be scheduled as the next external proposal. A scheduled
+	/// `external_propose` proposal is replaceable only if `may_replace` is set; others never are.
+	fn ensure_external_replaceable(proposal_hash: &T::Hash, may_replace: bool) -> DispatchResult {
+		if let Some((proposal, threshold)) = <NextExternal<T, I>>::get() {
+			if may_replace && threshold == VoteThreshold::SuperMajorityApprove {
+				return Ok(())
+			}
+			ensure!(
+				Self::proposal_hash(&proposal) != *proposal_hash,
+				Error::<T, I>::DuplicateProposal,
+			);
+			Err(Error::<T, I>::ProposalAlreadyQueued)?;
+		}
+		Ok(())
+	}
+
+	/// Schedule `proposal_hash` to be tabled as the next external referendum.
+	fn note_external(proposal_hash: T::Hash, threshold: VoteThreshold) {
+		<NextExternal<T, I>>::put((Self::bound_hash(proposal_hash), threshold));
+		<MetadataOf<T, I>>::remove(MetadataOwner::External);
+		Self::deposit_event(RawEvent::ExternalProposed(proposal_hash, threshold));
+	}
+
+	/// Start a referendum
+	fn inject_referendum(
+		end: T::BlockNumber,
+		proposal: BoundedCallOf<T>,
+		threshold: VoteThreshold,
+		delay: T::BlockNumber,
+	) -> ReferendumIndex {
+		let ref_index = Self::referendum_count();
+		ReferendumCount::<I>::put(ref_index + 1);
+		let item = ReferendumInfo { end, proposal, threshold, delay };
+		<ReferendumInfoOf<T, I>>::insert(ref_index, item);
+		Self::deposit_event(RawEvent::Started(ref_index, threshold));
+		T::ReferendumHooks::on_started(ref_index);
+		ref_index
+	}
+
+	/// Start a multi-option referendum.
+	fn inject_multi_referendum(
+		end: T::BlockNumber,
+		options: Vec<BoundedCallOf<T>>,
+		rule: TallyRule,
+		delay: T::BlockNumber,
+	) -> ReferendumIndex {
+		let ref_index = Self::referendum_count();
+		ReferendumCount::<I>::put(ref_index + 1);
+		let item = MultiReferendumInfo { end, options, rule, delay };
+		<MultiReferendumInfoOf<T, I>>::insert(ref_index, item);
+		<MultiReferenda<I>>::append_or_put(&[ref_index][..]);
+		Self::deposit_event(RawEvent::MultiStarted(ref_index, rule));
+		T::ReferendumHooks::on_started(ref_index);
+		ref_index
+	}
+
+	/// Remove all info on a referendum.
+	fn clear_referendum(ref_index: ReferendumIndex) {
+		<ReferendumInfoOf<T, I>>::remove(ref_index);
+		if let Some((deposit, depositors)) = <ReferendumDeposits<T, I>>::take(ref_index) {
+			for d in &depositors {
+				T::Currency::unreserve(d, deposit);
+			}
+			Self::deposit_event(RawEvent::DepositsRefunded(ref_index));
+		}
+		<MetadataOf<T, I>>::remove(MetadataOwner::Referendum(ref_index));
+		for a in <Abstainers<T, I>>::take(ref_index) {
+			Self::unindex_vote(&a, ref_index);
+		}
+		if <MultiReferendumInfoOf<T, I>>::take(ref_index).is_some() {
+			<MultiReferenda<I>>::mutate(|r| r.retain(|i| *i != ref_index));
+			for v in Self::voters_for(ref_index) {
+				<BallotOf<T, I>>::remove((ref_index, v));
+			}
+		}
+
+		LowestUnbaked::<I>::mutate(|i| if *i == ref_index {
+			*i += 1;
+			let end = ReferendumCount::<I>::get();
+			while !Self::is_ongoing_referendum(*i) && *i < end {
+				*i += 1;
+			}
+		});
+		for v in <VotersFor<T, I>>::take(ref_index) {
+			<VoteOf<T, I>>::remove((ref_index, &v));
+			<VotedAt<T, I>>::remove((ref_index, &v));
+			<BalanceSnapshots<T, I>>::remove((ref_index, &v));
+			Self::unindex_vote(&v, ref_index);
+		}
+	}
+
+	/// Slash the deposits held for the referendum `ref_index`, if any.
+	fn slash_referendum_deposits(ref_index: ReferendumIndex) {
+		if let Some((deposit, depositors)) = <ReferendumDeposits<T, I>>::take(ref_index) {
+			for d in &depositors {
+				T::Slash::on_unbalanced(T::Currency::slash_reserved(d, deposit).0);
+			}
+			Self::deposit_event(RawEvent::DepositsSlashed(ref_index));
+		}
+	}
+
+	/// Check that `who` may take part directly in one more referendum, `ref_index`, which ends at
+	/// `end`, and reserve the deposit of doing so.
+	fn reserve_vote_deposit(
+		who: &T::AccountId,
+		ref_index: ReferendumIndex,
+		end: T::BlockNumber,
+	) -> DispatchResult {
+		ensure!(
+			(Self::voting(who).len() as u32) < T::MaxVotes::get(),
+			Error::<T, I>::MaxVotesReached,
+		);
+		let deposit = T::VoteDeposit::get();
+		if !deposit.is_zero() {
+			T::Currency::reserve(who, deposit)?;
+			<VoteDeposits<T, I>>::insert((ref_index, who), (deposit, end));
+		}
+		Ok(())
+	}
+
+	/// Add `ref_index` to the referenda `who` has a direct vote in.
+	fn index_vote(who: &T::AccountId, ref_index: ReferendumIndex) {
+		<Voting<T, I>>::mutate(who, |indices|
+			if let Err(i) = indices.binary_search(&ref_index) {
+				indices.insert(i, ref_index);
+			}
+		);
+	}
+
+	/// Remove `ref_index` from the referenda `who` has a direct vote in.
+	fn unindex_vote(who: &T::AccountId, ref_index: ReferendumIndex) {
+		<Voting<T, I>>::mutate_exists(who, |maybe_indices| {
+			if let Some(indices) = maybe_indices {
+				indices.retain(|i| *i != ref_index);
+				if indices.is_empty() {
+					*maybe_indices = None;
+				}
+			}
+		});
+	}
+
+	/// Enact a proposal from a referendum.
+	fn enact_proposal(proposal: BoundedCallOf<T>, index: ReferendumIndex) -> DispatchResult {
+		let proposal_hash = match proposal.lookup_hash() {
+			Some(hash) => hash,
+			// An inline call was never noted, so there is no deposit to return or slash.
+			None => return match Self::peek::<T::Proposal>(&proposal) {
+				Ok((call, _)) => {
+					let ok = call.dispatch(frame_system::RawOrigin::Root.into()).is_ok();
+					Self::deposit_event(RawEvent::Executed(index, ok));
+					Ok(())
+				}
+				Err(_) => {
+					Self::deposit_event(RawEvent::PreimageInvalid(Self::proposal_hash(&proposal), index));
+					Err(Error::<T, I>::PreimageInvalid.into())
+				}
+			},
+		};
+		if let Some((encoded_proposal, who, amount, _)) = <Preimages<T, I>>::take(&proposal_hash) {
+			let len_ok = proposal.len().map_or(true, |len| len as usize == encoded_proposal.len());
+			let decoded = T::Proposal::decode(&mut &encoded_proposal[..]).ok().filter(|_| len_ok);
+			if let Some(proposal) = decoded {
+				let _ = T::Currency::unreserve(&who, amount);
+				Self::deposit_event(RawEvent::PreimageUsed(proposal_hash, who, amount));
+
+				let ok = proposal.dispatch(frame_system::RawOrigin::Root.into()).is_ok();
+				Self::deposit_event(RawEvent::Executed(index, ok));
+
+				Ok(())
+			} else {
+				T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
+				Self::deposit_event(RawEvent::PreimageInvalid(proposal_hash, index));
+				Err(Error::<T, I>::PreimageInvalid.into())
+			}
+		} else {
+			Self::deposit_event(RawEvent::PreimageMissing(proposal_hash, index));
+			Err(Error::<T, I>::PreimageMissing.into())
+		}
+	}
+
+	/// Table the next waiting proposal for a vote, returning the number of deposits refunded.
+	fn launch_next(now: T::BlockNumber) -> Result<u32, DispatchError> {
+		if LastTabledWasExternal::<I>::take() {
+			Self::launch_public(now).or_else(|_| Self::launch_external(now).map(|_| 0))
+		} else {
+			Self::launch_external(now).map(|_| 0).or_else(|_| Self::launch_public(now))
+		}.map_err(|_| Error::<T, I>::NoneWaiting.into())
+	}
+
+	/// Table the waiting external proposal for a vote, if there is one.
+	fn launch_external(now: T::BlockNumber) -> DispatchResult {
+		if let Some((proposal, threshold)) = <NextExternal<T, I>>::take() {
+			LastTabledWasExternal::<I>::put(true);
+			Self::deposit_event(RawEvent::ExternalTabled);
+			let ref_index = Self::inject_referendum(
+				now + T::VotingPeriod::get(),
+				proposal,
+				threshold,
+				T::EnactmentPeriod::get(),
+			);
+			Self::transfer_metadata(MetadataOwner::External, ref_index);
+			Ok(())
+		} else {
+			Err(Error::<T, I>::NoneWaiting)?
+		}
+	}
+
+	/// Table the waiting public proposal with the highest backing for a vote.
+	///
+	/// The deposits of the proposer and of every seconder are refunded as the proposal is tabled;
+	/// their number is returned.
+	fn launch_public(now: T::BlockNumber) -> Result<u32, DispatchError> {
+		let mut public_props = Self::public_props();
+		if let Some((winner_index, _)) = public_props.iter()
+			.enumerate()
+			.max_by_key(|x| Self::locked_for((x.1).0).unwrap_or_else(Zero::zero)
+				/* ^^ defensive only: All current public proposals have an amount locked*/)
+		{
+			let (prop_index, proposal, _) = public_props.swap_remove(winner_index);
+			<PublicProps<T, I>>::put(public_props);
+
+			let mut refunded = 0;
+			if let Some((deposit, depositors)) = <DepositOf<T, I>>::take(prop_index) {
+				let hold = T::HoldDeposits::get();
+				if !hold {
+					// refund depositors
+					for d in &depositors {
+						T::Currency::unreserve(d, deposit);
+					}
+				}
+				refunded = depositors.len() as u32;
+				Self::deposit_event(RawEvent::Tabled(prop_index, deposit, depositors.clone()));
+				let ref_index = Self::inject_referendum(
+					now + T::VotingPeriod::get(),
+					proposal,
+					VoteThreshold::SuperMajorityApprove,
+					T::EnactmentPeriod::get(),
+				);
+				if hold {
+					<ReferendumDeposits<T, I>>::insert(ref_index, (deposit, depositors));
+				}
+				Self::transfer_metadata(MetadataOwner::Proposal(prop_index), ref_index);
+			}
+			Ok(refunded)
+		} else {
+			Err(Error::<T, I>::NoneWaiting)?
+		}
+
+	}
+
+	/// Conclude referendum `index`, returning the number of delegations counted in its tally.
+	fn bake_referendum(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		info: ReferendumInfo<T::BlockNumber, T::Hash>
+	) -> Result<u32, DispatchError> {
+		let (tally, delegations) = Self::count_tally(index);
+		let total_issuance = T::Currency::total_issuance();
+		let approved = T::ApprovalCriterion::approved(info.threshold, tally, total_issuance);
+		let enactment_period = T::EnactmentPeriod::get();
+
+		// Logic defined in https://www.slideshare.net/gavofyork/governance-in-polkadot-poc3
+		// Essentially, we extend the lock-period of the coins behind the winning votes to be the
+		// vote strength times the public delay period from now.
+		for (a, lock_periods) in Self::voters_for(index).into_iter()
+			.map(|a| (a.clone(), Self::vote_of((index, a))))
+			// ^^^ defensive only: all items come from `voters`; for an item to be in `voters`
+			// there must be a vote registered; qed
+			.filter(|&(_, vote)| vote.aye == approved)  // Just the winning coins
+			.map(|(a, vote)| (a, Self::conviction_params(vote.conviction).lock_periods))
+			.filter(|&(_, lock_periods)| !lock_periods.is_zero()) // Just the lock votes
+		{
+			// now plus: the base lock period multiplied by the number of periods this voter
+			// offered to lock should they win...
+			let locked_until = now + enactment_period * lock_periods.into();
+			// ...for the balance they voted with...
+			let locked = Self::accumulate_prior_lock(&a, locked_until, T::Currency::total_balance(&a));
+			// ...extend their bondage until at least then.
+			T::Currency::extend_lock(
+				T::LockId::get(),
+				&a,
+				locked,
+				WithdrawReason::Transfer.into()
+			);
+		}
+
+		Self::clear_referendum(index);
+		<FinishedOf<T, I>>::insert(index, Finished { approved, end: info.end });
+
+		if approved {
+			Self::deposit_event(RawEvent::Passed(index));
+			T::ReferendumHooks::on_passed(index);
+			let turnout = Perbill::from_rational_approximation(tally.turnout, total_issuance);
+			let delay = T::EnactmentDelayCurve::delay(turnout, info.delay);
+			Self::schedule_enactment(now, delay, info.proposal, index);
+		} else {
+			Self::deposit_event(RawEvent::NotPassed(index));
+			T::ReferendumHooks::on_failed(index);
+		}
+
+		Ok(delegations)
+	}
+
+	/// Conclude a multi-option referendum in favour of its highest-scoring option, if any option
+	/// scored at all. Ties go to the earlier option.
+	fn bake_multi_referendum(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		info: MultiReferendumInfo<T::BlockNumber, T::Hash>,
+	) {
+		let scores = Self::multi_tally(index);
+		Self::clear_referendum(index);
+
+		let winner = scores.into_iter().enumerate()
+			.filter(|(_, score)| !score.is_zero())
+			.fold(None, |best: Option<(usize, BalanceOf<T, I>)>, (i, score)| match best {
+				Some((_, best_score)) if best_score >= score => best,
+				_ => Some((i, score)),
+			});
+		<FinishedOf<T, I>>::insert(index, Finished { approved: winner.is_some(), end: info.end });
+		match winner.and_then(|(i, _)| info.options.get(i).cloned().map(|o| (i, o))) {
+			Some((i, proposal)) => {
+				Self::deposit_event(RawEvent::MultiPassed(index, i as u32));
+				T::ReferendumHooks::on_passed(index);
+				Self::schedule_enactment(now, info.delay, proposal, index);
+			}
+			None => {
+				Self::deposit_event(RawEvent::NotPassed(index));
+				T::ReferendumHooks::on_failed(index);
+			}
+		}
+	}
+
+	/// Enact `proposal`, decided by referendum `index`, `delay` blocks after `now`.
+	///
+	/// The proposal is dropped if it would have to wait in a dispatch queue that is already full.
+	fn schedule_enactment(
+		now: T::BlockNumber,
+		delay: T::BlockNumber,
+		proposal: BoundedCallOf<T>,
+		index: ReferendumIndex,
+	) {
+		if delay.is_zero() {
+			let _ = Self::enact_proposal(proposal, index);
+		} else {
+			let mut queue = <DispatchQueue<T, I>>::get();
+			if queue.len() as u32 >= T::MaxQueued::get() {
+				Self::deposit_event(RawEvent::QueueFull(index));
+				return;
+			}
+			let item = (now + delay, proposal, index);
+			let pos = queue.binary_search_by_key(&item.0, |x| x.0).unwrap_or_else(|e| e);
+			queue.insert(pos, item);
+			<DispatchQueue<T, I>>::put(queue);
+		}
+	}
+
+	/// Current era is ending; we should finish up any proposals.
+	///
+	/// Returns the weight consumed.
+	fn begin_block(now: T::BlockNumber) -> Result<Weight, DispatchError> {
+		let mut weight: Weight = 0;
+
+		// let the effective minimum deposit decay towards the minimum.
+		let minimum = T::MinimumDeposit::get();
+		let effective = Self::effective_minimum_deposit();
+		if effective != minimum {
+			let excess = effective.saturating_sub(minimum);
+			let shed = (T::DepositDecay::get() * excess).max(1u32.into());
+			let decayed = excess.saturating_sub(shed);
+			<EffectiveMinimumDeposit<T, I>>::put(minimum.saturating_add(decayed));
+		}
+
+		// pick out another public referendum if it's time, unless tabling is paused.
+		if (now % T::LaunchPeriod::get()).is_zero() && !LaunchPaused::<I>::get() {
+			let proposals = <PublicProps<T, I>>::decode_len().unwrap_or(0) as Weight;
 			// Errors come from the queue being empty. we don't really care about that, and even if
 			// we did, there is nothing we can do here.
-			let _ = Self::launch_next(now);
+			let refunded = Self::launch_next(now).unwrap_or(0) as Weight;
+			weight = weight.saturating_add(weight_for::launch(proposals, refunded));
 		}
 
 		// tally up votes for any expiring referenda.
+		let active = ReferendumCount::<I>::get().saturating_sub(LowestUnbaked::<I>::get());
+		weight = weight.saturating_add(weight_for::base(active));
 		for (index, info) in Self::maturing_referenda_at(now).into_iter() {
-			Self::bake_referendum(now, index, info)?;
+			let voters = <VotersFor<T, I>>::decode_len(index).unwrap_or(0) as Weight;
+			let delegations = Self::bake_referendum(now, index, info)? as Weight;
+			weight = weight.saturating_add(weight_for::bake(voters, delegations));
+		}
+		for index in MultiReferenda::<I>::get() {
+			match Self::multi_referendum_info(index) {
+				Some(info) if info.end == now => {
+					let voters = <VotersFor<T, I>>::decode_len(index).unwrap_or(0) as Weight;
+					weight = weight.saturating_add(weight_for::bake(voters, 0));
+					Self::bake_multi_referendum(now, index, info);
+				}
+				_ => (),
+			}
 		}
 
-		let queue = <DispatchQueue<T>>::get();
+		let queue = <DispatchQueue<T, I>>::get();
 		let mut used = 0;
 		// It's stored in order, so the earliest will always be at the start.
-		for &(_, proposal_hash, index) in queue.iter().take_while(|x| x.0 == now) {
-			let _ = Self::enact_proposal(proposal_hash.clone(), index);
+		for &(_, ref proposal, index) in queue.iter().take_while(|x| x.0 == now) {
+			let bytes = Self::proposal_len(proposal) as Weight;
+			weight = weight.saturating_add(weight_for::enact(bytes));
+			let _ = Self::enact_proposal(proposal.clone(), index);
 			used += 1;
 		}
 		if used != 0 {
-			<DispatchQueue<T>>::put(&queue[used..]);
+			<DispatchQueue<T, I>>::put(&queue[used..]);
 		}
-		Ok(())
+		weight = weight.saturating_add(weight_for::dispatch(queue.len() as Weight));
+		Ok(weight)
 	}
 }
 
@@ -1585,8 +4612,9 @@ mod tests {
 	use super::*;
 	use std::cell::RefCell;
 	use frame_support::{
-		impl_outer_origin, impl_outer_dispatch, assert_noop, assert_ok, parameter_types,
-		ord_parameter_types, traits::Contains, weights::Weight,
+		impl_outer_origin, impl_outer_dispatch, impl_outer_event, assert_noop, assert_ok, parameter_types,
+		ord_parameter_types, traits::{Contains, StorePreimage, Imbalance},
+		weights::{Weight, GetDispatchInfo},
 	};
 	use sp_core::H256;
 	use sp_runtime::{
@@ -1612,6 +4640,18 @@ mod tests {
 		}
 	}
 
+	mod democracy {
+		pub use crate::Event;
+	}
+
+	impl_outer_event! {
+		pub enum TestEvent for Test {
+			system<T>,
+			pallet_balances<T>,
+			democracy<T>,
+		}
+	}
+
 	// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
 	#[derive(Clone, Eq, PartialEq, Debug)]
 	pub struct Test;
@@ -1631,7 +4671,7 @@ mod tests {
 		type AccountId = u64;
 		type Lookup = IdentityLookup<Self::AccountId>;
 		type Header = Header;
-		type Event = ();
+		type Event = TestEvent;
 		type BlockHashCount = BlockHashCount;
 		type MaximumBlockWeight = MaximumBlockWeight;
 		type MaximumBlockLength = MaximumBlockLength;
@@ -1647,7 +4687,7 @@ mod tests {
 	}
 	impl pallet_balances::Trait for Test {
 		type Balance = u64;
-		type Event = ();
+		type Event = TestEvent;
 		type DustRemoval = ();
 		type ExistentialDeposit = ExistentialDeposit;
 		type AccountStore = System;
@@ -1655,10 +4695,21 @@ mod tests {
 	parameter_types! {
 		pub const LaunchPeriod: u64 = 2;
 		pub const VotingPeriod: u64 = 2;
-		pub const EmergencyVotingPeriod: u64 = 1;
+		pub const FastTrackVotingPeriod: u64 = 2;
 		pub const MinimumDeposit: u64 = 1;
 		pub const EnactmentPeriod: u64 = 2;
 		pub const CooloffPeriod: u64 = 2;
+		pub const MaxProposals: u32 = 100;
+		pub const MaxDeposits: u32 = 5;
+		pub const MaxVetoers: u32 = 3;
+		pub const MaxOptions: u32 = 3;
+		pub const MaxDelegationTargets: u32 = 3;
+		pub const MaxDelegators: u32 = 4;
+		pub const MaxVotes: u32 = 5;
+		pub const MaxQueued: u32 = 2;
+		pub const MaxPreimageChunks: u32 = 3;
+		pub const FinishedRetention: u64 = 4;
+		pub const DemocracyLockId: LockIdentifier = *b"democrac";
 	}
 	ord_parameter_types! {
 		pub const One: u64 = 1;
@@ -1666,6 +4717,7 @@ mod tests {
 		pub const Three: u64 = 3;
 		pub const Four: u64 = 4;
 		pub const Five: u64 = 5;
+		pub const Six: u64 = 6;
 	}
 	pub struct OneToFive;
 	impl Contains<u64> for OneToFive {
@@ -1675,19 +4727,120 @@ mod tests {
 	}
 	thread_local! {
 		static PREIMAGE_BYTE_DEPOSIT: RefCell<u64> = RefCell::new(0);
+		static PREIMAGE_CHUNK_DEPOSIT: RefCell<u64> = RefCell::new(0);
+		static VOTE_DEPOSIT: RefCell<u64> = RefCell::new(0);
+		static CONVICTION_SCHEDULE: RefCell<ConvictionSchedule> =
+			RefCell::new(DefaultConvictionSchedule::get());
+		static DISCOUNT_PERIOD: RefCell<u64> = RefCell::new(0);
+		static ENACTMENT_STEPS: RefCell<Vec<(Perbill, u64)>> = RefCell::new(vec![]);
+		static SLASHED: RefCell<u64> = RefCell::new(0);
+		static HOOKS_CALLED: RefCell<Vec<(&'static str, ReferendumIndex)>> = RefCell::new(vec![]);
+		static PARTICIPATION: RefCell<Vec<(&'static str, u64)>> = RefCell::new(vec![]);
+		static REMOVE_VOTES_ON_DELEGATION: RefCell<bool> = RefCell::new(false);
+		static HOLD_DEPOSITS: RefCell<bool> = RefCell::new(false);
+		static QUADRATIC_VOTING: RefCell<bool> = RefCell::new(false);
+		static SNAPSHOT_BALANCES: RefCell<bool> = RefCell::new(false);
+		static INDEX_VOTES: RefCell<bool> = RefCell::new(false);
+		static DEPOSIT_SURCHARGE: RefCell<u64> = RefCell::new(0);
 	}
 	pub struct PreimageByteDeposit;
 	impl Get<u64> for PreimageByteDeposit {
 		fn get() -> u64 { PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow()) }
 	}
+	pub struct PreimageChunkDeposit;
+	impl Get<u64> for PreimageChunkDeposit {
+		fn get() -> u64 { PREIMAGE_CHUNK_DEPOSIT.with(|v| *v.borrow()) }
+	}
+	pub struct VoteDeposit;
+	impl Get<u64> for VoteDeposit {
+		fn get() -> u64 { VOTE_DEPOSIT.with(|v| *v.borrow()) }
+	}
+	pub struct TestConvictionSchedule;
+	impl Get<ConvictionSchedule> for TestConvictionSchedule {
+		fn get() -> ConvictionSchedule { CONVICTION_SCHEDULE.with(|v| *v.borrow()) }
+	}
+	pub struct SlashedDeposits;
+	impl OnUnbalanced<NegativeImbalanceOf<Test>> for SlashedDeposits {
+		fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+			SLASHED.with(|v| *v.borrow_mut() += amount.peek());
+		}
+	}
+	pub struct RecordHooks;
+	impl ReferendumHooks for RecordHooks {
+		fn on_started(index: ReferendumIndex) {
+			HOOKS_CALLED.with(|v| v.borrow_mut().push(("started", index)));
+		}
+		fn on_passed(index: ReferendumIndex) {
+			HOOKS_CALLED.with(|v| v.borrow_mut().push(("passed", index)));
+		}
+		fn on_failed(index: ReferendumIndex) {
+			HOOKS_CALLED.with(|v| v.borrow_mut().push(("failed", index)));
+		}
+		fn on_cancelled(index: ReferendumIndex) {
+			HOOKS_CALLED.with(|v| v.borrow_mut().push(("cancelled", index)));
+		}
+	}
+	pub struct RecordParticipation;
+	impl OnVote<u64> for RecordParticipation {
+		fn on_vote(who: &u64, _index: ReferendumIndex) {
+			PARTICIPATION.with(|v| v.borrow_mut().push(("vote", *who)));
+		}
+		fn on_remove_vote(who: &u64, _index: ReferendumIndex) {
+			PARTICIPATION.with(|v| v.borrow_mut().push(("remove_vote", *who)));
+		}
+	}
+	impl OnDelegation<u64> for RecordParticipation {
+		fn on_delegate(who: &u64, _to: &u64, _class: Option<VoteThreshold>) {
+			PARTICIPATION.with(|v| v.borrow_mut().push(("delegate", *who)));
+		}
+		fn on_undelegate(who: &u64, _class: Option<VoteThreshold>) {
+			PARTICIPATION.with(|v| v.borrow_mut().push(("undelegate", *who)));
+		}
+	}
+	pub struct EnactmentSteps;
+	impl Get<Vec<(Perbill, u64)>> for EnactmentSteps {
+		fn get() -> Vec<(Perbill, u64)> { ENACTMENT_STEPS.with(|v| v.borrow().clone()) }
+	}
+	pub struct DiscountPeriod;
+	impl Get<u64> for DiscountPeriod {
+		fn get() -> u64 { DISCOUNT_PERIOD.with(|v| *v.borrow()) }
+	}
+	pub struct DepositSurcharge;
+	impl Get<u64> for DepositSurcharge {
+		fn get() -> u64 { DEPOSIT_SURCHARGE.with(|v| *v.borrow()) }
+	}
+	pub struct RemoveVotesOnDelegation;
+	impl Get<bool> for RemoveVotesOnDelegation {
+		fn get() -> bool { REMOVE_VOTES_ON_DELEGATION.with(|v| *v.borrow()) }
+	}
+	pub struct HoldDeposits;
+	impl Get<bool> for HoldDeposits {
+		fn get() -> bool { HOLD_DEPOSITS.with(|v| *v.borrow()) }
+	}
+	pub struct QuadraticVoting;
+	impl Get<bool> for QuadraticVoting {
+		fn get() -> bool { QUADRATIC_VOTING.with(|v| *v.borrow()) }
+	}
+	pub struct SnapshotBalances;
+	impl Get<bool> for SnapshotBalances {
+		fn get() -> bool { SNAPSHOT_BALANCES.with(|v| *v.borrow()) }
+	}
+	pub struct IndexVotes;
+	impl Get<bool> for IndexVotes {
+		fn get() -> bool { INDEX_VOTES.with(|v| *v.borrow()) }
+	}
+	parameter_types! {
+		pub const DiscountFloor: Perbill = Perbill::from_percent(20);
+		pub const DepositDecay: Perbill = Perbill::from_percent(50);
+	}
 	impl super::Trait for Test {
 		type Proposal = Call;
-		type Event = ();
+		type Event = TestEvent;
 		type Currency = pallet_balances::Module<Self>;
 		type EnactmentPeriod = EnactmentPeriod;
 		type LaunchPeriod = LaunchPeriod;
 		type VotingPeriod = VotingPeriod;
-		type EmergencyVotingPeriod = EmergencyVotingPeriod;
+		type FastTrackVotingPeriod = FastTrackVotingPeriod;
 		type MinimumDeposit = MinimumDeposit;
 		type ExternalOrigin = EnsureSignedBy<Two, u64>;
 		type ExternalMajorityOrigin = EnsureSignedBy<Three, u64>;
@@ -1697,7 +4850,37 @@ mod tests {
 		type VetoOrigin = EnsureSignedBy<OneToFive, u64>;
 		type CooloffPeriod = CooloffPeriod;
 		type PreimageByteDeposit = PreimageByteDeposit;
-		type Slash = ();
+		type PreimageChunkDeposit = PreimageChunkDeposit;
+		type MaxPreimageChunks = MaxPreimageChunks;
+		type VoteDeposit = VoteDeposit;
+		type Slash = SlashedDeposits;
+		type MaxProposals = MaxProposals;
+		type MaxDeposits = MaxDeposits;
+		type MaxVetoers = MaxVetoers;
+		type ApprovalCriterion = AdaptiveQuorumBiasing;
+		type MaxOptions = MaxOptions;
+		type MaxDelegationTargets = MaxDelegationTargets;
+		type MaxDelegators = MaxDelegators;
+		type MaxVotes = MaxVotes;
+		type ConvictionSchedule = TestConvictionSchedule;
+		type VoteWeightCurve = LinearLateDiscount<DiscountPeriod, DiscountFloor>;
+		type EnactmentDelayCurve = TurnoutSteps<EnactmentSteps>;
+		type MaxQueued = MaxQueued;
+		type ReferendumHooks = RecordHooks;
+		type OnVote = RecordParticipation;
+		type OnDelegation = RecordParticipation;
+		type FinishedRetention = FinishedRetention;
+		type RemoveVotesOnDelegation = RemoveVotesOnDelegation;
+		type HoldDeposits = HoldDeposits;
+		type QuadraticVoting = QuadraticVoting;
+		type SnapshotBalances = SnapshotBalances;
+		type IndexVotes = IndexVotes;
+		type DepositSurcharge = DepositSurcharge;
+		type DepositDecay = DepositDecay;
+		type InstantOrigin = EnsureSignedBy<Six, u64>;
+		type WhitelistOrigin = EnsureSignedBy<Six, u64>;
+		type DispatchWhitelistedOrigin = EnsureSignedBy<Two, u64>;
+		type LockId = DemocracyLockId;
 	}
 
 	fn new_test_ext() -> sp_io::TestExternalities {
@@ -1705,10 +4888,45 @@ mod tests {
 		pallet_balances::GenesisConfig::<Test>{
 			balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
 		}.assimilate_storage(&mut t).unwrap();
-		GenesisConfig::default().assimilate_storage(&mut t).unwrap();
+		GenesisConfig::<Test>::default().assimilate_storage(&mut t).unwrap();
 		sp_io::TestExternalities::new(t)
 	}
 
+	#[test]
+	fn genesis_proposals_and_referenda_should_work() {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		pallet_balances::GenesisConfig::<Test>{
+			balances: vec![(1, 10), (2, 20)],
+		}.assimilate_storage(&mut t).unwrap();
+		GenesisConfig::<Test> {
+			proposals: vec![
+				(1, set_balance_proposal_hash(2), 3),
+				(2, set_balance_proposal_hash(4), 5),
+			],
+			referenda: vec![(set_balance_proposal_hash(3), VoteThreshold::SimpleMajority, 1)],
+		}.assimilate_storage(&mut t).unwrap();
+		sp_io::TestExternalities::new(t).execute_with(|| {
+			assert_eq!(Democracy::public_prop_count(), 2);
+			assert_eq!(Democracy::public_props().len(), 2);
+			assert_eq!(Democracy::deposit_of(1), Some((5, vec![2])));
+			assert_eq!(Balances::reserved_balance(1), 3);
+			assert_eq!(Democracy::referendum_count(), 1);
+			assert_eq!(Democracy::referendum_info(0), Some(ReferendumInfo {
+				end: 2,
+				proposal: BoundedCall::Legacy { hash: set_balance_proposal_hash(3) },
+				threshold: VoteThreshold::SimpleMajority,
+				delay: 1,
+			}));
+
+			System::set_block_number(1);
+			assert_ok!(Democracy::vote(Origin::signed(1), 0, AYE));
+			fast_forward_to(2);
+			assert_eq!(last_event(), RawEvent::Passed(0));
+			assert_eq!(Democracy::referendum_count(), 2);
+			assert_eq!(Balances::reserved_balance(2), 0);
+		});
+	}
+
 	type System = frame_system::Module<Test>;
 	type Balances = pallet_balances::Module<Test>;
 	type Democracy = Module<Test>;
@@ -1735,12 +4953,17 @@ mod tests {
 		let h = BlakeTwo256::hash(&p[..]);
 		match Democracy::note_preimage(Origin::signed(6), p) {
 			Ok(_) => (),
-			Err(x) if x == Error::<Test>::DuplicatePreimage.into() => (),
+			Err(x) if x == Error::<Test, _>::DuplicatePreimage.into() => (),
 			Err(x) => panic!(x),
 		}
 		h
 	}
 
+	fn set_balance_proposal_lookup(value: u64) -> BoundedCall<H256> {
+		let len = set_balance_proposal(value).len() as u32;
+		BoundedCall::Lookup { hash: set_balance_proposal_hash_and_note(value), len }
+	}
+
 	fn propose_set_balance(who: u64, value: u64, delay: u64) -> DispatchResult {
 		Democracy::propose(
 			Origin::signed(who),
@@ -1759,7 +4982,8 @@ mod tests {
 
 	fn next_block() {
 		System::set_block_number(System::block_number() + 1);
-		assert_eq!(Democracy::begin_block(System::block_number()), Ok(()));
+		assert!(Democracy::begin_block(System::block_number()).is_ok());
+		assert_eq!(Democracy::do_try_state(), Ok(()));
 	}
 
 	fn fast_forward_to(n: u64) {
@@ -1768,22 +4992,302 @@ mod tests {
 		}
 	}
 
+	fn last_event() -> Event<Test> {
+		System::events().into_iter()
+			.filter_map(|r| if let TestEvent::democracy(e) = r.event { Some(e) } else { None })
+			.last()
+			.expect("Event expected")
+	}
+
 	#[test]
-	fn missing_preimage_should_fail() {
+	fn inline_proposal_should_be_enacted_without_preimage() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(1);
-			let r = Democracy::inject_referendum(
-				2,
-				set_balance_proposal_hash(2),
+			System::set_block_number(0);
+			let call = Call::Balances(pallet_balances::Call::set_balance(42, 2, 0));
+			let proposal = <Democracy as StorePreimage<H256>>::bound(call).unwrap();
+			assert!(!proposal.lookup_needed());
+			let r = Democracy::internal_start_referendum(
+				proposal,
 				VoteThreshold::SuperMajorityApprove,
-				0
+				0,
 			);
 			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
 
-			next_block();
-			next_block();
+			fast_forward_to(2);
 
-			assert_eq!(Balances::free_balance(42), 0);
+			assert_eq!(Balances::free_balance(42), 2);
+		});
+	}
+
+	#[test]
+	fn large_proposal_should_be_referenced_by_preimage() {
+		new_test_ext().execute_with(|| {
+			let data = vec![0u8; 200];
+			let len = data.encode().len() as u32;
+			let proposal = <Democracy as StorePreimage<H256>>::bound(data).unwrap();
+			assert!(proposal.lookup_needed());
+			assert_eq!(proposal.len(), Some(len));
+
+			let hash = proposal.lookup_hash().unwrap();
+			assert_eq!(Democracy::len(&hash), Some(len));
+			assert_eq!(Democracy::bound_hash(hash), proposal);
+
+			let unknown = set_balance_proposal_hash(2);
+			assert_eq!(Democracy::bound_hash(unknown), BoundedCall::Legacy { hash: unknown });
+		});
+	}
+
+	#[test]
+	fn migration_to_bounded_calls_works() {
+		use frame_support::storage::{unhashed, StorageValue, StorageMap};
+		new_test_ext().execute_with(|| {
+			let h = set_balance_proposal_hash(2);
+			StorageVersion::<DefaultInstance>::put(Releases::V1);
+			unhashed::put(&PublicProps::<Test>::hashed_key(), &vec![(0u32, h, 1u64)]);
+			unhashed::put(&NextExternal::<Test>::hashed_key(), &(h, VoteThreshold::SimpleMajority));
+			unhashed::put(&DispatchQueue::<Test>::hashed_key(), &vec![(5u64, h, 1u32)]);
+			unhashed::put(
+				&ReferendumInfoOf::<Test>::hashed_key_for(0),
+				&(3u64, h, VoteThreshold::SuperMajorityApprove, 2u64),
+			);
+
+			migration::migrate::<Test, _>();
+
+			let legacy = BoundedCall::Legacy { hash: h };
+			assert_eq!(Democracy::public_props(), vec![(0, legacy.clone(), 1)]);
+			assert_eq!(NextExternal::<Test>::get(), Some((legacy.clone(), VoteThreshold::SimpleMajority)));
+			assert_eq!(Democracy::dispatch_queue(), vec![(5, legacy.clone(), 1)]);
+			assert_eq!(
+				Democracy::referendum_info(0),
+				Some(ReferendumInfo::new(3, legacy, VoteThreshold::SuperMajorityApprove, 2)),
+			);
+			assert_eq!(StorageVersion::<DefaultInstance>::get(), Releases::latest());
+		});
+	}
+
+	#[test]
+	fn migration_to_prior_locks_works() {
+		use frame_support::storage::{unhashed, StorageValue, StorageMap};
+		new_test_ext().execute_with(|| {
+			StorageVersion::<DefaultInstance>::put(Releases::V2);
+			unhashed::put(&Locks::<Test>::hashed_key_for(1), &5u64);
+
+			migration::migrate::<Test, _>();
+
+			assert_eq!(Democracy::locks(1), Some(PriorLock::new(5, u64::max_value())));
+			assert_eq!(StorageVersion::<DefaultInstance>::get(), Releases::latest());
+		});
+	}
+
+	#[test]
+	fn migration_to_separate_locks_works() {
+		use frame_support::storage::{unhashed, StorageValue, StorageMap};
+		new_test_ext().execute_with(|| {
+			StorageVersion::<DefaultInstance>::put(Releases::V4);
+			unhashed::put(&Locks::<Test>::hashed_key_for(1), &(5u64, 10u64));
+
+			migration::migrate::<Test, _>();
+
+			assert_eq!(Democracy::locks(1), Some(PriorLock::new(5, 10)));
+			assert_eq!(StorageVersion::<DefaultInstance>::get(), Releases::latest());
+		});
+	}
+
+	#[test]
+	fn migration_to_bounded_deposits_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			// more seconds than the bound allows, as may have been made before it.
+			StorageVersion::<DefaultInstance>::put(Releases::V5);
+			let depositors = vec![1, 2, 3, 4, 5, 6, 6];
+			for who in &depositors[2..] {
+				assert_ok!(Balances::reserve(who, 2));
+			}
+			DepositOf::<Test>::insert(0, (2, depositors));
+
+			migration::migrate::<Test, _>();
+
+			assert_eq!(Democracy::deposit_of(0), Some((2, vec![1, 2, 3, 4, 5])));
+			assert_eq!(Balances::reserved_balance(5), 2);
+			assert_eq!(Balances::reserved_balance(6), 0);
+			assert_eq!(StorageVersion::<DefaultInstance>::get(), Releases::latest());
+		});
+	}
+
+	#[test]
+	fn migration_to_voting_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			for _ in 0..3 {
+				let proposal = BoundedCall::Legacy { hash: set_balance_proposal_hash(2) };
+				Democracy::inject_referendum(2, proposal, VoteThreshold::SuperMajorityApprove, 0);
+			}
+			assert_ok!(Democracy::vote(Origin::signed(1), 2, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), 0, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(2), 1, NAY));
+			assert_eq!(Democracy::voting(1), vec![0, 2]);
+			Voting::<Test>::remove_all();
+			StorageVersion::<DefaultInstance>::put(Releases::V3);
+
+			let votes = migration::pre_migrate_to_voting::<Test, _>();
+			assert_eq!(votes, 3);
+			migration::migrate::<Test, _>();
+			migration::post_migrate_to_voting::<Test, _>(votes);
+
+			assert_eq!(Democracy::voting(1), vec![0, 2]);
+			assert_eq!(Democracy::voting(2), vec![1]);
+			assert_eq!(Democracy::voting(3), vec![]);
+
+			assert_ok!(Democracy::remove_vote(Origin::signed(2), 1));
+			assert!(!Voting::<Test>::contains_key(2));
+			Democracy::internal_cancel_referendum(0);
+			assert_eq!(Democracy::voting(1), vec![2]);
+		});
+	}
+
+	/// A governance action by one of the accounts of the mock.
+	#[derive(Clone, Debug)]
+	enum Action {
+		Vote(u64, ReferendumIndex, bool, Conviction),
+		RemoveVote(u64, ReferendumIndex),
+		Delegate(u64, u64, Conviction),
+		Undelegate(u64),
+		Cancel(ReferendumIndex),
+	}
+
+	impl quickcheck::Arbitrary for Action {
+		fn arbitrary<G: quickcheck::Gen>(g: &mut G) -> Self {
+			let account = |g: &mut G| 1 + g.next_u64() % 6;
+			let referendum = |g: &mut G| g.next_u32() % 2;
+			let conviction = |g: &mut G| Conviction::try_from((g.next_u32() % 7) as u8).unwrap();
+			match g.next_u32() % 10 {
+				0..=4 => {
+					let aye = g.next_u32() % 2 == 0;
+					Action::Vote(account(g), referendum(g), aye, conviction(g))
+				}
+				5 => Action::RemoveVote(account(g), referendum(g)),
+				6 | 7 => Action::Delegate(account(g), account(g), conviction(g)),
+				8 => Action::Undelegate(account(g)),
+				_ => Action::Cancel(referendum(g)),
+			}
+		}
+	}
+
+	/// Apply `action`, ignoring whether it was valid.
+	fn apply(action: Action) {
+		let _ = match action {
+			Action::Vote(who, r, aye, conviction) =>
+				Democracy::vote(Origin::signed(who), r, Vote { aye, conviction }),
+			Action::RemoveVote(who, r) => Democracy::remove_vote(Origin::signed(who), r),
+			Action::Delegate(who, to, conviction) =>
+				Democracy::delegate(Origin::signed(who), to, conviction),
+			Action::Undelegate(who) => Democracy::undelegate(Origin::signed(who)),
+			Action::Cancel(r) => {
+				if Democracy::is_active_referendum(r) {
+					Democracy::internal_cancel_referendum(r);
+				}
+				Ok(())
+			}
+		};
+	}
+
+	quickcheck::quickcheck! {
+		fn tally_should_stay_within_bounds(actions: Vec<Action>) -> bool {
+			new_test_ext().execute_with(|| {
+				System::set_block_number(1);
+				for _ in 0..2 {
+					let proposal = BoundedCall::Legacy { hash: set_balance_proposal_hash(2) };
+					Democracy::inject_referendum(10, proposal, VoteThreshold::SimpleMajority, 0);
+				}
+				let issuance = Balances::total_issuance();
+				actions.into_iter().all(|action| {
+					apply(action);
+					Democracy::do_try_state().is_ok() && (0..2).all(|r| {
+						let tally = Democracy::tally(r);
+						if !Democracy::is_active_referendum(r) {
+							return tally == Tally::default();
+						}
+						tally.turnout <= issuance &&
+							tally.ayes.checked_add(tally.nays).map_or(false, |v| v <= 6 * issuance)
+					})
+				})
+			})
+		}
+
+		fn fresh_aye_should_not_hurt_approval(actions: Vec<Action>, conviction: u8) -> bool {
+			new_test_ext().execute_with(|| {
+				System::set_block_number(1);
+				let _ = Balances::deposit_creating(&7, 70);
+				let threshold = VoteThreshold::SuperMajorityApprove;
+				let proposal = BoundedCall::Legacy { hash: set_balance_proposal_hash(2) };
+				Democracy::inject_referendum(10, proposal, threshold, 0);
+				actions.into_iter().for_each(apply);
+				if !Democracy::is_active_referendum(0) {
+					return true;
+				}
+				let electorate = Balances::total_issuance();
+				let before = Democracy::tally(0);
+				let conviction = Conviction::try_from(conviction % 7).unwrap();
+				assert_ok!(Democracy::vote(Origin::signed(7), 0, Vote { aye: true, conviction }));
+				let after = Democracy::tally(0);
+				let approved =
+					|t: Tally<u64>| threshold.approved(t.ayes, t.nays, t.turnout, electorate);
+				after.ayes >= before.ayes && after.nays == before.nays &&
+					after.turnout > before.turnout && (!approved(before) || approved(after))
+			})
+		}
+	}
+
+	#[test]
+	fn try_state_should_catch_corruption() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance(1, 2, 5));
+			let r = Democracy::inject_referendum(
+				3,
+				BoundedCall::Legacy { hash: set_balance_proposal_hash(2) },
+				VoteThreshold::SuperMajorityApprove,
+				0,
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_eq!(Democracy::do_try_state(), Ok(()));
+
+			let _ = Balances::unreserve(&1, 5);
+			assert_eq!(Democracy::do_try_state(), Err("Deposit not reserved"));
+			assert_ok!(Balances::reserve(&1, 5));
+
+			Voting::<Test>::remove(1);
+			assert_eq!(Democracy::do_try_state(), Err("Vote missing from `Voting`"));
+			Voting::<Test>::insert(1, vec![r]);
+
+			let legacy = |value| BoundedCall::Legacy { hash: set_balance_proposal_hash(value) };
+			DispatchQueue::<Test>::put(vec![(2, legacy(1), 0), (1, legacy(2), 1)]);
+			assert_eq!(Democracy::do_try_state(), Err("Dispatch queue not sorted"));
+			DispatchQueue::<Test>::kill();
+
+			ReferendumCount::<DefaultInstance>::put(0);
+			assert_eq!(Democracy::do_try_state(), Err("Referendum index out of range"));
+		});
+	}
+
+	#[test]
+	fn missing_preimage_should_fail() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				BoundedCall::Legacy { hash: set_balance_proposal_hash(2) },
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+
+			next_block();
+			next_block();
+
+			assert_eq!(Balances::free_balance(42), 0);
 		});
 	}
 
@@ -1801,7 +5305,7 @@ mod tests {
 			PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -1818,6 +5322,22 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn unnoting_preimage_should_return_deposit() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			assert_ok!(Democracy::note_preimage(Origin::signed(6), set_balance_proposal(2)));
+			assert_eq!(Balances::reserved_balance(6), 12);
+
+			<Democracy as StorePreimage<H256>>::unnote(&set_balance_proposal_hash(2));
+
+			assert_eq!(Democracy::len(&set_balance_proposal_hash(2)), None);
+			assert_eq!(Balances::reserved_balance(6), 0);
+			assert_eq!(Balances::free_balance(6), 60);
+		});
+	}
+
 	#[test]
 	fn preimage_deposit_should_be_reapable_earlier_by_owner() {
 		new_test_ext().execute_with(|| {
@@ -1829,24 +5349,141 @@ mod tests {
 
 			next_block();
 			assert_noop!(
-				Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2)),
-				Error::<Test>::Early
+				Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2), u32::max_value()),
+				Error::<Test, _>::Early
 			);
 			next_block();
-			assert_ok!(Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2)));
+			assert_ok!(
+				Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2), u32::max_value())
+			);
 
 			assert_eq!(Balances::free_balance(6), 60);
 			assert_eq!(Balances::reserved_balance(6), 0);
 		});
 	}
 
+	#[test]
+	fn reap_preimage_with_wrong_upper_bound_should_not_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Democracy::note_preimage(Origin::signed(6), set_balance_proposal(2)));
+			let len = set_balance_proposal(2).len() as u32;
+
+			next_block();
+			next_block();
+			assert_noop!(
+				Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2), len - 1),
+				Error::<Test, _>::WrongUpperBound
+			);
+			assert_ok!(Democracy::reap_preimage(Origin::signed(6), set_balance_proposal_hash(2), len));
+		});
+	}
+
+	#[test]
+	fn chunked_preimage_should_be_noted() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			let preimage = set_balance_proposal(2);
+			let (first, second) = preimage.split_at(preimage.len() / 2);
+			let h = set_balance_proposal_hash(2);
+
+			assert_noop!(
+				Democracy::note_preimage_chunk(Origin::signed(6), h, 1, first.to_vec()),
+				Error::<Test, _>::WrongChunkIndex
+			);
+			assert_ok!(Democracy::note_preimage_chunk(Origin::signed(6), h, 0, first.to_vec()));
+			assert_eq!(last_event(), RawEvent::PreimageChunkNoted(h, 6, 0));
+			assert_noop!(
+				Democracy::note_preimage_chunk(Origin::signed(5), h, 1, second.to_vec()),
+				Error::<Test, _>::NotUploader
+			);
+			assert_noop!(
+				Democracy::finalize_preimage(Origin::signed(6), h, u32::max_value()),
+				Error::<Test, _>::PreimageMismatch
+			);
+			assert_ok!(Democracy::note_preimage_chunk(Origin::signed(6), h, 1, second.to_vec()));
+			assert_eq!(Balances::reserved_balance(6), preimage.len() as u64);
+
+			assert_noop!(
+				Democracy::finalize_preimage(Origin::signed(6), h, preimage.len() as u32 - 1),
+				Error::<Test, _>::WrongUpperBound
+			);
+			assert_ok!(Democracy::finalize_preimage(Origin::signed(6), h, preimage.len() as u32));
+			assert_eq!(last_event(), RawEvent::PreimageNoted(h, 6, preimage.len() as u64));
+			assert_eq!(Democracy::fetch(&h), Some(preimage.clone()));
+			assert_eq!(Democracy::preimage_upload(&h), None);
+			assert!(!<PreimageChunks<Test>>::contains_key((h, 0)));
+			assert_eq!(Balances::reserved_balance(6), preimage.len() as u64);
+
+			assert_noop!(
+				Democracy::note_preimage_chunk(Origin::signed(6), h, 0, first.to_vec()),
+				Error::<Test, _>::DuplicatePreimage
+			);
+		});
+	}
+
+	#[test]
+	fn preimage_chunks_should_be_bounded() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			PREIMAGE_CHUNK_DEPOSIT.with(|v| *v.borrow_mut() = 5);
+			let h = set_balance_proposal_hash(2);
+
+			assert_noop!(
+				Democracy::note_preimage_chunk(Origin::signed(6), h, 0, vec![]),
+				Error::<Test, _>::EmptyChunk
+			);
+			for index in 0..3 {
+				assert_ok!(Democracy::note_preimage_chunk(Origin::signed(6), h, index, vec![0; 2]));
+			}
+			assert_eq!(Balances::reserved_balance(6), 3 * (5 + 2));
+			assert_noop!(
+				Democracy::note_preimage_chunk(Origin::signed(6), h, 3, vec![0; 2]),
+				Error::<Test, _>::TooManyChunks
+			);
+
+			// The upload can't be kept alive for longer than the chunks allow.
+			next_block();
+			next_block();
+			assert_ok!(Democracy::reap_preimage_upload(Origin::signed(5), h));
+			assert_eq!(Balances::free_balance(5), 50 + 3 * (5 + 2));
+		});
+	}
+
+	#[test]
+	fn unfinished_preimage_upload_should_be_reapable() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			let h = set_balance_proposal_hash(2);
+			assert_noop!(
+				Democracy::reap_preimage_upload(Origin::signed(5), h),
+				Error::<Test, _>::UploadMissing
+			);
+			assert_ok!(Democracy::note_preimage_chunk(Origin::signed(6), h, 0, vec![0; 10]));
+
+			next_block();
+			assert_noop!(Democracy::reap_preimage_upload(Origin::signed(5), h), Error::<Test, _>::Early);
+
+			next_block();
+			assert_ok!(Democracy::reap_preimage_upload(Origin::signed(5), h));
+			assert_eq!(last_event(), RawEvent::PreimageUploadReaped(h, 6, 10, 5));
+			assert_eq!(Balances::reserved_balance(6), 0);
+			assert_eq!(Balances::free_balance(6), 50);
+			assert_eq!(Balances::free_balance(5), 60);
+			assert_eq!(Democracy::preimage_upload(&h), None);
+		});
+	}
+
 	#[test]
 	fn preimage_deposit_should_be_reapable() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
 			assert_noop!(
-				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2)),
-				Error::<Test>::PreimageMissing
+				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value()),
+				Error::<Test, _>::PreimageMissing
 			);
 
 			PREIMAGE_BYTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
@@ -1857,12 +5494,14 @@ mod tests {
 			next_block();
 			next_block();
 			assert_noop!(
-				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2)),
-				Error::<Test>::Early
+				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value()),
+				Error::<Test, _>::Early
 			);
 
 			next_block();
-			assert_ok!(Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2)));
+			assert_ok!(
+				Democracy::reap_preimage(Origin::signed(5), set_balance_proposal_hash(2), u32::max_value())
+			);
 			assert_eq!(Balances::reserved_balance(6), 0);
 			assert_eq!(Balances::free_balance(6), 48);
 			assert_eq!(Balances::free_balance(5), 62);
@@ -1877,7 +5516,7 @@ mod tests {
 
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash(2),
+				BoundedCall::Legacy { hash: set_balance_proposal_hash(2) },
 				VoteThreshold::SuperMajorityApprove,
 				1
 			);
@@ -1885,7 +5524,7 @@ mod tests {
 
 			assert_noop!(
 				Democracy::note_imminent_preimage(Origin::signed(7), set_balance_proposal(2)),
-				Error::<Test>::NotImminent
+				Error::<Test, _>::NotImminent
 			);
 
 			next_block();
@@ -1904,12 +5543,20 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
 			let h = set_balance_proposal_hash_and_note(2);
-			let r = Democracy::inject_referendum(3, h, VoteThreshold::SuperMajorityApprove, 1);
+			let r = Democracy::inject_referendum(
+				3,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				1,
+			);
 			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
 			next_block();
 			next_block();
 			// now imminent.
-			assert_noop!(Democracy::reap_preimage(Origin::signed(6), h), Error::<Test>::Imminent);
+			assert_noop!(
+				Democracy::reap_preimage(Origin::signed(6), h, u32::max_value()),
+				Error::<Test, _>::Imminent
+			);
 		});
 	}
 
@@ -1930,7 +5577,7 @@ mod tests {
 				Democracy::referendum_info(0),
 				Some(ReferendumInfo {
 					end: 4,
-					proposal_hash: set_balance_proposal_hash_and_note(1),
+					proposal: set_balance_proposal_lookup(1),
 					threshold: VoteThreshold::SuperMajorityApprove,
 					delay: 2
 				})
@@ -1948,7 +5595,7 @@ mod tests {
 				Democracy::referendum_info(1),
 				Some(ReferendumInfo {
 					end: 6,
-					proposal_hash: set_balance_proposal_hash_and_note(2),
+					proposal: set_balance_proposal_lookup(2),
 					threshold: VoteThreshold::SuperMajorityApprove,
 					delay: 2
 				})
@@ -1962,7 +5609,7 @@ mod tests {
 				Democracy::referendum_info(2),
 				Some(ReferendumInfo {
 					end: 8,
-					proposal_hash: set_balance_proposal_hash_and_note(3),
+					proposal: set_balance_proposal_lookup(3),
 					threshold: VoteThreshold::SuperMajorityApprove,
 					delay: 2
 				})
@@ -1980,7 +5627,7 @@ mod tests {
 				Democracy::referendum_info(3),
 				Some(ReferendumInfo {
 					end: 10,
-					proposal_hash: set_balance_proposal_hash_and_note(5),
+					proposal: set_balance_proposal_lookup(5),
 					threshold: VoteThreshold::SuperMajorityApprove,
 					delay: 2
 				})
@@ -1999,7 +5646,7 @@ mod tests {
 				Democracy::referendum_info(4),
 				Some(ReferendumInfo {
 					end: 12,
-					proposal_hash: set_balance_proposal_hash_and_note(4),
+					proposal: set_balance_proposal_lookup(4),
 					threshold: VoteThreshold::SuperMajorityApprove,
 					delay: 2
 				})
@@ -2017,7 +5664,7 @@ mod tests {
 				Democracy::referendum_info(5),
 				Some(ReferendumInfo {
 					end: 14,
-					proposal_hash: set_balance_proposal_hash_and_note(6),
+					proposal: set_balance_proposal_lookup(6),
 					threshold: VoteThreshold::SuperMajorityApprove,
 					delay: 2
 				})
@@ -2025,278 +5672,1431 @@ mod tests {
 		});
 	}
 
-
+
+	#[test]
+	fn emergency_cancel_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				2
+			);
+			assert!(Democracy::referendum_info(r).is_some());
+
+			assert_noop!(Democracy::emergency_cancel(Origin::signed(3), r), BadOrigin);
+			assert_ok!(Democracy::emergency_cancel(Origin::signed(4), r));
+			assert!(Democracy::referendum_info(r).is_none());
+
+			// some time later...
+
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				2
+			);
+			assert!(Democracy::referendum_info(r).is_some());
+			assert_noop!(Democracy::emergency_cancel(Origin::signed(4), r), Error::<Test, _>::AlreadyCanceled);
+
+			// only the slower path remains.
+			assert_ok!(Democracy::cancel_referendum(Origin::ROOT, r.into()));
+			assert!(Democracy::referendum_info(r).is_none());
+		});
+	}
+
+	#[test]
+	fn proposal_events_should_be_emitted() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, 1));
+			assert_eq!(last_event(), RawEvent::Seconded(2, 0));
+			assert_ok!(Democracy::clear_public_proposals(Origin::ROOT, false));
+			assert_eq!(last_event(), RawEvent::PublicProposalsCleared);
+
+			let h = set_balance_proposal_hash_and_note(2);
+			assert_ok!(Democracy::external_propose_majority(Origin::signed(3), h));
+			assert_eq!(last_event(), RawEvent::ExternalProposed(h, VoteThreshold::SimpleMajority));
+
+			let r = Democracy::inject_referendum(
+				3,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SimpleMajority,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_eq!(last_event(), RawEvent::Voted(1, r, AYE));
+			assert_ok!(Democracy::cancel_referendum(Origin::ROOT, r));
+			assert_eq!(last_event(), RawEvent::Cancelled(r));
+		});
+	}
+
+	#[test]
+	#[cfg(feature = "legacy-proxy")]
+	fn proxy_events_should_be_emitted() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
+			assert_eq!(last_event(), RawEvent::ProxyOpened(10, 1));
+			assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
+			assert_eq!(last_event(), RawEvent::ProxyActivated(1, 10));
+			assert_ok!(Democracy::deactivate_proxy(Origin::signed(1), 10));
+			assert_eq!(last_event(), RawEvent::ProxyDeactivated(1, 10));
+			assert_ok!(Democracy::close_proxy(Origin::signed(10)));
+			assert_eq!(last_event(), RawEvent::ProxyClosed(10));
+		});
+	}
+
+	#[test]
+	fn veto_external_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash_and_note(2),
+			));
+			assert!(<NextExternal<Test>>::exists());
+
+			let h = set_balance_proposal_hash_and_note(2);
+			assert_ok!(Democracy::veto_external(Origin::signed(3), h.clone()));
+			// cancelled.
+			assert!(!<NextExternal<Test>>::exists());
+			// fails - same proposal can't be resubmitted.
+			assert_noop!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash(2),
+			), Error::<Test, _>::ProposalBlacklisted);
+
+			fast_forward_to(1);
+			// fails as we're still in cooloff period.
+			assert_noop!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash(2),
+			), Error::<Test, _>::ProposalBlacklisted);
+
+			fast_forward_to(2);
+			// works; as we're out of the cooloff period.
+			assert_ok!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash_and_note(2),
+			));
+			assert!(<NextExternal<Test>>::exists());
+
+			// 3 can't veto the same thing twice.
+			assert_noop!(
+				Democracy::veto_external(Origin::signed(3), h.clone()),
+				Error::<Test, _>::AlreadyVetoed
+			);
+
+			// 4 vetoes.
+			assert_ok!(Democracy::veto_external(Origin::signed(4), h.clone()));
+			// cancelled again.
+			assert!(!<NextExternal<Test>>::exists());
+
+			fast_forward_to(3);
+			// same proposal fails as we're still in cooloff
+			assert_noop!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash(2),
+			), Error::<Test, _>::ProposalBlacklisted);
+			// different proposal works fine.
+			assert_ok!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash_and_note(3),
+			));
+		});
+	}
+
+	#[test]
+	fn unused_weight_should_be_given_back_to_the_block() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			// Stand in for `CheckWeight`, which registers the weight charged for each call.
+			let charge = |call: crate::Call<Test>| {
+				let weight = Call::Democracy(call).get_dispatch_info().weight;
+				System::set_block_limits(0, 0);
+				System::register_extrinsic_weight(weight);
+				weight
+			};
+
+			assert_ok!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash_and_note(2),
+			));
+			let h = set_balance_proposal_hash(2);
+			let charged = charge(crate::Call::veto_external(h));
+			assert_ok!(Democracy::veto_external(Origin::signed(3), h));
+			// weighted for 3 earlier vetoers, but there were none.
+			assert_eq!(System::all_extrinsics_weight(), charged - 3 * 20_000);
+
+			let h = set_balance_proposal_hash(3);
+			assert_ok!(Democracy::note_preimage_chunk(Origin::signed(6), h, 0, vec![0; 10]));
+			let charged = charge(crate::Call::reap_preimage_upload(h));
+			assert_ok!(Democracy::reap_preimage_upload(Origin::signed(6), h));
+			// weighted for 3 chunks, but there was one.
+			assert_eq!(System::all_extrinsics_weight(), charged - 2 * 200_000);
+
+			assert_ok!(Democracy::delegate(Origin::signed(1), 2, Conviction::Locked1x));
+			let charged = charge(crate::Call::undelegate());
+			assert_ok!(Democracy::undelegate(Origin::signed(1)));
+			// weighted for 3 targets of 4 delegators each, but there was one of one.
+			assert_eq!(System::all_extrinsics_weight(), charged - 11 * 1_000);
+
+			let charged = charge(crate::Call::unlock(5));
+			assert!(Democracy::unlock(Origin::signed(1), 5).is_err());
+			// nothing to unlock.
+			assert_eq!(System::all_extrinsics_weight(), charged - 5_000);
+		});
+	}
+
+	#[test]
+	fn veto_external_records_vetoers_in_blacklist() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let h = set_balance_proposal_hash_and_note(2);
+			assert_ok!(Democracy::external_propose(Origin::signed(2), h));
+			assert_ok!(Democracy::veto_external(Origin::signed(4), h));
+			assert_eq!(<Blacklist<Test>>::get(&h), Some((2, vec![4])));
+
+			fast_forward_to(2);
+			assert_ok!(Democracy::external_propose(Origin::signed(2), h));
+			assert_ok!(Democracy::veto_external(Origin::signed(3), h));
+			// the vetoers are kept sorted and the cooloff restarts from the latest veto.
+			assert_eq!(<Blacklist<Test>>::get(&h), Some((4, vec![3, 4])));
+
+			fast_forward_to(4);
+			assert_ok!(Democracy::external_propose(Origin::signed(2), h));
+			// 4 still may not veto again, even after its own cooloff has passed.
+			assert_noop!(Democracy::veto_external(Origin::signed(4), h), Error::<Test, _>::AlreadyVetoed);
+		});
+	}
+
+	#[test]
+	fn paused_launch_should_not_table_referenda() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			assert_noop!(Democracy::set_launch_paused(Origin::signed(1), true), BadOrigin);
+			assert_ok!(Democracy::set_launch_paused(Origin::ROOT, true));
+			assert_eq!(last_event(), RawEvent::LaunchPausedSet(true));
+
+			fast_forward_to(4);
+			assert_eq!(Democracy::referendum_count(), 0);
+			assert_eq!(Democracy::public_props().len(), 1);
+
+			assert_ok!(Democracy::set_launch_paused(Origin::ROOT, false));
+			fast_forward_to(6);
+			assert_eq!(Democracy::referendum_count(), 1);
+			assert!(Democracy::public_props().is_empty());
+		});
+	}
+
+	#[test]
+	fn instances_should_have_separate_storage() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			fast_forward_to(2);
+			assert_eq!(PublicPropCount::<DefaultInstance>::get(), 1);
+			assert_eq!(ReferendumCount::<DefaultInstance>::get(), 1);
+			assert_eq!(PublicPropCount::<Instance1>::get(), 0);
+			assert_eq!(ReferendumCount::<Instance1>::get(), 0);
+		});
+	}
+
+	#[test]
+	fn emergency_calls_should_be_operational() {
+		let h = set_balance_proposal_hash(2);
+		let calls: Vec<super::Call<Test>> = vec![
+			super::Call::emergency_cancel(0),
+			super::Call::fast_track(h, 2, 0),
+			super::Call::veto_external(h),
+			super::Call::cancel_referendum(0),
+			super::Call::cancel_queued(0),
+			super::Call::clear_public_proposals(true),
+		];
+		for call in calls {
+			assert_eq!(call.get_dispatch_info().class, DispatchClass::Operational);
+		}
+	}
+
+	#[test]
+	fn veto_external_beyond_max_vetoers_should_not_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let h = set_balance_proposal_hash_and_note(2);
+			for who in 2..5 {
+				assert_ok!(Democracy::external_propose(Origin::signed(2), h));
+				assert_ok!(Democracy::veto_external(Origin::signed(who), h));
+				fast_forward_to(2 * (who - 1));
+			}
+			assert_eq!(<Blacklist<Test>>::get(&h), Some((6, vec![2, 3, 4])));
+
+			assert_ok!(Democracy::external_propose(Origin::signed(2), h));
+			assert_noop!(Democracy::veto_external(Origin::signed(5), h), Error::<Test, _>::TooManyVetoers);
+		});
+	}
+
+	#[test]
+	fn external_referendum_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_noop!(
+				Democracy::external_propose(
+					Origin::signed(1),
+					set_balance_proposal_hash(2),
+				),
+				BadOrigin,
+			);
+			assert_ok!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash_and_note(2),
+			));
+			assert_noop!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash(1),
+			), Error::<Test, _>::ProposalAlreadyQueued);
+			assert_noop!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash(2),
+			), Error::<Test, _>::DuplicateProposal);
+			fast_forward_to(2);
+			assert_eq!(
+				Democracy::referendum_info(0),
+				Some(ReferendumInfo {
+					end: 4,
+					proposal: set_balance_proposal_lookup(2),
+					threshold: VoteThreshold::SuperMajorityApprove,
+					delay: 2
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn external_majority_referendum_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_noop!(
+				Democracy::external_propose_majority(
+					Origin::signed(1),
+					set_balance_proposal_hash(2)
+				),
+				BadOrigin,
+			);
+			assert_ok!(Democracy::external_propose_majority(
+				Origin::signed(3),
+				set_balance_proposal_hash_and_note(2)
+			));
+			fast_forward_to(2);
+			assert_eq!(
+				Democracy::referendum_info(0),
+				Some(ReferendumInfo {
+					end: 4,
+					proposal: set_balance_proposal_lookup(2),
+					threshold: VoteThreshold::SimpleMajority,
+					delay: 2,
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn external_origins_should_be_independent() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let h = set_balance_proposal_hash_and_note(2);
+			for who in &[1, 3] {
+				assert_noop!(Democracy::external_propose(Origin::signed(*who), h), BadOrigin);
+			}
+			for who in &[1, 2] {
+				let origin = Origin::signed(*who);
+				assert_noop!(Democracy::external_propose_majority(origin, h), BadOrigin);
+			}
+			for who in &[2, 3] {
+				let origin = Origin::signed(*who);
+				assert_noop!(Democracy::external_propose_default(origin, h), BadOrigin);
+			}
+		});
+	}
+
+	#[test]
+	fn external_default_referendum_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_noop!(
+				Democracy::external_propose_default(
+					Origin::signed(3),
+					set_balance_proposal_hash(2)
+				),
+				BadOrigin,
+			);
+			assert_ok!(Democracy::external_propose_default(
+				Origin::signed(1),
+				set_balance_proposal_hash_and_note(2)
+			));
+			fast_forward_to(2);
+			assert_eq!(
+				Democracy::referendum_info(0),
+				Some(ReferendumInfo {
+					end: 4,
+					proposal: set_balance_proposal_lookup(2),
+					threshold: VoteThreshold::SuperMajorityAgainst,
+					delay: 2,
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn external_proposals_should_not_be_silently_replaced() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash(2),
+			));
+			// A majority-carries proposal may replace one made through `external_propose`...
+			assert_ok!(Democracy::external_propose_majority(
+				Origin::signed(3),
+				set_balance_proposal_hash(2),
+			));
+			assert_eq!(
+				<NextExternal<Test>>::get().map(|(_, threshold)| threshold),
+				Some(VoteThreshold::SimpleMajority),
+			);
+			// ...but nothing replaces it in turn.
+			assert_noop!(
+				Democracy::external_propose_majority(
+					Origin::signed(3),
+					set_balance_proposal_hash(2),
+				),
+				Error::<Test, _>::DuplicateProposal,
+			);
+			assert_noop!(
+				Democracy::external_propose_default(
+					Origin::signed(1),
+					set_balance_proposal_hash(3),
+				),
+				Error::<Test, _>::ProposalAlreadyQueued,
+			);
+			assert_noop!(
+				Democracy::external_propose(Origin::signed(2), set_balance_proposal_hash(3)),
+				Error::<Test, _>::ProposalAlreadyQueued,
+			);
+		});
+	}
+
+	#[test]
+	fn fast_track_referendum_works() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let h = set_balance_proposal_hash_and_note(2);
+			assert_noop!(Democracy::fast_track(Origin::signed(5), h, 3, 2), Error::<Test, _>::ProposalMissing);
+			assert_ok!(Democracy::external_propose_majority(
+				Origin::signed(3),
+				set_balance_proposal_hash_and_note(2)
+			));
+			assert_noop!(Democracy::fast_track(Origin::signed(1), h, 3, 2), BadOrigin);
+			assert_ok!(Democracy::fast_track(Origin::signed(5), h, 2, 0));
+			assert_eq!(
+				Democracy::referendum_info(0),
+				Some(ReferendumInfo {
+					end: 2,
+					proposal: set_balance_proposal_lookup(2),
+					threshold: VoteThreshold::SimpleMajority,
+					delay: 0,
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn whitelisted_proposal_should_be_tabled() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let h = set_balance_proposal_hash_and_note(2);
+			assert_noop!(
+				Democracy::dispatch_whitelisted(Origin::signed(2), h),
+				Error::<Test, _>::NotWhitelisted,
+			);
+			assert_noop!(Democracy::whitelist_proposal(Origin::signed(2), h), BadOrigin);
+			assert_ok!(Democracy::whitelist_proposal(Origin::signed(6), h));
+			assert_eq!(last_event(), RawEvent::Whitelisted(h));
+			assert_noop!(
+				Democracy::whitelist_proposal(Origin::signed(6), h),
+				Error::<Test, _>::AlreadyWhitelisted,
+			);
+			assert_noop!(Democracy::dispatch_whitelisted(Origin::signed(6), h), BadOrigin);
+
+			<Blacklist<Test>>::insert(h, (1, vec![3]));
+			assert_noop!(
+				Democracy::dispatch_whitelisted(Origin::signed(2), h),
+				Error::<Test, _>::ProposalBlacklisted,
+			);
+			fast_forward_to(1);
+
+			assert_ok!(Democracy::dispatch_whitelisted(Origin::signed(2), h));
+			assert_eq!(last_event(), RawEvent::WhitelistedTabled(h, 0));
+			assert!(!Democracy::is_whitelisted(h));
+			assert_eq!(
+				Democracy::referendum_info(0),
+				Some(ReferendumInfo {
+					end: 3,
+					proposal: set_balance_proposal_lookup(2),
+					threshold: VoteThreshold::SimpleMajority,
+					delay: 2,
+				})
+			);
+			assert_noop!(
+				Democracy::dispatch_whitelisted(Origin::signed(2), h),
+				Error::<Test, _>::NotWhitelisted,
+			);
+
+			assert_ok!(Democracy::whitelist_proposal(Origin::signed(6), h));
+			assert_noop!(Democracy::remove_whitelisted_proposal(Origin::signed(2), h), BadOrigin);
+			assert_ok!(Democracy::remove_whitelisted_proposal(Origin::signed(6), h));
+			assert_eq!(last_event(), RawEvent::WhitelistRemoved(h));
+			assert!(!Democracy::is_whitelisted(h));
+		});
+	}
+
+	#[test]
+	fn fast_track_should_enforce_minimum_voting_period() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let h = set_balance_proposal_hash_and_note(2);
+			assert_ok!(Democracy::external_propose_majority(Origin::signed(3), h));
+			assert_noop!(
+				Democracy::fast_track(Origin::signed(5), h, 0, 0),
+				Error::<Test, _>::VotingPeriodLow,
+			);
+			assert_noop!(
+				Democracy::fast_track(Origin::signed(5), h, 1, 0),
+				Error::<Test, _>::VotingPeriodLow,
+			);
+			// The instant origin may go below the minimum, but never down to zero.
+			assert_noop!(
+				Democracy::fast_track(Origin::signed(6), h, 0, 0),
+				Error::<Test, _>::VotingPeriodLow,
+			);
+			assert_ok!(Democracy::fast_track(Origin::signed(6), h, 1, 0));
+			assert_eq!(Democracy::referendum_info(0).map(|info| info.end), Some(1));
+			assert_ok!(Democracy::vote(Origin::signed(1), 0, AYE));
+
+			fast_forward_to(1);
+			assert_eq!(Balances::free_balance(42), 2);
+		});
+	}
+
+	#[test]
+	fn fast_track_referendum_fails_when_no_simple_majority() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let h = set_balance_proposal_hash_and_note(2);
+			assert_ok!(Democracy::external_propose(
+				Origin::signed(2),
+				set_balance_proposal_hash_and_note(2)
+			));
+			assert_noop!(
+				Democracy::fast_track(Origin::signed(5), h, 3, 2),
+				Error::<Test, _>::NotSimpleMajority
+			);
+		});
+	}
+
+	#[test]
+	fn locked_for_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			assert_ok!(propose_set_balance_and_note(1, 4, 4));
+			assert_ok!(propose_set_balance_and_note(1, 3, 3));
+			assert_eq!(Democracy::locked_for(0), Some(2));
+			assert_eq!(Democracy::locked_for(1), Some(4));
+			assert_eq!(Democracy::locked_for(2), Some(3));
+		});
+	}
+
+	#[test]
+	fn single_proposal_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			assert!(Democracy::referendum_info(0).is_none());
+
+			// start of 2 => next referendum scheduled.
+			fast_forward_to(2);
+
+			let r = 0;
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+
+			assert_eq!(Democracy::referendum_count(), 1);
+			assert_eq!(
+				Democracy::referendum_info(0),
+				Some(ReferendumInfo {
+					end: 4,
+					proposal: set_balance_proposal_lookup(2),
+					threshold: VoteThreshold::SuperMajorityApprove,
+					delay: 2
+				})
+			);
+			assert_eq!(Democracy::voters_for(r), vec![1]);
+			assert_eq!(Democracy::vote_of((r, 1)), AYE);
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 0, turnout: 10 });
+
+			fast_forward_to(3);
+
+			// referendum still running
+			assert!(Democracy::referendum_info(0).is_some());
+
+			// referendum runs during 2 and 3, ends @ start of 4.
+			fast_forward_to(4);
+
+			assert!(Democracy::referendum_info(0).is_none());
+			assert_eq!(Democracy::dispatch_queue(), vec![
+				(6, set_balance_proposal_lookup(2), 0)
+			]);
+
+			// referendum passes and wait another two blocks for enactment.
+			fast_forward_to(6);
+
+			assert_eq!(Balances::free_balance(42), 2);
+		});
+	}
+
+	#[test]
+	fn set_enactment_delay_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			fast_forward_to(2);
+			assert_ok!(Democracy::vote(Origin::signed(1), 0, AYE));
+			fast_forward_to(4);
+			assert_eq!(Democracy::dispatch_queue(), vec![(6, set_balance_proposal_lookup(2), 0)]);
+
+			assert_noop!(Democracy::set_enactment_delay(Origin::signed(1), 0, 4), BadOrigin);
+			assert_noop!(
+				Democracy::set_enactment_delay(Origin::ROOT, 1, 4),
+				Error::<Test, _>::ProposalMissing
+			);
+
+			// postponed by the fast-track origin.
+			assert_ok!(Democracy::set_enactment_delay(Origin::signed(5), 0, 4));
+			assert_eq!(last_event(), RawEvent::EnactmentRescheduled(0, 8));
+			assert_eq!(Democracy::dispatch_queue(), vec![(8, set_balance_proposal_lookup(2), 0)]);
+			fast_forward_to(6);
+			assert_eq!(Balances::free_balance(42), 0);
+
+			// then brought forward by root.
+			assert_ok!(Democracy::set_enactment_delay(Origin::ROOT, 0, 0));
+			assert_eq!(Democracy::dispatch_queue(), vec![(7, set_balance_proposal_lookup(2), 0)]);
+			fast_forward_to(7);
+			assert_eq!(Balances::free_balance(42), 2);
+		});
+	}
+
+	#[test]
+	fn cancel_queued_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+
+			// start of 2 => next referendum scheduled.
+			fast_forward_to(2);
+
+			assert_ok!(Democracy::vote(Origin::signed(1), 0, AYE));
+
+			fast_forward_to(4);
+
+			assert_eq!(Democracy::dispatch_queue(), vec![
+				(6, set_balance_proposal_lookup(2), 0)
+			]);
+
+			assert_noop!(Democracy::cancel_queued(Origin::ROOT, 1), Error::<Test, _>::ProposalMissing);
+			assert_ok!(Democracy::cancel_queued(Origin::ROOT, 0));
+			assert_eq!(Democracy::dispatch_queue(), vec![]);
+			assert_eq!(last_event(), RawEvent::QueuedCancelled(0));
+		});
+	}
+
+	#[test]
+	fn abstentions_should_count_toward_turnout() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, BIG_NAY));
+			assert_ok!(Democracy::delegate(Origin::signed(5), 2, Conviction::Locked1x));
+
+			assert_ok!(Democracy::abstain(Origin::signed(2), r));
+			assert_eq!(last_event(), RawEvent::Abstained(2, r));
+			assert_noop!(
+				Democracy::abstain(Origin::signed(2), r),
+				Error::<Test, _>::AlreadyAbstaining
+			);
+			assert_ok!(Democracy::abstain(Origin::signed(6), r));
+			assert_eq!(Democracy::voters_for(r), vec![1]);
+			assert_eq!(Democracy::abstainers(r), vec![2, 6]);
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 0, turnout: 140 });
+
+			assert_ok!(Democracy::vote(Origin::signed(6), r, NAY));
+			assert_ok!(Democracy::remove_vote(Origin::signed(2), r));
+			assert_noop!(Democracy::remove_vote(Origin::signed(2), r), Error::<Test, _>::NotVoter);
+			assert_eq!(Democracy::abstainers(r), vec![]);
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 6, turnout: 70 });
+
+			assert_ok!(Democracy::abstain(Origin::signed(3), r));
+			fast_forward_to(2);
+			assert_eq!(Democracy::abstainers(r), vec![]);
+			assert_eq!(Democracy::locks(3), None);
+		});
+	}
+
+	#[test]
+	fn class_delegation_should_override_delegation() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let inject = |threshold| Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				threshold,
+				0
+			);
+			let a = inject(VoteThreshold::SuperMajorityApprove);
+			let b = inject(VoteThreshold::SimpleMajority);
+			for r in &[a, b] {
+				assert_ok!(Democracy::vote(Origin::signed(2), *r, BIG_AYE));
+				assert_ok!(Democracy::vote(Origin::signed(3), *r, BIG_NAY));
+			}
+
+			assert_ok!(Democracy::delegate(Origin::signed(1), 2, Conviction::Locked1x));
+			assert_ok!(Democracy::delegate_class(
+				Origin::signed(1),
+				VoteThreshold::SimpleMajority,
+				3,
+				Conviction::Locked1x
+			));
+			assert_eq!(last_event(), RawEvent::ClassDelegated(1, VoteThreshold::SimpleMajority, 3));
+			assert_eq!(Democracy::tally(a), Tally { ayes: 30, nays: 30, turnout: 60 });
+			assert_eq!(Democracy::tally(b), Tally { ayes: 20, nays: 40, turnout: 60 });
+
+			assert_ok!(Democracy::undelegate(Origin::signed(1)));
+			assert_eq!(Democracy::tally(a), Tally { ayes: 20, nays: 30, turnout: 50 });
+			assert_eq!(Democracy::tally(b), Tally { ayes: 20, nays: 40, turnout: 60 });
+			assert_eq!(Balances::locks(1)[0].amount, u64::max_value());
+
+			let class = VoteThreshold::SimpleMajority;
+			assert_ok!(Democracy::undelegate_class(Origin::signed(1), class));
+			assert_noop!(
+				Democracy::undelegate_class(Origin::signed(1), class),
+				Error::<Test, _>::NotDelegated
+			);
+			assert_eq!(Democracy::tally(b), Tally { ayes: 20, nays: 30, turnout: 50 });
+			assert_eq!(Balances::locks(1)[0].amount, 10);
+		});
+	}
+
+	#[test]
+	fn delegators_should_be_indexed_and_bounded() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let class = VoteThreshold::SimpleMajority;
+			for who in 1..5 {
+				assert_ok!(Democracy::delegate(Origin::signed(who), 5, Conviction::None));
+			}
+			assert_noop!(
+				Democracy::delegate(Origin::signed(6), 5, Conviction::None),
+				Error::<Test, _>::TooManyDelegators
+			);
+			assert_noop!(
+				Democracy::delegate_class(Origin::signed(6), class, 5, Conviction::None),
+				Error::<Test, _>::TooManyDelegators
+			);
+			assert_eq!(Democracy::delegators(5), vec![1, 2, 3, 4]);
+
+			// An account is indexed once, for as long as any of its delegations goes to them.
+			assert_ok!(Democracy::delegate_class(Origin::signed(1), class, 5, Conviction::None));
+			assert_ok!(Democracy::undelegate(Origin::signed(1)));
+			assert_eq!(Democracy::delegators(5), vec![1, 2, 3, 4]);
+			assert_ok!(Democracy::undelegate_class(Origin::signed(1), class));
+			assert_eq!(Democracy::delegators(5), vec![2, 3, 4]);
+
+			assert_ok!(Democracy::delegate(Origin::signed(2), 6, Conviction::None));
+			assert_eq!(Democracy::delegators(5), vec![3, 4]);
+			assert_eq!(Democracy::delegators(6), vec![2]);
+			assert_ok!(Democracy::delegate(Origin::signed(6), 5, Conviction::None));
+			assert_eq!(Democracy::do_try_state(), Ok(()));
+		});
+	}
+
+	#[test]
+	fn delegations_should_be_charged_when_baked() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::None));
+			assert_ok!(Democracy::delegate(Origin::signed(3), 2, Conviction::None));
+			assert_eq!(Democracy::count_tally(r).1, 2);
+
+			let info = Democracy::referendum_info(r).unwrap();
+			assert_eq!(Democracy::bake_referendum(2, r, info), Ok(2));
+		});
+	}
+
+	#[test]
+	fn referendum_hooks_should_be_called() {
+		new_test_ext().execute_with(|| {
+			HOOKS_CALLED.with(|v| v.borrow_mut().clear());
+			System::set_block_number(1);
+			for value in 2..5 {
+				let r = Democracy::inject_referendum(
+					2,
+					set_balance_proposal_lookup(value),
+					VoteThreshold::SimpleMajority,
+					0
+				);
+				let vote = if value == 3 { NAY } else { AYE };
+				assert_ok!(Democracy::vote(Origin::signed(1), r, vote));
+			}
+			assert_ok!(Democracy::cancel_referendum(Origin::ROOT, 2));
+
+			fast_forward_to(2);
+			assert_eq!(HOOKS_CALLED.with(|v| v.borrow_mut().split_off(0)), vec![
+				("started", 0),
+				("started", 1),
+				("started", 2),
+				("cancelled", 2),
+				("passed", 0),
+				("failed", 1),
+			]);
+		});
+	}
+
+	#[test]
+	fn dispatch_queue_should_be_bounded() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			for value in 2..5 {
+				let r = Democracy::inject_referendum(
+					2,
+					set_balance_proposal_lookup(value),
+					VoteThreshold::SimpleMajority,
+					3
+				);
+				assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			}
+
+			fast_forward_to(2);
+			assert_eq!(Democracy::dispatch_queue(), vec![
+				(5, set_balance_proposal_lookup(3), 1),
+				(5, set_balance_proposal_lookup(2), 0),
+			]);
+			assert_eq!(last_event(), RawEvent::QueueFull(2));
+		});
+	}
+
+	#[test]
+	#[cfg(feature = "legacy-proxy")]
+	fn proxy_should_work() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(Democracy::proxy(10), None);
+			assert!(System::allow_death(&10));
+
+			assert_noop!(Democracy::activate_proxy(Origin::signed(1), 10), Error::<Test, _>::NotOpen);
+
+			assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
+			assert!(!System::allow_death(&10));
+			assert_eq!(Democracy::proxy(10), Some(ProxyState::Open(1)));
+
+			assert_noop!(Democracy::activate_proxy(Origin::signed(2), 10), Error::<Test, _>::WrongOpen);
+			assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
+			assert_eq!(Democracy::proxy(10), Some(ProxyState::Active(1)));
+
+			// Can't set when already set.
+			assert_noop!(Democracy::activate_proxy(Origin::signed(2), 10), Error::<Test, _>::AlreadyProxy);
+
+			// But this works because 11 isn't proxying.
+			assert_ok!(Democracy::open_proxy(Origin::signed(11), 2));
+			assert_ok!(Democracy::activate_proxy(Origin::signed(2), 11));
+			assert_eq!(Democracy::proxy(10), Some(ProxyState::Active(1)));
+			assert_eq!(Democracy::proxy(11), Some(ProxyState::Active(2)));
+
+			// 2 cannot fire 1's proxy:
+			assert_noop!(Democracy::deactivate_proxy(Origin::signed(2), 10), Error::<Test, _>::WrongProxy);
+
+			// 1 deactivates their proxy:
+			assert_ok!(Democracy::deactivate_proxy(Origin::signed(1), 10));
+			assert_eq!(Democracy::proxy(10), Some(ProxyState::Open(1)));
+			// but the proxy account cannot be killed until the proxy is closed.
+			assert!(!System::allow_death(&10));
+
+			// and then 10 closes it completely:
+			assert_ok!(Democracy::close_proxy(Origin::signed(10)));
+			assert_eq!(Democracy::proxy(10), None);
+			assert!(System::allow_death(&10));
+
+			// 11 just closes without 2's "permission".
+			assert_ok!(Democracy::close_proxy(Origin::signed(11)));
+			assert_eq!(Democracy::proxy(11), None);
+			assert!(System::allow_death(&11));
+		});
+	}
+
+	#[test]
+	fn remove_proxies_should_work() {
+		new_test_ext().execute_with(|| {
+			Democracy::force_proxy(1, 10);
+			Democracy::force_proxy(2, 11);
+			assert!(!System::allow_death(&10));
+
+			migration::remove_proxies::<Test, _>();
+			assert_eq!(Democracy::proxy(10), None);
+			assert_eq!(Democracy::proxy(11), None);
+			assert!(System::allow_death(&10));
+			assert!(System::allow_death(&11));
+		});
+	}
+
+	#[test]
+	fn migration_removing_proxies_works() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::<DefaultInstance>::put(Releases::V6);
+			Democracy::force_proxy(1, 10);
+
+			migration::migrate::<Test, _>();
+			if cfg!(feature = "legacy-proxy") {
+				// the built-in proxy is left alone while it is enabled.
+				assert_eq!(Democracy::proxy(10), Some(ProxyState::Active(1)));
+				assert_eq!(StorageVersion::<DefaultInstance>::get(), Releases::V8);
+				return;
+			}
+			assert_eq!(Democracy::proxy(10), None);
+			assert!(System::allow_death(&10));
+			assert_eq!(StorageVersion::<DefaultInstance>::get(), Releases::V9);
+
+			// the migration only runs once.
+			Democracy::force_proxy(1, 10);
+			migration::migrate::<Test, _>();
+			assert_eq!(Democracy::proxy(10), Some(ProxyState::Active(1)));
+		});
+	}
+
+	#[test]
+	#[cfg(not(feature = "legacy-proxy"))]
+	fn proxy_calls_should_be_deprecated() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(Democracy::open_proxy(Origin::signed(10), 1), Error::<Test, _>::ProxyDeprecated);
+			Democracy::force_proxy(1, 10);
+			assert_noop!(Democracy::proxy_vote(Origin::signed(10), 0, AYE), Error::<Test, _>::ProxyDeprecated);
+			assert_noop!(Democracy::close_proxy(Origin::signed(10)), Error::<Test, _>::ProxyDeprecated);
+		});
+	}
+
+	#[test]
+	#[cfg(feature = "legacy-proxy")]
+	fn single_proposal_should_work_with_proxy() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+
+			fast_forward_to(2);
+			let r = 0;
+			assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
+			assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
+			assert_ok!(Democracy::proxy_vote(Origin::signed(10), r, AYE));
+
+			assert_eq!(Democracy::voters_for(r), vec![1]);
+			assert_eq!(Democracy::vote_of((r, 1)), AYE);
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 0, turnout: 10 });
+
+			fast_forward_to(6);
+			assert_eq!(Balances::free_balance(42), 2);
+		});
+	}
+
+	#[test]
+	fn conviction_params_should_scale_votes_in_tenths() {
+		let params = |votes_in_tenths| ConvictionParams { votes_in_tenths, lock_periods: 0 };
+		assert_eq!(params(1).votes(25u64), (2, 25));
+		assert_eq!(params(10).votes(25u64), (25, 25));
+		assert_eq!(params(15).votes(7u64), (10, 7));
+		assert_eq!(params(60).votes(25u64), (150, 25));
+		assert_eq!(params(20).votes(u64::max_value()), (u64::max_value(), u64::max_value()));
+	}
+
+	#[test]
+	fn custom_conviction_schedule_should_work() {
+		new_test_ext().execute_with(|| {
+			let mut schedule = DefaultConvictionSchedule::get();
+			schedule[0] = ConvictionParams { votes_in_tenths: 5, lock_periods: 0 };
+			schedule[1] = ConvictionParams { votes_in_tenths: 30, lock_periods: 5 };
+			CONVICTION_SCHEDULE.with(|v| *v.borrow_mut() = schedule);
+
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, BIG_AYE));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 65, nays: 0, turnout: 30 });
+
+			fast_forward_to(2);
+			assert_eq!(Democracy::locks(1), None);
+			assert_eq!(Democracy::locks(2), Some(PriorLock::new(12, 20)));
+
+			CONVICTION_SCHEDULE.with(|v| *v.borrow_mut() = DefaultConvictionSchedule::get());
+		});
+	}
+
+	#[test]
+	fn low_turnout_should_delay_enactment() {
+		new_test_ext().execute_with(|| {
+			ENACTMENT_STEPS.with(|v| *v.borrow_mut() = vec![(Perbill::from_percent(10), 3)]);
+
+			System::set_block_number(1);
+			let inject = |value| Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(value),
+				VoteThreshold::SimpleMajority,
+				0
+			);
+			// a turnout of 10 out of 210 is below the step.
+			let low = inject(2);
+			assert_ok!(Democracy::vote(Origin::signed(1), low, AYE));
+			// a turnout of 60 out of 210 is above it.
+			let high = inject(3);
+			assert_ok!(Democracy::vote(Origin::signed(6), high, AYE));
+
+			fast_forward_to(2);
+			assert_eq!(Balances::free_balance(42), 3);
+
+			fast_forward_to(4);
+			assert_eq!(Balances::free_balance(42), 3);
+
+			fast_forward_to(5);
+			assert_eq!(Balances::free_balance(42), 2);
+
+			ENACTMENT_STEPS.with(|v| *v.borrow_mut() = vec![]);
+		});
+	}
+
+	#[test]
+	fn late_votes_should_be_discounted() {
+		new_test_ext().execute_with(|| {
+			DISCOUNT_PERIOD.with(|v| *v.borrow_mut() = 2);
+
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				3,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_eq!(Democracy::voted_at((r, 1)), Some(1));
+
+			System::set_block_number(2);
+			assert_ok!(Democracy::vote(Origin::signed(2), r, BIG_AYE));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 13, nays: 0, turnout: 30 });
+
+			assert_ok!(Democracy::remove_vote(Origin::signed(2), r));
+			assert_eq!(Democracy::voted_at((r, 2)), None);
+
+			fast_forward_to(4);
+			assert_eq!(Democracy::voted_at((r, 1)), None);
+			assert!(!<VoteOf<Test>>::contains_key((r, 1)));
+
+			DISCOUNT_PERIOD.with(|v| *v.borrow_mut() = 0);
+		});
+	}
+
 	#[test]
-	fn emergency_cancel_should_work() {
+	fn proposal_metadata_should_follow_its_referendum() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(0);
-			let r = Democracy::inject_referendum(
-				2,
-				set_balance_proposal_hash_and_note(2),
-				VoteThreshold::SuperMajorityApprove,
-				2
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			let (h1, h2) = (H256::repeat_byte(1), H256::repeat_byte(2));
+			assert_noop!(
+				Democracy::set_metadata(Origin::signed(2), MetadataOwner::Proposal(0), Some(h1)),
+				Error::<Test, _>::NotProposer
 			);
-			assert!(Democracy::referendum_info(r).is_some());
-
-			assert_noop!(Democracy::emergency_cancel(Origin::signed(3), r), BadOrigin);
-			assert_ok!(Democracy::emergency_cancel(Origin::signed(4), r));
-			assert!(Democracy::referendum_info(r).is_none());
-
-			// some time later...
+			assert_noop!(
+				Democracy::set_metadata(Origin::signed(1), MetadataOwner::Proposal(1), Some(h1)),
+				Error::<Test, _>::ProposalMissing
+			);
+			assert_ok!(
+				Democracy::set_metadata(Origin::signed(1), MetadataOwner::Proposal(0), Some(h1))
+			);
+			assert_eq!(last_event(), RawEvent::MetadataSet(MetadataOwner::Proposal(0), h1));
+			assert_eq!(Democracy::metadata_of(MetadataOwner::Proposal(0)), Some(h1));
 
-			let r = Democracy::inject_referendum(
-				2,
-				set_balance_proposal_hash_and_note(2),
-				VoteThreshold::SuperMajorityApprove,
-				2
+			fast_forward_to(2);
+			assert_eq!(Democracy::metadata_of(MetadataOwner::Proposal(0)), None);
+			assert_eq!(Democracy::metadata_of(MetadataOwner::Referendum(0)), Some(h1));
+			assert_noop!(
+				Democracy::set_metadata(Origin::signed(1), MetadataOwner::Referendum(0), Some(h2)),
+				BadOrigin
 			);
-			assert!(Democracy::referendum_info(r).is_some());
-			assert_noop!(Democracy::emergency_cancel(Origin::signed(4), r), Error::<Test>::AlreadyCanceled);
+			assert_noop!(
+				Democracy::set_metadata(Origin::ROOT, MetadataOwner::Referendum(1), Some(h2)),
+				Error::<Test, _>::ReferendumInvalid
+			);
+			assert_ok!(
+				Democracy::set_metadata(Origin::ROOT, MetadataOwner::Referendum(0), Some(h2))
+			);
+			assert_eq!(Democracy::metadata_of(MetadataOwner::Referendum(0)), Some(h2));
+
+			fast_forward_to(4);
+			assert_eq!(Democracy::metadata_of(MetadataOwner::Referendum(0)), None);
 		});
 	}
 
 	#[test]
-	fn veto_external_works() {
+	fn external_metadata_should_work() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(0);
+			let h = H256::repeat_byte(1);
+			assert_noop!(
+				Democracy::set_metadata(Origin::signed(2), MetadataOwner::External, Some(h)),
+				Error::<Test, _>::NoProposal
+			);
 			assert_ok!(Democracy::external_propose(
 				Origin::signed(2),
 				set_balance_proposal_hash_and_note(2),
 			));
-			assert!(<NextExternal<Test>>::exists());
+			assert_noop!(
+				Democracy::set_metadata(Origin::signed(4), MetadataOwner::External, Some(h)),
+				BadOrigin
+			);
+			assert_ok!(
+				Democracy::set_metadata(Origin::signed(2), MetadataOwner::External, Some(h))
+			);
+			assert_ok!(Democracy::set_metadata(Origin::signed(2), MetadataOwner::External, None));
+			assert_eq!(last_event(), RawEvent::MetadataCleared(MetadataOwner::External));
+			assert_ok!(
+				Democracy::set_metadata(Origin::signed(3), MetadataOwner::External, Some(h))
+			);
 
-			let h = set_balance_proposal_hash_and_note(2);
-			assert_ok!(Democracy::veto_external(Origin::signed(3), h.clone()));
-			// cancelled.
-			assert!(!<NextExternal<Test>>::exists());
-			// fails - same proposal can't be resubmitted.
-			assert_noop!(Democracy::external_propose(
-				Origin::signed(2),
-				set_balance_proposal_hash(2),
-			), Error::<Test>::ProposalBlacklisted);
+			assert_ok!(Democracy::veto_external(Origin::signed(4), set_balance_proposal_hash(2)));
+			assert_eq!(Democracy::metadata_of(MetadataOwner::External), None);
 
-			fast_forward_to(1);
-			// fails as we're still in cooloff period.
-			assert_noop!(Democracy::external_propose(
-				Origin::signed(2),
-				set_balance_proposal_hash(2),
-			), Error::<Test>::ProposalBlacklisted);
+			let hash = set_balance_proposal_hash_and_note(3);
+			assert_ok!(Democracy::external_propose_majority(Origin::signed(3), hash));
+			assert_ok!(
+				Democracy::set_metadata(Origin::signed(3), MetadataOwner::External, Some(h))
+			);
+			assert_ok!(Democracy::fast_track(Origin::signed(5), hash, 2, 0));
+			assert_eq!(Democracy::metadata_of(MetadataOwner::External), None);
+			assert_eq!(Democracy::metadata_of(MetadataOwner::Referendum(0)), Some(h));
+		});
+	}
 
-			fast_forward_to(2);
-			// works; as we're out of the cooloff period.
-			assert_ok!(Democracy::external_propose(
-				Origin::signed(2),
-				set_balance_proposal_hash_and_note(2),
-			));
-			assert!(<NextExternal<Test>>::exists());
+	fn start_multi_referendum(rule: TallyRule) -> ReferendumIndex {
+		let options = vec![
+			set_balance_proposal_hash_and_note(2),
+			set_balance_proposal_hash_and_note(4),
+			set_balance_proposal_hash_and_note(6),
+		];
+		assert_ok!(Democracy::start_multi_referendum(Origin::ROOT, options, rule, 0));
+		Democracy::referendum_count() - 1
+	}
 
-			// 3 can't veto the same thing twice.
-			assert_noop!(
-				Democracy::veto_external(Origin::signed(3), h.clone()),
-				Error::<Test>::AlreadyVetoed
-			);
+	#[test]
+	fn multi_referendum_approval_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let r = start_multi_referendum(TallyRule::Approval);
+			assert_eq!(last_event(), RawEvent::MultiStarted(r, TallyRule::Approval));
 
-			// 4 vetoes.
-			assert_ok!(Democracy::veto_external(Origin::signed(4), h.clone()));
-			// cancelled again.
-			assert!(!<NextExternal<Test>>::exists());
+			assert_ok!(Democracy::vote_multi(Origin::signed(1), r, vec![0, 1]));
+			assert_eq!(last_event(), RawEvent::BallotCast(1, r));
+			assert_ok!(Democracy::vote_multi(Origin::signed(2), r, vec![1]));
+			assert_ok!(Democracy::vote_multi(Origin::signed(3), r, vec![2]));
+			assert_eq!(Democracy::voters_for(r), vec![1, 2, 3]);
+			assert_eq!(Democracy::locks(1), Some(PriorLock::new(3, 10)));
+
+			// Options 1 and 2 tie; the earlier one wins.
+			assert_eq!(Democracy::multi_tally(r), vec![10, 30, 30]);
 
 			fast_forward_to(3);
-			// same proposal fails as we're still in cooloff
-			assert_noop!(Democracy::external_propose(
-				Origin::signed(2),
-				set_balance_proposal_hash(2),
-			), Error::<Test>::ProposalBlacklisted);
-			// different proposal works fine.
-			assert_ok!(Democracy::external_propose(
-				Origin::signed(2),
-				set_balance_proposal_hash_and_note(3),
+			assert!(System::events().iter().any(|e|
+				e.event == TestEvent::democracy(RawEvent::MultiPassed(r, 1))
 			));
+			assert_eq!(Democracy::multi_referendum_info(r), None);
+			assert!(Democracy::multi_referenda().is_empty());
+			assert!(Democracy::voters_for(r).is_empty());
+			assert_eq!(Balances::free_balance(42), 4);
 		});
 	}
 
 	#[test]
-	fn external_referendum_works() {
+	fn multi_referendum_ranked_should_work() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(0);
+			System::set_block_number(1);
+			let r = start_multi_referendum(TallyRule::Ranked);
+
+			assert_ok!(Democracy::vote_multi(Origin::signed(1), r, vec![0]));
+			// A later ballot replaces the earlier one.
+			assert_ok!(Democracy::vote_multi(Origin::signed(1), r, vec![2, 0]));
+			assert_ok!(Democracy::vote_multi(Origin::signed(2), r, vec![0]));
+			assert_eq!(Democracy::multi_tally(r), vec![80, 0, 30]);
+
+			assert_ok!(Democracy::vote_multi(Origin::signed(3), r, vec![2, 1, 0]));
+			assert_eq!(Democracy::multi_tally(r), vec![110, 60, 120]);
+
+			fast_forward_to(3);
+			assert_eq!(Balances::free_balance(42), 6);
+		});
+	}
+
+	#[test]
+	fn multi_referendum_without_ballots_should_not_pass() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let r = start_multi_referendum(TallyRule::Approval);
+			fast_forward_to(3);
+			assert_eq!(last_event(), RawEvent::NotPassed(r));
+			assert_eq!(Balances::free_balance(42), 0);
+		});
+	}
+
+	#[test]
+	fn invalid_multi_referenda_and_ballots_should_not_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let h = set_balance_proposal_hash(2);
 			assert_noop!(
-				Democracy::external_propose(
-					Origin::signed(1),
-					set_balance_proposal_hash(2),
-				),
-				BadOrigin,
+				Democracy::start_multi_referendum(Origin::signed(1), vec![h, h], TallyRule::Approval, 0),
+				BadOrigin
 			);
-			assert_ok!(Democracy::external_propose(
-				Origin::signed(2),
-				set_balance_proposal_hash_and_note(2),
-			));
-			assert_noop!(Democracy::external_propose(
-				Origin::signed(2),
-				set_balance_proposal_hash(1),
-			), Error::<Test>::DuplicateProposal);
-			fast_forward_to(2);
-			assert_eq!(
-				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
-					end: 4,
-					proposal_hash: set_balance_proposal_hash(2),
-					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
+			assert_noop!(
+				Democracy::start_multi_referendum(Origin::ROOT, vec![h], TallyRule::Approval, 0),
+				Error::<Test, _>::WrongOptionCount
+			);
+			assert_noop!(
+				Democracy::start_multi_referendum(Origin::ROOT, vec![h; 4], TallyRule::Approval, 0),
+				Error::<Test, _>::WrongOptionCount
+			);
+
+			let r = start_multi_referendum(TallyRule::Ranked);
+			assert_noop!(Democracy::vote_multi(Origin::signed(1), r, vec![]), Error::<Test, _>::InvalidBallot);
+			assert_noop!(Democracy::vote_multi(Origin::signed(1), r, vec![3]), Error::<Test, _>::InvalidBallot);
+			assert_noop!(
+				Democracy::vote_multi(Origin::signed(1), r, vec![1, 0, 1]),
+				Error::<Test, _>::InvalidBallot
+			);
+			assert_noop!(
+				Democracy::vote_multi(Origin::signed(1), r + 1, vec![0]),
+				Error::<Test, _>::ReferendumInvalid
 			);
 		});
 	}
 
 	#[test]
-	fn external_majority_referendum_works() {
+	fn multi_referendum_ballots_should_be_bounded_deposited_and_removable() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(0);
+			System::set_block_number(1);
+			VOTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			let r = start_multi_referendum(TallyRule::Approval);
+			assert_noop!(Democracy::remove_vote(Origin::signed(1), r), Error::<Test, _>::NotVoter);
+
+			assert_ok!(Democracy::vote_multi(Origin::signed(1), r, vec![0]));
+			assert_ok!(Democracy::vote_multi(Origin::signed(1), r, vec![1]));
+			assert_ok!(Democracy::vote_multi(Origin::signed(2), r, vec![1]));
+			assert_eq!(Balances::reserved_balance(1), 1);
+			assert_eq!(Democracy::voting(1), vec![r]);
+			assert_eq!(Democracy::do_try_state(), Ok(()));
+
+			assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+			assert_eq!(last_event(), RawEvent::VoteRemoved(1, r));
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert!(!<BallotOf<Test>>::contains_key((r, 1)));
+			assert!(Democracy::voting(1).is_empty());
+			assert_eq!(Democracy::voters_for(r), vec![2]);
+			assert_eq!(Democracy::multi_tally(r), vec![0, 20, 0]);
+			assert_noop!(Democracy::remove_vote(Origin::signed(1), r), Error::<Test, _>::NotVoter);
+			// The deposit of a ballot cannot be reaped while its referendum is ongoing.
+			assert_noop!(Democracy::reap_vote(Origin::signed(2), 2, r), Error::<Test, _>::Early);
+
+			for i in 10..15 {
+				let h = set_balance_proposal_lookup(i);
+				let v = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 0);
+				assert_ok!(Democracy::vote(Origin::signed(1), v, AYE));
+			}
 			assert_noop!(
-				Democracy::external_propose_majority(
-					Origin::signed(1),
-					set_balance_proposal_hash(2)
-				),
-				BadOrigin,
-			);
-			assert_ok!(Democracy::external_propose_majority(
-				Origin::signed(3),
-				set_balance_proposal_hash_and_note(2)
-			));
-			fast_forward_to(2);
-			assert_eq!(
-				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
-					end: 4,
-					proposal_hash: set_balance_proposal_hash(2),
-					threshold: VoteThreshold::SimpleMajority,
-					delay: 2,
-				})
+				Democracy::vote_multi(Origin::signed(1), r, vec![0]),
+				Error::<Test, _>::MaxVotesReached
 			);
 		});
 	}
 
 	#[test]
-	fn external_default_referendum_works() {
+	fn lowest_unbaked_should_stop_at_ongoing_multi_referendum() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(0);
-			assert_noop!(
-				Democracy::external_propose_default(
-					Origin::signed(3),
-					set_balance_proposal_hash(2)
-				),
-				BadOrigin,
+			System::set_block_number(1);
+			let first = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(10),
+				VoteThreshold::SuperMajorityApprove,
+				0
 			);
-			assert_ok!(Democracy::external_propose_default(
-				Origin::signed(1),
-				set_balance_proposal_hash_and_note(2)
-			));
-			fast_forward_to(2);
-			assert_eq!(
-				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
-					end: 4,
-					proposal_hash: set_balance_proposal_hash(2),
-					threshold: VoteThreshold::SuperMajorityAgainst,
-					delay: 2,
-				})
+			let multi = start_multi_referendum(TallyRule::Approval);
+			Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(11),
+				VoteThreshold::SuperMajorityApprove,
+				0
 			);
+
+			assert_ok!(Democracy::cancel_referendum(Origin::ROOT, first.into()));
+			assert_eq!(Democracy::lowest_unbaked(), multi);
+			assert_eq!(Democracy::do_try_state(), Ok(()));
 		});
 	}
 
 	#[test]
-	fn fast_track_referendum_works() {
+	fn remove_vote_should_work() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(0);
-			let h = set_balance_proposal_hash_and_note(2);
-			assert_noop!(Democracy::fast_track(Origin::signed(5), h, 3, 2), Error::<Test>::ProposalMissing);
-			assert_ok!(Democracy::external_propose_majority(
-				Origin::signed(3),
-				set_balance_proposal_hash_and_note(2)
-			));
-			assert_noop!(Democracy::fast_track(Origin::signed(1), h, 3, 2), BadOrigin);
-			assert_ok!(Democracy::fast_track(Origin::signed(5), h, 0, 0));
-			assert_eq!(
-				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
-					end: 1,
-					proposal_hash: set_balance_proposal_hash_and_note(2),
-					threshold: VoteThreshold::SimpleMajority,
-					delay: 0,
-				})
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
 			);
+			assert_noop!(Democracy::remove_vote(Origin::signed(1), r), Error::<Test, _>::NotVoter);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
+
+			assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+			assert_eq!(last_event(), RawEvent::VoteRemoved(1, r));
+			assert_eq!(Democracy::voters_for(r), vec![2]);
+			assert!(!<VoteOf<Test>>::contains_key((r, 1)));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 2, nays: 0, turnout: 20 });
+
+			assert_noop!(Democracy::remove_vote(Origin::signed(1), r + 1), Error::<Test, _>::ReferendumInvalid);
 		});
 	}
 
 	#[test]
-	fn fast_track_referendum_fails_when_no_simple_majority() {
+	fn vote_deposits_should_be_returned() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(0);
-			let h = set_balance_proposal_hash_and_note(2);
-			assert_ok!(Democracy::external_propose(
-				Origin::signed(2),
-				set_balance_proposal_hash_and_note(2)
-			));
+			System::set_block_number(1);
+			VOTE_DEPOSIT.with(|v| *v.borrow_mut() = 11);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
 			assert_noop!(
-				Democracy::fast_track(Origin::signed(5), h, 3, 2),
-				Error::<Test>::NotSimpleMajority
+				Democracy::vote(Origin::signed(1), r, AYE),
+				BalancesError::<Test, _>::InsufficientBalance,
+			);
+			assert_noop!(
+				Democracy::vote_batch(Origin::signed(1), vec![(r, AYE)]),
+				Error::<Test, _>::InsufficientFunds,
 			);
+
+			VOTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(3), r, NAY));
+			assert_ok!(Democracy::vote(Origin::signed(3), r, AYE));
+			assert_eq!(Balances::reserved_balance(3), 1);
+			assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Democracy::vote_deposit((r, 1)), None);
+			assert_noop!(Democracy::reap_vote(Origin::signed(2), 2, r), Error::<Test, _>::Early);
+
+			fast_forward_to(2);
+			assert!(!<VoteOf<Test>>::contains_key((r, 2)));
+			assert_eq!(Democracy::vote_deposit((r, 2)), Some((1, 2)));
+			assert_ok!(Democracy::reap_vote(Origin::signed(2), 2, r));
+			assert_eq!(last_event(), RawEvent::VoteReaped(2, r, 1));
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_noop!(Democracy::reap_vote(Origin::signed(2), 2, r), Error::<Test, _>::NotVoter);
+
+			assert_noop!(Democracy::reap_vote(Origin::signed(2), 3, r), Error::<Test, _>::Early);
+			fast_forward_to(6);
+			assert_ok!(Democracy::reap_vote(Origin::signed(2), 3, r));
+			assert_eq!(Balances::reserved_balance(3), 0);
+			assert_eq!(Balances::free_balance(3), 30);
 		});
 	}
 
 	#[test]
-	fn locked_for_should_work() {
+	#[cfg(feature = "legacy-proxy")]
+	fn proxy_governance_calls_should_work() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
-			assert_ok!(propose_set_balance_and_note(1, 2, 2));
-			assert_ok!(propose_set_balance_and_note(1, 4, 4));
-			assert_ok!(propose_set_balance_and_note(1, 3, 3));
-			assert_eq!(Democracy::locked_for(0), Some(2));
-			assert_eq!(Democracy::locked_for(1), Some(4));
-			assert_eq!(Democracy::locked_for(2), Some(3));
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_noop!(
+				Democracy::proxy_delegate(Origin::signed(10), 2, Conviction::Locked1x),
+				Error::<Test, _>::NotProxy
+			);
+			assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
+			assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
+
+			assert_ok!(Democracy::proxy_vote(Origin::signed(10), r, AYE));
+			assert_ok!(Democracy::proxy_remove_vote(Origin::signed(10), r));
+			assert_eq!(last_event(), RawEvent::VoteRemoved(1, r));
+			assert!(Democracy::voters_for(r).is_empty());
+
+			assert_ok!(Democracy::proxy_delegate(Origin::signed(10), 2, Conviction::Locked1x));
+			assert_eq!(last_event(), RawEvent::Delegated(1, 2));
+			assert_eq!(Democracy::delegations(1), (2, Conviction::Locked1x));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 3, nays: 0, turnout: 30 });
+
+			assert_ok!(Democracy::proxy_undelegate(Origin::signed(10)));
+			assert_eq!(last_event(), RawEvent::Undelegated(1));
+			assert!(!<Delegations<Test>>::contains_key(1));
+			assert_noop!(Democracy::proxy_undelegate(Origin::signed(10)), Error::<Test, _>::NotDelegated);
 		});
 	}
 
 	#[test]
-	fn single_proposal_should_work() {
+	fn single_proposal_should_work_with_delegation() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(0);
+
 			assert_ok!(propose_set_balance_and_note(1, 2, 1));
-			assert!(Democracy::referendum_info(0).is_none());
 
-			// start of 2 => next referendum scheduled.
 			fast_forward_to(2);
 
+			// Delegate vote.
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()));
+
 			let r = 0;
 			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
-
-			assert_eq!(Democracy::referendum_count(), 1);
-			assert_eq!(
-				Democracy::referendum_info(0),
-				Some(ReferendumInfo {
-					end: 4,
-					proposal_hash: set_balance_proposal_hash_and_note(2),
-					threshold: VoteThreshold::SuperMajorityApprove,
-					delay: 2
-				})
-			);
 			assert_eq!(Democracy::voters_for(r), vec![1]);
 			assert_eq!(Democracy::vote_of((r, 1)), AYE);
-			assert_eq!(Democracy::tally(r), (1, 0, 1));
-
-			fast_forward_to(3);
-
-			// referendum still running
-			assert!(Democracy::referendum_info(0).is_some());
-
-			// referendum runs during 2 and 3, ends @ start of 4.
-			fast_forward_to(4);
-
-			assert!(Democracy::referendum_info(0).is_none());
-			assert_eq!(Democracy::dispatch_queue(), vec![
-				(6, set_balance_proposal_hash_and_note(2), 0)
-			]);
+			// Delegated vote is counted.
+			assert_eq!(Democracy::tally(r), Tally { ayes: 3, nays: 0, turnout: 30 });
 
-			// referendum passes and wait another two blocks for enactment.
 			fast_forward_to(6);
 
 			assert_eq!(Balances::free_balance(42), 2);
@@ -2304,117 +7104,304 @@ mod tests {
 	}
 
 	#[test]
-	fn cancel_queued_should_work() {
+	fn governance_queries_should_work() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(0);
-			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			assert_ok!(propose_set_balance(1, 4, 1));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
 
-			// start of 2 => next referendum scheduled.
 			fast_forward_to(2);
 
+			assert_eq!(Democracy::proposal_statuses(), vec![ProposalStatus {
+				index: 1,
+				proposal_hash: set_balance_proposal_hash(4),
+				proposer: 1,
+				deposit: 1,
+				backers: vec![1],
+			}]);
+
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked2x));
 			assert_ok!(Democracy::vote(Origin::signed(1), 0, AYE));
+			assert_eq!(Democracy::referendum_statuses(), vec![ReferendumStatus {
+				index: 0,
+				end: 4,
+				proposal_hash: set_balance_proposal_hash(2),
+				threshold: VoteThreshold::SuperMajorityApprove,
+				delay: 2,
+				tally: Tally { ayes: 3, nays: 0, turnout: 30 },
+			}]);
 
-			fast_forward_to(4);
+			assert_eq!(Democracy::account_governance(1), AccountGovernance {
+				votes: vec![(0, AYE)],
+				delegation: None,
+				prior_locked: 0,
+				prior_lock_expiry: 0,
+			});
+			assert_eq!(Democracy::account_governance(2), AccountGovernance {
+				votes: vec![],
+				delegation: Some((1, Conviction::Locked2x)),
+				prior_locked: 0,
+				prior_lock_expiry: 0,
+			});
+		});
+	}
 
-			assert_eq!(Democracy::dispatch_queue(), vec![
-				(6, set_balance_proposal_hash_and_note(2), 0)
-			]);
+	#[test]
+	fn abstentions_should_be_bounded_and_deposited_like_votes() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			VOTE_DEPOSIT.with(|v| *v.borrow_mut() = 1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::abstain(Origin::signed(1), r));
+			assert_eq!(Balances::reserved_balance(1), 1);
+			assert_eq!(Democracy::voting(1), vec![r]);
 
-			assert_noop!(Democracy::cancel_queued(Origin::ROOT, 1), Error::<Test>::ProposalMissing);
-			assert_ok!(Democracy::cancel_queued(Origin::ROOT, 0));
-			assert_eq!(Democracy::dispatch_queue(), vec![]);
+			// Turning the abstention into a vote keeps its deposit.
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_eq!(Balances::reserved_balance(1), 1);
+			assert_ok!(Democracy::abstain(Origin::signed(1), r));
+			assert_eq!(Balances::reserved_balance(1), 1);
+			assert_eq!(Democracy::do_try_state(), Ok(()));
+
+			assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert!(Democracy::voting(1).is_empty());
+
+			for i in 10..15 {
+				let h = set_balance_proposal_lookup(i);
+				let v = Democracy::inject_referendum(2, h, VoteThreshold::SuperMajorityApprove, 0);
+				assert_ok!(Democracy::abstain(Origin::signed(1), v));
+			}
+			assert_noop!(Democracy::abstain(Origin::signed(1), r), Error::<Test, _>::MaxVotesReached);
+
+			fast_forward_to(2);
+			assert!(Democracy::voting(1).is_empty());
+			assert_eq!(Democracy::vote_deposit((r + 1, 1)), Some((1, 2)));
 		});
 	}
 
 	#[test]
-	fn proxy_should_work() {
+	fn participation_hooks_should_be_called() {
 		new_test_ext().execute_with(|| {
-			assert_eq!(Democracy::proxy(10), None);
-			assert!(System::allow_death(&10));
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			PARTICIPATION.with(|v| v.borrow_mut().clear());
 
-			assert_noop!(Democracy::activate_proxy(Origin::signed(1), 10), Error::<Test>::NotOpen);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+			assert_ok!(Democracy::abstain(Origin::signed(3), r));
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::None));
+			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_ok!(Democracy::delegate_class(
+				Origin::signed(4),
+				VoteThreshold::SuperMajorityApprove,
+				1,
+				Conviction::None
+			));
+			assert_ok!(Democracy::undelegate_class(
+				Origin::signed(4),
+				VoteThreshold::SuperMajorityApprove
+			));
 
-			assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
-			assert!(!System::allow_death(&10));
-			assert_eq!(Democracy::proxy(10), Some(ProxyState::Open(1)));
+			assert_eq!(PARTICIPATION.with(|v| v.borrow_mut().split_off(0)), vec![
+				("vote", 1),
+				("remove_vote", 1),
+				("vote", 3),
+				("delegate", 2),
+				("undelegate", 2),
+				("delegate", 4),
+				("undelegate", 4),
+			]);
+		});
+	}
 
-			assert_noop!(Democracy::activate_proxy(Origin::signed(2), 10), Error::<Test>::WrongOpen);
-			assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
-			assert_eq!(Democracy::proxy(10), Some(ProxyState::Active(1)));
+	#[test]
+	fn vote_batch_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let inject = || Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			let refs = (0..6).map(|_| inject()).collect::<Vec<_>>();
 
-			// Can't set when already set.
-			assert_noop!(Democracy::activate_proxy(Origin::signed(2), 10), Error::<Test>::AlreadyProxy);
+			assert_noop!(
+				Democracy::vote_batch(Origin::signed(1), vec![(refs[0], AYE), (refs.len() as u32, NAY)]),
+				Error::<Test, _>::ReferendumInvalid
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), refs[0], NAY));
+			assert_ok!(Democracy::vote_batch(
+				Origin::signed(1),
+				vec![(refs[0], AYE), (refs[1], BIG_NAY), (refs[2], AYE), (refs[3], AYE)],
+			));
+			assert_eq!(last_event(), RawEvent::Voted(1, refs[3], AYE));
+			assert_eq!(Democracy::vote_of((refs[0], 1)), AYE);
+			assert_eq!(Democracy::vote_of((refs[1], 1)), BIG_NAY);
+			assert_eq!(Democracy::voting(1), refs[..4].to_vec());
 
-			// But this works because 11 isn't proxying.
-			assert_ok!(Democracy::open_proxy(Origin::signed(11), 2));
-			assert_ok!(Democracy::activate_proxy(Origin::signed(2), 11));
-			assert_eq!(Democracy::proxy(10), Some(ProxyState::Active(1)));
-			assert_eq!(Democracy::proxy(11), Some(ProxyState::Active(2)));
+			assert_noop!(
+				Democracy::vote_batch(Origin::signed(1), vec![(refs[4], AYE), (refs[5], AYE)]),
+				Error::<Test, _>::MaxVotesReached
+			);
+			assert_ok!(Democracy::vote_batch(Origin::signed(1), vec![(refs[4], AYE)]));
+			assert_noop!(
+				Democracy::vote(Origin::signed(1), refs[5], AYE),
+				Error::<Test, _>::MaxVotesReached
+			);
+		});
+	}
 
-			// 2 cannot fire 1's proxy:
-			assert_noop!(Democracy::deactivate_proxy(Origin::signed(2), 10), Error::<Test>::WrongProxy);
+	#[test]
+	fn quadratic_voting_should_work() {
+		new_test_ext().execute_with(|| {
+			QUADRATIC_VOTING.with(|v| *v.borrow_mut() = true);
 
-			// 1 deactivates their proxy:
-			assert_ok!(Democracy::deactivate_proxy(Origin::signed(1), 10));
-			assert_eq!(Democracy::proxy(10), Some(ProxyState::Open(1)));
-			// but the proxy account cannot be killed until the proxy is closed.
-			assert!(!System::allow_death(&10));
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SimpleMajority,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, Vote {
+				aye: true,
+				conviction: Conviction::Locked6x,
+			}));
+			assert_ok!(Democracy::vote(Origin::signed(6), r, BIG_NAY));
+			// sqrt(10) = 3 at 6x against sqrt(60) = 7 at 1x.
+			assert_eq!(Democracy::tally(r), Tally { ayes: 18, nays: 7, turnout: 70 });
 
-			// and then 10 closes it completely:
-			assert_ok!(Democracy::close_proxy(Origin::signed(10)));
-			assert_eq!(Democracy::proxy(10), None);
-			assert!(System::allow_death(&10));
+			assert_ok!(Democracy::delegate(Origin::signed(4), 6, Conviction::Locked1x));
+			// sqrt(40) = 6 more.
+			assert_eq!(Democracy::tally(r), Tally { ayes: 18, nays: 13, turnout: 110 });
 
-			// 11 just closes without 2's "permission".
-			assert_ok!(Democracy::close_proxy(Origin::signed(11)));
-			assert_eq!(Democracy::proxy(11), None);
-			assert!(System::allow_death(&11));
+			QUADRATIC_VOTING.with(|v| *v.borrow_mut() = false);
 		});
 	}
 
 	#[test]
-	fn single_proposal_should_work_with_proxy() {
+	fn snapshot_balances_should_weight_votes() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(0);
-			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			System::set_block_number(1);
+			SNAPSHOT_BALANCES.with(|v| *v.borrow_mut() = true);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(5), r, AYE));
+			assert_eq!(Democracy::balance_snapshot((r, 5)), Some(50));
+
+			// Buying more after voting, even if the vote is changed, adds nothing.
+			let _ = Balances::make_free_balance_be(&5, 80);
+			assert_ok!(Democracy::vote(Origin::signed(5), r, NAY));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 0, nays: 5, turnout: 50 });
+
+			// Selling after voting still counts.
+			let _ = Balances::make_free_balance_be(&5, 20);
+			assert_eq!(Democracy::tally(r), Tally { ayes: 0, nays: 2, turnout: 20 });
 
 			fast_forward_to(2);
-			let r = 0;
-			assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
-			assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
-			assert_ok!(Democracy::proxy_vote(Origin::signed(10), r, AYE));
+			assert_eq!(Democracy::balance_snapshot((r, 5)), None);
+		});
+	}
 
-			assert_eq!(Democracy::voters_for(r), vec![1]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
-			assert_eq!(Democracy::tally(r), (1, 0, 1));
+	#[test]
+	fn vote_index_should_write_vote_records_off_chain() {
+		let mut ext = new_test_ext();
+		let r = ext.execute_with(|| {
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(4), r, AYE));
+
+			INDEX_VOTES.with(|v| *v.borrow_mut() = true);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), r, NAY));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(3), r, AYE));
+			assert_ok!(Democracy::remove_vote(Origin::signed(3), r));
+			INDEX_VOTES.with(|v| *v.borrow_mut() = false);
 
+			// The records are not in state, so pruning the outcome leaves them be.
 			fast_forward_to(6);
-			assert_eq!(Balances::free_balance(42), 2);
+			assert_ok!(Democracy::prune_finished(Origin::ROOT, vec![r]));
+			r
 		});
+
+		let record = |vote, balance| VoteRecord::<u64, u64> { vote, balance, at: 1 }.encode();
+		let key = |who| Democracy::vote_index_key(r, &who);
+		assert_eq!(ext.offchain_storage(&key(1)), Some(Some(&record(NAY, 10)[..])));
+		assert_eq!(ext.offchain_storage(&key(2)), Some(Some(&record(AYE, 20)[..])));
+		assert_eq!(ext.offchain_storage(&key(3)), Some(None));
+		assert_eq!(ext.offchain_storage(&key(4)), None);
 	}
 
 	#[test]
-	fn single_proposal_should_work_with_delegation() {
+	fn voting_power_should_include_delegations() {
 		new_test_ext().execute_with(|| {
-			System::set_block_number(0);
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_eq!(Democracy::voting_power_of(1, r), 0);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, BIG_AYE));
+			assert_eq!(Democracy::voting_power_of(1, r), 10);
 
-			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x));
+			assert_ok!(Democracy::delegate(Origin::signed(3), 2, Conviction::None));
+			assert_eq!(Democracy::voting_power_of(1, r), 10 + 20 + 3);
+			assert_eq!(Democracy::voting_power_of(2, r), 0);
+			assert_eq!(Democracy::tally(r).ayes, 33);
+		});
+	}
 
-			fast_forward_to(2);
+	#[test]
+	fn account_locks_should_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SimpleMajority,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, BIG_AYE));
+			assert_ok!(Democracy::vote(Origin::signed(3), r, NAY));
+			assert_eq!(Democracy::account_locks(1), vec![(Some(r), 10, UnlockAt::IfWon(4))]);
+			assert_eq!(Democracy::account_locks(3), vec![]);
 
-			// Delegate vote.
-			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()));
+			fast_forward_to(3);
 
-			let r = 0;
-			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
-			assert_eq!(Democracy::voters_for(r), vec![1]);
-			assert_eq!(Democracy::vote_of((r, 1)), AYE);
-			// Delegated vote is counted.
-			assert_eq!(Democracy::tally(r), (3, 0, 3));
+			assert_eq!(Democracy::account_locks(1), vec![(None, 10, UnlockAt::Block(4))]);
 
-			fast_forward_to(6);
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x));
+			assert_eq!(Democracy::account_locks(2), vec![(None, 20, UnlockAt::WhileDelegating)]);
 
-			assert_eq!(Balances::free_balance(42), 2);
+			fast_forward_to(4);
+
+			assert_eq!(Democracy::account_locks(1), vec![]);
 		});
 	}
 
@@ -2436,7 +7423,7 @@ mod tests {
 			assert_eq!(Democracy::voters_for(r), vec![1]);
 
 			// Delegated vote is counted.
-			assert_eq!(Democracy::tally(r), (6, 0, 6));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 6, nays: 0, turnout: 60 });
 
 			fast_forward_to(6);
 
@@ -2458,16 +7445,174 @@ mod tests {
 			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
 			// Vote.
 			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
-			// Delegate vote.
-			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()));
+			// Delegating is refused while the vote stands.
+			assert_noop!(
+				Democracy::delegate(Origin::signed(2), 1, Conviction::max_value()),
+				Error::<Test, _>::VotesExist
+			);
 			assert_eq!(Democracy::voters_for(r), vec![1, 2]);
 			assert_eq!(Democracy::vote_of((r, 1)), AYE);
 			// Delegated vote is not counted.
-			assert_eq!(Democracy::tally(r), (3, 0, 3));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 3, nays: 0, turnout: 30 });
+
+			fast_forward_to(6);
+
+			assert_eq!(Balances::free_balance(42), 2);
+		});
+	}
+
+	#[test]
+	fn delegating_should_respect_direct_votes() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let start = |threshold| Democracy::inject_referendum(
+				10,
+				set_balance_proposal_lookup(2),
+				threshold,
+				0
+			);
+			let r = start(VoteThreshold::SuperMajorityApprove);
+			let other = start(VoteThreshold::SimpleMajority);
+			assert_ok!(Democracy::vote(Origin::signed(2), r, AYE));
+
+			// Delegation covering a direct vote is refused...
+			assert_noop!(
+				Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x),
+				Error::<Test, _>::VotesExist
+			);
+			assert_noop!(
+				Democracy::delegate_class(
+					Origin::signed(2),
+					VoteThreshold::SuperMajorityApprove,
+					1,
+					Conviction::Locked1x,
+				),
+				Error::<Test, _>::VotesExist
+			);
+			// ...but not one covering other referenda only.
+			assert_ok!(Democracy::delegate_class(
+				Origin::signed(2),
+				VoteThreshold::SimpleMajority,
+				1,
+				Conviction::Locked1x,
+			));
+			assert_ok!(Democracy::vote(Origin::signed(1), other, AYE));
+			assert_eq!(Democracy::tally(other), Tally { ayes: 3, nays: 0, turnout: 30 });
+			let class = VoteThreshold::SimpleMajority;
+			assert_ok!(Democracy::undelegate_class(Origin::signed(2), class));
+
+			// With `RemoveVotesOnDelegation`, delegating removes the votes instead.
+			REMOVE_VOTES_ON_DELEGATION.with(|v| *v.borrow_mut() = true);
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x));
+			REMOVE_VOTES_ON_DELEGATION.with(|v| *v.borrow_mut() = false);
+			assert!(System::events().iter().any(|e|
+				e.event == TestEvent::democracy(RawEvent::VoteRemoved(2, r))
+			));
+			assert_eq!(Democracy::voters_for(r), vec![]);
+			assert_eq!(Democracy::voting(2), vec![]);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 3, nays: 0, turnout: 30 });
+
+			// A direct vote while delegating overrides the delegation in its referendum only.
+			assert_ok!(Democracy::vote(Origin::signed(2), r, NAY));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 2, turnout: 30 });
+			assert_eq!(Democracy::tally(other), Tally { ayes: 3, nays: 0, turnout: 30 });
+
+			// Undelegating keeps the direct vote, and withdraws the delegated one.
+			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 2, turnout: 30 });
+			assert_eq!(Democracy::tally(other), Tally { ayes: 1, nays: 0, turnout: 10 });
+			assert_eq!(Democracy::voting(2), vec![r]);
+		});
+	}
+
+	#[test]
+	fn split_delegation_should_count_in_proportion() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(propose_set_balance_and_note(1, 2, 1));
+			fast_forward_to(2);
+
+			// 6 delegates half of its 60 to each of 1 and 2, the latter delegating on to 3.
+			let half = Perbill::from_percent(50);
+			let split = vec![(1, half), (2, half)];
+			assert_ok!(Democracy::delegate_split(Origin::signed(6), split, Conviction::None));
+			assert_ok!(Democracy::delegate(Origin::signed(2), 3, Conviction::None));
+
+			let r = 0;
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 4, nays: 0, turnout: 40 });
+			assert_ok!(Democracy::vote(Origin::signed(3), r, NAY));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 4, nays: 8, turnout: 120 });
+
+			// 6 votes for itself, with the whole of its balance.
+			assert_ok!(Democracy::vote(Origin::signed(6), r, AYE));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 7, nays: 5, turnout: 120 });
+		});
+	}
+
+	#[test]
+	fn invalid_split_delegation_should_not_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let half = Perbill::from_percent(50);
+			let split = |targets: Vec<(u64, Perbill)>|
+				Democracy::delegate_split(Origin::signed(6), targets, Conviction::None);
+			assert_noop!(split(vec![]), Error::<Test, _>::InvalidSplit);
+			assert_noop!(split(vec![(1, half), (1, half)]), Error::<Test, _>::InvalidSplit);
+			let too_much = vec![(1, half), (2, Perbill::from_percent(51))];
+			assert_noop!(split(too_much), Error::<Test, _>::InvalidSplit);
+			assert_noop!(
+				split(vec![(1, half), (2, half), (3, half), (4, half)]),
+				Error::<Test, _>::InvalidSplit
+			);
+			assert_ok!(split(vec![(1, Perbill::from_percent(20)), (2, half)]));
+		});
+	}
+
+	#[test]
+	fn split_delegation_should_replace_delegation() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			assert_ok!(Democracy::delegate(Origin::signed(6), 1, Conviction::None));
+			assert_ok!(Democracy::delegate_split(
+				Origin::signed(6),
+				vec![(2, Perbill::from_percent(50))],
+				Conviction::None,
+			));
+			assert!(!<Delegations<Test>>::contains_key(6));
+			assert!(Democracy::delegators(1).is_empty());
+			assert_eq!(Democracy::delegators(2), vec![6]);
+
+			assert_ok!(Democracy::delegate(Origin::signed(6), 1, Conviction::None));
+			assert_eq!(Democracy::split_delegations(6), None);
+			assert!(Democracy::delegators(2).is_empty());
 
-			fast_forward_to(6);
+			assert_ok!(Democracy::undelegate(Origin::signed(6)));
+			assert_noop!(Democracy::undelegate(Origin::signed(6)), Error::<Test, _>::NotDelegated);
+			assert!(Democracy::delegators(1).is_empty());
+		});
+	}
 
-			assert_eq!(Balances::free_balance(42), 2);
+	#[test]
+	fn split_delegation_should_respect_max_delegators() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			for who in 2..6 {
+				assert_ok!(Democracy::delegate(Origin::signed(who), 1, Conviction::None));
+			}
+			let split = vec![(3, Perbill::from_percent(50)), (1, Perbill::from_percent(50))];
+			assert_noop!(
+				Democracy::delegate_split(Origin::signed(6), split, Conviction::None),
+				Error::<Test, _>::TooManyDelegators
+			);
+
+			// Already a delegator of 1, account 2 may split its delegation across 1 and 6.
+			let split = vec![(1, Perbill::from_percent(50)), (6, Perbill::from_percent(50))];
+			assert_ok!(Democracy::delegate_split(Origin::signed(2), split, Conviction::None));
+			assert_eq!(Democracy::delegators(1), vec![2, 3, 4, 5]);
+			assert_eq!(Democracy::delegators(6), vec![2]);
+			assert_eq!(Democracy::do_try_state(), Ok(()));
 		});
 	}
 
@@ -2491,7 +7636,7 @@ mod tests {
 			assert_eq!(Democracy::vote_of((r, 1)), AYE);
 
 			// Delegated vote is not counted.
-			assert_eq!(Democracy::tally(r), (1, 0, 1));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			fast_forward_to(6);
 
@@ -2523,7 +7668,7 @@ mod tests {
 			assert_eq!(Democracy::vote_of((r, 1)), AYE);
 
 			// Delegated vote is not counted.
-			assert_eq!(Democracy::tally(r), (3, 0, 3));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 3, nays: 0, turnout: 30 });
 
 			fast_forward_to(6);
 
@@ -2536,10 +7681,10 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
 			assert_ok!(propose_set_balance_and_note(1, 2, 5));
-			assert_ok!(Democracy::second(Origin::signed(2), 0));
-			assert_ok!(Democracy::second(Origin::signed(5), 0));
-			assert_ok!(Democracy::second(Origin::signed(5), 0));
-			assert_ok!(Democracy::second(Origin::signed(5), 0));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
 			assert_eq!(Balances::free_balance(1), 5);
 			assert_eq!(Balances::free_balance(2), 15);
 			assert_eq!(Balances::free_balance(5), 35);
@@ -2551,10 +7696,10 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
 			assert_ok!(propose_set_balance_and_note(1, 2, 5));
-			assert_ok!(Democracy::second(Origin::signed(2), 0));
-			assert_ok!(Democracy::second(Origin::signed(5), 0));
-			assert_ok!(Democracy::second(Origin::signed(5), 0));
-			assert_ok!(Democracy::second(Origin::signed(5), 0));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
 			fast_forward_to(3);
 			assert_eq!(Balances::free_balance(1), 10);
 			assert_eq!(Balances::free_balance(2), 20);
@@ -2562,11 +7707,117 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn held_deposits_should_be_returned_after_referendum() {
+		new_test_ext().execute_with(|| {
+			HOLD_DEPOSITS.with(|v| *v.borrow_mut() = true);
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 5));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			fast_forward_to(2);
+			assert_eq!(Democracy::referendum_deposits(0), Some((5, vec![1, 2])));
+			assert_eq!(Balances::reserved_balance(2), 5);
+			assert_eq!(Democracy::do_try_state(), Ok(()));
+
+			fast_forward_to(4);
+			assert_eq!(Democracy::referendum_deposits(0), None);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			HOLD_DEPOSITS.with(|v| *v.borrow_mut() = false);
+		});
+	}
+
+	#[test]
+	fn held_deposits_should_follow_cancellation() {
+		new_test_ext().execute_with(|| {
+			HOLD_DEPOSITS.with(|v| *v.borrow_mut() = true);
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			assert_ok!(propose_set_balance_and_note(5, 3, 1));
+			fast_forward_to(2);
+
+			// root cancellation returns the deposits.
+			assert_ok!(Democracy::cancel_referendum(Origin::ROOT, 0));
+			assert_eq!(last_event(), RawEvent::DepositsRefunded(0));
+			assert_eq!(Balances::free_balance(1), 10);
+			assert_eq!(Balances::free_balance(2), 20);
+
+			// emergency cancellation slashes them.
+			fast_forward_to(4);
+			let slashed = SLASHED.with(|v| *v.borrow());
+			assert_ok!(Democracy::emergency_cancel(Origin::signed(4), 1));
+			assert!(System::events().iter().any(|r|
+				r.event == TestEvent::democracy(RawEvent::DepositsSlashed(1))
+			));
+			assert_eq!(Balances::reserved_balance(5), 0);
+			assert_eq!(Balances::free_balance(5), 49);
+			assert_eq!(SLASHED.with(|v| *v.borrow()), slashed + 1);
+			HOLD_DEPOSITS.with(|v| *v.borrow_mut() = false);
+		});
+	}
+
+	#[test]
+	fn cleared_proposal_deposits_should_be_slashed() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			assert_ok!(propose_set_balance_and_note(5, 3, 3));
+			let slashed = SLASHED.with(|v| *v.borrow());
+
+			assert_ok!(Democracy::clear_public_proposals(Origin::ROOT, false));
+			assert_eq!(Democracy::deposit_of(0), None);
+			assert_eq!(Democracy::deposit_of(1), None);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::free_balance(1), 8);
+			assert_eq!(Balances::free_balance(2), 18);
+			assert_eq!(Balances::free_balance(5), 47);
+			assert_eq!(SLASHED.with(|v| *v.borrow()), slashed + 7);
+		});
+	}
+
+	#[test]
+	fn cleared_proposal_deposits_may_be_refunded() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			let slashed = SLASHED.with(|v| *v.borrow());
+
+			assert_ok!(Democracy::clear_public_proposals(Origin::ROOT, true));
+			assert_eq!(Democracy::public_props(), vec![]);
+			assert_eq!(Democracy::deposit_of(0), None);
+			assert_eq!(Balances::free_balance(1), 10);
+			assert_eq!(Balances::free_balance(2), 20);
+			assert_eq!(SLASHED.with(|v| *v.borrow()), slashed);
+		});
+	}
+
+	#[test]
+	fn deposits_should_be_refunded_when_tabled() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
+			assert_eq!(Balances::reserved_balance(5), 4);
+
+			fast_forward_to(2);
+			assert!(Democracy::is_active_referendum(0));
+			assert_eq!(Democracy::deposit_of(0), None);
+			assert_eq!(Balances::reserved_balance(1), 0);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(Balances::reserved_balance(5), 0);
+		});
+	}
+
 	#[test]
 	fn proposal_with_deposit_below_minimum_should_not_work() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
-			assert_noop!(propose_set_balance(1, 2, 0), Error::<Test>::ValueLow);
+			assert_noop!(propose_set_balance(1, 2, 0), Error::<Test, _>::ValueLow);
 		});
 	}
 
@@ -2578,12 +7829,99 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn proposals_beyond_max_proposals_should_not_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let _ = Balances::make_free_balance_be(&1, 1_000);
+			for i in 0..MaxProposals::get() as u64 {
+				assert_ok!(propose_set_balance(1, i, 1));
+			}
+			assert_noop!(propose_set_balance(1, 100, 1), Error::<Test, _>::TooManyProposals);
+
+			// Tabling a proposal frees a slot in the queue.
+			fast_forward_to(2);
+			assert_ok!(propose_set_balance(1, 100, 1));
+		});
+	}
+
 	#[test]
 	fn poor_seconder_should_not_work() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
 			assert_ok!(propose_set_balance_and_note(2, 2, 11));
-			assert_noop!(Democracy::second(Origin::signed(1), 0), BalancesError::<Test, _>::InsufficientBalance);
+			assert_noop!(
+				Democracy::second(Origin::signed(1), 0, u32::max_value()),
+				BalancesError::<Test, _>::InsufficientBalance
+			);
+		});
+	}
+
+	#[test]
+	fn second_with_wrong_upper_bound_should_not_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 5));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, 1));
+			assert_noop!(Democracy::second(Origin::signed(5), 0, 1), Error::<Test, _>::WrongUpperBound);
+			assert_ok!(Democracy::second(Origin::signed(5), 0, 2));
+			assert_eq!(Democracy::deposit_of(0).unwrap().1, vec![1, 2, 5]);
+		});
+	}
+
+	#[test]
+	fn unsecond_should_return_deposit() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 5));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
+			assert_eq!(Balances::reserved_balance(2), 10);
+
+			assert_noop!(Democracy::unsecond(Origin::signed(2), 0, 2), Error::<Test, _>::WrongUpperBound);
+			assert_ok!(Democracy::unsecond(Origin::signed(2), 0, 3));
+			assert_eq!(last_event(), RawEvent::Unseconded(2, 0));
+			assert_eq!(Democracy::deposit_of(0).unwrap().1, vec![1, 2]);
+			assert_eq!(Balances::reserved_balance(2), 5);
+
+			assert_ok!(Democracy::unsecond(Origin::signed(2), 0, 2));
+			assert_noop!(Democracy::unsecond(Origin::signed(2), 0, 1), Error::<Test, _>::NotSeconder);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_noop!(Democracy::unsecond(Origin::signed(2), 1, 1), Error::<Test, _>::ProposalMissing);
+		});
+	}
+
+	#[test]
+	fn unsecond_should_keep_proposer_deposit() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 5));
+			assert_noop!(Democracy::unsecond(Origin::signed(1), 0, 1), Error::<Test, _>::NotSeconder);
+
+			// the proposer's own second may be withdrawn, but not the proposal's deposit.
+			assert_ok!(Democracy::second(Origin::signed(1), 0, 1));
+			assert_ok!(Democracy::unsecond(Origin::signed(1), 0, 2));
+			assert_eq!(Democracy::deposit_of(0).unwrap().1, vec![1]);
+			assert_eq!(Balances::reserved_balance(1), 5);
+		});
+	}
+
+	#[test]
+	fn seconds_beyond_max_deposits_should_not_work() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(propose_set_balance_and_note(1, 2, 2));
+			for _ in 1..MaxDeposits::get() {
+				assert_ok!(Democracy::second(Origin::signed(5), 0, u32::max_value()));
+			}
+			assert_noop!(
+				Democracy::second(Origin::signed(5), 0, u32::max_value()),
+				Error::<Test, _>::TooManyDeposits
+			);
+
+			// withdrawing a second makes room for another.
+			assert_ok!(Democracy::unsecond(Origin::signed(5), 0, u32::max_value()));
+			assert_ok!(Democracy::second(Origin::signed(2), 0, u32::max_value()));
 		});
 	}
 
@@ -2609,13 +7947,13 @@ mod tests {
 			System::set_block_number(1);
 			let r1 = Democracy::inject_referendum(
 				3,
-				set_balance_proposal_hash_and_note(3),
+				set_balance_proposal_lookup(3),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
 			let r2 = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -2623,7 +7961,7 @@ mod tests {
 			assert_ok!(Democracy::vote(Origin::signed(1), r2, AYE));
 			assert_eq!(Democracy::voters_for(r2), vec![1]);
 			assert_eq!(Democracy::vote_of((r2, 1)), AYE);
-			assert_eq!(Democracy::tally(r2), (1, 0, 1));
+			assert_eq!(Democracy::tally(r2), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			next_block();
 			assert_eq!(Balances::free_balance(42), 2);
@@ -2631,7 +7969,7 @@ mod tests {
 			assert_ok!(Democracy::vote(Origin::signed(1), r1, AYE));
 			assert_eq!(Democracy::voters_for(r1), vec![1]);
 			assert_eq!(Democracy::vote_of((r1, 1)), AYE);
-			assert_eq!(Democracy::tally(r1), (1, 0, 1));
+			assert_eq!(Democracy::tally(r1), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			next_block();
 			assert_eq!(Balances::free_balance(42), 3);
@@ -2644,7 +7982,7 @@ mod tests {
 			System::set_block_number(1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -2652,7 +7990,7 @@ mod tests {
 
 			assert_eq!(Democracy::voters_for(r), vec![1]);
 			assert_eq!(Democracy::vote_of((r, 1)), AYE);
-			assert_eq!(Democracy::tally(r), (1, 0, 1));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 0, turnout: 10 });
 
 			next_block();
 			next_block();
@@ -2661,13 +7999,90 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn finished_outcomes_should_be_kept_until_pruned() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let pass = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			let fail = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(3),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			let cancel = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(4),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), pass, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(1), fail, NAY));
+			assert_ok!(Democracy::cancel_referendum(Origin::ROOT, cancel.into()));
+
+			fast_forward_to(2);
+			assert_eq!(Democracy::finished_of(pass), Some(Finished { approved: true, end: 2 }));
+			assert_eq!(Democracy::finished_of(fail), Some(Finished { approved: false, end: 2 }));
+			assert_eq!(Democracy::finished_of(cancel), None);
+
+			fast_forward_to(5);
+			assert_noop!(
+				Democracy::prune_finished(Origin::ROOT, vec![pass, fail]),
+				Error::<Test, _>::NotPrunable
+			);
+			fast_forward_to(6);
+			assert_noop!(Democracy::prune_finished(Origin::signed(1), vec![pass]), BadOrigin);
+			assert_noop!(
+				Democracy::prune_finished(Origin::ROOT, vec![pass, cancel]),
+				Error::<Test, _>::NotPrunable
+			);
+			assert_ok!(Democracy::prune_finished(Origin::ROOT, vec![pass, fail]));
+			assert_eq!(last_event(), RawEvent::FinishedPruned(fail));
+			assert_eq!(Democracy::finished_of(pass), None);
+			assert_eq!(Democracy::finished_of(fail), None);
+		});
+	}
+
+	#[test]
+	fn minimum_deposit_should_rise_with_congestion_and_decay() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			DEPOSIT_SURCHARGE.with(|v| *v.borrow_mut() = 10);
+			assert_eq!(Democracy::effective_minimum_deposit(), 1);
+			assert_ok!(propose_set_balance(1, 2, 1));
+			assert_eq!(Democracy::effective_minimum_deposit(), 11);
+			assert_noop!(propose_set_balance(2, 3, 10), Error::<Test, _>::ValueLow);
+			assert_ok!(propose_set_balance(2, 3, 11));
+			assert_eq!(Democracy::effective_minimum_deposit(), 21);
+
+			// Half of the excess decays every block.
+			next_block();
+			assert_eq!(Democracy::effective_minimum_deposit(), 11);
+			next_block();
+			assert_eq!(Democracy::effective_minimum_deposit(), 6);
+			// One of the proposals has been tabled meanwhile.
+			assert_ok!(propose_set_balance(3, 4, 6));
+			assert_eq!(Democracy::effective_minimum_deposit(), 21);
+			next_block();
+			assert_eq!(Democracy::effective_minimum_deposit(), 11);
+			fast_forward_to(10);
+			assert_eq!(Democracy::effective_minimum_deposit(), 1);
+			DEPOSIT_SURCHARGE.with(|v| *v.borrow_mut() = 0);
+		});
+	}
+
 	#[test]
 	fn cancel_referendum_should_work() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -2687,7 +8102,7 @@ mod tests {
 			System::set_block_number(1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -2695,7 +8110,7 @@ mod tests {
 
 			assert_eq!(Democracy::voters_for(r), vec![1]);
 			assert_eq!(Democracy::vote_of((r, 1)), NAY);
-			assert_eq!(Democracy::tally(r), (0, 1, 1));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 0, nays: 1, turnout: 10 });
 
 			next_block();
 			next_block();
@@ -2710,7 +8125,7 @@ mod tests {
 			System::set_block_number(1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -2722,7 +8137,7 @@ mod tests {
 			assert_ok!(Democracy::vote(Origin::signed(5), r, BIG_NAY));
 			assert_ok!(Democracy::vote(Origin::signed(6), r, BIG_AYE));
 
-			assert_eq!(Democracy::tally(r), (110, 100, 210));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 110, nays: 100, turnout: 210 });
 
 			next_block();
 			next_block();
@@ -2731,13 +8146,32 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn turnout_should_count_capital_regardless_of_conviction() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(1), r, AYE));
+			assert_ok!(Democracy::vote(Origin::signed(2), r, Vote {
+				aye: false,
+				conviction: Conviction::Locked6x
+			}));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 1, nays: 120, turnout: 30 });
+		});
+	}
+
 	#[test]
 	fn delayed_enactment_should_work() {
 		new_test_ext().execute_with(|| {
 			System::set_block_number(1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				1
 			);
@@ -2748,7 +8182,7 @@ mod tests {
 			assert_ok!(Democracy::vote(Origin::signed(5), r, AYE));
 			assert_ok!(Democracy::vote(Origin::signed(6), r, AYE));
 
-			assert_eq!(Democracy::tally(r), (21, 0, 21));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 21, nays: 0, turnout: 210 });
 
 			next_block();
 			assert_eq!(Balances::free_balance(42), 0);
@@ -2765,14 +8199,14 @@ mod tests {
 			System::set_block_number(1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
 			assert_ok!(Democracy::vote(Origin::signed(5), r, BIG_NAY));
 			assert_ok!(Democracy::vote(Origin::signed(6), r, BIG_AYE));
 
-			assert_eq!(Democracy::tally(r), (60, 50, 110));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 60, nays: 50, turnout: 110 });
 
 			next_block();
 			next_block();
@@ -2790,7 +8224,7 @@ mod tests {
 			System::set_block_number(1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -2798,7 +8232,7 @@ mod tests {
 			assert_ok!(Democracy::vote(Origin::signed(5), r, BIG_NAY));
 			assert_ok!(Democracy::vote(Origin::signed(6), r, BIG_AYE));
 
-			assert_eq!(Democracy::tally(r), (100, 50, 150));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 100, nays: 50, turnout: 150 });
 
 			next_block();
 			next_block();
@@ -2813,7 +8247,7 @@ mod tests {
 			System::set_block_number(0);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -2838,52 +8272,211 @@ mod tests {
 				conviction: Conviction::Locked1x
 			}));
 
-			assert_eq!(Democracy::tally(r), (250, 100, 150));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 250, nays: 100, turnout: 150 });
 
 			fast_forward_to(2);
 
 			assert_eq!(Balances::locks(1), vec![]);
 			assert_eq!(Balances::locks(2), vec![BalanceLock {
-				id: DEMOCRACY_ID,
-				amount: u64::max_value(),
+				id: DemocracyLockId::get(),
+				amount: 20,
 				reasons: pallet_balances::Reasons::Misc,
 			}]);
-			assert_eq!(Democracy::locks(2), Some(18));
+			assert_eq!(Democracy::locks(2), Some(PriorLock::new(18, 20)));
 			assert_eq!(Balances::locks(3), vec![BalanceLock {
-				id: DEMOCRACY_ID,
-				amount: u64::max_value(),
+				id: DemocracyLockId::get(),
+				amount: 30,
 				reasons: pallet_balances::Reasons::Misc,
 			}]);
-			assert_eq!(Democracy::locks(3), Some(10));
+			assert_eq!(Democracy::locks(3), Some(PriorLock::new(10, 30)));
 			assert_eq!(Balances::locks(4), vec![BalanceLock {
-				id: DEMOCRACY_ID,
-				amount: u64::max_value(),
+				id: DemocracyLockId::get(),
+				amount: 40,
 				reasons: pallet_balances::Reasons::Misc,
 			}]);
-			assert_eq!(Democracy::locks(4), Some(6));
+			assert_eq!(Democracy::locks(4), Some(PriorLock::new(6, 40)));
 			assert_eq!(Balances::locks(5), vec![]);
 
 			assert_eq!(Balances::free_balance(42), 2);
 
-			assert_noop!(Democracy::unlock(Origin::signed(1), 1), Error::<Test>::NotLocked);
+			assert_noop!(Democracy::unlock(Origin::signed(1), 1), Error::<Test, _>::NotLocked);
 
 			fast_forward_to(5);
-			assert_noop!(Democracy::unlock(Origin::signed(1), 4), Error::<Test>::NotExpired);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 4));
+			assert_eq!(Balances::locks(4)[0].amount, 40);
+			assert_eq!(Democracy::locks(4), Some(PriorLock::new(6, 40)));
 			fast_forward_to(6);
 			assert_ok!(Democracy::unlock(Origin::signed(1), 4));
-			assert_noop!(Democracy::unlock(Origin::signed(1), 4), Error::<Test>::NotLocked);
+			assert_eq!(Balances::locks(4), vec![]);
+			assert_noop!(Democracy::unlock(Origin::signed(1), 4), Error::<Test, _>::NotLocked);
 
 			fast_forward_to(9);
-			assert_noop!(Democracy::unlock(Origin::signed(1), 3), Error::<Test>::NotExpired);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 3));
+			assert_eq!(Balances::locks(3)[0].amount, 30);
 			fast_forward_to(10);
 			assert_ok!(Democracy::unlock(Origin::signed(1), 3));
-			assert_noop!(Democracy::unlock(Origin::signed(1), 3), Error::<Test>::NotLocked);
+			assert_eq!(Balances::locks(3), vec![]);
+			assert_noop!(Democracy::unlock(Origin::signed(1), 3), Error::<Test, _>::NotLocked);
 
 			fast_forward_to(17);
-			assert_noop!(Democracy::unlock(Origin::signed(1), 2), Error::<Test>::NotExpired);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Balances::locks(2)[0].amount, 20);
 			fast_forward_to(18);
 			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
-			assert_noop!(Democracy::unlock(Origin::signed(1), 2), Error::<Test>::NotLocked);
+			assert_eq!(Balances::locks(2), vec![]);
+			assert_noop!(Democracy::unlock(Origin::signed(1), 2), Error::<Test, _>::NotLocked);
+		});
+	}
+
+	#[test]
+	fn prior_locks_should_accumulate() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let r = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(2), r, Vote {
+				aye: true,
+				conviction: Conviction::Locked4x
+			}));
+			fast_forward_to(2);
+			assert_eq!(Democracy::locks(2), Some(PriorLock::new(18, 20)));
+
+			// A shorter lock over a larger balance is kept alongside the longer one.
+			let _ = Balances::make_free_balance_be(&2, 100);
+			let r = Democracy::inject_referendum(
+				4,
+				set_balance_proposal_lookup(3),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(2), r, Vote {
+				aye: true,
+				conviction: Conviction::Locked1x
+			}));
+			fast_forward_to(4);
+			assert_eq!(Democracy::locks(2), Some(PriorLock(vec![(6, 100), (18, 20)])));
+			assert_eq!(Balances::locks(2)[0].amount, 100);
+
+			// Undelegating folds the delegation's conviction into the prior lock too.
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked6x));
+			assert_eq!(Balances::locks(2)[0].amount, u64::max_value());
+			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_eq!(Democracy::locks(2), Some(PriorLock::new(68, 100)));
+			assert_eq!(Balances::locks(2)[0].amount, 100);
+		});
+	}
+
+	#[test]
+	fn overlapping_locks_should_lock_the_greatest() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(0);
+			let long = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(2),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			let short = Democracy::inject_referendum(
+				2,
+				set_balance_proposal_lookup(3),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			let vote = |conviction| Vote { aye: true, conviction };
+			assert_ok!(Democracy::vote(Origin::signed(2), long, vote(Conviction::Locked2x)));
+			assert_ok!(Democracy::vote(Origin::signed(2), short, vote(Conviction::Locked1x)));
+			fast_forward_to(2);
+			assert_eq!(Democracy::locks(2), Some(PriorLock::new(6, 20)));
+			assert_eq!(Balances::locks(2)[0].amount, 20);
+
+			// A smaller lock outlasting a greater one takes over once the greater one expires.
+			let _ = Balances::make_free_balance_be(&2, 100);
+			let r = Democracy::inject_referendum(
+				3,
+				set_balance_proposal_lookup(4),
+				VoteThreshold::SuperMajorityApprove,
+				0
+			);
+			assert_ok!(Democracy::vote(Origin::signed(2), r, vote(Conviction::Locked1x)));
+			fast_forward_to(3);
+			assert_eq!(Democracy::locks(2), Some(PriorLock(vec![(5, 100), (6, 20)])));
+			assert_eq!(Balances::locks(2)[0].amount, 100);
+
+			fast_forward_to(5);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Democracy::locks(2), Some(PriorLock::new(6, 20)));
+			assert_eq!(Balances::locks(2)[0].amount, 20);
+
+			fast_forward_to(6);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Democracy::locks(2), None);
+			assert_eq!(Balances::locks(2), vec![]);
+		});
+	}
+
+	#[test]
+	fn prior_lock_should_keep_only_greatest_locks() {
+		let mut prior = PriorLock::<u64, u64>::default();
+		prior.accumulate(10, 5);
+		prior.accumulate(5, 10);
+		prior.accumulate(8, 7);
+		prior.accumulate(7, 3);
+		prior.accumulate(3, 0);
+		assert_eq!(prior, PriorLock(vec![(5, 10), (8, 7), (10, 5)]));
+		assert_eq!((prior.locked(), prior.expiry()), (10, 10));
+
+		prior.accumulate(8, 10);
+		assert_eq!(prior, PriorLock(vec![(8, 10), (10, 5)]));
+
+		prior.rejig(8);
+		assert_eq!(prior, PriorLock::new(10, 5));
+		prior.rejig(10);
+		assert!(prior.is_unlocked());
+		assert_eq!((prior.locked(), prior.expiry()), (0, 0));
+	}
+
+	#[test]
+	fn unlock_should_keep_delegation_lock() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x));
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Balances::locks(2)[0].amount, u64::max_value());
+
+			// Undelegating leaves a prior lock of one enactment period.
+			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_eq!(Democracy::locks(2), Some(PriorLock::new(3, 20)));
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Balances::locks(2)[0].amount, 20);
+
+			fast_forward_to(3);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Balances::locks(2), vec![]);
+			assert_eq!(Democracy::locks(2), None);
+		});
+	}
+
+	#[test]
+	fn split_delegation_should_lock_the_part_delegated() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let split = vec![(1, Perbill::from_percent(25)), (3, Perbill::from_percent(25))];
+			assert_ok!(Democracy::delegate_split(Origin::signed(2), split, Conviction::Locked1x));
+			assert_eq!(Balances::locks(2)[0].amount, 10);
+
+			// Undelegating leaves a prior lock of one enactment period on the part delegated.
+			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_eq!(Democracy::locks(2), Some(PriorLock::new(3, 10)));
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Balances::locks(2)[0].amount, 10);
+
+			fast_forward_to(3);
+			assert_ok!(Democracy::unlock(Origin::signed(1), 2));
+			assert_eq!(Balances::locks(2), vec![]);
 		});
 	}
 
@@ -2893,7 +8486,7 @@ mod tests {
 			System::set_block_number(0);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0,
 			);
@@ -2915,7 +8508,7 @@ mod tests {
 			System::set_block_number(1);
 			let r = Democracy::inject_referendum(
 				2,
-				set_balance_proposal_hash_and_note(2),
+				set_balance_proposal_lookup(2),
 				VoteThreshold::SuperMajorityApprove,
 				0
 			);
@@ -2937,7 +8530,7 @@ mod tests {
 				conviction: Conviction::Locked1x
 			}));
 
-			assert_eq!(Democracy::tally(r), (250, 100, 150));
+			assert_eq!(Democracy::tally(r), Tally { ayes: 250, nays: 100, turnout: 150 });
 
 			next_block();
 			next_block();
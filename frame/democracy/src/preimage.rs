@@ -0,0 +1,88 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The preimage layer of the democracy pallet.
+//!
+//! Proposals are referenced by `BoundedCall`: small calls are held inline while larger ones are
+//! referenced by the hash of a preimage kept in `Preimages`.
+
+use super::*;
+use codec::Compact;
+use sp_runtime::DispatchError;
+use frame_support::{storage::StorageMap, traits::StorePreimage};
+
+impl<T: Trait<I>, I: Instance> QueryPreimage<T::Hash> for Module<T, I> {
+	fn len(hash: &T::Hash) -> Option<u32> {
+		// The preimage is the first item of the stored tuple, so the value begins with its
+		// compact-encoded length; there's no need to read the rest.
+		let key = <Preimages<T, I>>::hashed_key_for(hash);
+		let mut prefix = [0u8; 5];
+		sp_io::storage::read(&key, &mut prefix, 0)?;
+		Compact::<u32>::decode(&mut &prefix[..]).ok().map(|len| len.0)
+	}
+
+	fn fetch(hash: &T::Hash) -> Option<Vec<u8>> {
+		<Preimages<T, I>>::get(hash).map(|(data, ..)| data)
+	}
+}
+
+impl<T: Trait<I>, I: Instance> StorePreimage<T::Hash> for Module<T, I> {
+	const MAX_INLINE_LEN: u32 = 128;
+
+	/// Note a preimage on behalf of the system. No deposit is taken, and the preimage is
+	/// attributed to the default account.
+	fn note(bytes: Vec<u8>) -> Result<T::Hash, DispatchError> {
+		let hash = T::Hashing::hash(&bytes[..]);
+		if !<Preimages<T, I>>::contains_key(&hash) {
+			let now = <frame_system::Module<T>>::block_number();
+			let preimage = (bytes, T::AccountId::default(), BalanceOf::<T, I>::zero(), now);
+			<Preimages<T, I>>::insert(hash, preimage);
+		}
+		Ok(hash)
+	}
+
+	/// Remove a preimage, returning the deposit held for it to whoever noted it.
+	fn unnote(hash: &T::Hash) {
+		if let Some((_, who, deposit, _)) = <Preimages<T, I>>::take(hash) {
+			let _ = T::Currency::unreserve(&who, deposit);
+		}
+	}
+}
+
+impl<T: Trait<I>, I: Instance> Module<T, I> {
+	/// The hash of `proposal`, computed from its encoding if it is held inline.
+	pub(crate) fn proposal_hash(proposal: &BoundedCallOf<T>) -> T::Hash {
+		match proposal {
+			BoundedCall::Inline(data) => T::Hashing::hash(&data[..]),
+			BoundedCall::Legacy { hash } | BoundedCall::Lookup { hash, .. } => *hash,
+		}
+	}
+
+	/// The length of the preimage of `proposal`, or zero if it is not known.
+	pub(crate) fn proposal_len(proposal: &BoundedCallOf<T>) -> u32 {
+		proposal.len()
+			.or_else(|| proposal.lookup_hash().and_then(|hash| Self::len(&hash)))
+			.unwrap_or(0)
+	}
+
+	/// Reference a proposal by `hash`, recording the length of its preimage if it has been noted.
+	pub(crate) fn bound_hash(hash: T::Hash) -> BoundedCallOf<T> {
+		match Self::len(&hash) {
+			Some(len) => BoundedCall::Lookup { hash, len },
+			None => BoundedCall::Legacy { hash },
+		}
+	}
+}
@@ -21,6 +21,7 @@ use serde::{Serialize, Deserialize};
 use codec::{Encode, Decode};
 use sp_runtime::traits::{Zero, IntegerSquareRoot};
 use sp_std::ops::{Add, Mul, Div, Rem};
+use crate::Tally;
 
 /// A means of determining if a vote is past pass threshold.
 #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug)]
@@ -41,6 +42,44 @@ pub trait Approved<Balance> {
 	fn approved(&self, approve: Balance, against: Balance, voters: Balance, electorate: Balance) -> bool;
 }
 
+/// The criterion by which a finished referendum is judged to be approved.
+///
+/// The `VoteThreshold` a referendum was started with identifies the origin that tabled it, so
+/// implementations may apply a different curve to each kind of referendum.
+pub trait ApprovalCriterion<Balance> {
+	/// Given the `tally` of a referendum started with `threshold`, from a total electorate size of
+	/// `electorate`, returns true if the overall outcome is in favor of approval.
+	fn approved(threshold: VoteThreshold, tally: Tally<Balance>, electorate: Balance) -> bool;
+}
+
+/// The default approval criterion: each `VoteThreshold` biases its quorum adaptively to
+/// turnout, as described by the variant.
+pub struct AdaptiveQuorumBiasing;
+
+impl<Balance> ApprovalCriterion<Balance> for AdaptiveQuorumBiasing where VoteThreshold: Approved<Balance> {
+	fn approved(threshold: VoteThreshold, tally: Tally<Balance>, electorate: Balance) -> bool {
+		threshold.approved(tally.ayes, tally.nays, tally.turnout, electorate)
+	}
+}
+
+/// The approval criterion for quadratic voting, under which the votes of a voter are those of the
+/// square root of their balance: as `AdaptiveQuorumBiasing`, with the turnout and electorate
+/// brought to the same scale as the votes by taking their square roots first.
+pub struct QuadraticQuorumBiasing;
+
+impl<Balance: IntegerSquareRoot> ApprovalCriterion<Balance> for QuadraticQuorumBiasing where
+	VoteThreshold: Approved<Balance>,
+{
+	fn approved(threshold: VoteThreshold, tally: Tally<Balance>, electorate: Balance) -> bool {
+		threshold.approved(
+			tally.ayes,
+			tally.nays,
+			tally.turnout.integer_sqrt(),
+			electorate.integer_sqrt(),
+		)
+	}
+}
+
 /// Return `true` iff `n1 / d1 < n2 / d2`. `d1` and `d2` may not be zero.
 fn compare_rationals<T: Zero + Mul<T, Output = T> + Div<T, Output = T> + Rem<T, Output = T> + Ord + Copy>(mut n1: T, mut d1: T, mut n2: T, mut d2: T) -> bool {
 	// Uses a continued fractional representation for a non-overflowing compare.
@@ -93,10 +132,128 @@ impl<Balance: IntegerSquareRoot + Zero + Ord + Add<Balance, Output = Balance> +
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use quickcheck::{quickcheck, Arbitrary, Gen, TestResult};
 
 	#[test]
 	fn should_work() {
 		assert_eq!(VoteThreshold::SuperMajorityApprove.approved(60, 50, 110, 210), false);
 		assert_eq!(VoteThreshold::SuperMajorityApprove.approved(100, 50, 150, 210), true);
 	}
+
+	#[test]
+	fn adaptive_quorum_biasing_should_follow_threshold() {
+		let tally = Tally { ayes: 60, nays: 50, turnout: 110 };
+		let approved = |t| <AdaptiveQuorumBiasing as ApprovalCriterion<u64>>::approved(t, tally, 210);
+		assert_eq!(approved(VoteThreshold::SuperMajorityApprove), false);
+		assert_eq!(approved(VoteThreshold::SimpleMajority), true);
+		assert_eq!(approved(VoteThreshold::SuperMajorityAgainst), true);
+	}
+
+	#[test]
+	fn quadratic_quorum_biasing_should_scale_turnout() {
+		// 9 aye and 7 nay votes, from a turnout of 10_000 out of 20_736: the fourth roots of which
+		// are 10 and 12.
+		let tally = Tally { ayes: 9, nays: 7, turnout: 10_000 };
+		let approved = |t|
+			<QuadraticQuorumBiasing as ApprovalCriterion<u64>>::approved(t, tally, 20_736);
+		assert_eq!(approved(VoteThreshold::SuperMajorityApprove), true);
+		assert_eq!(approved(VoteThreshold::SimpleMajority), true);
+		assert_eq!(approved(VoteThreshold::SuperMajorityAgainst), true);
+		let tally = Tally { ayes: 8, nays: 7, turnout: 10_000 };
+		let approved = |t|
+			<QuadraticQuorumBiasing as ApprovalCriterion<u64>>::approved(t, tally, 20_736);
+		assert_eq!(approved(VoteThreshold::SuperMajorityApprove), false);
+	}
+
+	/// The votes in a referendum, with `voters` never beyond `electorate`. Balances are spread
+	/// over the whole range of `u128`, so as to catch any overflow.
+	#[derive(Clone, Copy, Debug)]
+	struct Votes {
+		approve: u128,
+		against: u128,
+		voters: u128,
+		electorate: u128,
+	}
+
+	fn balance<G: Gen>(g: &mut G) -> u128 {
+		match g.next_u32() % 3 {
+			0 => u128::arbitrary(g),
+			1 => (g.next_u64() as u128) << (g.next_u32() % 64),
+			_ => u128::max_value() - u128::arbitrary(g),
+		}
+	}
+
+	impl Arbitrary for Votes {
+		fn arbitrary<G: Gen>(g: &mut G) -> Self {
+			let (a, b) = (balance(g), balance(g));
+			Votes {
+				approve: balance(g),
+				against: balance(g),
+				voters: a.min(b),
+				electorate: a.max(b),
+			}
+		}
+	}
+
+	const THRESHOLDS: [VoteThreshold; 3] = [
+		VoteThreshold::SuperMajorityApprove,
+		VoteThreshold::SimpleMajority,
+		VoteThreshold::SuperMajorityAgainst,
+	];
+
+	fn approved(threshold: VoteThreshold, v: Votes) -> bool {
+		threshold.approved(v.approve, v.against, v.voters, v.electorate)
+	}
+
+	quickcheck! {
+		fn more_approval_should_not_reject(v: Votes, extra: u128) -> bool {
+			let more = Votes { approve: v.approve.saturating_add(extra), ..v };
+			THRESHOLDS.iter().all(|t| !approved(*t, v) || approved(*t, more))
+		}
+
+		fn more_rejection_should_not_approve(v: Votes, extra: u128) -> bool {
+			let more = Votes { against: v.against.saturating_add(extra), ..v };
+			THRESHOLDS.iter().all(|t| approved(*t, v) || !approved(*t, more))
+		}
+
+		fn higher_turnout_should_not_hurt_the_majority(v: Votes, extra: u128) -> TestResult {
+			// Nothing passes without voters, whatever the threshold.
+			if v.voters.integer_sqrt().is_zero() || v.voters == v.electorate {
+				return TestResult::discard();
+			}
+			let voters = v.voters.saturating_add(extra).min(v.electorate);
+			let more = Votes { voters, ..v };
+			TestResult::from_bool(
+				(!approved(VoteThreshold::SuperMajorityApprove, v) ||
+					approved(VoteThreshold::SuperMajorityApprove, more)) &&
+				(approved(VoteThreshold::SuperMajorityAgainst, v) ||
+					!approved(VoteThreshold::SuperMajorityAgainst, more))
+			)
+		}
+
+		fn thresholds_should_be_ordered(v: Votes) -> bool {
+			let [approve, simple, against] = [
+				approved(THRESHOLDS[0], v),
+				approved(THRESHOLDS[1], v),
+				approved(THRESHOLDS[2], v),
+			];
+			(!approve || simple) && (!simple || against)
+		}
+
+		fn thresholds_should_be_symmetric(v: Votes) -> bool {
+			let mirror = Votes { approve: v.against, against: v.approve, ..v };
+			let approve = approved(VoteThreshold::SuperMajorityApprove, v);
+			let against = approved(VoteThreshold::SuperMajorityAgainst, mirror);
+			!(approve && against)
+		}
+
+		fn full_turnout_should_be_simple_majority(v: Votes) -> TestResult {
+			if v.voters.integer_sqrt().is_zero() {
+				return TestResult::discard();
+			}
+			let full = Votes { electorate: v.voters, ..v };
+			let simple = approved(VoteThreshold::SimpleMajority, full);
+			TestResult::from_bool(THRESHOLDS.iter().all(|t| approved(*t, full) == simple))
+		}
+	}
 }
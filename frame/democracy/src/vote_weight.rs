@@ -0,0 +1,78 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Vote weight curves, discounting votes according to when they were cast.
+
+use sp_std::marker::PhantomData;
+use sp_runtime::{Perbill, PerThing, traits::{AtLeast32Bit, Saturating}};
+use frame_support::traits::Get;
+
+/// A curve giving the proportion of its weight that a vote retains, according to how long before
+/// the end of voting it was cast.
+pub trait VoteWeightCurve<BlockNumber> {
+	/// The proportion of its weight retained by a vote cast `before_end` blocks before the end of
+	/// voting.
+	fn weight(before_end: BlockNumber) -> Perbill;
+}
+
+/// No curve at all: every vote keeps its full weight, whenever it was cast.
+impl<BlockNumber> VoteWeightCurve<BlockNumber> for () {
+	fn weight(_: BlockNumber) -> Perbill {
+		Perbill::one()
+	}
+}
+
+/// Votes cast at least `Period` blocks before the end of voting keep their full weight. Later
+/// votes are discounted linearly, down to `Floor` for a vote cast in the very last block.
+pub struct LinearLateDiscount<Period, Floor>(PhantomData<(Period, Floor)>);
+
+impl<BlockNumber, Period, Floor> VoteWeightCurve<BlockNumber> for LinearLateDiscount<Period, Floor> where
+	BlockNumber: AtLeast32Bit + Clone,
+	Period: Get<BlockNumber>,
+	Floor: Get<Perbill>,
+{
+	fn weight(before_end: BlockNumber) -> Perbill {
+		let period = Period::get();
+		if before_end >= period {
+			return Perbill::one();
+		}
+		let floor = Floor::get();
+		let span = Perbill::one().saturating_sub(floor).deconstruct();
+		let ramp = Perbill::from_rational_approximation(before_end, period);
+		floor.saturating_add(Perbill::from_parts(ramp * span))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::parameter_types;
+
+	parameter_types! {
+		pub const Period: u64 = 10;
+		pub const Floor: Perbill = Perbill::from_percent(20);
+	}
+
+	#[test]
+	fn linear_late_discount_should_work() {
+		let weight = |b| <LinearLateDiscount<Period, Floor> as VoteWeightCurve<u64>>::weight(b);
+		assert_eq!(weight(20), Perbill::one());
+		assert_eq!(weight(10), Perbill::one());
+		assert_eq!(weight(5), Perbill::from_percent(60));
+		assert_eq!(weight(0), Perbill::from_percent(20));
+		assert_eq!(<() as VoteWeightCurve<u64>>::weight(0), Perbill::one());
+	}
+}
@@ -180,6 +180,21 @@ pub trait Contains<T: Ord> {
 	fn count() -> usize { Self::sorted_members().len() }
 }
 
+/// A filter over a reference to some type, parameterised by an instance, e.g. the kind of
+/// proxy that an account has been given.
+pub trait InstanceFilter<T>: Sized + Send + Sync {
+	/// Determine if a given value should be allowed through the filter.
+	fn filter(&self, t: &T) -> bool;
+
+	/// Determines whether `self` matches at least everything that `_o` does.
+	fn is_superset(&self, _o: &Self) -> bool { false }
+}
+
+impl<T> InstanceFilter<T> for () {
+	fn filter(&self, _: &T) -> bool { true }
+	fn is_superset(&self, _o: &Self) -> bool { true }
+}
+
 /// Determiner to say whether a given account is unused.
 pub trait IsDeadAccount<AccountId> {
 	/// Is the given account dead?
@@ -1042,3 +1057,110 @@ pub trait GetCallMetadata {
 	/// Return a [`CallMetadata`], containing function and pallet name of the Call.
 	fn get_call_metadata(&self) -> CallMetadata;
 }
+
+/// A reference to a call which is either held inline, if it is small enough, or must be looked
+/// up by the hash of its preimage.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum Bounded<Hash> {
+	/// A hash whose preimage length is not known. This is the form taken by references which
+	/// predate the bounded-call layer, or which were made before their preimage was noted.
+	Legacy {
+		/// The hash of the encoded call.
+		hash: Hash,
+	},
+	/// The encoded call, small enough to be stored in place.
+	Inline(Vec<u8>),
+	/// The hash of the encoded call along with its length in bytes.
+	Lookup {
+		/// The hash of the encoded call.
+		hash: Hash,
+		/// The length of the encoded call.
+		len: u32,
+	},
+}
+
+impl<Hash> Bounded<Hash> {
+	/// The length of the encoded call, if known.
+	pub fn len(&self) -> Option<u32> {
+		match self {
+			Bounded::Legacy { .. } => None,
+			Bounded::Inline(data) => Some(data.len() as u32),
+			Bounded::Lookup { len, .. } => Some(*len),
+		}
+	}
+
+	/// Whether a preimage lookup is needed in order to recover the call.
+	pub fn lookup_needed(&self) -> bool {
+		match self {
+			Bounded::Inline(_) => false,
+			Bounded::Legacy { .. } | Bounded::Lookup { .. } => true,
+		}
+	}
+}
+
+impl<Hash: Clone> Bounded<Hash> {
+	/// The hash under which the preimage must be looked up, or `None` if it is held inline.
+	pub fn lookup_hash(&self) -> Option<Hash> {
+		match self {
+			Bounded::Legacy { hash } | Bounded::Lookup { hash, .. } => Some(hash.clone()),
+			Bounded::Inline(_) => None,
+		}
+	}
+}
+
+/// A source of preimages which can be queried by hash.
+pub trait QueryPreimage<Hash> {
+	/// The length of the preimage of `hash`, if it is known.
+	fn len(hash: &Hash) -> Option<u32>;
+
+	/// The preimage of `hash`, if it is available.
+	fn fetch(hash: &Hash) -> Option<Vec<u8>>;
+
+	/// Whether the preimage of `hash` is available.
+	fn have(hash: &Hash) -> bool {
+		Self::len(hash).is_some()
+	}
+
+	/// Recover the call referenced by `bounded` without consuming its preimage, returning it
+	/// along with the length of its encoding.
+	fn peek<T: Decode>(bounded: &Bounded<Hash>) -> Result<(T, u32), DispatchError> {
+		let encoded = match bounded {
+			Bounded::Inline(data) => data.clone(),
+			Bounded::Legacy { hash } | Bounded::Lookup { hash, .. } =>
+				Self::fetch(hash).ok_or(DispatchError::Other("preimage unavailable"))?,
+		};
+		let len = encoded.len() as u32;
+		if let Some(expected) = bounded.len() {
+			if expected != len {
+				return Err(DispatchError::Other("preimage length mismatch"));
+			}
+		}
+		T::decode(&mut &encoded[..])
+			.map(|t| (t, len))
+			.map_err(|_| DispatchError::Other("preimage undecodable"))
+	}
+}
+
+/// A store of preimages into which new preimages may be placed.
+pub trait StorePreimage<Hash>: QueryPreimage<Hash> {
+	/// The largest encoding which will be held inline by `bound` rather than noted as a preimage.
+	const MAX_INLINE_LEN: u32;
+
+	/// Place `bytes` in the store, returning its hash.
+	fn note(bytes: Vec<u8>) -> Result<Hash, DispatchError>;
+
+	/// Remove the preimage of `hash` from the store, if present.
+	fn unnote(hash: &Hash);
+
+	/// Convert `call` into a `Bounded` reference, noting its preimage if it is too large to be
+	/// held inline.
+	fn bound<T: Encode>(call: T) -> Result<Bounded<Hash>, DispatchError> {
+		let data = call.encode();
+		let len = data.len() as u32;
+		if len <= Self::MAX_INLINE_LEN {
+			Ok(Bounded::Inline(data))
+		} else {
+			Self::note(data).map(|hash| Bounded::Lookup { hash, len })
+		}
+	}
+}
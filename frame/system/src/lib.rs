@@ -350,6 +350,10 @@ decl_storage! {
 		/// Total weight for all extrinsics put together, for the current block.
 		AllExtrinsicsWeight: Option<Weight>;
 
+		/// The part of the weight registered by `CheckWeight` for the extrinsic being applied which
+		/// has not been refunded yet, and so is the most of it which may still be.
+		ExtrinsicWeight: Option<Weight>;
+
 		/// Total length (in bytes) for all extrinsics put together, for the current block.
 		AllExtrinsicsLen: Option<u32>;
 
@@ -835,6 +839,24 @@ impl<T: Trait> Module<T> {
 		AllExtrinsicsWeight::put(next_weight);
 	}
 
+	/// Take back some of the weight accounted for the extrinsic being applied, such as the part of
+	/// a bound declared up front which it turned out not to use.
+	///
+	/// The refunds of an extrinsic are capped, together, at the weight `CheckWeight` registered for
+	/// it, which may be less than the weight its call declared if it was wrapped in a call of a
+	/// fixed weight. Nothing is written if no weight has been registered for it.
+	///
+	/// NOTE: this only frees up room in the current block. The fee paid for the weight is not
+	/// refunded.
+	pub fn refund_weight_unchecked(weight: Weight) {
+		if let Some(refundable) = ExtrinsicWeight::get() {
+			let weight = weight.min(refundable);
+			ExtrinsicWeight::put(refundable - weight);
+			let current_weight = AllExtrinsicsWeight::get().unwrap_or_default();
+			AllExtrinsicsWeight::put(current_weight.saturating_sub(weight));
+		}
+	}
+
 	/// Start the execution of a particular block.
 	pub fn initialize(
 		number: &T::BlockNumber,
@@ -862,6 +884,7 @@ impl<T: Trait> Module<T> {
 	pub fn finalize() -> T::Header {
 		ExtrinsicCount::kill();
 		AllExtrinsicsWeight::kill();
+		ExtrinsicWeight::kill();
 		AllExtrinsicsLen::kill();
 
 		let number = <Number<T>>::take();
@@ -952,6 +975,14 @@ impl<T: Trait> Module<T> {
 		AllExtrinsicsLen::put(len as u32);
 	}
 
+	/// Register the weight of the extrinsic being applied, as `CheckWeight` does but regardless
+	/// of the limits. This should only be used in some integration tests.
+	#[cfg(any(feature = "std", test))]
+	pub fn register_extrinsic_weight(weight: Weight) {
+		AllExtrinsicsWeight::put(Self::all_extrinsics_weight().saturating_add(weight));
+		ExtrinsicWeight::put(weight);
+	}
+
 	/// Return the chain's current runtime version.
 	pub fn runtime_version() -> RuntimeVersion { T::Version::get() }
 
@@ -987,6 +1018,9 @@ impl<T: Trait> Module<T> {
 			}
 		);
 
+		// Nothing more may be refunded for this extrinsic.
+		ExtrinsicWeight::kill();
+
 		let next_extrinsic_index = Self::extrinsic_index().unwrap_or_default() + 1u32;
 
 		storage::unhashed::put(well_known_keys::EXTRINSIC_INDEX, &next_extrinsic_index);
@@ -1198,8 +1232,10 @@ impl<T: Trait + Send + Sync> CheckWeight<T> {
 	) -> Result<(), TransactionValidityError> {
 		let next_len = Self::check_block_length(info, len)?;
 		let next_weight = Self::check_weight(info)?;
+		let added_weight = next_weight - Module::<T>::all_extrinsics_weight();
 		AllExtrinsicsLen::put(next_len);
 		AllExtrinsicsWeight::put(next_weight);
+		ExtrinsicWeight::put(added_weight);
 		Ok(())
 	}
 
@@ -1796,6 +1832,30 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn refunds_are_capped_at_the_registered_weight() {
+		new_test_ext().execute_with(|| {
+			AllExtrinsicsWeight::put(1_000);
+			let info = DispatchInfo { weight: 100, ..Default::default() };
+			assert!(CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, info, 0).is_ok());
+			assert_eq!(System::all_extrinsics_weight(), 1_100);
+
+			// Refunds for more than was registered, as for a call wrapped in one of a fixed weight.
+			System::refund_weight_unchecked(60);
+			assert_eq!(System::all_extrinsics_weight(), 1_040);
+			System::refund_weight_unchecked(60);
+			assert_eq!(System::all_extrinsics_weight(), 1_000);
+			System::refund_weight_unchecked(60);
+			assert_eq!(System::all_extrinsics_weight(), 1_000);
+
+			// Nothing is refunded once the extrinsic has been applied.
+			assert!(CheckWeight::<Test>(PhantomData).pre_dispatch(&1, CALL, info, 0).is_ok());
+			System::note_applied_extrinsic(&Ok(()), 0, info);
+			System::refund_weight_unchecked(60);
+			assert_eq!(System::all_extrinsics_weight(), 1_100);
+		});
+	}
+
 	#[test]
 	fn signed_ext_check_weight_works_normal_tx() {
 		new_test_ext().execute_with(|| {
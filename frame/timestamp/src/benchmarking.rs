@@ -21,7 +21,7 @@ use super::*;
 use sp_std::prelude::*;
 
 use frame_system::RawOrigin;
-use frame_benchmarking::benchmarks;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
 
 const MAX_TIME: u32 = 100;
 
@@ -34,3 +34,5 @@ benchmarks! {
 		let n in ...;
 	}: _(RawOrigin::None, n.into())
 }
+
+impl_benchmark_test_suite!(crate::tests::new_test_ext(), crate::tests::Test);
@@ -46,7 +46,7 @@
 
 use crate::Error;
 use sp_std::{convert::{TryFrom, TryInto}, ops::{Range, Index, IndexMut}};
-use sp_wasm_interface::{Pointer, WordSize};
+use sp_wasm_interface::{Pointer, WordSize, AllocationStats};
 
 /// The minimal alignment guaranteed by this allocator. The alignment of 8 is chosen because it is
 /// the alignment guaranteed by wasm32.
@@ -292,6 +292,9 @@ pub struct FreeingBumpHeapAllocator {
 	bumper: u32,
 	free_lists: FreeLists,
 	total_size: u32,
+	// The size of the heap when the statistics were last reset, from which the peak is counted.
+	stats_base: u32,
+	stats: AllocationStats,
 }
 
 impl FreeingBumpHeapAllocator {
@@ -308,9 +311,25 @@ impl FreeingBumpHeapAllocator {
 			bumper: aligned_heap_base,
 			free_lists: FreeLists::new(),
 			total_size: 0,
+			stats_base: 0,
+			stats: AllocationStats::default(),
 		}
 	}
 
+	/// The statistics of the allocations since they were last reset, or since the allocator was
+	/// created. The sizes are those taken from the heap, including the headers and the rounding of
+	/// each allocation to a power of two.
+	pub fn stats(&self) -> AllocationStats {
+		self.stats
+	}
+
+	/// Reset the statistics of the allocations, so that the peak is counted from the current size
+	/// of the heap.
+	pub fn reset_stats(&mut self) {
+		self.stats_base = self.total_size;
+		self.stats = AllocationStats::default();
+	}
+
 	/// Gets requested number of bytes to allocate and returns a pointer.
 	/// The maximum size which can be allocated at once is 16 MiB.
 	/// There is no minimum size, but whatever size is passed into
@@ -356,6 +375,10 @@ impl FreeingBumpHeapAllocator {
 		self.total_size += order.size() + HEADER_SIZE;
 		trace!("Heap size is {} bytes after allocation", self.total_size);
 
+		self.stats.peak = self.stats.peak.max(self.total_size.saturating_sub(self.stats_base));
+		self.stats.total += (order.size() + HEADER_SIZE) as u64;
+		self.stats.count += 1;
+
 		Ok(Pointer::new(header_ptr + HEADER_SIZE))
 	}
 
@@ -722,6 +745,29 @@ mod tests {
 		assert_eq!(heap.total_size, 0);
 	}
 
+	#[test]
+	fn should_track_allocation_stats() {
+		// given
+		let mut mem = [0u8; PAGE_SIZE as usize];
+		let mut heap = FreeingBumpHeapAllocator::new(0);
+		let kept = heap.allocate(&mut mem[..], 8).unwrap();
+		heap.reset_stats();
+
+		// when
+		let ptr = heap.allocate(&mut mem[..], 9).unwrap();
+		heap.deallocate(&mut mem[..], ptr).unwrap();
+		let ptr = heap.allocate(&mut mem[..], 8).unwrap();
+		heap.deallocate(&mut mem[..], ptr).unwrap();
+		heap.deallocate(&mut mem[..], kept).unwrap();
+
+		// then
+		assert_eq!(heap.stats(), AllocationStats {
+			peak: HEADER_SIZE + 16,
+			total: ((HEADER_SIZE + 16) + (HEADER_SIZE + 8)) as u64,
+			count: 2,
+		});
+	}
+
 	#[test]
 	fn should_read_and_write_u64_correctly() {
 		// given
@@ -742,6 +742,24 @@ pub trait Offchain {
 	}
 }
 
+/// Interface that provides functions to index data in the offchain database as blocks are
+/// imported.
+///
+/// The entries are written to the persistent local storage of the offchain workers when the
+/// block is imported. They are not part of the consensus state.
+#[runtime_interface]
+pub trait OffchainIndex {
+	/// Write a key value pair to the offchain database.
+	fn set(&mut self, key: &[u8], value: &[u8]) {
+		self.set_offchain_storage(key, Some(value));
+	}
+
+	/// Remove a key and its value from the offchain database.
+	fn clear(&mut self, key: &[u8]) {
+		self.set_offchain_storage(key, None);
+	}
+}
+
 /// Wasm only interface that provides functions for calling into the allocator.
 #[runtime_interface(wasm_only)]
 trait Allocator {
@@ -923,6 +941,7 @@ pub type SubstrateHostFunctions = (
 	storage::HostFunctions,
 	misc::HostFunctions,
 	offchain::HostFunctions,
+	offchain_index::HostFunctions,
 	crypto::HostFunctions,
 	hashing::HostFunctions,
 	allocator::HostFunctions,
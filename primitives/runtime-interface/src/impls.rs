@@ -364,6 +364,20 @@ impl<T: codec::Codec> PassBy for Option<T> {
 	type PassBy = Codec<Self>;
 }
 
+/// Implement `PassBy` with `Codec` for the tuples of up to as many elements as given.
+macro_rules! impl_pass_by_codec_for_tuples {
+	() => {};
+	( $first:ident $( , $rest:ident )* ) => {
+		impl<$first: codec::Codec, $( $rest: codec::Codec ),*> PassBy for ($first, $( $rest, )*) {
+			type PassBy = Codec<Self>;
+		}
+
+		impl_pass_by_codec_for_tuples!($( $rest ),*);
+	};
+}
+
+impl_pass_by_codec_for_tuples!(A, B, C, D, E, F, G, H, I, J, K, L);
+
 /// Implement `PassBy` with `Inner` for the given fixed sized hash types.
 macro_rules! for_primitive_types {
 	{ $( $hash:ident $n:expr ),* $(,)? } => {
@@ -103,6 +103,11 @@ pub trait TestApi {
 	fn get_and_return_i128(val: i128) -> i128 {
 		val
 	}
+
+	/// Gets a tuple and returns this value
+	fn get_and_return_tuple(val: (u32, u64, bool)) -> (u32, u64, bool) {
+		val
+	}
 }
 
 /// This function is not used, but we require it for the compiler to include `sp-io`.
@@ -208,6 +213,12 @@ wasm_export_functions! {
 		}
 	}
 
+	fn test_tuple_as_parameter_and_return_value() {
+		for val in &[(0u32, 0u64, false), (u32::max_value(), u64::max_value(), true), (1, 5000, false)] {
+			assert_eq!(*val, test_api::get_and_return_tuple(*val));
+		}
+	}
+
 	fn test_vec_return_value_memory_is_freed() {
 		let mut len = 0;
 		for _ in 0..1024 {
@@ -117,6 +117,11 @@ fn test_u128_i128_as_parameter_and_return_value() {
 	call_wasm_method::<HostFunctions>("test_u128_i128_as_parameter_and_return_value");
 }
 
+#[test]
+fn test_tuple_as_parameter_and_return_value() {
+	call_wasm_method::<HostFunctions>("test_tuple_as_parameter_and_return_value");
+}
+
 #[test]
 fn test_vec_return_value_memory_is_freed() {
 	call_wasm_method::<HostFunctions>("test_vec_return_value_memory_is_freed");
@@ -26,7 +26,7 @@ use sp_trie::{TrieMut, MemoryDB, trie_types::TrieDBMut};
 use crate::{
 	trie_backend::TrieBackend,
 	trie_backend_essence::TrieBackendStorage,
-	UsageInfo, StorageKey, StorageValue, StorageCollection,
+	UsageInfo, StorageKey, StorageValue, StorageCollection, ChildStorageCollection,
 };
 
 /// A state backend is used to read state data and can have changes committed
@@ -219,10 +219,70 @@ pub trait Backend<H: Hasher>: std::fmt::Debug {
 		unimplemented!()
 	}
 
-	/// Commit given transaction to storage.
-	fn commit(&self, _storage_root: H::Out, _transaction: Self::Transaction) -> Result<(), Self::Error> {
+	/// Commit given transaction to storage, along with the main and child storage changes it was
+	/// built from.
+	fn commit(
+		&self,
+		_storage_root: H::Out,
+		_transaction: Self::Transaction,
+		_storage_changes: StorageCollection,
+		_child_storage_changes: ChildStorageCollection,
+	) -> Result<(), Self::Error> {
 		unimplemented!()
 	}
+
+	/// Get the number of database reads and writes since the last reset, as `(reads,
+	/// repeat_reads, writes, repeat_writes)`, or zeros if they are not counted.
+	fn read_write_count(&self) -> (u32, u32, u32, u32) {
+		(0, 0, 0, 0)
+	}
+
+	/// Get the number of reads and writes of the keys of child tries since the last reset, as
+	/// `(reads, repeat_reads, writes, repeat_writes)`.
+	fn child_read_write_count(&self) -> (u32, u32, u32, u32) {
+		(0, 0, 0, 0)
+	}
+
+	/// Reset the database read and write counts, and the storage proofs recorded since the last
+	/// reset, of the main trie and of the child tries, if they are counted.
+	fn reset_read_write_count(&self) {}
+
+	/// Get the encoded size of the storage proof of the reads since the last reset, or zero if the
+	/// backend doesn't record one.
+	fn proof_size(&self) -> u32 {
+		0
+	}
+
+	/// Get the encoded size of the storage proof of the reads of child tries since the last reset,
+	/// or zero if the backend doesn't record one.
+	fn child_proof_size(&self) -> u32 {
+		0
+	}
+
+	/// Get the keys whose accesses are not counted as database reads and writes.
+	fn get_whitelist(&self) -> Vec<StorageKey> {
+		Vec::new()
+	}
+
+	/// Set the keys whose accesses are not counted as database reads and writes.
+	fn set_whitelist(&self, _: Vec<StorageKey>) {}
+
+	/// Get the storage keys of the child tries whose accesses are not counted as reads and writes.
+	fn get_child_whitelist(&self) -> Vec<StorageKey> {
+		Vec::new()
+	}
+
+	/// Set the storage keys of the child tries whose accesses are not counted as reads and writes.
+	fn set_child_whitelist(&self, _: Vec<StorageKey>) {}
+
+	/// Get the hints of how the values under each key prefix count towards the proof size, as
+	/// `(prefix, max_size)`, where values are measured if `max_size` is `None`.
+	fn get_pov_hints(&self) -> Vec<(StorageKey, Option<u32>)> {
+		Vec::new()
+	}
+
+	/// Set the hints of how the values under each key prefix count towards the proof size.
+	fn set_pov_hints(&self, _: Vec<(StorageKey, Option<u32>)>) {}
 }
 
 impl<'a, T: Backend<H>, H: Hasher> Backend<H> for &'a T {
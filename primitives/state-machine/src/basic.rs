@@ -231,6 +231,10 @@ impl Externalities for BasicExternalities {
 		}
 	}
 
+	fn set_offchain_storage(&mut self, _key: &[u8], _value: Option<&[u8]>) {
+		// There is no block to import the changes of basic externalities with.
+	}
+
 	fn chain_id(&self) -> u64 { 42 }
 
 	fn storage_root(&mut self) -> Vec<u8> {
@@ -276,6 +280,42 @@ impl Externalities for BasicExternalities {
 	fn wipe(&mut self) {}
 
 	fn commit(&mut self) {}
+
+	fn read_write_count(&self) -> (u32, u32, u32, u32) {
+		(0, 0, 0, 0)
+	}
+
+	fn child_read_write_count(&self) -> (u32, u32, u32, u32) {
+		(0, 0, 0, 0)
+	}
+
+	fn reset_read_write_count(&mut self) {}
+
+	fn proof_size(&self) -> u32 {
+		0
+	}
+
+	fn child_proof_size(&self) -> u32 {
+		0
+	}
+
+	fn get_whitelist(&self) -> Vec<Vec<u8>> {
+		Vec::new()
+	}
+
+	fn set_whitelist(&mut self, _: Vec<Vec<u8>>) {}
+
+	fn get_child_whitelist(&self) -> Vec<Vec<u8>> {
+		Vec::new()
+	}
+
+	fn set_child_whitelist(&mut self, _: Vec<Vec<u8>>) {}
+
+	fn get_pov_hints(&self) -> Vec<(Vec<u8>, Option<u32>)> {
+		Vec::new()
+	}
+
+	fn set_pov_hints(&mut self, _: Vec<(Vec<u8>, Option<u32>)>) {}
 }
 
 impl sp_externalities::ExtensionStore for BasicExternalities {
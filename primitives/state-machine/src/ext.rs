@@ -404,6 +404,15 @@ where
 		});
 	}
 
+	fn set_offchain_storage(&mut self, key: &[u8], value: Option<&[u8]>) {
+		trace!(target: "state-trace", "{:04x}: SetOffchain {}={:?}",
+			self.id,
+			HexDisplay::from(&key),
+			value.as_ref().map(HexDisplay::from)
+		);
+		self.overlay.set_offchain_storage(key.to_vec(), value.map(|v| v.to_vec()));
+	}
+
 	fn chain_id(&self) -> u64 {
 		42
 	}
@@ -534,9 +543,55 @@ where
 		self.backend.commit(
 			changes.transaction_storage_root,
 			changes.transaction,
+			changes.main_storage_changes,
+			changes.child_storage_changes,
 		).expect(EXT_NOT_ALLOWED_TO_FAIL);
 		self.storage_transaction_cache.reset();
 	}
+
+	fn read_write_count(&self) -> (u32, u32, u32, u32) {
+		self.backend.read_write_count()
+	}
+
+	fn child_read_write_count(&self) -> (u32, u32, u32, u32) {
+		self.backend.child_read_write_count()
+	}
+
+	fn reset_read_write_count(&mut self) {
+		self.backend.reset_read_write_count()
+	}
+
+	fn proof_size(&self) -> u32 {
+		self.backend.proof_size()
+	}
+
+	fn child_proof_size(&self) -> u32 {
+		self.backend.child_proof_size()
+	}
+
+	fn get_whitelist(&self) -> Vec<Vec<u8>> {
+		self.backend.get_whitelist()
+	}
+
+	fn set_whitelist(&mut self, new: Vec<Vec<u8>>) {
+		self.backend.set_whitelist(new)
+	}
+
+	fn get_child_whitelist(&self) -> Vec<Vec<u8>> {
+		self.backend.get_child_whitelist()
+	}
+
+	fn set_child_whitelist(&mut self, new: Vec<Vec<u8>>) {
+		self.backend.set_child_whitelist(new)
+	}
+
+	fn get_pov_hints(&self) -> Vec<(Vec<u8>, Option<u32>)> {
+		self.backend.get_pov_hints()
+	}
+
+	fn set_pov_hints(&mut self, new: Vec<(Vec<u8>, Option<u32>)>) {
+		self.backend.set_pov_hints(new)
+	}
 }
 
 impl<'a, H, B, N> sp_externalities::ExtensionStore for Ext<'a, H, N, B>
@@ -64,7 +64,7 @@ pub use changes_trie::{
 };
 pub use overlayed_changes::{
 	OverlayedChanges, StorageChanges, StorageTransactionCache, StorageKey, StorageValue,
-	StorageCollection, ChildStorageCollection,
+	StorageCollection, ChildStorageCollection, OffchainStorageCollection,
 };
 pub use proving_backend::{
 	create_proof_check_backend, ProofRecorder, ProvingBackend, ProvingBackendRecorder,
@@ -45,6 +45,9 @@ pub type StorageCollection = Vec<(StorageKey, Option<StorageValue>)>;
 /// In memory arrays of storage values for multiple child tries.
 pub type ChildStorageCollection = Vec<(StorageKey, StorageCollection)>;
 
+/// In memory array of entries of the offchain database.
+pub type OffchainStorageCollection = Vec<(StorageKey, Option<StorageValue>)>;
+
 /// The overlayed changes to state to be queried on top of the backend.
 ///
 /// A transaction shares all prospective changes within an inner overlay
@@ -78,6 +81,8 @@ pub struct OverlayedChangeSet {
 	pub top: BTreeMap<StorageKey, OverlayedValue>,
 	/// Child storage changes.
 	pub children: HashMap<StorageKey, (BTreeMap<StorageKey, OverlayedValue>, OwnedChildInfo)>,
+	/// Changes to the offchain database.
+	pub offchain: BTreeMap<StorageKey, Option<StorageValue>>,
 }
 
 /// A storage changes structure that can be generated by the data collected in [`OverlayedChanges`].
@@ -101,6 +106,10 @@ pub struct StorageChanges<Transaction, H: Hasher, N: BlockNumber> {
 	///
 	/// If changes trie is disabled the value is set to `None`.
 	pub changes_trie_transaction: Option<ChangesTrieTransaction<H, N>>,
+	/// All changes to the offchain database.
+	///
+	/// A value of `None` means that it was deleted.
+	pub offchain_storage_changes: OffchainStorageCollection,
 }
 
 impl<Transaction, H: Hasher, N: BlockNumber> StorageChanges<Transaction, H, N> {
@@ -111,6 +120,7 @@ impl<Transaction, H: Hasher, N: BlockNumber> StorageChanges<Transaction, H, N> {
 		Transaction,
 		H::Out,
 		Option<ChangesTrieTransaction<H, N>>,
+		OffchainStorageCollection,
 	) {
 		(
 			self.main_storage_changes,
@@ -118,6 +128,7 @@ impl<Transaction, H: Hasher, N: BlockNumber> StorageChanges<Transaction, H, N> {
 			self.transaction,
 			self.transaction_storage_root,
 			self.changes_trie_transaction,
+			self.offchain_storage_changes,
 		)
 	}
 }
@@ -162,6 +173,7 @@ impl<Transaction: Default, H: Hasher, N: BlockNumber> Default for StorageChanges
 			transaction: Default::default(),
 			transaction_storage_root: Default::default(),
 			changes_trie_transaction: None,
+			offchain_storage_changes: Default::default(),
 		}
 	}
 }
@@ -172,6 +184,7 @@ impl FromIterator<(StorageKey, OverlayedValue)> for OverlayedChangeSet {
 		Self {
 			top: iter.into_iter().collect(),
 			children: Default::default(),
+			offchain: Default::default(),
 		}
 	}
 }
@@ -179,13 +192,14 @@ impl FromIterator<(StorageKey, OverlayedValue)> for OverlayedChangeSet {
 impl OverlayedChangeSet {
 	/// Whether the change set is empty.
 	pub fn is_empty(&self) -> bool {
-		self.top.is_empty() && self.children.is_empty()
+		self.top.is_empty() && self.children.is_empty() && self.offchain.is_empty()
 	}
 
 	/// Clear the change set.
 	pub fn clear(&mut self) {
 		self.top.clear();
 		self.children.clear();
+		self.offchain.clear();
 	}
 }
 
@@ -209,6 +223,14 @@ impl OverlayedChanges {
 			.map(|x| x.value.as_ref().map(AsRef::as_ref))
 	}
 
+	/// Returns a double-Option: None if no change to the offchain database is pending under the
+	/// key; Some(None) if it is to be deleted. Some(Some(...)) for a key whose value is to be set.
+	pub fn offchain_storage(&self, key: &[u8]) -> Option<Option<&[u8]>> {
+		self.prospective.offchain.get(key)
+			.or_else(|| self.committed.offchain.get(key))
+			.map(|x| x.as_ref().map(AsRef::as_ref))
+	}
+
 	/// Returns a double-Option: None if the key is unknown (i.e. and the query should be referred
 	/// to the backend); Some(None) if the key has been deleted. Some(Some(...)) for a key whose
 	/// value has been set.
@@ -242,6 +264,13 @@ impl OverlayedChanges {
 		}
 	}
 
+	/// Inserts the given key-value pair into the prospective offchain change set.
+	///
+	/// `None` can be used to delete a value specified by the given key.
+	pub(crate) fn set_offchain_storage(&mut self, key: StorageKey, val: Option<StorageValue>) {
+		self.prospective.offchain.insert(key, val);
+	}
+
 	/// Inserts the given key-value pair into the prospective child change set.
 	///
 	/// `None` can be used to delete a value specified by the given key.
@@ -421,6 +450,8 @@ impl OverlayedChanges {
 					}
 				}
 			}
+			let offchain_to_commit = mem::replace(&mut self.prospective.offchain, BTreeMap::new());
+			self.committed.offchain.extend(offchain_to_commit);
 		}
 	}
 
@@ -489,6 +520,7 @@ impl OverlayedChanges {
 			.expect("Changes trie transaction was generated by `changes_trie_root`; qed");
 
 		let (main_storage_changes, child_storage_changes) = self.drain_committed();
+		let offchain_storage_changes = mem::replace(&mut self.committed.offchain, BTreeMap::new());
 
 		Ok(StorageChanges {
 			main_storage_changes: main_storage_changes.collect(),
@@ -496,6 +528,7 @@ impl OverlayedChanges {
 			transaction,
 			transaction_storage_root,
 			changes_trie_transaction,
+			offchain_storage_changes: offchain_storage_changes.into_iter().collect(),
 		})
 	}
 
@@ -709,6 +742,44 @@ mod tests {
 		assert!(overlayed.storage(&key).unwrap().is_none());
 	}
 
+	#[test]
+	fn offchain_storage_changes_are_drained_once_committed() {
+		let backend = InMemoryBackend::<Blake2Hasher>::default();
+		let mut overlay = OverlayedChanges::default();
+		let mut cache = StorageTransactionCache::default();
+		let root = {
+			let mut ext = Ext::new(
+				&mut overlay,
+				&mut cache,
+				&backend,
+				crate::changes_trie::disabled_state::<_, u64>(),
+				None,
+			);
+			ext.set_offchain_storage(b"kept", Some(b"value"));
+			ext.set_offchain_storage(b"cleared", None);
+			ext.storage_root()
+		};
+		overlay.commit_prospective();
+
+		// Changes of a failed extrinsic are discarded with its other changes.
+		overlay.set_offchain_storage(b"discarded".to_vec(), Some(vec![1]));
+		overlay.discard_prospective();
+
+		let changes = overlay.drain_storage_changes::<_, _, u64>(
+			&backend,
+			None,
+			Default::default(),
+			&mut cache,
+		).unwrap();
+		assert_eq!(changes.offchain_storage_changes, vec![
+			(b"cleared".to_vec(), None),
+			(b"kept".to_vec(), Some(b"value".to_vec())),
+		]);
+		// The offchain database is not part of the storage root.
+		assert_eq!(root, backend.storage_root(std::iter::empty()).0.encode());
+		assert!(overlay.is_empty());
+	}
+
 	#[test]
 	fn overlayed_storage_root_works() {
 		let initial: BTreeMap<_, _> = vec![
@@ -113,6 +113,12 @@ impl<H: Hasher, N: ChangesTrieBlockNumber> TestExternalities<H, N>
 		self.extensions.register(ext);
 	}
 
+	/// Returns the pending change to the offchain database under the given key, as
+	/// `OverlayedChanges::offchain_storage` does.
+	pub fn offchain_storage(&self, key: &[u8]) -> Option<Option<&[u8]>> {
+		self.overlay.offchain_storage(key)
+	}
+
 	/// Get mutable reference to changes trie storage.
 	pub fn changes_trie_storage(&mut self) -> &mut ChangesTrieInMemoryStorage<H, N> {
 		&mut self.changes_trie_storage
@@ -289,6 +289,23 @@ pub trait FunctionContext {
 	fn deallocate_memory(&mut self, ptr: Pointer<u8>) -> Result<()>;
 	/// Provides access to the sandbox.
 	fn sandbox(&mut self) -> &mut dyn Sandbox;
+	/// The statistics of the allocations since they were last reset, if the allocator keeps them.
+	fn allocation_stats(&self) -> AllocationStats {
+		AllocationStats::default()
+	}
+	/// Reset the statistics of the allocations.
+	fn reset_allocation_stats(&mut self) {}
+}
+
+/// The statistics of the allocations of a wasm instance since they were last reset.
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct AllocationStats {
+	/// The greatest number of bytes the heap held at once, beyond those it held at the reset.
+	pub peak: u32,
+	/// The number of bytes allocated, including those freed since.
+	pub total: u64,
+	/// The number of allocations.
+	pub count: u32,
 }
 
 /// Sandbox memory identifier.
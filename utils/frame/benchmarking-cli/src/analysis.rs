@@ -0,0 +1,656 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Linear models fitted to the results of a benchmark.
+
+use frame_benchmarking::BenchmarkResults;
+use structopt::clap::arg_enum;
+
+arg_enum! {
+	/// The model fitted to the results of a benchmark.
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	pub enum AnalysisChoice {
+		// Least squares on the sweep of each component, see `Analysis::min_squares`.
+		MinSquares,
+		// The median of slopes on the sweep of each component, see `Analysis::median_slopes`.
+		MedianSlopes,
+		// Least squares on every component at once, see `Analysis::multivariate`.
+		Multivariate,
+	}
+}
+
+arg_enum! {
+	/// The clock the time taken by the benchmarked code is measured by for the weights.
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	pub enum Clock {
+		// The wall-clock time, which includes the time spent waiting for I/O or preempted.
+		Wall,
+		// The CPU time used by the benchmarking thread.
+		Cpu,
+	}
+}
+
+impl Clock {
+	/// The measurement of the time taken by the benchmarked code by this clock.
+	pub fn selector(&self) -> BenchmarkSelector {
+		match self {
+			Clock::Wall => BenchmarkSelector::ExtrinsicTime,
+			Clock::Cpu => BenchmarkSelector::ExtrinsicCpuTime,
+		}
+	}
+}
+
+arg_enum! {
+	/// What the weights make of the time taken to recalculate the storage root after a call.
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	pub enum StorageRoot {
+		// Leave it out of the weights.
+		Ignore,
+		// Add the model of it to the weight of each call.
+		Extrinsic,
+		// Charge it once per block, as the longest time it took after any benchmark.
+		Block,
+	}
+}
+
+impl AnalysisChoice {
+	/// Fit the chosen model to the results of `run_benchmark`.
+	///
+	/// The multivariate model falls back to `min_squares` if there are too few results for it.
+	pub fn fit(&self, results: &[BenchmarkResults], selector: BenchmarkSelector) -> Option<Analysis> {
+		match self {
+			AnalysisChoice::MinSquares => Analysis::min_squares(results, selector),
+			AnalysisChoice::MedianSlopes => Analysis::median_slopes(results, selector),
+			AnalysisChoice::Multivariate => Analysis::multivariate(results, selector)
+				.or_else(|| Analysis::min_squares(results, selector)),
+		}
+	}
+}
+
+/// The measurement of a benchmark to fit a model to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BenchmarkSelector {
+	/// The time taken by the benchmarked code.
+	ExtrinsicTime,
+	/// The CPU time used by the benchmarked code.
+	ExtrinsicCpuTime,
+	/// The time taken to recalculate the storage root.
+	StorageRootTime,
+	/// The number of distinct keys read from the database.
+	Reads,
+	/// The number of distinct keys written to the database.
+	Writes,
+	/// The encoded size of the storage proof of the reads.
+	ProofSize,
+	/// The number of distinct keys of child tries read.
+	ChildReads,
+	/// The number of distinct keys of child tries written.
+	ChildWrites,
+	/// The encoded size of the storage proof of the reads of child tries.
+	ChildProofSize,
+	/// The greatest number of bytes held on the heap at once.
+	PeakMemory,
+	/// The number of bytes allocated.
+	AllocatedMemory,
+}
+
+impl BenchmarkSelector {
+	fn select(&self, result: &BenchmarkResults) -> f64 {
+		match self {
+			BenchmarkSelector::ExtrinsicTime => result.extrinsic_time as f64,
+			BenchmarkSelector::ExtrinsicCpuTime => result.extrinsic_cpu_time as f64,
+			BenchmarkSelector::StorageRootTime => result.storage_root_time as f64,
+			BenchmarkSelector::Reads => result.reads as f64,
+			BenchmarkSelector::Writes => result.writes as f64,
+			BenchmarkSelector::ProofSize => result.proof_size as f64,
+			BenchmarkSelector::ChildReads => result.child_reads as f64,
+			BenchmarkSelector::ChildWrites => result.child_writes as f64,
+			BenchmarkSelector::ChildProofSize => result.child_proof_size as f64,
+			BenchmarkSelector::PeakMemory => result.peak_memory as f64,
+			BenchmarkSelector::AllocatedMemory => result.allocated_memory as f64,
+		}
+	}
+
+	/// The median of this measurement over `results`, or `None` if there are none.
+	pub fn median(&self, results: &[BenchmarkResults]) -> Option<u128> {
+		if results.is_empty() {
+			return None
+		}
+		Some(median(&results.iter().map(|result| self.select(result)).collect::<Vec<_>>()) as u128)
+	}
+}
+
+/// Drop the first `warmup` runs of each step of a benchmark, and then the `trim` percent of the
+/// remaining runs with the lowest and with the highest measurement by `selector`.
+///
+/// The runs of each step must follow one another, as they do in the results of `run_benchmark`.
+/// The order of the kept runs is preserved.
+pub fn filter_results(
+	results: Vec<BenchmarkResults>,
+	warmup: u32,
+	trim: u32,
+	selector: BenchmarkSelector,
+) -> Vec<BenchmarkResults> {
+	let mut runs_by_step: Vec<Vec<BenchmarkResults>> = Vec::new();
+	for result in results {
+		match runs_by_step.last_mut() {
+			Some(runs) if runs[0].step == result.step => runs.push(result),
+			_ => runs_by_step.push(vec![result]),
+		}
+	}
+
+	runs_by_step.into_iter().flat_map(|runs| {
+		let runs = runs.into_iter().skip(warmup as usize).collect::<Vec<_>>();
+		let cut = runs.len() * trim as usize / 100;
+		let mut by_measurement = (0 .. runs.len()).collect::<Vec<_>>();
+		by_measurement.sort_by(|&a, &b| selector.select(&runs[a])
+			.partial_cmp(&selector.select(&runs[b]))
+			.unwrap_or(std::cmp::Ordering::Equal)
+		);
+		let outliers = by_measurement[.. cut].iter()
+			.chain(&by_measurement[by_measurement.len() - cut ..])
+			.cloned()
+			.collect::<Vec<_>>();
+		runs.into_iter()
+			.enumerate()
+			.filter(move |(i, _)| !outliers.contains(i))
+			.map(|(_, run)| run)
+	}).collect()
+}
+
+/// The fixed time taken by a call into the runtime, to enter the executor and to set up the
+/// externalities for it, which would otherwise dominate the weights of small extrinsics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Overhead {
+	/// The time, in nanoseconds.
+	pub time: u128,
+}
+
+impl Overhead {
+	/// The overhead measured by the given times of runtime calls which do next to nothing, or
+	/// `None` if there are none.
+	pub fn measure(times: &[u128]) -> Option<Self> {
+		if times.is_empty() {
+			return None
+		}
+		let time = median(&times.iter().map(|&time| time as f64).collect::<Vec<_>>()) as u128;
+		Some(Overhead { time })
+	}
+
+	/// Subtract the overhead from the times of `results`, so that they are only the marginal cost
+	/// of the benchmarked code. Entering the executor doesn't wait on I/O, so the overhead is taken
+	/// off the wall-clock and the CPU time alike.
+	pub fn subtract(&self, results: &mut [BenchmarkResults]) {
+		for result in results {
+			result.extrinsic_time = result.extrinsic_time.saturating_sub(self.time);
+			result.extrinsic_cpu_time = result.extrinsic_cpu_time.saturating_sub(self.time);
+		}
+	}
+}
+
+/// A linear model of a measurement: its base value plus a slope for each component.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+	/// The value of the measurement when every component is zero.
+	pub base: u128,
+	/// The increase of the measurement for each unit of each component.
+	pub slopes: Vec<u128>,
+	/// The names of the components, in the order of `slopes`.
+	pub names: Vec<String>,
+	/// The standard errors of the base and then of each slope, if the model estimates them.
+	pub errors: Option<Vec<f64>>,
+}
+
+impl Analysis {
+	/// Fit a model to the results of `run_benchmark` by least squares, one component at a time.
+	///
+	/// Each component is swept over its range while every other one is at the top of its own, so
+	/// the slope of a component is fitted over its sweep alone. The intercept of that fit is the
+	/// base plus the contribution of every other component at the top of its range, which is
+	/// subtracted back out. Negative estimates are taken to be zero.
+	pub fn min_squares(results: &[BenchmarkResults], selector: BenchmarkSelector) -> Option<Self> {
+		Self::per_component(results, selector, linear_fit, mean)
+	}
+
+	/// Fit a model to the results of `run_benchmark` by the median of slopes, one component at a
+	/// time.
+	///
+	/// The components are swept as for `min_squares`, but the slope of a sweep is the median of the
+	/// slopes between each pair of its points and the intercept is the median of the intercepts
+	/// through each point at that slope. A few results which are far off, such as those slowed
+	/// down by the operating system, hardly move the model.
+	pub fn median_slopes(results: &[BenchmarkResults], selector: BenchmarkSelector) -> Option<Self> {
+		Self::per_component(results, selector, median_fit, median)
+	}
+
+	// A model fitted to the sweep of each component by `fit`, with the bases found from each sweep
+	// combined by `combine`.
+	fn per_component(
+		results: &[BenchmarkResults],
+		selector: BenchmarkSelector,
+		fit: fn(&[(f64, f64)]) -> (f64, f64),
+		combine: fn(&[f64]) -> f64,
+	) -> Option<Self> {
+		let first = results.first()?;
+		let names = first.components.iter().map(|(name, _)| format!("{:?}", name)).collect::<Vec<_>>();
+		let highest = (0 .. names.len())
+			.map(|i| results.iter().map(|r| r.components[i].1).max().unwrap_or_default() as f64)
+			.collect::<Vec<_>>();
+
+		if names.is_empty() {
+			let values = results.iter().map(|r| selector.select(r)).collect::<Vec<_>>();
+			let base = combine(&values);
+			return Some(Analysis { base: base.max(0.0).round() as u128, slopes: vec![], names, errors: None })
+		}
+
+		// The intercept and slope of the sweep of each component.
+		let fits = (0 .. names.len()).map(|i| {
+			let points = results.iter()
+				.filter(|r| r.components.iter().enumerate()
+					.all(|(j, c)| j == i || c.1 as f64 == highest[j])
+				)
+				.map(|r| (r.components[i].1 as f64, selector.select(r)))
+				.collect::<Vec<_>>();
+			fit(&points)
+		}).collect::<Vec<_>>();
+		let slopes = fits.iter().map(|&(_, slope)| slope.max(0.0)).collect::<Vec<_>>();
+
+		let bases = fits.iter().enumerate().map(|(i, &(intercept, _))| {
+			let others = slopes.iter().zip(&highest).enumerate()
+				.filter(|&(j, _)| j != i)
+				.map(|(_, (slope, high))| slope * high)
+				.sum::<f64>();
+			intercept - others
+		}).collect::<Vec<_>>();
+		let base = combine(&bases);
+
+		Some(Analysis {
+			base: base.max(0.0).round() as u128,
+			slopes: slopes.iter().map(|slope| slope.round() as u128).collect(),
+			names,
+			errors: None,
+		})
+	}
+
+	/// Fit a model to the results of `run_benchmark` by multiple linear regression.
+	///
+	/// Every sampled combination of component values is used at once, so the slopes are not
+	/// skewed by the values the other components are held at. A component which is never varied
+	/// cannot be told apart from the base and has a slope of zero. Negative estimates are taken to
+	/// be zero, and `None` is returned if there are fewer results than terms in the model.
+	pub fn multivariate(results: &[BenchmarkResults], selector: BenchmarkSelector) -> Option<Self> {
+		let first = results.first()?;
+		let names = first.components.iter().map(|(name, _)| format!("{:?}", name)).collect::<Vec<_>>();
+		let varied = (0 .. names.len())
+			.filter(|&i| results.iter().any(|r| r.components[i].1 != first.components[i].1))
+			.collect::<Vec<_>>();
+
+		// The base is the coefficient of a term which is always one.
+		let terms = varied.len() + 1;
+		if results.len() < terms {
+			return None
+		}
+		let rows = results.iter()
+			.map(|r| std::iter::once(1.0)
+				.chain(varied.iter().map(|&i| r.components[i].1 as f64))
+				.collect::<Vec<_>>()
+			)
+			.collect::<Vec<_>>();
+		let values = results.iter().map(|r| selector.select(r)).collect::<Vec<_>>();
+
+		// Solve the normal equations `XᵀX b = Xᵀy`.
+		let mut xtx = vec![vec![0.0; terms]; terms];
+		let mut xty = vec![0.0; terms];
+		for (row, value) in rows.iter().zip(&values) {
+			for i in 0 .. terms {
+				xty[i] += row[i] * value;
+				for j in 0 .. terms {
+					xtx[i][j] += row[i] * row[j];
+				}
+			}
+		}
+		let inverse = invert(xtx)?;
+		let coefficients = inverse.iter()
+			.map(|row| row.iter().zip(&xty).map(|(a, b)| a * b).sum::<f64>())
+			.collect::<Vec<_>>();
+
+		// The variance of a coefficient is the variance of the residuals scaled by the diagonal of
+		// the inverse of `XᵀX`.
+		let residuals = rows.iter().zip(&values)
+			.map(|(row, value)| {
+				let fitted = row.iter().zip(&coefficients).map(|(x, c)| x * c).sum::<f64>();
+				(value - fitted).powi(2)
+			})
+			.sum::<f64>();
+		let freedom = results.len() - terms;
+		let variance = if freedom > 0 { residuals / freedom as f64 } else { 0.0 };
+		let term_errors = (0 .. terms)
+			.map(|i| (variance * inverse[i][i]).max(0.0).sqrt())
+			.collect::<Vec<_>>();
+
+		let mut slopes = vec![0; names.len()];
+		let mut errors = vec![term_errors[0]];
+		errors.resize(names.len() + 1, 0.0);
+		for (term, &i) in varied.iter().enumerate() {
+			slopes[i] = coefficients[term + 1].max(0.0).round() as u128;
+			errors[i + 1] = term_errors[term + 1];
+		}
+
+		Some(Analysis {
+			base: coefficients[0].max(0.0).round() as u128,
+			slopes,
+			names,
+			errors: Some(errors),
+		})
+	}
+}
+
+impl std::ops::Add for Analysis {
+	type Output = Analysis;
+
+	/// The model of the sum of two measurements of the same benchmark. The standard errors are
+	/// combined as if the measurements were independent.
+	fn add(self, other: Analysis) -> Analysis {
+		let errors = match (self.errors, other.errors) {
+			(Some(a), Some(b)) => Some(a.iter().zip(&b).map(|(a, b)| (a * a + b * b).sqrt()).collect()),
+			_ => None,
+		};
+		Analysis {
+			base: self.base + other.base,
+			slopes: self.slopes.iter().zip(&other.slopes).map(|(a, b)| a + b).collect(),
+			names: self.names,
+			errors,
+		}
+	}
+}
+
+impl std::fmt::Display for Analysis {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let error = |i: usize| match &self.errors {
+			Some(errors) => format!(" (±{:.2})", errors[i]),
+			None => String::new(),
+		};
+		write!(f, "{}{}", self.base, error(0))?;
+		for (i, (name, slope)) in self.names.iter().zip(&self.slopes).enumerate() {
+			write!(f, "\n    + {}{} * {}", slope, error(i + 1), name)?;
+		}
+		Ok(())
+	}
+}
+
+/// The intercept and slope of the least squares line through `points`. The line is flat if the
+/// points do not vary along the x axis.
+fn linear_fit(points: &[(f64, f64)]) -> (f64, f64) {
+	if points.is_empty() {
+		return (0.0, 0.0)
+	}
+	let n = points.len() as f64;
+	let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+	let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+	let var_x = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum::<f64>();
+	if var_x == 0.0 {
+		return (mean_y, 0.0)
+	}
+	let cov = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum::<f64>();
+	let slope = cov / var_x;
+	(mean_y - slope * mean_x, slope)
+}
+
+/// The intercept and slope of the line through `points` whose slope is the median of the slopes
+/// between every pair of points, and whose intercept is the median of the intercepts through each
+/// point at that slope. The line is flat if the points do not vary along the x axis.
+fn median_fit(points: &[(f64, f64)]) -> (f64, f64) {
+	if points.is_empty() {
+		return (0.0, 0.0)
+	}
+	let mut slopes = Vec::new();
+	for (i, a) in points.iter().enumerate() {
+		for b in &points[i + 1 ..] {
+			if a.0 != b.0 {
+				slopes.push((b.1 - a.1) / (b.0 - a.0));
+			}
+		}
+	}
+	let slope = if slopes.is_empty() { 0.0 } else { median(&slopes) };
+	let intercepts = points.iter().map(|p| p.1 - slope * p.0).collect::<Vec<_>>();
+	(median(&intercepts), slope)
+}
+
+/// The mean of `values`, which must not be empty.
+fn mean(values: &[f64]) -> f64 {
+	values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// The median of `values`, which must not be empty.
+fn median(values: &[f64]) -> f64 {
+	let mut sorted = values.to_vec();
+	sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+	let middle = sorted.len() / 2;
+	if sorted.len() % 2 == 0 {
+		(sorted[middle - 1] + sorted[middle]) / 2.0
+	} else {
+		sorted[middle]
+	}
+}
+
+/// The inverse of a square matrix by Gauss-Jordan elimination, or `None` if it is singular.
+fn invert(mut matrix: Vec<Vec<f64>>) -> Option<Vec<Vec<f64>>> {
+	let size = matrix.len();
+	let mut inverse = (0 .. size)
+		.map(|i| (0 .. size).map(|j| if i == j { 1.0 } else { 0.0 }).collect::<Vec<_>>())
+		.collect::<Vec<_>>();
+
+	for column in 0 .. size {
+		// Pivot on the largest remaining entry of the column to keep the error small.
+		let pivot = (column .. size)
+			.max_by(|&a, &b| matrix[a][column].abs().partial_cmp(&matrix[b][column].abs())
+				.unwrap_or(std::cmp::Ordering::Equal)
+			)?;
+		if matrix[pivot][column].abs() < 1e-10 {
+			return None
+		}
+		matrix.swap(column, pivot);
+		inverse.swap(column, pivot);
+
+		let scale = matrix[column][column];
+		for j in 0 .. size {
+			matrix[column][j] /= scale;
+			inverse[column][j] /= scale;
+		}
+		for row in 0 .. size {
+			let factor = matrix[row][column];
+			if row == column || factor == 0.0 {
+				continue
+			}
+			for j in 0 .. size {
+				matrix[row][j] -= factor * matrix[column][j];
+				inverse[row][j] -= factor * inverse[column][j];
+			}
+		}
+	}
+
+	Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_benchmarking::BenchmarkParameter;
+
+	fn result(n: u32, m: u32, time: u128, reads: u32) -> BenchmarkResults {
+		BenchmarkResults {
+			components: vec![(BenchmarkParameter::n, n), (BenchmarkParameter::m, m)],
+			extrinsic_time: time,
+			reads,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn filter_results_should_drop_warmup_and_outliers() {
+		let times = [
+			(1, 500), (1, 20), (1, 21), (1, 90), (1, 19), (1, 1), (1, 22),
+			(2, 300), (2, 40), (2, 41),
+		];
+		let results = times.iter()
+			.map(|&(n, time)| BenchmarkResults { step: n, ..result(n, 0, time, 0) })
+			.collect::<Vec<_>>();
+
+		let filtered = filter_results(results.clone(), 1, 20, BenchmarkSelector::ExtrinsicTime);
+		let kept = filtered.iter().map(|r| r.extrinsic_time).collect::<Vec<_>>();
+		assert_eq!(kept, vec![20, 21, 19, 22, 40, 41]);
+
+		let unfiltered = filter_results(results.clone(), 0, 0, BenchmarkSelector::ExtrinsicTime);
+		assert_eq!(unfiltered, results);
+	}
+
+	#[test]
+	fn filter_results_should_group_by_step_and_trim_by_the_selection() {
+		// Two steps whose components were clamped to the same values.
+		let times = [(0, 100, 7), (0, 10, 8), (0, 11, 9), (1, 200, 1), (1, 20, 50), (1, 21, 2)];
+		let results = times.iter()
+			.map(|&(step, cpu, time)| BenchmarkResults {
+				step,
+				extrinsic_cpu_time: cpu,
+				..result(1, 0, time, 0)
+			})
+			.collect::<Vec<_>>();
+
+		let filtered = filter_results(results.clone(), 0, 34, BenchmarkSelector::ExtrinsicCpuTime);
+		let kept = filtered.iter().map(|r| r.extrinsic_cpu_time).collect::<Vec<_>>();
+		assert_eq!(kept, vec![11, 21]);
+
+		let filtered = filter_results(results, 0, 34, BenchmarkSelector::ExtrinsicTime);
+		let kept = filtered.iter().map(|r| r.extrinsic_time).collect::<Vec<_>>();
+		assert_eq!(kept, vec![8, 2]);
+	}
+
+	#[test]
+	fn overhead_should_be_subtracted() {
+		let overhead = Overhead::measure(&[5, 7, 6, 100, 6]).unwrap();
+		assert_eq!(overhead, Overhead { time: 6 });
+
+		let mut results = vec![
+			BenchmarkResults { extrinsic_cpu_time: 40, ..result(1, 0, 50, 0) },
+			result(2, 0, 3, 0),
+		];
+		overhead.subtract(&mut results);
+		assert_eq!(results.iter().map(|r| r.extrinsic_time).collect::<Vec<_>>(), vec![44, 0]);
+		assert_eq!(results.iter().map(|r| r.extrinsic_cpu_time).collect::<Vec<_>>(), vec![34, 0]);
+		assert_eq!(Overhead::measure(&[]), None);
+	}
+
+	#[test]
+	fn analyses_should_add() {
+		let a = Analysis {
+			base: 10,
+			slopes: vec![1, 2],
+			names: vec!["n".into(), "m".into()],
+			errors: Some(vec![3.0, 0.0, 1.0]),
+		};
+		let b = Analysis { base: 5, slopes: vec![0, 3], errors: Some(vec![4.0, 1.0, 0.0]), ..a.clone() };
+		assert_eq!(a.clone() + b.clone(), Analysis {
+			base: 15,
+			slopes: vec![1, 5],
+			names: vec!["n".into(), "m".into()],
+			errors: Some(vec![5.0, 1.0, 1.0]),
+		});
+		assert_eq!((a + Analysis { errors: None, ..b }).errors, None);
+	}
+
+	#[test]
+	fn min_squares_should_work() {
+		// time = 10 + 3n + 7m, reads = 2 + n, sampled as `run_benchmark` does.
+		let results = vec![
+			result(1, 5, 10 + 3 + 35, 3),
+			result(3, 5, 10 + 9 + 35, 5),
+			result(5, 5, 10 + 15 + 35, 7),
+			result(5, 1, 10 + 15 + 7, 7),
+			result(5, 3, 10 + 15 + 21, 7),
+			result(5, 5, 10 + 15 + 35, 7),
+		];
+
+		let time = Analysis::min_squares(&results, BenchmarkSelector::ExtrinsicTime).unwrap();
+		assert_eq!(time.base, 10);
+		assert_eq!(time.slopes, vec![3, 7]);
+		assert_eq!(time.names, vec!["n".to_string(), "m".to_string()]);
+
+		let reads = Analysis::min_squares(&results, BenchmarkSelector::Reads).unwrap();
+		assert_eq!(reads.base, 2);
+		assert_eq!(reads.slopes, vec![1, 0]);
+	}
+
+	#[test]
+	fn median_slopes_should_ignore_outliers() {
+		// time = 10 + 3n + 7m, with one sample of each sweep slowed down.
+		let results = vec![
+			result(1, 5, 10 + 3 + 35, 3),
+			result(2, 5, 10 + 6 + 35 + 500, 4),
+			result(3, 5, 10 + 9 + 35, 5),
+			result(4, 5, 10 + 12 + 35, 6),
+			result(5, 5, 10 + 15 + 35, 7),
+			result(5, 1, 10 + 15 + 7, 7),
+			result(5, 2, 10 + 15 + 14, 7),
+			result(5, 3, 10 + 15 + 21 + 500, 7),
+			result(5, 4, 10 + 15 + 28, 7),
+			result(5, 5, 10 + 15 + 35, 7),
+		];
+
+		let time = Analysis::median_slopes(&results, BenchmarkSelector::ExtrinsicTime).unwrap();
+		assert_eq!(time.base, 10);
+		assert_eq!(time.slopes, vec![3, 7]);
+		assert_ne!(Analysis::min_squares(&results, BenchmarkSelector::ExtrinsicTime), Some(time));
+
+		let reads = Analysis::median_slopes(&results, BenchmarkSelector::Reads).unwrap();
+		assert_eq!(reads.base, 2);
+		assert_eq!(reads.slopes, vec![1, 0]);
+	}
+
+	#[test]
+	fn multivariate_should_work() {
+		// time = 10 + 3n + 7m over every combination, with noise on two of the samples.
+		let mut results = Vec::new();
+		for n in 1 ..= 4 {
+			for m in 1 ..= 4 {
+				results.push(result(n, m, (10 + 3 * n + 7 * m) as u128, 2 + n));
+			}
+		}
+		results[0].extrinsic_time += 1;
+		results[3].extrinsic_time -= 1;
+
+		let time = Analysis::multivariate(&results, BenchmarkSelector::ExtrinsicTime).unwrap();
+		assert_eq!(time.base, 10);
+		assert_eq!(time.slopes, vec![3, 7]);
+		let errors = time.errors.unwrap();
+		assert_eq!(errors.len(), 3);
+		assert!(errors.iter().all(|&e| e > 0.0 && e < 1.0));
+
+		let reads = Analysis::multivariate(&results, BenchmarkSelector::Reads).unwrap();
+		assert_eq!(reads.base, 2);
+		assert_eq!(reads.slopes, vec![1, 0]);
+		assert!(reads.errors.unwrap().iter().all(|&e| e < 1e-6));
+	}
+
+	#[test]
+	fn multivariate_should_ignore_fixed_components() {
+		let results = vec![result(1, 5, 13, 0), result(2, 5, 16, 0), result(3, 5, 19, 0)];
+		let time = Analysis::multivariate(&results, BenchmarkSelector::ExtrinsicTime).unwrap();
+		assert_eq!(time.base, 10);
+		assert_eq!(time.slopes, vec![3, 0]);
+
+		assert_eq!(Analysis::multivariate(&[], BenchmarkSelector::ExtrinsicTime), None);
+	}
+}
@@ -0,0 +1,233 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The comparison of two sets of benchmark results, as printed by `benchmark --json`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use crate::analysis::Clock;
+
+/// The `benchmark-diff` command, which reports the extrinsics whose weight changed between two
+/// sets of results printed by `benchmark --json`.
+#[derive(Debug, structopt::StructOpt, Clone)]
+pub struct BenchmarkDiffCmd {
+	/// The results to compare from, such as those of the last release.
+	#[structopt(parse(from_os_str))]
+	pub old: PathBuf,
+
+	/// The results to compare to.
+	#[structopt(parse(from_os_str))]
+	pub new: PathBuf,
+
+	/// Report the extrinsics whose weight changed by more than this percentage.
+	#[structopt(long, default_value = "10")]
+	pub threshold: u32,
+
+	/// The clock by which the time taken by the benchmarked code is weighed.
+	#[structopt(
+		long,
+		value_name = "CLOCK",
+		possible_values = &Clock::variants(),
+		case_insensitive = true,
+		default_value = "Wall",
+	)]
+	pub clock: Clock,
+
+	/// The weight of a database read.
+	#[structopt(long, default_value = "25000")]
+	pub db_read_weight: u64,
+
+	/// The weight of a database write.
+	#[structopt(long, default_value = "100000")]
+	pub db_write_weight: u64,
+}
+
+impl BenchmarkDiffCmd {
+	/// Print the extrinsics whose weight changed by more than the threshold, and those which were
+	/// added or removed, as a Markdown list.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let old = self.weights(&self.old)?;
+		let new = self.weights(&self.new)?;
+		let changes = compare(&old, &new, self.threshold);
+		if changes.is_empty() {
+			println!("No weight changed by more than {}%.", self.threshold);
+		} else {
+			println!("Weights changed by more than {}%:", self.threshold);
+			changes.iter().for_each(|change| println!("- {}", change));
+		}
+		Ok(())
+	}
+
+	fn weights(&self, path: &Path) -> Result<BTreeMap<(String, String), u128>, String> {
+		let json = std::fs::read_to_string(path)
+			.map_err(|e| format!("Failed to read {:?}: {:?}", path, e))?;
+		let points = serde_json::from_str::<Vec<DataPoint>>(&json)
+			.map_err(|e| format!("Failed to parse {:?}: {:?}", path, e))?;
+		Ok(weights(&points, self.clock, self.db_read_weight, self.db_write_weight))
+	}
+}
+
+/// A raw data point of a benchmark, as printed by `benchmark --json`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct DataPoint {
+	pub pallet: String,
+	pub extrinsic: String,
+	pub components: Vec<(String, u32)>,
+	pub extrinsic_time: u128,
+	pub extrinsic_cpu_time: u128,
+	pub reads: u32,
+	pub writes: u32,
+}
+
+/// The weight of each extrinsic, by pallet and name, which is the greatest of the median weights
+/// of the repetitions at each set of component values.
+pub fn weights(
+	points: &[DataPoint],
+	clock: Clock,
+	db_read_weight: u64,
+	db_write_weight: u64,
+) -> BTreeMap<(String, String), u128> {
+	let mut repetitions = BTreeMap::<(&str, &str, &[(String, u32)]), Vec<u128>>::new();
+	for point in points {
+		let time = match clock {
+			Clock::Wall => point.extrinsic_time,
+			Clock::Cpu => point.extrinsic_cpu_time,
+		};
+		let weight = time
+			+ point.reads as u128 * db_read_weight as u128
+			+ point.writes as u128 * db_write_weight as u128;
+		repetitions.entry((point.pallet.as_str(), point.extrinsic.as_str(), point.components.as_slice()))
+			.or_default()
+			.push(weight);
+	}
+
+	let mut weights = BTreeMap::<(String, String), u128>::new();
+	for ((pallet, extrinsic, _), mut repeated) in repetitions {
+		repeated.sort();
+		let median = repeated[repeated.len() / 2];
+		let weight = weights.entry((pallet.to_string(), extrinsic.to_string())).or_default();
+		*weight = (*weight).max(median);
+	}
+	weights
+}
+
+/// A change of the weight of an extrinsic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+	pub pallet: String,
+	pub extrinsic: String,
+	/// The weight before, or `None` if the extrinsic was added.
+	pub old: Option<u128>,
+	/// The weight after, or `None` if the extrinsic was removed.
+	pub new: Option<u128>,
+}
+
+impl fmt::Display for Change {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}::{}: ", self.pallet, self.extrinsic)?;
+		match (self.old, self.new) {
+			(Some(old), Some(new)) => write!(
+				f,
+				"{} -> {} ({:+.1}%)",
+				old,
+				new,
+				(new as f64 - old as f64) * 100.0 / (old as f64).max(1.0),
+			),
+			(None, Some(new)) => write!(f, "added at {}", new),
+			(Some(old), None) => write!(f, "removed from {}", old),
+			(None, None) => Ok(()),
+		}
+	}
+}
+
+/// The extrinsics whose weight changed by more than `threshold` percent from `old` to `new`, and
+/// those which are only in one of them.
+pub fn compare(
+	old: &BTreeMap<(String, String), u128>,
+	new: &BTreeMap<(String, String), u128>,
+	threshold: u32,
+) -> Vec<Change> {
+	let mut keys = old.keys().chain(new.keys()).collect::<Vec<_>>();
+	keys.sort();
+	keys.dedup();
+	keys.into_iter().filter_map(|key| {
+		let (before, after) = (old.get(key).cloned(), new.get(key).cloned());
+		let changed = match (before, after) {
+			(Some(before), Some(after)) => {
+				let delta = if after > before { after - before } else { before - after };
+				delta * 100 > before * threshold as u128
+			}
+			_ => true,
+		};
+		if changed {
+			Some(Change { pallet: key.0.clone(), extrinsic: key.1.clone(), old: before, new: after })
+		} else {
+			None
+		}
+	}).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn point(extrinsic: &str, n: u32, time: u128, reads: u32) -> DataPoint {
+		DataPoint {
+			pallet: "pallet-democracy".into(),
+			extrinsic: extrinsic.into(),
+			components: vec![("n".into(), n)],
+			extrinsic_time: time,
+			extrinsic_cpu_time: time / 2,
+			reads,
+			writes: 0,
+		}
+	}
+
+	#[test]
+	fn weights_should_be_the_worst_median() {
+		let points = vec![
+			point("vote", 1, 100, 1),
+			point("vote", 1, 300, 1),
+			point("vote", 1, 200, 1),
+			point("vote", 10, 1_000, 2),
+			point("vote", 10, 5_000, 2),
+			point("vote", 10, 900, 2),
+		];
+		let key = ("pallet-democracy".to_string(), "vote".to_string());
+		assert_eq!(weights(&points, Clock::Wall, 10, 100).get(&key), Some(&1_020));
+		assert_eq!(weights(&points, Clock::Cpu, 10, 100).get(&key), Some(&520));
+	}
+
+	#[test]
+	fn changes_above_the_threshold_should_be_reported() {
+		let key = |extrinsic: &str| ("pallet-democracy".to_string(), extrinsic.to_string());
+		let old = vec![(key("vote"), 1_000), (key("second"), 1_000), (key("propose"), 1_000)]
+			.into_iter().collect();
+		let new = vec![(key("vote"), 1_200), (key("second"), 1_050), (key("delegate"), 500)]
+			.into_iter().collect();
+		let changes = compare(&old, &new, 10);
+		assert_eq!(
+			changes.iter().map(|change| change.to_string()).collect::<Vec<_>>(),
+			vec![
+				"pallet-democracy::delegate: added at 500",
+				"pallet-democracy::propose: removed from 1000",
+				"pallet-democracy::vote: 1000 -> 1200 (+20.0%)",
+			],
+		);
+	}
+}
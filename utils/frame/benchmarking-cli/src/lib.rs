@@ -14,28 +14,49 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
+mod analysis;
+mod diff;
+mod machine;
+mod progress;
+mod snapshot;
+mod worst_case;
+mod writer;
+
 use std::fmt::Debug;
+use std::path::PathBuf;
+use std::time::Instant;
 use sp_runtime::{traits::{Block as BlockT, Header as HeaderT, NumberFor}};
 use sc_client::StateMachine;
 use sc_cli::{ExecutionStrategy, WasmExecutionMethod, VersionInfo};
 use sc_client_db::BenchmarkingState;
 use sc_service::{Configuration, ChainSpec};
 use sc_executor::{NativeExecutor, NativeExecutionDispatch};
+use sp_externalities::Extensions;
 use codec::{Encode, Decode};
-use frame_benchmarking::BenchmarkResults;
-use sp_core::tasks;
+use frame_benchmarking::{BenchmarkResults, BenchmarkMetadata, ProgressExt};
+use sp_core::{tasks, hexdisplay::HexDisplay};
+use analysis::{AnalysisChoice, BenchmarkSelector, Clock, StorageRoot};
+use worst_case::Search;
+
+pub use diff::BenchmarkDiffCmd;
 
 /// The `benchmark` command used to benchmark FRAME Pallets.
 #[derive(Debug, structopt::StructOpt, Clone)]
 pub struct BenchmarkCmd {
-	/// Select a FRAME Pallet to benchmark.
+	/// Select a FRAME Pallet to benchmark, a comma separated list of them, or `*` for every
+	/// benchmarked pallet of the runtime.
 	#[structopt(short, long)]
 	pub pallet: String,
 
-	/// Select an extrinsic to benchmark.
+	/// Select an extrinsic to benchmark, or a comma separated list of them. A `*` in a name stands
+	/// for any run of characters, so that `*` selects every benchmark of a pallet.
 	#[structopt(short, long)]
 	pub extrinsic: String,
 
+	/// Also run the benchmarks tagged `#[extra]` which are selected through a `*`.
+	#[structopt(long)]
+	pub extra: bool,
+
 	/// Select how many samples we should take across the variable components.
 	#[structopt(short, long, use_delimiter = true)]
 	pub steps: Vec<u32>,
@@ -52,6 +73,153 @@ pub struct BenchmarkCmd {
 	#[structopt(short, long, default_value = "1")]
 	pub repeat: u32,
 
+	/// Select how many runs of each set of component values are discarded before the measured
+	/// ones, to warm up caches.
+	#[structopt(long, default_value = "0")]
+	pub warmup: u32,
+
+	/// Discard this percentage of the fastest and of the slowest measured runs of each set of
+	/// component values, so that jitter doesn't skew the analysis.
+	#[structopt(long, default_value = "0")]
+	pub trim: u32,
+
+	/// Override the steps of one benchmark, as `<pallet>::<extrinsic>=<steps>` where `<steps>` is
+	/// a comma separated list like that of `--steps`. May be given once for each benchmark.
+	#[structopt(
+		long,
+		value_name = "PALLET::EXTRINSIC=STEPS",
+		number_of_values = 1,
+		parse(try_from_str = parse_steps_override),
+	)]
+	pub override_steps: Vec<((String, String), Vec<u32>)>,
+
+	/// Override the repetitions of one benchmark, as `<pallet>::<extrinsic>=<repeat>`. May be given
+	/// once for each benchmark.
+	#[structopt(
+		long,
+		value_name = "PALLET::EXTRINSIC=REPEAT",
+		number_of_values = 1,
+		parse(try_from_str = parse_repeat_override),
+	)]
+	pub override_repeat: Vec<((String, String), u32)>,
+
+	/// Write the weights fitted to the results into a Rust file, or into a file per pallet if this
+	/// is a directory.
+	#[structopt(long, parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	/// A Handlebars template to render the weight file from, instead of the default one.
+	#[structopt(long, parse(from_os_str))]
+	pub template: Option<PathBuf>,
+
+	/// A file whose contents, such as a license, are put at the top of the weight file.
+	#[structopt(long, parse(from_os_str))]
+	pub header: Option<PathBuf>,
+
+	/// The weight of a database read in the weight file.
+	#[structopt(long, default_value = "25000")]
+	pub db_read_weight: u64,
+
+	/// The weight of a database write in the weight file.
+	#[structopt(long, default_value = "100000")]
+	pub db_write_weight: u64,
+
+	/// The weight of a read of a key of a child trie in the weight file.
+	#[structopt(long, default_value = "25000")]
+	pub db_child_read_weight: u64,
+
+	/// The weight of a write to a key of a child trie in the weight file.
+	#[structopt(long, default_value = "100000")]
+	pub db_child_write_weight: u64,
+
+	/// The model fitted to the results to write the weight file.
+	#[structopt(
+		long,
+		value_name = "MODEL",
+		possible_values = &AnalysisChoice::variants(),
+		case_insensitive = true,
+		default_value = "Multivariate",
+	)]
+	pub analysis: AnalysisChoice,
+
+	/// The clock by which the time taken by the benchmarked code is measured for the weights. The
+	/// time by both clocks is printed either way.
+	#[structopt(
+		long,
+		value_name = "CLOCK",
+		possible_values = &Clock::variants(),
+		case_insensitive = true,
+		default_value = "Wall",
+	)]
+	pub clock: Clock,
+
+	/// What the weights make of the time taken to recalculate the storage root after a call: leave
+	/// it out, add it to the weight of each call, or write it as a per-block constant.
+	#[structopt(
+		long,
+		value_name = "CHARGE",
+		possible_values = &StorageRoot::variants(),
+		case_insensitive = true,
+		default_value = "Ignore",
+	)]
+	pub storage_root: StorageRoot,
+
+	/// Measure the fixed time taken by a call into the runtime, to enter the executor and to set up
+	/// the externalities, and subtract it from the time taken by every benchmark.
+	#[structopt(long)]
+	pub subtract_overhead: bool,
+
+	/// Refuse to run if the calibration before the benchmarks finds this machine much slower or
+	/// faster than the reference hardware, rather than only noting it in the output.
+	#[structopt(long)]
+	pub strict: bool,
+
+	/// Carry on with the other benchmarks when one fails, then print which passed and which failed,
+	/// and exit with an error if any did.
+	#[structopt(long)]
+	pub continue_on_error: bool,
+
+	/// Report the progress of each benchmark run on stderr as a JSON line per repetition, for other
+	/// tools to follow, rather than as a progress bar.
+	#[structopt(long)]
+	pub progress_json: bool,
+
+	/// Instead of sweeping each component with the others at their highest, search for the
+	/// combination of component values at which each benchmark takes the longest by the `--clock`,
+	/// and print it. `Grid` times every combination of the values the components would be swept
+	/// at, so keep `--steps` small, while `Climb` only times those on its way from the highest
+	/// values to a slowest combination.
+	#[structopt(
+		long,
+		value_name = "SEARCH",
+		possible_values = &Search::variants(),
+		case_insensitive = true,
+	)]
+	pub worst_case: Option<Search>,
+
+	/// Run the benchmarks on top of the state of a live chain, fetched from the RPC server of one
+	/// of its nodes at this URI, rather than on top of the genesis of the chain spec. The code of
+	/// the chain spec is run all the same. Child tries are not fetched.
+	#[structopt(long, value_name = "URI")]
+	pub snapshot_uri: Option<String>,
+
+	/// The hash of the block of the live chain whose state is fetched, rather than the best block.
+	#[structopt(long, value_name = "HASH", requires = "snapshot-uri")]
+	pub snapshot_at: Option<String>,
+
+	/// A file to write the state fetched from `--snapshot-uri` to or, without `--snapshot-uri`, to
+	/// read a state written before from, to benchmark on top of.
+	#[structopt(long, parse(from_os_str))]
+	pub snapshot: Option<PathBuf>,
+
+	/// Print every raw data point as JSON instead of the table.
+	#[structopt(long)]
+	pub json: bool,
+
+	/// Write every raw data point into a CSV file.
+	#[structopt(long, parse(from_os_str))]
+	pub output_csv: Option<PathBuf>,
+
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub shared_params: sc_cli::SharedParams,
@@ -93,74 +261,421 @@ impl BenchmarkCmd {
 		<BB as BlockT>::Hash: std::str::FromStr,
 		ExecDispatch: NativeExecutionDispatch + 'static,
 	{
+		if self.trim >= 50 {
+			return Err("--trim must be less than 50 percent".into())
+		}
+
 		let spec = config.chain_spec.expect("chain_spec is always Some");
 		let wasm_method = self.wasm_method.into();
 		let strategy = self.execution.unwrap_or(ExecutionStrategy::Native);
 
 		let genesis_storage = spec.build_storage()?;
+		let storage = match (&self.snapshot_uri, &self.snapshot) {
+			(Some(uri), path) => {
+				let at = self.snapshot_at.as_ref()
+					.map(|at| at.parse::<BB::Hash>().map_err(|_| format!("Invalid block hash {}", at)))
+					.transpose()?;
+				let pairs = snapshot::fetch(uri, at)?;
+				if let Some(path) = path {
+					snapshot::save(&pairs, path)?;
+				}
+				snapshot::into_storage(pairs, &genesis_storage)
+			}
+			(None, Some(path)) => snapshot::into_storage(snapshot::load(path)?, &genesis_storage),
+			(None, None) => genesis_storage,
+		};
 		let mut changes = Default::default();
-		let state = BenchmarkingState::<BB>::new(genesis_storage)?;
+		let state = BenchmarkingState::<BB>::new(storage)?;
+
+		let calibration = machine::Calibration::measure(&state)?;
+		eprintln!("Machine: {}", calibration);
+		if self.strict && !calibration.is_within_tolerance() {
+			return Err("The machine is too far off the reference hardware, see --strict".into())
+		}
+
 		let executor = NativeExecutor::<ExecDispatch>::new(
 			wasm_method,
 			None, // heap pages
 			2, // The runtime instances cache size.
 		);
 
-		let result = StateMachine::<_, _, NumberFor<BB>, _>::new(
-			&state,
-			None,
-			&mut changes,
-			&executor,
-			"Benchmark_dispatch_benchmark",
-			&(
-				&self.pallet,
-				&self.extrinsic,
-				self.lowest_range_values.clone(),
-				self.highest_range_values.clone(),
-				self.steps.clone(),
-				self.repeat,
-			).encode(),
-			Default::default(),
-			&sp_state_machine::backend::BackendRuntimeCode::new(&state).runtime_code()?,
-			tasks::executor(),
-		)
-		.execute(strategy.into())
-		.map_err(|e| format!("Error executing runtime benchmark: {:?}", e))?;
-
-		let results = <Result<Vec<BenchmarkResults>, String> as Decode>::decode(&mut &result[..])
-			.map_err(|e| format!("Failed to decode benchmark results: {:?}", e))?;
-
-		match results {
-			Ok(results) => {
+		// Run a runtime call, reporting the progress of a benchmark run to `progress` if given.
+		let mut execute = |method: &str, data: &[u8], progress: Option<ProgressExt>| -> Result<Vec<u8>, String> {
+			let mut extensions = Extensions::new();
+			if let Some(progress) = progress {
+				extensions.register(progress);
+			}
+			StateMachine::<_, _, NumberFor<BB>, _>::new(
+				&state,
+				None,
+				&mut changes,
+				&executor,
+				method,
+				data,
+				extensions,
+				&sp_state_machine::backend::BackendRuntimeCode::new(&state).runtime_code()?,
+				tasks::executor(),
+			)
+			.execute(strategy.into())
+			.map_err(|e| format!("Error executing runtime benchmark: {:?}", e))
+		};
+
+		let pallets: Vec<String> = if self.pallet == "*" {
+			let result = execute("Benchmark_pallets", &[], None)?;
+			<Vec<Vec<u8>> as Decode>::decode(&mut &result[..])
+				.map_err(|e| format!("Failed to decode the pallets: {:?}", e))?
+				.iter()
+				.map(|pallet| String::from_utf8_lossy(pallet).into_owned())
+				.collect()
+		} else {
+			self.pallet.split(',').map(|pallet| pallet.trim().to_string()).collect()
+		};
+		let patterns = self.extrinsic.split(',').map(str::trim).collect::<Vec<_>>();
+
+		// The benchmarks which failed, as `(pallet, extrinsic, error)`, if asked to carry on after
+		// failures.
+		let mut failures = Vec::new();
+
+		let mut selected = Vec::new();
+		for pallet in pallets {
+			// The benchmarks named in full are run as asked. Those matched through a `*` are run
+			// unless they are skipped or, unless asked for, extra.
+			let extrinsics: Vec<String> = if patterns.iter().any(|pattern| pattern.contains('*')) {
+				let listed = execute("Benchmark_benchmarks", &pallet.encode(), None).and_then(|result| {
+					<Result<Vec<BenchmarkMetadata>, String> as Decode>::decode(&mut &result[..])
+						.map_err(|e| format!("Failed to decode the benchmarks: {:?}", e))?
+						.map_err(|e| format!("Failed to list the benchmarks of {}: {}", pallet, e))
+				});
+				let benchmarks = match listed {
+					Ok(benchmarks) => benchmarks,
+					Err(error) if self.continue_on_error => {
+						eprintln!("Error: {}", error);
+						failures.push((pallet, self.extrinsic.clone(), error));
+						continue
+					}
+					Err(error) => return Err(error.into()),
+				};
+				benchmarks.into_iter().filter_map(|benchmark| {
+					let name = String::from_utf8_lossy(&benchmark.name).into_owned();
+					if patterns.contains(&name.as_str()) {
+						Some(name)
+					} else if !patterns.iter().any(|pattern| matches_pattern(pattern, &name)) {
+						None
+					} else if let Some(reason) = benchmark.skip {
+						eprintln!("Skipping {}: {}", name, String::from_utf8_lossy(&reason));
+						None
+					} else if benchmark.extra && !self.extra {
+						eprintln!("Skipping {}: it is an extra benchmark, see --extra", name);
+						None
+					} else {
+						Some(name)
+					}
+				}).collect()
+			} else {
+				patterns.iter().map(|pattern| pattern.to_string()).collect()
+			};
+			selected.extend(extrinsics.into_iter().map(|extrinsic| (pallet.clone(), extrinsic)));
+		}
+
+		let overhead = if self.subtract_overhead {
+			// The first call instantiates the runtime, which the later calls reuse, so it is left
+			// out. `Core_version` does next to nothing, so that its calls, timed around the
+			// executor, measure only entering the executor and setting up the externalities.
+			execute("Core_version", &[], None)?;
+			let times = (0 .. self.repeat.max(1)).map(|_| {
+				let start = Instant::now();
+				execute("Core_version", &[], None)?;
+				Ok(start.elapsed().as_nanos())
+			}).collect::<Result<Vec<_>, String>>()?;
+			let overhead = analysis::Overhead::measure(&times)
+				.ok_or_else(|| String::from("Failed to measure the overhead: no results"))?;
+			eprintln!("Overhead: {} ns per call into the runtime", overhead.time);
+			Some(overhead)
+		} else {
+			None
+		};
+
+		if let Some(search) = self.worst_case {
+			for (pallet, extrinsic) in selected {
+				let result = execute("Benchmark_benchmarks", &pallet.encode(), None)?;
+				let benchmark = <Result<Vec<BenchmarkMetadata>, String> as Decode>::decode(&mut &result[..])
+					.map_err(|e| format!("Failed to decode the benchmarks: {:?}", e))?
+					.map_err(|e| format!("Failed to list the benchmarks of {}: {}", pallet, e))?
+					.into_iter()
+					.find(|benchmark| benchmark.name == extrinsic.as_bytes())
+					.ok_or_else(|| format!("Could not find the benchmark {} of {}", extrinsic, pallet))?;
+				if benchmark.components.is_empty() {
+					eprintln!("Skipping {}: it has no components to search", extrinsic);
+					continue
+				}
+
+				// The values each component would be swept at.
+				let steps = self.steps_of(&pallet, &extrinsic);
+				let mut prev_steps = 10;
+				let values = benchmark.components.iter().zip(&benchmark.spacings).enumerate()
+					.map(|(idx, ((_, low, high), spacing))| {
+						let steps = steps.get(idx).cloned().unwrap_or(prev_steps);
+						prev_steps = steps;
+						let lowest = self.lowest_range_values.get(idx).cloned().unwrap_or(*low);
+						let highest = self.highest_range_values.get(idx).cloned().unwrap_or(*high);
+						spacing.values(lowest, highest, steps)
+							.map_err(|e| format!("Invalid range for {} of {}: {}", extrinsic, pallet, e))
+					})
+					.collect::<Result<Vec<_>, _>>()?;
+				let repeat = self.repeat_of(&pallet, &extrinsic);
+
+				let worst = worst_case::find_worst_case(&values, search, |combination| {
+					// Pin every component to its value in the combination.
+					let result = execute(
+						"Benchmark_dispatch_benchmark",
+						&(
+							&pallet,
+							&extrinsic,
+							combination.to_vec(),
+							combination.to_vec(),
+							Vec::<u32>::new(),
+							repeat + self.warmup,
+						).encode(),
+						None,
+					)?;
+					let results = <Result<Vec<BenchmarkResults>, String> as Decode>::decode(&mut &result[..])
+						.map_err(|e| format!("Failed to decode benchmark results: {:?}", e))?
+						.map_err(|e| format!("Failed to run {} of {}: {}", extrinsic, pallet, e))?;
+					let results = analysis::filter_results(
+						results,
+						self.warmup,
+						self.trim,
+						self.clock.selector(),
+					);
+					self.clock.selector().median(&results)
+						.ok_or_else(|| format!("No results for {} of {} at {:?}", extrinsic, pallet, combination))
+				})?;
+
+				let components = benchmark.components.iter()
+					.zip(&worst.values)
+					.map(|((name, _, _), value)| format!("{:?} = {}", name, value))
+					.collect::<Vec<_>>();
+				println!(
+					"Pallet: {:?}, Extrinsic: {:?}, Worst case: [{}] at {} ns, against {} ns at the highest values",
+					pallet,
+					extrinsic,
+					components.join(", "),
+					worst.time,
+					worst.highest_time,
+				);
+			}
+			return Ok(())
+		}
+
+		let mut batches = Vec::new();
+		let benchmarks = selected.len();
+		for (benchmark, (pallet, extrinsic)) in selected.into_iter().enumerate() {
+			let steps = self.steps_of(&pallet, &extrinsic);
+			let repeat = self.repeat_of(&pallet, &extrinsic);
+
+			let results = execute(
+				"Benchmark_dispatch_benchmark",
+				&(
+					&pallet,
+					&extrinsic,
+					self.lowest_range_values.clone(),
+					self.highest_range_values.clone(),
+					steps,
+					repeat + self.warmup,
+				).encode(),
+				Some(progress::extension(&pallet, &extrinsic, benchmark, benchmarks, self.progress_json)),
+			);
+			// Clear the progress bar, so that it doesn't run into what is printed next.
+			if !self.progress_json {
+				eprint!("\r\x1b[K");
+			}
+			let results = results.and_then(|result| {
+				<Result<Vec<BenchmarkResults>, String> as Decode>::decode(&mut &result[..])
+					.map_err(|e| format!("Failed to decode benchmark results: {:?}", e))
+			});
+
+			match results {
+				Ok(Ok(results)) => {
+					let mut results = analysis::filter_results(
+						results,
+						self.warmup,
+						self.trim,
+						self.clock.selector(),
+					);
+					if let Some(overhead) = overhead {
+						overhead.subtract(&mut results);
+					}
+					batches.push((pallet, extrinsic, results));
+				}
+				Ok(Err(error)) | Err(error) if self.continue_on_error => {
+					eprintln!("Error: {:?}", error);
+					failures.push((pallet, extrinsic, error));
+				}
+				Ok(Err(error)) => eprintln!("Error: {:?}", error),
+				Err(error) => return Err(error.into()),
+			}
+		}
+
+		if batches.is_empty() {
+			return self.summarize(0, &failures)
+		}
+
+		if self.json {
+			println!("{}", writer::raw_json(&batches)?);
+		} else {
+			for (pallet, extrinsic, results) in &batches {
 				// Print benchmark metadata
 				println!(
 					"Pallet: {:?}, Extrinsic: {:?}, Lowest values: {:?}, Highest values: {:?}, Steps: {:?}, Repeat: {:?}",
-					self.pallet,
-					self.extrinsic,
+					pallet,
+					extrinsic,
 					self.lowest_range_values,
 					self.highest_range_values,
-					self.steps,
-					self.repeat,
+					self.steps_of(pallet, extrinsic),
+					self.repeat_of(pallet, extrinsic),
 				);
 
 				// Print the table header
-				results[0].0.iter().for_each(|param| print!("{:?},", param.0));
+				results[0].components.iter().for_each(|param| print!("{:?},", param.0));
 
-				print!("extrinsic_time,storage_root_time\n");
+				print!("extrinsic_time,extrinsic_cpu_time,storage_root_time,reads,repeat_reads,writes,repeat_writes,proof_size,");
+				print!("child_reads,child_repeat_reads,child_writes,child_repeat_writes,child_proof_size,");
+				print!("peak_memory,allocated_memory,allocations\n");
 				// Print the values
 				results.iter().for_each(|result| {
-					let parameters = &result.0;
+					let parameters = &result.components;
 					parameters.iter().for_each(|param| print!("{:?},", param.1));
 					// Print extrinsic time and storage root time
-					print!("{:?},{:?}\n", result.1, result.2);
+					print!(
+						"{:?},{:?},{:?},",
+						result.extrinsic_time,
+						result.extrinsic_cpu_time,
+						result.storage_root_time,
+					);
+					// Print the database reads and writes, and the storage proof size
+					print!(
+						"{:?},{:?},{:?},{:?},{:?},",
+						result.reads,
+						result.repeat_reads,
+						result.writes,
+						result.repeat_writes,
+						result.proof_size,
+					);
+					// Print those of child tries
+					print!(
+						"{:?},{:?},{:?},{:?},{:?},",
+						result.child_reads,
+						result.child_repeat_reads,
+						result.child_writes,
+						result.child_repeat_writes,
+						result.child_proof_size,
+					);
+					// Print the allocations
+					print!(
+						"{:?},{:?},{:?}\n",
+						result.peak_memory,
+						result.allocated_memory,
+						result.allocations,
+					);
 				});
 
-				eprintln!("Done.");
+				// Print the worst allocations, which time alone may not give away
+				match results.iter().max_by_key(|result| result.peak_memory) {
+					Some(worst) if worst.allocations > 0 => println!(
+						"Memory: a peak of up to {} bytes, at {:?}, with {} bytes allocated in {} allocations",
+						worst.peak_memory,
+						worst.components,
+						worst.allocated_memory,
+						worst.allocations,
+					),
+					_ => println!("Memory: not measured, as the allocations are only tracked in wasm, see --execution"),
+				}
+
+				// Print the keys whose accesses were not counted
+				let mut whitelist = results.iter()
+					.flat_map(|result| result.whitelist.iter())
+					.collect::<Vec<_>>();
+				whitelist.sort();
+				whitelist.dedup();
+				println!("Whitelisted keys:");
+				whitelist.iter().for_each(|key| println!("0x{}", HexDisplay::from(*key)));
+
+				// Print every model fitted to the results, with the standard error of each term if
+				// the model estimates it
+				let selectors = [
+					("Extrinsic time", BenchmarkSelector::ExtrinsicTime),
+					("Extrinsic CPU time", BenchmarkSelector::ExtrinsicCpuTime),
+					("Storage root time", BenchmarkSelector::StorageRootTime),
+					("Reads", BenchmarkSelector::Reads),
+					("Writes", BenchmarkSelector::Writes),
+					("Proof size", BenchmarkSelector::ProofSize),
+					("Child reads", BenchmarkSelector::ChildReads),
+					("Child writes", BenchmarkSelector::ChildWrites),
+					("Child proof size", BenchmarkSelector::ChildProofSize),
+					("Peak memory", BenchmarkSelector::PeakMemory),
+					("Allocated memory", BenchmarkSelector::AllocatedMemory),
+				];
+				let choices = [
+					AnalysisChoice::MinSquares,
+					AnalysisChoice::MedianSlopes,
+					AnalysisChoice::Multivariate,
+				];
+				for choice in choices.iter() {
+					println!("{}:", choice);
+					for (label, selector) in selectors.iter() {
+						if let Some(analysis) = choice.fit(results, *selector) {
+							println!("{} ~= {}", label, analysis);
+						}
+					}
+				}
 			}
-			Err(error) => eprintln!("Error: {:?}", error),
 		}
 
-		Ok(())
+		if let Some(path) = &self.output_csv {
+			writer::write_csv(&batches, path)?;
+		}
+
+		if let Some(path) = &self.output {
+			writer::write_results(&batches, path, &self, &calibration)?;
+		}
+
+		eprintln!("Done.");
+
+		self.summarize(batches.len(), &failures)
+	}
+
+	// Print how many benchmarks passed and which failed, with their errors, if asked to carry on
+	// after failures, and fail if any did.
+	// The steps of the benchmark `extrinsic` of `pallet`, as overridden or as given by `--steps`.
+	fn steps_of(&self, pallet: &str, extrinsic: &str) -> Vec<u32> {
+		self.override_steps.iter()
+			.find(|((p, e), _)| p == pallet && e == extrinsic)
+			.map_or_else(|| self.steps.clone(), |(_, steps)| steps.clone())
+	}
+
+	// The repetitions of the benchmark `extrinsic` of `pallet`, as overridden or as given by
+	// `--repeat`.
+	fn repeat_of(&self, pallet: &str, extrinsic: &str) -> u32 {
+		self.override_repeat.iter()
+			.find(|((p, e), _)| p == pallet && e == extrinsic)
+			.map_or(self.repeat, |(_, repeat)| *repeat)
+	}
+
+	fn summarize(&self, passed: usize, failures: &[(String, String, String)]) -> sc_cli::Result<()> {
+		if !self.continue_on_error {
+			return Ok(())
+		}
+
+		eprintln!("Passed: {}, failed: {}", passed, failures.len());
+		for (pallet, extrinsic, error) in failures {
+			eprintln!("Failed: {}, {}: {}", pallet, extrinsic, error);
+		}
+
+		if failures.is_empty() {
+			Ok(())
+		} else {
+			Err(format!("{} of {} benchmarks failed", failures.len(), passed + failures.len()).into())
+		}
 	}
 
 	/// Update and prepare a `Configuration` with command line parameters
@@ -182,3 +697,91 @@ impl BenchmarkCmd {
 		Ok(())
 	}
 }
+
+// Parse an override of the steps of one benchmark, given as `<pallet>::<extrinsic>=<steps>`.
+fn parse_steps_override(s: &str) -> Result<((String, String), Vec<u32>), String> {
+	parse_override(s, |value| value.split(',')
+		.map(|step| step.trim().parse::<u32>().map_err(|e| e.to_string()))
+		.collect()
+	)
+}
+
+// Parse an override of the repetitions of one benchmark, given as `<pallet>::<extrinsic>=<repeat>`.
+fn parse_repeat_override(s: &str) -> Result<((String, String), u32), String> {
+	parse_override(s, |value| value.trim().parse::<u32>().map_err(|e| e.to_string()))
+}
+
+// Whether `name` matches `pattern`, in which a `*` stands for any run of characters.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+	let mut parts = pattern.split('*');
+	let first = parts.next().unwrap_or_default();
+	if !name.starts_with(first) {
+		return false
+	}
+	let mut rest = &name[first.len() ..];
+	let parts = parts.collect::<Vec<_>>();
+	let (last, middle) = match parts.split_last() {
+		Some(split) => split,
+		None => return rest.is_empty(),
+	};
+	for part in middle {
+		match rest.find(part) {
+			Some(index) => rest = &rest[index + part.len() ..],
+			None => return false,
+		}
+	}
+	rest.ends_with(last)
+}
+
+fn parse_override<T>(
+	s: &str,
+	parse_value: impl Fn(&str) -> Result<T, String>,
+) -> Result<((String, String), T), String> {
+	let expected = || format!("Expected `<pallet>::<extrinsic>=<value>`, got {:?}", s);
+	let mut parts = s.splitn(2, '=');
+	let name = parts.next().unwrap_or_default();
+	let value = parts.next().ok_or_else(expected)?;
+	let mut names = name.splitn(2, "::");
+	let pallet = names.next().unwrap_or_default().trim();
+	let extrinsic = names.next().ok_or_else(expected)?.trim();
+	if pallet.is_empty() || extrinsic.is_empty() {
+		return Err(expected())
+	}
+	let value = parse_value(value).map_err(|e| format!("Invalid value in {:?}: {}", s, e))?;
+	Ok(((pallet.to_string(), extrinsic.to_string()), value))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn overrides_should_parse() {
+		assert_eq!(
+			parse_steps_override("pallet-democracy::undelegate=5,2"),
+			Ok((("pallet-democracy".into(), "undelegate".into()), vec![5, 2])),
+		);
+		assert_eq!(
+			parse_repeat_override("pallet-democracy::undelegate=20"),
+			Ok((("pallet-democracy".into(), "undelegate".into()), 20)),
+		);
+		assert!(parse_repeat_override("pallet-democracy::undelegate").is_err());
+		assert!(parse_repeat_override("undelegate=20").is_err());
+		assert!(parse_repeat_override("pallet-democracy::=20").is_err());
+		assert!(parse_repeat_override("::undelegate=20").is_err());
+		assert!(parse_steps_override("pallet-democracy::undelegate=5,x").is_err());
+	}
+
+	#[test]
+	fn patterns_should_match() {
+		assert!(matches_pattern("*", "vote"));
+		assert!(matches_pattern("vote", "vote"));
+		assert!(!matches_pattern("vote", "votes"));
+		assert!(matches_pattern("on_initialize_*", "on_initialize_bake"));
+		assert!(!matches_pattern("on_initialize_*", "enact_proposal"));
+		assert!(matches_pattern("*_proxy*", "activate_proxy"));
+		assert!(!matches_pattern("*_proxy*", "proxy_vote"));
+		assert!(matches_pattern("a*a", "aba"));
+		assert!(!matches_pattern("a*a", "a"));
+	}
+}
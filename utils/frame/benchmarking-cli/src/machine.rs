@@ -0,0 +1,145 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A quick check of how fast the benchmarking machine is compared to the reference hardware, so
+//! that weights measured on a machine much slower or faster than it can be told apart.
+
+use std::fmt;
+use std::time::Instant;
+use sp_core::hashing::blake2_256;
+use sp_runtime::traits::Block as BlockT;
+use sp_state_machine::Backend;
+use sc_client_db::BenchmarkingState;
+
+// The speeds of the reference hardware: the MiB hashed with `blake2_256` per second, the MiB
+// copied per second, and the storage reads of the genesis state per second.
+const REFERENCE_HASHING: f64 = 600.0;
+const REFERENCE_MEMCPY: f64 = 8_000.0;
+const REFERENCE_STORAGE: f64 = 200_000.0;
+
+// A machine is fine if it is at most this many times slower or faster than the reference hardware
+// at every task.
+const TOLERANCE: f64 = 2.0;
+
+const MIB: usize = 1024 * 1024;
+
+/// How fast the benchmarking machine is at each task, relative to the reference hardware, which is
+/// at `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+	/// Hashing with `blake2_256`.
+	pub hashing: f64,
+	/// Copying memory.
+	pub memcpy: f64,
+	/// Reading storage from the database.
+	pub storage: f64,
+}
+
+impl Calibration {
+	/// Measure how fast this machine is, reading the storage of `state`. It takes a fraction of a
+	/// second on the reference hardware.
+	pub fn measure<B: BlockT>(state: &BenchmarkingState<B>) -> Result<Self, String> {
+		let calibration = Calibration {
+			hashing: hashing_speed() / REFERENCE_HASHING,
+			memcpy: memcpy_speed() / REFERENCE_MEMCPY,
+			storage: storage_speed(state)? / REFERENCE_STORAGE,
+		};
+		// The reads are not those of any benchmark.
+		state.reset_read_write_count();
+		Ok(calibration)
+	}
+
+	/// Whether the machine is close enough to the reference hardware at every task.
+	pub fn is_within_tolerance(&self) -> bool {
+		[self.hashing, self.memcpy, self.storage].iter()
+			.all(|speed| *speed >= 1.0 / TOLERANCE && *speed <= TOLERANCE)
+	}
+}
+
+impl fmt::Display for Calibration {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"hashing {:.2}x, memcpy {:.2}x, storage {:.2}x the speed of the reference hardware",
+			self.hashing,
+			self.memcpy,
+			self.storage,
+		)?;
+		if !self.is_within_tolerance() {
+			write!(f, " (more than {}x off)", TOLERANCE)?;
+		}
+		Ok(())
+	}
+}
+
+// The MiB hashed with `blake2_256` per second.
+fn hashing_speed() -> f64 {
+	let mut data = vec![0u8; MIB];
+	let start = Instant::now();
+	for _ in 0 .. 64 {
+		// Each hash depends on the last, so that none of them is optimised away.
+		let hash = blake2_256(&data);
+		data[.. 32].copy_from_slice(&hash);
+	}
+	64.0 / start.elapsed().as_secs_f64()
+}
+
+// The MiB copied per second.
+fn memcpy_speed() -> f64 {
+	let source = vec![1u8; 16 * MIB];
+	let mut destination = vec![0u8; 16 * MIB];
+	let start = Instant::now();
+	for _ in 0 .. 32 {
+		destination.copy_from_slice(&source);
+		// Read the copy, so that it is not optimised away. Safe, as the pointer is to an element.
+		let _ = unsafe { std::ptr::read_volatile(&destination[16 * MIB - 1]) };
+	}
+	(32 * 16) as f64 / start.elapsed().as_secs_f64()
+}
+
+// The storage reads of the keys of the genesis state per second.
+fn storage_speed<B: BlockT>(state: &BenchmarkingState<B>) -> Result<f64, String> {
+	let keys = state.keys(&[]);
+	if keys.is_empty() {
+		return Err("Failed to calibrate the storage: the genesis state is empty".into())
+	}
+	let mut reads = 0;
+	let start = Instant::now();
+	while reads < 10_000 {
+		for key in &keys {
+			state.storage(key).map_err(|e| format!("Failed to calibrate the storage: {:?}", e))?;
+		}
+		reads += keys.len();
+	}
+	Ok(reads as f64 / start.elapsed().as_secs_f64())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn tolerance_should_bound_every_task() {
+		let calibration = Calibration { hashing: 1.0, memcpy: 0.6, storage: 1.9 };
+		assert!(calibration.is_within_tolerance());
+		assert!(!Calibration { hashing: 0.4, ..calibration }.is_within_tolerance());
+		assert!(!Calibration { storage: 2.5, ..calibration }.is_within_tolerance());
+		assert_eq!(
+			Calibration { memcpy: 0.25, ..calibration }.to_string(),
+			"hashing 1.00x, memcpy 0.25x, storage 1.90x the speed of the reference hardware (more than 2x off)",
+		);
+	}
+}
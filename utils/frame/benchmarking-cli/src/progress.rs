@@ -0,0 +1,176 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The reporting of the progress of benchmark runs, which may take hours for a whole runtime, as
+//! a progress bar or as a JSON line per report for other tools to follow.
+
+use std::io::Write;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use frame_benchmarking::{BenchmarkProgress, ProgressExt};
+
+// The width of the progress bar, in characters.
+const BAR_WIDTH: usize = 30;
+
+/// A report of how far the run of a benchmark has got.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ProgressEvent {
+	pub pallet: String,
+	pub extrinsic: String,
+	/// The position of the benchmark among those selected, from zero.
+	pub benchmark: usize,
+	/// The number of benchmarks selected.
+	pub benchmarks: usize,
+	/// The set of component values being run, from zero.
+	pub step: u32,
+	/// The number of sets of component values of the run.
+	pub steps: u32,
+	/// The repetition being run at this set of component values, from zero.
+	pub repeat: u32,
+	/// The number of repetitions at each set of component values.
+	pub repeats: u32,
+	/// The estimated number of seconds left until the run of the benchmark is done, once any
+	/// repetition is.
+	pub eta: Option<u64>,
+}
+
+impl ProgressEvent {
+	/// The fraction of the repetitions of the run which are done.
+	pub fn done(&self) -> f64 {
+		let total = self.steps as u64 * self.repeats as u64;
+		if total == 0 {
+			return 1.0
+		}
+		(self.step as u64 * self.repeats as u64 + self.repeat as u64) as f64 / total as f64
+	}
+
+	/// The progress as a single line with a bar, such as
+	/// `[2/10] pallet-balances transfer [=====>    ] step 3/20, repeat 1/5, ETA 1m05s`.
+	pub fn render(&self) -> String {
+		let filled = (self.done() * BAR_WIDTH as f64) as usize;
+		let bar = format!(
+			"{}{}{}",
+			"=".repeat(filled.min(BAR_WIDTH)),
+			if filled < BAR_WIDTH { ">" } else { "" },
+			" ".repeat(BAR_WIDTH.saturating_sub(filled + 1)),
+		);
+		let eta = self.eta.map_or_else(|| "-".to_string(), |eta| format_duration(eta));
+		format!(
+			"[{}/{}] {} {} [{}] step {}/{}, repeat {}/{}, ETA {}",
+			self.benchmark + 1,
+			self.benchmarks,
+			self.pallet,
+			self.extrinsic,
+			bar,
+			self.step + 1,
+			self.steps,
+			self.repeat + 1,
+			self.repeats,
+			eta,
+		)
+	}
+}
+
+/// The `ProgressExt` extension which reports the progress of the run of a benchmark on stderr, as
+/// a progress bar or, if `json`, as a JSON line per report.
+pub fn extension(
+	pallet: &str,
+	extrinsic: &str,
+	benchmark: usize,
+	benchmarks: usize,
+	json: bool,
+) -> ProgressExt {
+	let (pallet, extrinsic) = (pallet.to_string(), extrinsic.to_string());
+	let start = Instant::now();
+	ProgressExt(Box::new(move |progress: BenchmarkProgress| {
+		let mut event = ProgressEvent {
+			pallet: pallet.clone(),
+			extrinsic: extrinsic.clone(),
+			benchmark,
+			benchmarks,
+			step: progress.step,
+			steps: progress.steps,
+			repeat: progress.repeat,
+			repeats: progress.repeats,
+			eta: None,
+		};
+		event.eta = eta(start.elapsed(), event.done()).map(|eta| eta.as_secs());
+		if json {
+			if let Ok(line) = serde_json::to_string(&event) {
+				eprintln!("{}", line);
+			}
+		} else {
+			// Draw over the last report, which is cleared by the next line printed.
+			eprint!("\r{}\x1b[K", event.render());
+			std::io::stderr().flush().ok();
+		}
+	}))
+}
+
+// The time left if a fraction `done` of the work took `elapsed`, or `None` if none of it is done.
+fn eta(elapsed: Duration, done: f64) -> Option<Duration> {
+	if done <= 0.0 {
+		return None
+	}
+	Some(elapsed.mul_f64((1.0 - done).max(0.0) / done))
+}
+
+// Format a number of seconds such as `1h02m`, `1m05s` or `7s`.
+fn format_duration(secs: u64) -> String {
+	match (secs / 3600, secs % 3600 / 60, secs % 60) {
+		(0, 0, s) => format!("{}s", s),
+		(0, m, s) => format!("{}m{:02}s", m, s),
+		(h, m, _) => format!("{}h{:02}m", h, m),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn event(step: u32, repeat: u32, eta: Option<u64>) -> ProgressEvent {
+		ProgressEvent {
+			pallet: "pallet-balances".into(),
+			extrinsic: "transfer".into(),
+			benchmark: 1,
+			benchmarks: 10,
+			step,
+			steps: 20,
+			repeat,
+			repeats: 5,
+			eta,
+		}
+	}
+
+	#[test]
+	fn progress_should_count_every_repetition() {
+		assert_eq!(event(0, 0, None).done(), 0.0);
+		assert_eq!(event(10, 0, None).done(), 0.5);
+		assert_eq!(event(19, 4, None).done(), 99.0 / 100.0);
+		assert_eq!(eta(Duration::from_secs(30), 0.25), Some(Duration::from_secs(90)));
+		assert_eq!(eta(Duration::from_secs(30), 0.0), None);
+	}
+
+	#[test]
+	fn progress_should_render_as_a_bar() {
+		assert_eq!(
+			event(10, 0, Some(65)).render(),
+			"[2/10] pallet-balances transfer [===============>              ] step 11/20, repeat 1/5, ETA 1m05s",
+		);
+		assert_eq!(format_duration(7), "7s");
+		assert_eq!(format_duration(3720), "1h02m");
+	}
+}
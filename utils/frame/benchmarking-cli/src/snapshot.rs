@@ -0,0 +1,153 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The state of a live chain at some block, fetched from one of its nodes over RPC, for the
+//! benchmarks to run on top of, so that their worst cases reflect the real sizes of its storage
+//! rather than those of a synthetic setup.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::mpsc;
+use futures::{Future, future::{self, Loop}};
+use jsonrpc_core_client::{transports::http, RpcError};
+use codec::{Encode, Decode};
+use sc_rpc_api::state::StateClient;
+use sp_core::storage::{Storage, StorageKey, well_known_keys};
+
+// The number of keys asked for at once.
+const PAGE_SIZE: u32 = 512;
+
+/// The key-value pairs of the main trie of a state. Child tries are not part of a snapshot.
+pub type Pairs = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Fetch the main trie of the state at the block `at`, or at the best block, from the node whose
+/// RPC server is at `uri`.
+pub fn fetch<Hash>(uri: &str, at: Option<Hash>) -> Result<Pairs, String> where
+	Hash: serde::Serialize + serde::de::DeserializeOwned + Clone + Send + 'static,
+{
+	let (sender, receiver) = mpsc::channel();
+	let uri = uri.to_string();
+	hyper::rt::run(future::lazy(move || {
+		http::connect(&uri)
+			.and_then(move |client: StateClient<Hash>| fetch_pairs(client, at))
+			.then(move |pairs| {
+				sender.send(pairs).ok();
+				Ok(())
+			})
+	}));
+	receiver.recv()
+		.map_err(|_| String::from("Failed to fetch the state: the request was dropped"))?
+		.map_err(|e| format!("Failed to fetch the state: {:?}", e))
+}
+
+// Page through the keys of the state, fetching the values of each page at once.
+fn fetch_pairs<Hash>(
+	client: StateClient<Hash>,
+	at: Option<Hash>,
+) -> impl Future<Item = Pairs, Error = RpcError> where
+	Hash: serde::Serialize + serde::de::DeserializeOwned + Clone + Send + 'static,
+{
+	future::loop_fn((Pairs::new(), None), move |(mut pairs, start): (Pairs, Option<StorageKey>)| {
+		let client = client.clone();
+		let at = at.clone();
+		client.storage_keys_paged(None, PAGE_SIZE, start, at.clone())
+			.and_then(move |keys| {
+				let last = if keys.len() < PAGE_SIZE as usize { None } else { keys.last().cloned() };
+				future::join_all(keys.into_iter().map(move |key| {
+					client.storage(key.clone(), at.clone()).map(move |value| (key, value))
+				})).map(move |values| {
+					for (key, value) in values {
+						if let Some(value) = value {
+							pairs.insert(key.0, value.0);
+						}
+					}
+					match last {
+						Some(last) => {
+							eprint!("\rFetched {} keys", pairs.len());
+							Loop::Continue((pairs, Some(last)))
+						}
+						None => {
+							eprintln!("\rFetched {} keys", pairs.len());
+							Loop::Break(pairs)
+						}
+					}
+				})
+			})
+	})
+}
+
+/// Write a snapshot to `path`, to be benchmarked on again without fetching it.
+pub fn save(pairs: &Pairs, path: &Path) -> Result<(), String> {
+	std::fs::write(path, pairs.encode()).map_err(|e| format!("Failed to write {:?}: {:?}", path, e))
+}
+
+/// Read a snapshot written by `save`.
+pub fn load(path: &Path) -> Result<Pairs, String> {
+	let encoded = std::fs::read(path).map_err(|e| format!("Failed to read {:?}: {:?}", path, e))?;
+	Pairs::decode(&mut &encoded[..]).map_err(|e| format!("Failed to decode {:?}: {:?}", path, e))
+}
+
+/// The storage to benchmark on: the snapshot, but with the code and the heap pages of `genesis`,
+/// so that the runtime benchmarked is the one of the chain spec, which is built with the
+/// benchmarks, rather than the one of the live chain.
+///
+/// The roots of the child tries are left out along with the child tries, lest they point to
+/// tries which are not there.
+pub fn into_storage(mut pairs: Pairs, genesis: &Storage) -> Storage {
+	pairs = pairs.into_iter()
+		.filter(|(key, _)| !well_known_keys::is_child_storage_key(key))
+		.collect();
+	for key in &[well_known_keys::CODE, well_known_keys::HEAP_PAGES] {
+		match genesis.top.get(*key) {
+			Some(value) => pairs.insert(key.to_vec(), value.clone()),
+			None => pairs.remove(*key),
+		};
+	}
+	Storage { top: pairs, children: Default::default() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn snapshot_should_run_the_code_of_the_chain_spec() {
+		let pairs = vec![
+			(well_known_keys::CODE.to_vec(), b"live code".to_vec()),
+			(well_known_keys::HEAP_PAGES.to_vec(), 64u64.encode()),
+			(b"votes".to_vec(), vec![1, 2, 3]),
+			(b":child_storage:default:votes".to_vec(), vec![0; 32]),
+		].into_iter().collect();
+		let mut genesis = Storage::default();
+		genesis.top.insert(well_known_keys::CODE.to_vec(), b"benchmarked code".to_vec());
+		genesis.top.insert(b"votes".to_vec(), vec![]);
+
+		let storage = into_storage(pairs, &genesis);
+		assert_eq!(storage.top, vec![
+			(well_known_keys::CODE.to_vec(), b"benchmarked code".to_vec()),
+			(b"votes".to_vec(), vec![1, 2, 3]),
+		].into_iter().collect());
+	}
+
+	#[test]
+	fn snapshot_should_be_saved_and_loaded() {
+		let pairs: Pairs = vec![(b"key".to_vec(), b"value".to_vec())].into_iter().collect();
+		let path = std::env::temp_dir().join("benchmarking-cli-snapshot-test");
+		save(&pairs, &path).unwrap();
+		assert_eq!(load(&path).unwrap(), pairs);
+		std::fs::remove_file(&path).ok();
+	}
+}
@@ -0,0 +1,164 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The search for the combination of component values at which a benchmark is the slowest, which
+//! is not always the one at which every component is at its highest.
+
+use std::collections::HashMap;
+use structopt::clap::arg_enum;
+
+arg_enum! {
+	/// How the combinations of component values are searched for the slowest one.
+	#[derive(Clone, Copy, Debug, PartialEq)]
+	pub enum Search {
+		// Time every combination of the values the components are swept at.
+		Grid,
+		// From the highest values, step one component at a time towards a slower combination for
+		// as long as there is one.
+		Climb,
+	}
+}
+
+/// The slowest combination of component values found by a search.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorstCase {
+	/// The value of each component.
+	pub values: Vec<u32>,
+	/// The time taken at these values.
+	pub time: u128,
+	/// The time taken with every component at its highest value.
+	pub highest_time: u128,
+}
+
+/// Search the combinations of `values`, which are the values each component may take in
+/// increasing order, for the one at which `time` is the greatest. `time` is asked for each
+/// combination at most once.
+pub fn find_worst_case(
+	values: &[Vec<u32>],
+	search: Search,
+	mut time: impl FnMut(&[u32]) -> Result<u128, String>,
+) -> Result<WorstCase, String> {
+	if values.iter().any(|values| values.is_empty()) {
+		return Err("A component has no values to search".into())
+	}
+
+	// The times taken so far, by the index into `values` of each component.
+	let mut times = HashMap::<Vec<usize>, u128>::new();
+	let mut time_at = |indices: &[usize]| -> Result<u128, String> {
+		if let Some(time) = times.get(indices) {
+			return Ok(*time)
+		}
+		let combination = indices.iter().zip(values).map(|(&i, values)| values[i]).collect::<Vec<_>>();
+		let taken = time(&combination)?;
+		times.insert(indices.to_vec(), taken);
+		Ok(taken)
+	};
+
+	let highest = values.iter().map(|values| values.len() - 1).collect::<Vec<_>>();
+	let highest_time = time_at(&highest)?;
+	let (mut worst, mut worst_time) = (highest.clone(), highest_time);
+
+	match search {
+		Search::Grid => {
+			let mut indices = vec![0; values.len()];
+			loop {
+				let taken = time_at(&indices)?;
+				if taken > worst_time {
+					worst = indices.clone();
+					worst_time = taken;
+				}
+				// Count through the combinations, the first component being the fastest to change.
+				let next = indices.iter().zip(&highest).position(|(i, last)| i < last);
+				match next {
+					Some(position) => {
+						indices[position] += 1;
+						indices[.. position].iter_mut().for_each(|i| *i = 0);
+					}
+					None => break,
+				}
+			}
+		}
+		Search::Climb => loop {
+			let mut neighbours = Vec::new();
+			for position in 0 .. worst.len() {
+				if worst[position] > 0 {
+					let mut lower = worst.clone();
+					lower[position] -= 1;
+					neighbours.push(lower);
+				}
+				if worst[position] < highest[position] {
+					let mut higher = worst.clone();
+					higher[position] += 1;
+					neighbours.push(higher);
+				}
+			}
+			let mut climbed = false;
+			for neighbour in neighbours {
+				let taken = time_at(&neighbour)?;
+				if taken > worst_time {
+					worst = neighbour;
+					worst_time = taken;
+					climbed = true;
+				}
+			}
+			if !climbed {
+				break
+			}
+		},
+	}
+
+	Ok(WorstCase {
+		values: worst.iter().zip(values).map(|(&i, values)| values[i]).collect(),
+		time: worst_time,
+		highest_time,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// A cost which peaks at `x = 3` whatever `y`, and grows with `y`.
+	fn cost(combination: &[u32]) -> Result<u128, String> {
+		let (x, y) = (combination[0] as i64, combination[1] as i64);
+		Ok((100 - (x - 3) * (x - 3) + y) as u128)
+	}
+
+	#[test]
+	fn grid_should_find_an_interior_peak() {
+		let values = vec![vec![0, 1, 2, 3, 4, 5, 6], vec![0, 5, 10]];
+		let mut timed = 0;
+		let worst = find_worst_case(&values, Search::Grid, |c| { timed += 1; cost(c) }).unwrap();
+		assert_eq!(worst, WorstCase { values: vec![3, 10], time: 110, highest_time: 101 });
+		assert_eq!(timed, 21);
+	}
+
+	#[test]
+	fn climb_should_find_an_interior_peak() {
+		let values = vec![vec![0, 1, 2, 3, 4, 5, 6], vec![0, 5, 10]];
+		let mut timed = 0;
+		let worst = find_worst_case(&values, Search::Climb, |c| { timed += 1; cost(c) }).unwrap();
+		assert_eq!(worst, WorstCase { values: vec![3, 10], time: 110, highest_time: 101 });
+		assert!(timed < 21);
+	}
+
+	#[test]
+	fn search_should_handle_no_components() {
+		let worst = find_worst_case(&[], Search::Climb, |_| Ok(7)).unwrap();
+		assert_eq!(worst, WorstCase { values: vec![], time: 7, highest_time: 7 });
+		assert!(find_worst_case(&[vec![]], Search::Grid, |_| Ok(7)).is_err());
+	}
+}
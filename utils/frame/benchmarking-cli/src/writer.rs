@@ -0,0 +1,382 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Rendering of the results of benchmarks into weight files and raw data.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use frame_benchmarking::BenchmarkResults;
+
+use crate::BenchmarkCmd;
+use crate::analysis::{Analysis, BenchmarkSelector, StorageRoot};
+use crate::machine::Calibration;
+
+const TEMPLATE: &str = include_str!("./template.hbs");
+
+/// Everything the template is rendered from.
+#[derive(Serialize, Debug)]
+struct TemplateData {
+	header: String,
+	version: String,
+	date: String,
+	steps: String,
+	repeat: u32,
+	clock: String,
+	machine: String,
+	command: String,
+	pallet: String,
+	db_read_weight: String,
+	db_write_weight: String,
+	db_child_read_weight: String,
+	db_child_write_weight: String,
+	storage_root_weight: Option<String>,
+	benchmarks: Vec<BenchmarkData>,
+}
+
+/// The weight function of one benchmark.
+#[derive(Serialize, Debug)]
+struct BenchmarkData {
+	name: String,
+	signature: String,
+	base_weight: String,
+	base_reads: String,
+	base_writes: String,
+	component_weight: Vec<ComponentSlope>,
+	component_reads: Vec<ComponentSlope>,
+	component_writes: Vec<ComponentSlope>,
+	base_child_reads: String,
+	base_child_writes: String,
+	component_child_reads: Vec<ComponentSlope>,
+	component_child_writes: Vec<ComponentSlope>,
+	proof_signature: String,
+	base_proof_size: String,
+	component_proof_size: Vec<ComponentSlope>,
+}
+
+/// The slope of a measurement in one component.
+#[derive(Serialize, Debug)]
+struct ComponentSlope {
+	name: String,
+	slope: String,
+}
+
+/// Render the results of the benchmarks of each pallet into a weight file.
+///
+/// `batches` holds the pallet, the extrinsic and the results of every benchmark which was run.
+/// If `path` is a directory, the file of each pallet is written into it and named after the
+/// pallet, otherwise the weights of every pallet are written to `path` itself.
+pub fn write_results(
+	batches: &[(String, String, Vec<BenchmarkResults>)],
+	path: &Path,
+	cmd: &BenchmarkCmd,
+	calibration: &Calibration,
+) -> Result<(), String> {
+	let template = match &cmd.template {
+		Some(template) => std::fs::read_to_string(template)
+			.map_err(|e| format!("Failed to read the template: {:?}", e))?,
+		None => TEMPLATE.to_string(),
+	};
+	let header = match &cmd.header {
+		Some(header) => {
+			let mut header = std::fs::read_to_string(header)
+				.map_err(|e| format!("Failed to read the header: {:?}", e))?;
+			if !header.ends_with('\n') {
+				header.push('\n');
+			}
+			header.push('\n');
+			header
+		}
+		None => String::new(),
+	};
+
+	let mut pallets = BTreeMap::<&str, Vec<BenchmarkData>>::new();
+	let mut storage_root_times = BTreeMap::<&str, u128>::new();
+	for (pallet, extrinsic, results) in batches {
+		let benchmark = benchmark_data(extrinsic, results, cmd)
+			.ok_or_else(|| format!("No results for the benchmark {} of {}", extrinsic, pallet))?;
+		pallets.entry(pallet.as_str()).or_default().push(benchmark);
+		let longest = results.iter().map(|result| result.storage_root_time).max().unwrap_or(0);
+		let time = storage_root_times.entry(pallet.as_str()).or_default();
+		*time = (*time).max(longest);
+	}
+
+	let mut handlebars = handlebars::Handlebars::new();
+	handlebars.register_escape_fn(handlebars::no_escape);
+
+	for (pallet, benchmarks) in pallets {
+		let storage_root_weight = match cmd.storage_root {
+			StorageRoot::Block => storage_root_times.get(pallet).map(|time| underscore(*time)),
+			StorageRoot::Ignore | StorageRoot::Extrinsic => None,
+		};
+		let pallet = pallet.replace("-", "_");
+		let data = TemplateData {
+			header: header.clone(),
+			version: env!("CARGO_PKG_VERSION").to_string(),
+			date: chrono::Utc::now().format("%Y-%m-%d").to_string(),
+			steps: format!("{:?}", cmd.steps),
+			repeat: cmd.repeat,
+			clock: cmd.clock.to_string(),
+			machine: calibration.to_string(),
+			command: std::env::args().collect::<Vec<_>>().join(" "),
+			db_read_weight: underscore(cmd.db_read_weight as u128),
+			db_write_weight: underscore(cmd.db_write_weight as u128),
+			db_child_read_weight: underscore(cmd.db_child_read_weight as u128),
+			db_child_write_weight: underscore(cmd.db_child_write_weight as u128),
+			storage_root_weight,
+			pallet: pallet.clone(),
+			benchmarks,
+		};
+		let rendered = handlebars.render_template(&template, &data)
+			.map_err(|e| format!("Failed to render the weights of {}: {:?}", pallet, e))?;
+
+		let file = if path.is_dir() {
+			path.join(&pallet).with_extension("rs")
+		} else {
+			PathBuf::from(path)
+		};
+		std::fs::write(&file, rendered)
+			.map_err(|e| format!("Failed to write {:?}: {:?}", file, e))?;
+	}
+
+	Ok(())
+}
+
+/// A raw data point of a benchmark.
+#[derive(Serialize, Debug)]
+struct DataPoint<'a> {
+	pallet: &'a str,
+	extrinsic: &'a str,
+	components: Vec<(String, u32)>,
+	repeat: u32,
+	extrinsic_time: u128,
+	extrinsic_cpu_time: u128,
+	storage_root_time: u128,
+	reads: u32,
+	repeat_reads: u32,
+	writes: u32,
+	repeat_writes: u32,
+	proof_size: u32,
+	child_reads: u32,
+	child_repeat_reads: u32,
+	child_writes: u32,
+	child_repeat_writes: u32,
+	child_proof_size: u32,
+	peak_memory: u32,
+	allocated_memory: u64,
+	allocations: u32,
+}
+
+// Every raw data point of the benchmarks. The repetitions of a set of component values are run
+// one after the other, so a data point is a repetition of the previous one if their component
+// values are the same.
+fn data_points(batches: &[(String, String, Vec<BenchmarkResults>)]) -> Vec<DataPoint> {
+	let mut points = Vec::new();
+	for (pallet, extrinsic, results) in batches {
+		let mut previous = None;
+		let mut repeat = 0;
+		for result in results {
+			repeat = if previous == Some(&result.components) { repeat + 1 } else { 0 };
+			previous = Some(&result.components);
+			points.push(DataPoint {
+				pallet,
+				extrinsic,
+				components: result.components.iter()
+					.map(|(name, value)| (format!("{:?}", name), *value))
+					.collect(),
+				repeat,
+				extrinsic_time: result.extrinsic_time,
+				extrinsic_cpu_time: result.extrinsic_cpu_time,
+				storage_root_time: result.storage_root_time,
+				reads: result.reads,
+				repeat_reads: result.repeat_reads,
+				writes: result.writes,
+				repeat_writes: result.repeat_writes,
+				proof_size: result.proof_size,
+				child_reads: result.child_reads,
+				child_repeat_reads: result.child_repeat_reads,
+				child_writes: result.child_writes,
+				child_repeat_writes: result.child_repeat_writes,
+				child_proof_size: result.child_proof_size,
+				peak_memory: result.peak_memory,
+				allocated_memory: result.allocated_memory,
+				allocations: result.allocations,
+			});
+		}
+	}
+	points
+}
+
+/// Every raw data point of the benchmarks as JSON.
+pub fn raw_json(batches: &[(String, String, Vec<BenchmarkResults>)]) -> Result<String, String> {
+	serde_json::to_string_pretty(&data_points(batches))
+		.map_err(|e| format!("Failed to serialize the results: {:?}", e))
+}
+
+/// Write every raw data point of the benchmarks to `path` as CSV, with a column for each component
+/// of any of the benchmarks, which is empty for the benchmarks without it.
+pub fn write_csv(
+	batches: &[(String, String, Vec<BenchmarkResults>)],
+	path: &Path,
+) -> Result<(), String> {
+	let points = data_points(batches);
+	let mut names = Vec::<&str>::new();
+	for point in &points {
+		for (name, _) in &point.components {
+			if !names.contains(&name.as_str()) {
+				names.push(name);
+			}
+		}
+	}
+
+	let mut csv = String::from("pallet,extrinsic,");
+	names.iter().for_each(|name| csv.push_str(&format!("{},", name)));
+	csv.push_str("repeat,extrinsic_time,extrinsic_cpu_time,storage_root_time,reads,repeat_reads,writes,repeat_writes,proof_size,");
+	csv.push_str("child_reads,child_repeat_reads,child_writes,child_repeat_writes,child_proof_size,");
+	csv.push_str("peak_memory,allocated_memory,allocations\n");
+	for point in &points {
+		csv.push_str(&format!("{},{},", point.pallet, point.extrinsic));
+		for name in &names {
+			if let Some((_, value)) = point.components.iter().find(|(n, _)| n == name) {
+				csv.push_str(&value.to_string());
+			}
+			csv.push(',');
+		}
+		csv.push_str(&format!(
+			"{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+			point.repeat,
+			point.extrinsic_time,
+			point.extrinsic_cpu_time,
+			point.storage_root_time,
+			point.reads,
+			point.repeat_reads,
+			point.writes,
+			point.repeat_writes,
+			point.proof_size,
+			point.child_reads,
+			point.child_repeat_reads,
+			point.child_writes,
+			point.child_repeat_writes,
+			point.child_proof_size,
+			point.peak_memory,
+			point.allocated_memory,
+			point.allocations,
+		));
+	}
+
+	std::fs::write(path, csv).map_err(|e| format!("Failed to write {:?}: {:?}", path, e))
+}
+
+// The weight function of the benchmark `name`, fitted to its results by the chosen model.
+fn benchmark_data(
+	name: &str,
+	results: &[BenchmarkResults],
+	cmd: &BenchmarkCmd,
+) -> Option<BenchmarkData> {
+	let choice = cmd.analysis;
+	let weight = choice.fit(results, cmd.clock.selector())?;
+	let weight = match cmd.storage_root {
+		StorageRoot::Extrinsic => weight + choice.fit(results, BenchmarkSelector::StorageRootTime)?,
+		StorageRoot::Ignore | StorageRoot::Block => weight,
+	};
+	let reads = choice.fit(results, BenchmarkSelector::Reads)?;
+	let writes = choice.fit(results, BenchmarkSelector::Writes)?;
+	let child_reads = choice.fit(results, BenchmarkSelector::ChildReads)?;
+	let child_writes = choice.fit(results, BenchmarkSelector::ChildWrites)?;
+	// The proof of the reads of child tries is part of that of the call.
+	let proof_size = choice.fit(results, BenchmarkSelector::ProofSize)?
+		+ choice.fit(results, BenchmarkSelector::ChildProofSize)?;
+
+	Some(BenchmarkData {
+		name: name.to_string(),
+		signature: signature(&[&weight, &reads, &writes, &child_reads, &child_writes]),
+		base_weight: underscore(weight.base),
+		base_reads: underscore(reads.base),
+		base_writes: underscore(writes.base),
+		component_weight: slopes(&weight),
+		component_reads: slopes(&reads),
+		component_writes: slopes(&writes),
+		base_child_reads: underscore(child_reads.base),
+		base_child_writes: underscore(child_writes.base),
+		component_child_reads: slopes(&child_reads),
+		component_child_writes: slopes(&child_writes),
+		proof_signature: signature(&[&proof_size]),
+		base_proof_size: underscore(proof_size.base),
+		component_proof_size: slopes(&proof_size),
+	})
+}
+
+// The parameters of a function computed from some models of the same benchmark. A component
+// which none of the models depend on is an unused parameter.
+fn signature(analyses: &[&Analysis]) -> String {
+	analyses[0].names.iter().enumerate()
+		.map(|(i, name)| {
+			let used = analyses.iter().any(|analysis| analysis.slopes[i] > 0);
+			format!("{}{}: u32", if used { "" } else { "_" }, name)
+		})
+		.collect::<Vec<_>>()
+		.join(", ")
+}
+
+// The non-zero slopes of a model.
+fn slopes(analysis: &Analysis) -> Vec<ComponentSlope> {
+	analysis.names.iter().zip(&analysis.slopes)
+		.filter(|&(_, slope)| *slope > 0)
+		.map(|(name, slope)| ComponentSlope { name: name.clone(), slope: underscore(*slope) })
+		.collect()
+}
+
+// Format a number with an underscore between every group of three digits.
+fn underscore(n: u128) -> String {
+	let digits = n.to_string();
+	let mut formatted = String::new();
+	for (i, digit) in digits.chars().enumerate() {
+		if i > 0 && (digits.len() - i) % 3 == 0 {
+			formatted.push('_');
+		}
+		formatted.push(digit);
+	}
+	formatted
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn data_points_should_count_repeats() {
+		let result = |n| BenchmarkResults {
+			components: vec![(frame_benchmarking::BenchmarkParameter::n, n)],
+			..Default::default()
+		};
+		let batches = vec![(
+			"pallet".to_string(),
+			"extrinsic".to_string(),
+			vec![result(1), result(1), result(2), result(2), result(2), result(1)],
+		)];
+		let repeats = data_points(&batches).iter().map(|p| p.repeat).collect::<Vec<_>>();
+		assert_eq!(repeats, vec![0, 1, 0, 1, 2, 0]);
+	}
+
+	#[test]
+	fn underscore_should_work() {
+		assert_eq!(underscore(0), "0");
+		assert_eq!(underscore(999), "999");
+		assert_eq!(underscore(1_000), "1_000");
+		assert_eq!(underscore(12_345_678), "12_345_678");
+	}
+}